@@ -58,22 +58,10 @@ pub fn execute(
             endowment_id,
             setup,
         } => executers::setup_donation_match(deps, env, info, endowment_id, setup),
-        ExecuteMsg::UpdateDelegate {
-            endowment_id,
-            setting,
-            action,
-            delegate_address,
-            delegate_expiry,
-        } => executers::update_delegate(
-            deps,
-            env,
-            info,
-            endowment_id,
-            setting,
-            action,
-            delegate_address,
-            delegate_expiry,
-        ),
+        ExecuteMsg::UpdateDelegate(msg) => executers::update_delegate(deps, env, info, msg),
+        ExecuteMsg::UpdateEndowmentParent { id, parent } => {
+            executers::update_endowment_parent(deps, env, info, id, parent)
+        }
         ExecuteMsg::UpdateConfig(msg) => executers::update_config(deps, env, info, msg),
     }
 }
@@ -91,6 +79,27 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::EndowmentPermissions { id, updater } => to_binary(
             &queriers::query_endowment_permissions(deps, env, id, updater)?,
         ),
+        QueryMsg::ListPermissions { id } => to_binary(&queriers::query_list_permissions(deps, id)?),
+        QueryMsg::Delegations {
+            delegate,
+            start_after,
+            limit,
+        } => to_binary(&queriers::query_delegations(
+            deps,
+            delegate,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::EndowmentSettingsHistory {
+            id,
+            start_after,
+            limit,
+        } => to_binary(&queriers::query_endowment_settings_history(
+            deps,
+            id,
+            start_after,
+            limit,
+        )?),
     }
 }
 
@@ -1,4 +1,6 @@
-use crate::state::{CONFIG, CONTROLLER, SETTINGS};
+use crate::state::{
+    record_settings_changes, save_controller, sync_delegations, CONFIG, CONTROLLER, SETTINGS,
+};
 use angel_core::errors::core::ContractError;
 use angel_core::msgs::accounts::EndowmentDetailsResponse;
 use angel_core::msgs::accounts_settings_controller::*;
@@ -6,14 +8,15 @@ use angel_core::msgs::registrar::ConfigExtensionResponse as RegistrarConfigExten
 use angel_core::msgs::registrar::QueryMsg::ConfigExtension as RegistrarConfigExtension;
 use angel_core::msgs::subdao::InstantiateMsg as DaoInstantiateMsg;
 use angel_core::structs::{
-    DaoSetup, DonationMatch, EndowmentController, EndowmentSettings, EndowmentType,
+    DaoSetup, DelegateAction, DonationMatch, EndowmentController, EndowmentSetting,
+    EndowmentSettings, EndowmentType,
 };
 use cosmwasm_std::{
     to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, ReplyOn, Response, StdError, SubMsg,
     SubMsgResult, WasmMsg,
 };
 
-pub fn dao_reply(deps: DepsMut, _env: Env, msg: SubMsgResult) -> Result<Response, ContractError> {
+pub fn dao_reply(deps: DepsMut, env: Env, msg: SubMsgResult) -> Result<Response, ContractError> {
     match msg {
         SubMsgResult::Ok(subcall) => {
             let mut id: u32 = 0;
@@ -36,9 +39,18 @@ pub fn dao_reply(deps: DepsMut, _env: Env, msg: SubMsgResult) -> Result<Response
                 return Err(ContractError::AccountNotCreated {});
             }
             let mut endowment = SETTINGS.load(deps.storage, id)?;
+            let prev = endowment.clone();
             endowment.dao = Some(dao);
             endowment.dao_token = Some(dao_token);
             SETTINGS.save(deps.storage, id, &endowment)?;
+            record_settings_changes(
+                deps.storage,
+                id,
+                &prev,
+                &endowment,
+                &env.contract.address,
+                env.block.height,
+            )?;
 
             // set new CW3 as endowment owner to be picked up by the Registrar (EndowmentEntry)
             Ok(Response::default()
@@ -51,7 +63,7 @@ pub fn dao_reply(deps: DepsMut, _env: Env, msg: SubMsgResult) -> Result<Response
 
 pub fn donation_match_reply(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     msg: SubMsgResult,
 ) -> Result<Response, ContractError> {
     match msg {
@@ -76,8 +88,17 @@ pub fn donation_match_reply(
                 return Err(ContractError::AccountNotCreated {});
             }
             let mut endowment = SETTINGS.load(deps.storage, id)?;
+            let prev = endowment.clone();
             endowment.donation_match_contract = Some(donation_match_contract);
             SETTINGS.save(deps.storage, id, &endowment)?;
+            record_settings_changes(
+                deps.storage,
+                id,
+                &prev,
+                &endowment,
+                &env.contract.address,
+                env.block.height,
+            )?;
 
             // set new CW3 as endowment owner to be picked up by the Registrar (EndowmentEntry)
             Ok(Response::default().add_attribute(
@@ -151,10 +172,44 @@ pub fn create_endowment_settings(
     })?;
 
     CONTROLLER.save(deps.storage, msg.id, &msg.endowment_controller.clone())?;
+    sync_delegations(deps.storage, msg.id, None, &msg.endowment_controller)?;
 
     Ok(Response::new().add_attribute("action", "create_endowment_settings"))
 }
 
+pub fn update_endowment_parent(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u32,
+    parent: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let registrar_config_ext: RegistrarConfigExtensionResponse = deps
+        .querier
+        .query_wasm_smart(config.registrar_contract, &RegistrarConfigExtension {})?;
+
+    // Only the "accounts_contract" can call this entry.
+    if info.sender != registrar_config_ext.accounts_contract.unwrap() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut settings = SETTINGS.load(deps.storage, id)?;
+    let prev_settings = settings.clone();
+    settings.parent = parent;
+    SETTINGS.save(deps.storage, id, &settings)?;
+    record_settings_changes(
+        deps.storage,
+        id,
+        &prev_settings,
+        &settings,
+        &info.sender,
+        env.block.height,
+    )?;
+
+    Ok(Response::new().add_attribute("action", "update_endowment_parent"))
+}
+
 pub fn update_endowment_settings(
     deps: DepsMut,
     env: Env,
@@ -175,9 +230,11 @@ pub fn update_endowment_settings(
     let mut settings = SETTINGS
         .load(deps.storage, msg.id)
         .unwrap_or(EndowmentSettings::default());
-    let controller = CONTROLLER
+    let prev_settings = settings.clone();
+    let mut controller = CONTROLLER
         .load(deps.storage, msg.id)
         .unwrap_or(EndowmentController::default(&endow_detail.endow_type));
+    let prev_controller = controller.clone();
 
     let endow_state: angel_core::msgs::accounts::StateResponse = deps.querier.query_wasm_smart(
         accounts_contract,
@@ -303,10 +360,81 @@ pub fn update_endowment_settings(
     }
 
     SETTINGS.save(deps.storage, msg.id, &settings)?;
+    record_settings_changes(
+        deps.storage,
+        msg.id,
+        &prev_settings,
+        &settings,
+        &info.sender,
+        env.block.height,
+    )?;
+
+    // changes made through a delegate count against its usage limit
+    for setting in changed_settings(&prev_settings, &settings) {
+        controller.use_delegate(
+            setting,
+            &info.sender,
+            &endow_detail.owner,
+            settings.dao.as_ref(),
+        );
+    }
+    if controller != prev_controller {
+        save_controller(
+            deps.storage,
+            msg.id,
+            &prev_controller,
+            &controller,
+            &info.sender,
+            env.block.height,
+        )?;
+    }
 
     Ok(Response::new().add_attribute("action", "update_endowment_settings"))
 }
 
+/// Settings that differ between `old` & `new`, by the permission guarding them
+fn changed_settings(old: &EndowmentSettings, new: &EndowmentSettings) -> Vec<EndowmentSetting> {
+    [
+        (
+            EndowmentSetting::EarningsFee,
+            old.earnings_fee != new.earnings_fee,
+        ),
+        (
+            EndowmentSetting::WithdrawFee,
+            old.withdraw_fee != new.withdraw_fee,
+        ),
+        (
+            EndowmentSetting::DepositFee,
+            old.deposit_fee != new.deposit_fee,
+        ),
+        (EndowmentSetting::AumFee, old.aum_fee != new.aum_fee),
+        (
+            EndowmentSetting::BeneficiariesAllowlist,
+            old.beneficiaries_allowlist != new.beneficiaries_allowlist,
+        ),
+        (
+            EndowmentSetting::ContributorsAllowlist,
+            old.contributors_allowlist != new.contributors_allowlist,
+        ),
+        (
+            EndowmentSetting::MaturityAllowlist,
+            old.maturity_allowlist != new.maturity_allowlist,
+        ),
+        (
+            EndowmentSetting::SplitToLiquid,
+            old.split_to_liquid != new.split_to_liquid,
+        ),
+        (
+            EndowmentSetting::IgnoreUserSplits,
+            old.ignore_user_splits != new.ignore_user_splits,
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(setting, _)| setting)
+    .collect()
+}
+
 pub fn update_endowment_controller(
     deps: DepsMut,
     env: Env,
@@ -330,6 +458,7 @@ pub fn update_endowment_controller(
     let mut controller = CONTROLLER
         .load(deps.storage, msg.id)
         .unwrap_or(EndowmentController::default(&endow_detail.endow_type));
+    let prev_controller = controller.clone();
 
     let endow_state: angel_core::msgs::accounts::StateResponse = deps.querier.query_wasm_smart(
         accounts_contract,
@@ -339,88 +468,103 @@ pub fn update_endowment_controller(
         return Err(ContractError::UpdatesAfterClosed {});
     }
 
-    if !controller
-        .get_permissions("endowment_controller".to_string())
-        .unwrap()
-        .can_change(
-            &info.sender,
-            &endow_detail.owner,
-            settings.dao.as_ref(),
-            env.block.time,
-        )
-    {
+    if !controller.endowment_controller.can_change(
+        &info.sender,
+        &endow_detail.owner,
+        settings.dao.as_ref(),
+        env.block.time,
+    ) {
         return Err(ContractError::Unauthorized {});
     }
+    controller.use_delegate(
+        EndowmentSetting::EndowmentController,
+        &info.sender,
+        &endow_detail.owner,
+        settings.dao.as_ref(),
+    );
 
     // update the endowment controller permissions and any other passed fields
     if msg.endowment_controller != None {
         controller.set_permissions(
-            "endowment_controller".to_string(),
+            EndowmentSetting::EndowmentController,
             msg.endowment_controller.unwrap(),
         )?;
     }
     if msg.name != None {
-        controller.set_permissions("name".to_string(), msg.name.unwrap())?;
+        controller.set_permissions(EndowmentSetting::Name, msg.name.unwrap())?;
     }
     if msg.image != None {
-        controller.set_permissions("image".to_string(), msg.image.unwrap())?;
+        controller.set_permissions(EndowmentSetting::Image, msg.image.unwrap())?;
     }
     if msg.logo != None {
-        controller.set_permissions("logo".to_string(), msg.logo.unwrap())?;
+        controller.set_permissions(EndowmentSetting::Logo, msg.logo.unwrap())?;
     }
     if msg.categories != None {
-        controller.set_permissions("categories".to_string(), msg.categories.unwrap())?;
+        controller.set_permissions(EndowmentSetting::Categories, msg.categories.unwrap())?;
     }
     if msg.kyc_donors_only != None {
-        controller.set_permissions("kyc_donors_only".to_string(), msg.kyc_donors_only.unwrap())?;
+        controller.set_permissions(
+            EndowmentSetting::KycDonorsOnly,
+            msg.kyc_donors_only.unwrap(),
+        )?;
     }
     if msg.split_to_liquid != None {
-        controller.set_permissions("split_to_liquid".to_string(), msg.split_to_liquid.unwrap())?;
+        controller.set_permissions(
+            EndowmentSetting::SplitToLiquid,
+            msg.split_to_liquid.unwrap(),
+        )?;
     }
     if msg.ignore_user_splits != None {
         controller.set_permissions(
-            "ignore_user_splits".to_string(),
+            EndowmentSetting::IgnoreUserSplits,
             msg.ignore_user_splits.unwrap(),
         )?;
     }
     if msg.donation_match_active != None {
         controller.set_permissions(
-            "donation_match_active".to_string(),
+            EndowmentSetting::DonationMatchActive,
             msg.donation_match_active.unwrap(),
         )?;
     }
     if msg.beneficiaries_allowlist != None {
         controller.set_permissions(
-            "beneficiaries_allowlist".to_string(),
+            EndowmentSetting::BeneficiariesAllowlist,
             msg.beneficiaries_allowlist.unwrap(),
         )?;
     }
     if msg.contributors_allowlist != None {
         controller.set_permissions(
-            "contributors_allowlist".to_string(),
+            EndowmentSetting::ContributorsAllowlist,
             msg.contributors_allowlist.unwrap(),
         )?;
     }
     if msg.maturity_allowlist != None {
         controller.set_permissions(
-            "maturity_allowlist".to_string(),
+            EndowmentSetting::MaturityAllowlist,
             msg.maturity_allowlist.unwrap(),
         )?;
     }
     if msg.earnings_fee != None {
-        controller.set_permissions("earnings_fee".to_string(), msg.earnings_fee.unwrap())?;
+        controller.set_permissions(EndowmentSetting::EarningsFee, msg.earnings_fee.unwrap())?;
     }
     if msg.deposit_fee != None {
-        controller.set_permissions("deposit_fee".to_string(), msg.deposit_fee.unwrap())?;
+        controller.set_permissions(EndowmentSetting::DepositFee, msg.deposit_fee.unwrap())?;
     }
     if msg.withdraw_fee != None {
-        controller.set_permissions("withdraw_fee".to_string(), msg.withdraw_fee.unwrap())?;
+        controller.set_permissions(EndowmentSetting::WithdrawFee, msg.withdraw_fee.unwrap())?;
     }
     if msg.aum_fee != None {
-        controller.set_permissions("aum_fee".to_string(), msg.aum_fee.unwrap())?;
+        controller.set_permissions(EndowmentSetting::AumFee, msg.aum_fee.unwrap())?;
     }
 
-    CONTROLLER.save(deps.storage, msg.id, &controller)?;
+    save_controller(
+        deps.storage,
+        msg.id,
+        &prev_controller,
+        &controller,
+        &info.sender,
+        env.block.height,
+    )?;
 
     Ok(Response::new().add_attribute("action", "update_endowment_controller"))
 }
@@ -429,12 +573,9 @@ pub fn update_delegate(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    id: u32,
-    setting: String,
-    action: String,
-    delegate_address: String,
-    delegate_expiry: Option<u64>,
+    msg: UpdateDelegateMsg,
 ) -> Result<Response, ContractError> {
+    let id = msg.endowment_id;
     let config = CONFIG.load(deps.storage)?;
     let registrar_config_ext: RegistrarConfigExtensionResponse = deps
         .querier
@@ -451,35 +592,57 @@ pub fn update_delegate(
     let mut controller = CONTROLLER
         .load(deps.storage, id)
         .unwrap_or(EndowmentController::default(&endow_detail.endow_type));
+    let prev_controller = controller.clone();
 
-    // grab the current permissions for the setting of interest
-    let mut permissions = controller.get_permissions(setting.clone())?;
-
-    // update the delegate field appropraitely based on action
-    match action.as_str() {
-        "set" => {
-            permissions.set_delegate(
-                &info.sender,
-                &endow_detail.owner,
-                settings.dao.as_ref(),
-                deps.api.addr_validate(&delegate_address)?,
-                delegate_expiry,
-            );
+    if msg.settings.is_empty() {
+        return Err(ContractError::InvalidInputs {});
+    }
+    let delegate_address = deps.api.addr_validate(&msg.delegate_address)?;
+    for setting in msg.settings {
+        // the controller itself & donation matching can't be handed to a delegate
+        if matches!(
+            setting,
+            EndowmentSetting::EndowmentController | EndowmentSetting::DonationMatchActive
+        ) {
+            return Err(ContractError::InvalidInputs {});
         }
-        "revoke" => {
-            permissions.revoke_delegate(
-                &info.sender,
-                &endow_detail.owner,
-                settings.dao.as_ref(),
-                env.block.time,
-            );
+
+        // grab the current permissions for the setting of interest
+        let mut permissions = controller.get_permissions(setting);
+
+        // update the delegate field appropraitely based on action
+        match msg.action {
+            DelegateAction::Set => {
+                permissions.set_delegate(
+                    &info.sender,
+                    &endow_detail.owner,
+                    settings.dao.as_ref(),
+                    delegate_address.clone(),
+                    msg.delegate_expiry,
+                    msg.delegate_max_uses,
+                );
+            }
+            DelegateAction::Revoke => {
+                permissions.revoke_delegate(
+                    &info.sender,
+                    &endow_detail.owner,
+                    settings.dao.as_ref(),
+                    env.block.time,
+                );
+            }
         }
-        _ => return Err(ContractError::InvalidInputs {}),
-    }
 
-    // save mutated permissions back to Endowment Controller
-    controller.set_permissions(setting, permissions)?;
-    CONTROLLER.save(deps.storage, id, &controller)?;
+        // save mutated permissions back to Endowment Controller
+        controller.set_permissions(setting, permissions)?;
+    }
+    save_controller(
+        deps.storage,
+        id,
+        &prev_controller,
+        &controller,
+        &info.sender,
+        env.block.height,
+    )?;
 
     Ok(Response::default().add_attribute("action", "update_delegate"))
 }
@@ -527,6 +690,7 @@ pub fn setup_dao(
                 id: endowment_id,
                 quorum: msg.quorum,
                 threshold: msg.threshold,
+                veto_threshold: msg.veto_threshold,
                 voting_period: msg.voting_period,
                 timelock_period: msg.timelock_period,
                 expiration_period: msg.expiration_period,
@@ -536,6 +700,9 @@ pub fn setup_dao(
                 endow_type: endow_detail.endow_type,
                 endow_owner: endow_detail.owner.to_string(),
                 registrar_contract: config.registrar_contract.to_string(),
+                deposit_policy: msg.deposit_policy,
+                slash_recipient: msg.slash_recipient,
+                max_open_polls: msg.max_open_polls,
             })?,
             funds: vec![],
         }),
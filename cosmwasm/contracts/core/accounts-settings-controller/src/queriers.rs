@@ -1,11 +1,18 @@
-use crate::state::{CONFIG, CONTROLLER, SETTINGS};
+use crate::state::{CONFIG, CONTROLLER, DELEGATIONS, SETTINGS, SETTINGS_HISTORY};
 use angel_core::msgs::accounts::EndowmentDetailsResponse;
 use angel_core::msgs::accounts_settings_controller::{
-    ConfigResponse, EndowmentPermissionsResponse,
+    ConfigResponse, DelegationResponse, DelegationsResponse, EndowmentPermissionsResponse,
+    EndowmentSettingsHistoryResponse, ListPermissionsResponse, SettingPermissionsResponse,
 };
 use angel_core::msgs::registrar::ConfigExtensionResponse as RegistrarConfigResponse;
-use angel_core::structs::{EndowmentController, EndowmentSettings, EndowmentType};
-use cosmwasm_std::{Addr, Deps, Env, StdResult};
+use angel_core::structs::{
+    EndowmentController, EndowmentSetting, EndowmentSettings, EndowmentType,
+};
+use cosmwasm_std::{Addr, Deps, Env, Order, StdResult};
+use cw_storage_plus::Bound;
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
 
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
@@ -71,64 +78,127 @@ pub fn query_endowment_permissions(
 
     Ok(EndowmentPermissionsResponse {
         endowment_controller: endow_controller
-            .get_permissions("endowment_controller".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::EndowmentController)
             .can_change(&updater, owner, dao_ref, env.block.time),
         split_to_liquid: endow_controller
-            .get_permissions("split_to_liquid".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::SplitToLiquid)
             .can_change(&updater, owner, dao_ref, env.block.time),
         ignore_user_splits: endow_controller
-            .get_permissions("ignore_user_splits".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::IgnoreUserSplits)
             .can_change(&updater, owner, dao_ref, env.block.time),
         beneficiaries_allowlist: endow_controller
-            .get_permissions("beneficiaries_allowlist".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::BeneficiariesAllowlist)
             .can_change(&updater, owner, dao_ref, env.block.time),
         contributors_allowlist: endow_controller
-            .get_permissions("contributors_allowlist".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::ContributorsAllowlist)
             .can_change(&updater, owner, dao_ref, env.block.time),
         maturity_allowlist: endow_controller
-            .get_permissions("maturity_allowlist".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::MaturityAllowlist)
             .can_change(&updater, owner, dao_ref, env.block.time),
         earnings_fee: endow_controller
-            .get_permissions("earnings_fee".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::EarningsFee)
             .can_change(&updater, owner, dao_ref, env.block.time),
         withdraw_fee: endow_controller
-            .get_permissions("withdraw_fee".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::WithdrawFee)
             .can_change(&updater, owner, dao_ref, env.block.time),
         deposit_fee: endow_controller
-            .get_permissions("deposit_fee".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::DepositFee)
             .can_change(&updater, owner, dao_ref, env.block.time),
         aum_fee: endow_controller
-            .get_permissions("aum_fee".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::AumFee)
             .can_change(&updater, owner, dao_ref, env.block.time),
         kyc_donors_only: endow_controller
-            .get_permissions("kyc_donors_only".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::KycDonorsOnly)
             .can_change(&updater, owner, dao_ref, env.block.time),
         name: endow_controller
-            .get_permissions("name".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::Name)
             .can_change(&updater, owner, dao_ref, env.block.time),
         image: endow_controller
-            .get_permissions("image".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::Image)
             .can_change(&updater, owner, dao_ref, env.block.time),
         logo: endow_controller
-            .get_permissions("logo".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::Logo)
             .can_change(&updater, owner, dao_ref, env.block.time),
         categories: endow_controller
-            .get_permissions("categories".to_string())
-            .unwrap()
+            .get_permissions(EndowmentSetting::Categories)
             .can_change(&updater, owner, dao_ref, env.block.time),
     })
 }
+
+pub fn query_list_permissions(deps: Deps, id: u32) -> StdResult<ListPermissionsResponse> {
+    // this fails if no account is found
+    let controller = CONTROLLER.load(deps.storage, id)?;
+
+    Ok(ListPermissionsResponse {
+        permissions: EndowmentSetting::ALL
+            .into_iter()
+            .map(|setting| {
+                let permissions = controller.get_permissions(setting);
+                SettingPermissionsResponse {
+                    setting,
+                    delegate: permissions.delegate().map(|d| d.address().clone()),
+                    delegate_expiry: permissions.delegate().and_then(|d| d.expires()),
+                    delegate_uses_remaining: permissions
+                        .delegate()
+                        .and_then(|d| d.uses_remaining()),
+                    permissions,
+                }
+            })
+            .collect(),
+    })
+}
+
+pub fn query_endowment_settings_history(
+    deps: Deps,
+    id: u32,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<EndowmentSettingsHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let changes = SETTINGS_HISTORY
+        .prefix(id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, change)| change))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(EndowmentSettingsHistoryResponse { changes })
+}
+
+pub fn query_delegations(
+    deps: Deps,
+    delegate: String,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<DelegationsResponse> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let endowment_ids = DELEGATIONS
+        .prefix(&delegate)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<u32>>>()?;
+
+    let mut delegations = vec![];
+    for endowment_id in endowment_ids {
+        let controller = CONTROLLER.load(deps.storage, endowment_id)?;
+        for setting in EndowmentSetting::ALL {
+            let permissions = controller.get_permissions(setting);
+            if let Some(d) = permissions
+                .delegate()
+                .filter(|d| *d.address() == delegate && d.uses_remaining() != Some(0))
+            {
+                delegations.push(DelegationResponse {
+                    endowment_id,
+                    setting,
+                    expires: d.expires(),
+                    uses_remaining: d.uses_remaining(),
+                });
+            }
+        }
+    }
+
+    Ok(DelegationsResponse { delegations })
+}
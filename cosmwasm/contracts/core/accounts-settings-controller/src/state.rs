@@ -1,7 +1,9 @@
-use angel_core::structs::{EndowmentController, EndowmentSettings};
-use cosmwasm_schema::{cw_serde};
-use cosmwasm_std::Addr;
+use angel_core::msgs::accounts_settings_controller::SettingsChange;
+use angel_core::structs::{EndowmentController, EndowmentSetting, EndowmentSettings};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_vec, Addr, Empty, StdResult, Storage};
 use cw_storage_plus::{Item, Map};
+use serde::Serialize;
 
 #[cw_serde]
 pub struct Config {
@@ -12,3 +14,236 @@ pub struct Config {
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const SETTINGS: Map<u32, EndowmentSettings> = Map::new("endowment-settings");
 pub const CONTROLLER: Map<u32, EndowmentController> = Map::new("endowment-controller");
+// audit log of every settings/permissions mutation, keyed by (endowment ID, sequence)
+pub const SETTINGS_HISTORY: Map<(u32, u64), SettingsChange> = Map::new("settings-history");
+pub const SETTINGS_HISTORY_SEQ: Item<u64> = Item::new("settings-history-seq");
+// index of the Endowments an address is a delegate on, keyed by (delegate, endowment ID)
+pub const DELEGATIONS: Map<(&Addr, u32), Empty> = Map::new("delegations");
+
+/// Appends a change to the audit log, if the value actually changed
+pub fn record_change<T: Serialize + PartialEq>(
+    storage: &mut dyn Storage,
+    id: u32,
+    field: &str,
+    old_value: &T,
+    new_value: &T,
+    sender: &Addr,
+    height: u64,
+) -> StdResult<()> {
+    if old_value == new_value {
+        return Ok(());
+    }
+    let seq = SETTINGS_HISTORY_SEQ.may_load(storage)?.unwrap_or_default() + 1;
+    SETTINGS_HISTORY_SEQ.save(storage, &seq)?;
+    SETTINGS_HISTORY.save(
+        storage,
+        (id, seq),
+        &SettingsChange {
+            seq,
+            id,
+            field: field.to_string(),
+            old_value: String::from_utf8_lossy(&to_vec(old_value)?).to_string(),
+            new_value: String::from_utf8_lossy(&to_vec(new_value)?).to_string(),
+            sender: sender.clone(),
+            height,
+        },
+    )
+}
+
+pub fn record_settings_changes(
+    storage: &mut dyn Storage,
+    id: u32,
+    old: &EndowmentSettings,
+    new: &EndowmentSettings,
+    sender: &Addr,
+    height: u64,
+) -> StdResult<()> {
+    record_change(storage, id, "dao", &old.dao, &new.dao, sender, height)?;
+    record_change(
+        storage,
+        id,
+        "dao_token",
+        &old.dao_token,
+        &new.dao_token,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "donation_match_active",
+        &old.donation_match_active,
+        &new.donation_match_active,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "donation_match_contract",
+        &old.donation_match_contract,
+        &new.donation_match_contract,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "beneficiaries_allowlist",
+        &old.beneficiaries_allowlist,
+        &new.beneficiaries_allowlist,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "contributors_allowlist",
+        &old.contributors_allowlist,
+        &new.contributors_allowlist,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "maturity_allowlist",
+        &old.maturity_allowlist,
+        &new.maturity_allowlist,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "earnings_fee",
+        &old.earnings_fee,
+        &new.earnings_fee,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "withdraw_fee",
+        &old.withdraw_fee,
+        &new.withdraw_fee,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "deposit_fee",
+        &old.deposit_fee,
+        &new.deposit_fee,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "aum_fee",
+        &old.aum_fee,
+        &new.aum_fee,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "parent",
+        &old.parent,
+        &new.parent,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "split_to_liquid",
+        &old.split_to_liquid,
+        &new.split_to_liquid,
+        sender,
+        height,
+    )?;
+    record_change(
+        storage,
+        id,
+        "ignore_user_splits",
+        &old.ignore_user_splits,
+        &new.ignore_user_splits,
+        sender,
+        height,
+    )
+}
+
+pub fn record_controller_changes(
+    storage: &mut dyn Storage,
+    id: u32,
+    old: &EndowmentController,
+    new: &EndowmentController,
+    sender: &Addr,
+    height: u64,
+) -> StdResult<()> {
+    for setting in EndowmentSetting::ALL {
+        record_change(
+            storage,
+            id,
+            &format!("permissions.{}", setting),
+            &old.get_permissions(setting),
+            &new.get_permissions(setting),
+            sender,
+            height,
+        )?;
+    }
+    Ok(())
+}
+
+/// Saves an Endowment's controller, recording the permission changes and keeping the
+/// delegations index in sync
+pub fn save_controller(
+    storage: &mut dyn Storage,
+    id: u32,
+    old: &EndowmentController,
+    new: &EndowmentController,
+    sender: &Addr,
+    height: u64,
+) -> StdResult<()> {
+    CONTROLLER.save(storage, id, new)?;
+    record_controller_changes(storage, id, old, new, sender, height)?;
+    sync_delegations(storage, id, Some(old), new)
+}
+
+pub fn sync_delegations(
+    storage: &mut dyn Storage,
+    id: u32,
+    old: Option<&EndowmentController>,
+    new: &EndowmentController,
+) -> StdResult<()> {
+    let delegates = |controller: &EndowmentController| -> Vec<Addr> {
+        EndowmentSetting::ALL
+            .into_iter()
+            .filter_map(|setting| {
+                // a delegate that used up its changes can no longer act on the setting
+                controller
+                    .get_permissions(setting)
+                    .delegate()
+                    .filter(|d| d.uses_remaining() != Some(0))
+                    .map(|d| d.address().clone())
+            })
+            .collect()
+    };
+    let new_delegates = delegates(new);
+    if let Some(old) = old {
+        for delegate in delegates(old) {
+            if !new_delegates.contains(&delegate) {
+                DELEGATIONS.remove(storage, (&delegate, id));
+            }
+        }
+    }
+    for delegate in new_delegates.iter() {
+        DELEGATIONS.save(storage, (delegate, id), &Empty {})?;
+    }
+    Ok(())
+}
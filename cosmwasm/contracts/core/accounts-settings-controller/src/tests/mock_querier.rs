@@ -246,6 +246,7 @@ impl WasmMockQuerier {
                             pending_redemptions: 0,
                             proposal_link: None,
                             referral_id: None,
+                            parent: None,
                         })
                         .unwrap(),
                     )),
@@ -268,6 +269,7 @@ impl WasmMockQuerier {
                             pending_redemptions: 0,
                             proposal_link: None,
                             referral_id: None,
+                            parent: None,
                         })
                         .unwrap(),
                     )),
@@ -3,15 +3,20 @@ use crate::contract::{execute, instantiate, migrate, query, reply};
 use angel_core::errors::core::*;
 
 use angel_core::msgs::accounts_settings_controller::{
-    ConfigResponse, EndowmentPermissionsResponse, EndowmentSettingsResponse,
+    ConfigResponse, DelegationResponse, DelegationsResponse, EndowmentPermissionsResponse,
+    EndowmentSettingsHistoryResponse, EndowmentSettingsResponse, ListPermissionsResponse,
 };
 use angel_core::msgs::accounts_settings_controller::{
     CreateEndowSettingsMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpdateConfigMsg,
-    UpdateEndowmentSettingsMsg, UpdateMaturityAllowlist,
+    UpdateDelegateMsg, UpdateEndowmentSettingsMsg, UpdateMaturityAllowlist,
+};
+use angel_core::structs::{
+    DelegateAction, EndowmentController, EndowmentFee, EndowmentSetting, EndowmentType,
 };
-use angel_core::structs::{EndowmentController, EndowmentFee, EndowmentType};
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{from_binary, Addr, Decimal, Event, Reply, StdError, SubMsgResponse, Uint128};
+use cosmwasm_std::{
+    from_binary, from_slice, Addr, Decimal, Event, Reply, StdError, SubMsgResponse, Uint128,
+};
 
 const AP_TEAM: &str = "juno1rcznds2le2eflj3y4e8ep3e4upvq04sc65wdly";
 const ENDOW_ID: u32 = 1;
@@ -192,6 +197,38 @@ fn test_create_endowment_settings() {
     assert_eq!(endow_settings.split_to_liquid, msg.split_to_liquid);
     assert_eq!(endow_settings.ignore_user_splits, msg.ignore_user_splits);
     assert_eq!(endow_settings.earnings_fee, msg.earnings_fee);
+
+    // Only the "accounts_contract" can re-parent an Endowment
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(PLEB, &[]),
+        ExecuteMsg::UpdateEndowmentParent {
+            id: ENDOW_ID,
+            parent: Some(3),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("accounts-contract", &[]),
+        ExecuteMsg::UpdateEndowmentParent {
+            id: ENDOW_ID,
+            parent: Some(3),
+        },
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::EndowmentSettings { id: ENDOW_ID },
+    )
+    .unwrap();
+    let endow_settings: EndowmentSettingsResponse = from_binary(&res).unwrap();
+    assert_eq!(endow_settings.parent, Some(3));
 }
 
 #[test]
@@ -316,6 +353,79 @@ fn test_update_endowment_settings() {
     assert_eq!(endow_settings.earnings_fee, msg.earnings_fee);
     assert_eq!(endow_settings.deposit_fee, msg.deposit_fee);
     assert_eq!(endow_settings.withdraw_fee, msg.withdraw_fee);
+
+    // Every changed field is recorded in the settings history
+    let history: EndowmentSettingsHistoryResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::EndowmentSettingsHistory {
+                id: ENDOW_ID,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let fields: Vec<&str> = history.changes.iter().map(|c| c.field.as_str()).collect();
+    assert_eq!(
+        fields,
+        vec![
+            "beneficiaries_allowlist",
+            "contributors_allowlist",
+            "earnings_fee",
+            "withdraw_fee",
+            "deposit_fee",
+            "aum_fee",
+            "ignore_user_splits",
+        ]
+    );
+    let change = &history.changes[0];
+    assert_eq!(change.old_value, format!("[\"{}\"]", PLEB));
+    assert_eq!(change.new_value, format!("[\"{}\",\"{}\"]", AP_TEAM, PLEB));
+    assert_eq!(change.sender, Addr::unchecked("endowment-owner"));
+    assert_eq!(change.height, mock_env().block.height);
+
+    // History is paginated by the change sequence
+    let history: EndowmentSettingsHistoryResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::EndowmentSettingsHistory {
+                id: ENDOW_ID,
+                start_after: Some(history.changes[4].seq),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(history.changes.len(), 1);
+    assert_eq!(history.changes[0].field, "aum_fee");
+
+    // Re-sending the same settings records nothing new
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("endowment-owner", &[]),
+        ExecuteMsg::UpdateEndowmentSettings(msg),
+    )
+    .unwrap();
+    let history: EndowmentSettingsHistoryResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::EndowmentSettingsHistory {
+                id: ENDOW_ID,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(history.changes.len(), 7);
 }
 
 #[test]
@@ -355,55 +465,353 @@ fn test_update_delegate() {
     )
     .unwrap();
 
-    // "setting" name should be correct
+    // "setting" name & "action" are checked when the message is parsed
+    let err = from_slice::<ExecuteMsg>(
+        br#"{"update_delegate":{"endowment_id":1,"settings":["any-fee"],"action":"set","delegate_address":"new-delegate-address","delegate_expiry":null,"delegate_max_uses":null}}"#,
+    );
+    assert!(err.is_err());
+    let err = from_slice::<ExecuteMsg>(
+        br#"{"update_delegate":{"endowment_id":1,"settings":["aum_fee"],"action":"blahblah","delegate_address":"new-delegate-address","delegate_expiry":null,"delegate_max_uses":null}}"#,
+    );
+    assert!(err.is_err());
+    let msg = from_slice::<ExecuteMsg>(
+        br#"{"update_delegate":{"endowment_id":1,"settings":["aum_fee"],"action":"set","delegate_address":"new-delegate-address","delegate_expiry":null,"delegate_max_uses":null}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        msg,
+        ExecuteMsg::UpdateDelegate(UpdateDelegateMsg {
+            endowment_id: ENDOW_ID,
+            settings: vec![EndowmentSetting::AumFee],
+            action: DelegateAction::Set,
+            delegate_address: "new-delegate-address".to_string(),
+            delegate_expiry: None,
+            delegate_max_uses: None,
+        })
+    );
+
+    // The controller & donation matching can't be delegated
+    for setting in [
+        EndowmentSetting::EndowmentController,
+        EndowmentSetting::DonationMatchActive,
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("endowment-owner", &[]),
+            ExecuteMsg::UpdateDelegate(UpdateDelegateMsg {
+                endowment_id: ENDOW_ID,
+                settings: vec![EndowmentSetting::AumFee, setting],
+                action: DelegateAction::Set,
+                delegate_address: "new-delegate-address".to_string(),
+                delegate_expiry: None,
+                delegate_max_uses: None,
+            }),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidInputs {});
+    }
+
+    // Succeed to update the settings
     let info = mock_info("endowment-owner", &[]);
-    let _err = execute(
+    let res = execute(
         deps.as_mut(),
         mock_env(),
         info,
-        ExecuteMsg::UpdateDelegate {
+        ExecuteMsg::UpdateDelegate(UpdateDelegateMsg {
             endowment_id: ENDOW_ID,
-            setting: "any-fee".to_string(),
-            action: "set".to_string(),
+            settings: vec![EndowmentSetting::AumFee],
+            action: DelegateAction::Set,
             delegate_address: "new-delegate-address".to_string(),
-            delegate_expiry: None,
-        },
+            delegate_expiry: Some(1_700_000_000),
+            delegate_max_uses: None,
+        }),
     )
-    .unwrap_err();
+    .unwrap();
+    assert_eq!(0, res.messages.len());
 
-    // "action" should be either of "set" or "revoke"
-    let info = mock_info("endowment-owner", &[]);
-    let err = execute(
+    // Every setting's permissions are listed, with the delegate of "aum_fee"
+    let res: ListPermissionsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ListPermissions { id: ENDOW_ID },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.permissions.len(), EndowmentSetting::ALL.len());
+    for item in res.permissions {
+        if item.setting == EndowmentSetting::AumFee {
+            assert_eq!(item.delegate, Some(Addr::unchecked("new-delegate-address")));
+            assert_eq!(item.delegate_expiry, Some(1_700_000_000));
+        } else {
+            assert_eq!(item.delegate, None);
+        }
+    }
+
+    // The permission change is recorded in the settings history
+    let history: EndowmentSettingsHistoryResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::EndowmentSettingsHistory {
+                id: ENDOW_ID,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(history.changes.len(), 1);
+    assert_eq!(history.changes[0].field, "permissions.aum_fee");
+    assert_eq!(
+        history.changes[0].sender,
+        Addr::unchecked("endowment-owner")
+    );
+}
+
+#[test]
+fn test_scoped_delegate() {
+    // Instantiate the contract
+    let mut deps = mock_dependencies(&[]);
+    let instantiate_msg = InstantiateMsg {
+        owner_sc: AP_TEAM.to_string(),
+        registrar_contract: REGISTRAR_CONTRACT.to_string(),
+    };
+    let info = mock_info(AP_TEAM, &[]);
+    let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+    // Succeed to create EndowmentSettings
+    let msg = CreateEndowSettingsMsg {
+        id: ENDOW_ID,
+        donation_match_active: false,
+        donation_match_contract: None,
+        beneficiaries_allowlist: vec![],
+        contributors_allowlist: vec![],
+        maturity_allowlist: vec![],
+        endowment_controller: EndowmentController::default(&EndowmentType::Normal),
+        parent: None,
+        split_to_liquid: None,
+        ignore_user_splits: false,
+        earnings_fee: None,
+        deposit_fee: None,
+        withdraw_fee: None,
+        aum_fee: None,
+    };
+    let _ = execute(
         deps.as_mut(),
         mock_env(),
-        info,
-        ExecuteMsg::UpdateDelegate {
+        mock_info("accounts-contract", &[]),
+        ExecuteMsg::CreateEndowmentSettings(msg),
+    )
+    .unwrap();
+
+    // One grant covers both the allowlist & the AUM fee, valid for 100 seconds & 1 change each
+    let expiry = mock_env().block.time.seconds() + 100;
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("endowment-owner", &[]),
+        ExecuteMsg::UpdateDelegate(UpdateDelegateMsg {
             endowment_id: ENDOW_ID,
-            setting: "aum_fee".to_string(),
-            action: "blahblah".to_string(),
-            delegate_address: "new-delegate-address".to_string(),
-            delegate_expiry: None,
-        },
+            settings: vec![
+                EndowmentSetting::BeneficiariesAllowlist,
+                EndowmentSetting::AumFee,
+            ],
+            action: DelegateAction::Set,
+            delegate_address: "ops-lead".to_string(),
+            delegate_expiry: Some(expiry),
+            delegate_max_uses: Some(1),
+        }),
+    )
+    .unwrap();
+
+    let delegations: DelegationsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Delegations {
+                delegate: "ops-lead".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        delegations.delegations,
+        vec![
+            DelegationResponse {
+                endowment_id: ENDOW_ID,
+                setting: EndowmentSetting::BeneficiariesAllowlist,
+                expires: Some(expiry),
+                uses_remaining: Some(1),
+            },
+            DelegationResponse {
+                endowment_id: ENDOW_ID,
+                setting: EndowmentSetting::AumFee,
+                expires: Some(expiry),
+                uses_remaining: Some(1),
+            },
+        ]
+    );
+
+    // The delegate can change the allowlist once, before the expiry
+    let mut msg = UpdateEndowmentSettingsMsg {
+        id: ENDOW_ID,
+        donation_match_active: None,
+        beneficiaries_allowlist: Some(vec![PLEB.to_string()]),
+        contributors_allowlist: None,
+        maturity_allowlist: None,
+        split_to_liquid: None,
+        ignore_user_splits: None,
+        earnings_fee: None,
+        deposit_fee: None,
+        withdraw_fee: None,
+        aum_fee: None,
+    };
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("ops-lead", &[]),
+        ExecuteMsg::UpdateEndowmentSettings(msg.clone()),
+    )
+    .unwrap();
+    msg.beneficiaries_allowlist = Some(vec![AP_TEAM.to_string()]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("ops-lead", &[]),
+        ExecuteMsg::UpdateEndowmentSettings(msg.clone()),
     )
     .unwrap_err();
-    assert_eq!(err, ContractError::InvalidInputs {});
+    assert_eq!(err, ContractError::Unauthorized {});
+    let delegations: DelegationsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Delegations {
+                delegate: "ops-lead".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        delegations.delegations,
+        vec![DelegationResponse {
+            endowment_id: ENDOW_ID,
+            setting: EndowmentSetting::AumFee,
+            expires: Some(expiry),
+            uses_remaining: Some(1),
+        }]
+    );
 
-    // Succeed to update the settings
-    let info = mock_info("endowment-owner", &[]);
-    let res = execute(
+    // The AUM fee still has its own use left, but not after the expiry
+    msg.beneficiaries_allowlist = None;
+    msg.aum_fee = Some(EndowmentFee {
+        payout_address: Addr::unchecked("ops-lead"),
+        fee_percentage: Decimal::percent(1),
+        active: true,
+    });
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let _ = execute(
+        deps.as_mut(),
+        env,
+        mock_info("ops-lead", &[]),
+        ExecuteMsg::UpdateEndowmentSettings(msg.clone()),
+    )
+    .unwrap();
+    let endow_settings: EndowmentSettingsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::EndowmentSettings { id: ENDOW_ID },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        endow_settings.beneficiaries_allowlist,
+        vec![PLEB.to_string()]
+    );
+    assert_eq!(endow_settings.aum_fee, None);
+
+    let _ = execute(
         deps.as_mut(),
         mock_env(),
-        info,
-        ExecuteMsg::UpdateDelegate {
+        mock_info("ops-lead", &[]),
+        ExecuteMsg::UpdateEndowmentSettings(msg),
+    )
+    .unwrap();
+    let endow_settings: EndowmentSettingsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::EndowmentSettings { id: ENDOW_ID },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        endow_settings.aum_fee.unwrap().fee_percentage,
+        Decimal::percent(1)
+    );
+
+    // With both changes used up the delegate is dropped from the delegations index
+    let delegations: DelegationsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Delegations {
+                delegate: "ops-lead".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(delegations.delegations.is_empty());
+
+    // Revoking the grant clears the delegations index
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("endowment-owner", &[]),
+        ExecuteMsg::UpdateDelegate(UpdateDelegateMsg {
             endowment_id: ENDOW_ID,
-            setting: "aum_fee".to_string(),
-            action: "set".to_string(),
-            delegate_address: "new-delegate-address".to_string(),
+            settings: vec![
+                EndowmentSetting::BeneficiariesAllowlist,
+                EndowmentSetting::AumFee,
+            ],
+            action: DelegateAction::Revoke,
+            delegate_address: "ops-lead".to_string(),
             delegate_expiry: None,
-        },
+            delegate_max_uses: None,
+        }),
     )
     .unwrap();
-    assert_eq!(0, res.messages.len());
+    let delegations: DelegationsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Delegations {
+                delegate: "ops-lead".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(delegations.delegations.is_empty());
 }
 
 #[test]
@@ -454,6 +862,7 @@ fn test_setup_dao() {
             setup: angel_core::structs::DaoSetup {
                 quorum: Decimal::percent(10),
                 threshold: Decimal::percent(50),
+                veto_threshold: Some(Decimal::percent(33)),
                 voting_period: 300,
                 timelock_period: 200,
                 expiration_period: 500,
@@ -464,6 +873,9 @@ fn test_setup_dao() {
                     name: "New cw20".to_string(),
                     symbol: "NC2".to_string(),
                 },
+                deposit_policy: None,
+                slash_recipient: None,
+                max_open_polls: None,
             },
         },
     )
@@ -481,6 +893,7 @@ fn test_setup_dao() {
             setup: angel_core::structs::DaoSetup {
                 quorum: Decimal::percent(10),
                 threshold: Decimal::percent(50),
+                veto_threshold: None,
                 voting_period: 300,
                 timelock_period: 200,
                 expiration_period: 500,
@@ -491,6 +904,9 @@ fn test_setup_dao() {
                     name: "New cw20".to_string(),
                     symbol: "NC2".to_string(),
                 },
+                deposit_policy: None,
+                slash_recipient: None,
+                max_open_polls: None,
             },
         },
     )
@@ -1,12 +1,15 @@
 use crate::executers;
 use crate::queriers;
-use crate::state::{Config, Endowment, OldEndowment, CONFIG, ENDOWMENTS};
+use crate::state::{
+    add_strategy_position, Config, Endowment, OldEndowment, CHILDREN, CONFIG, ENDOWMENTS,
+    STRATEGY_POSITIONS,
+};
 use angel_core::errors::core::ContractError;
 use angel_core::msgs::accounts::*;
 use angel_core::structs::Investments;
 use cosmwasm_std::{
-    entry_point, from_binary, from_slice, to_binary, Binary, Deps, DepsMut, Env, MessageInfo,
-    Reply, Response, StdError, StdResult,
+    entry_point, from_binary, from_slice, to_binary, Binary, Deps, DepsMut, Empty, Env,
+    MessageInfo, Reply, Response, StdError, StdResult,
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw20::Cw20ReceiveMsg;
@@ -33,6 +36,7 @@ pub fn instantiate(
             registrar_contract: deps.api.addr_validate(&msg.registrar_contract)?,
             next_account_id: 1_u32,
             max_general_category_id: 1_u8,
+            referral: None,
         },
     )?;
 
@@ -118,10 +122,22 @@ pub fn execute(
         ExecuteMsg::StrategiesRedeem { id, strategies } => {
             executers::strategies_redeem(deps, env, info, id, strategies)
         }
+        ExecuteMsg::StrategiesClaim { id, strategies } => {
+            executers::strategies_claim(deps, info, id, strategies)
+        }
+        ExecuteMsg::ReinvestToLocked {
+            id,
+            amount,
+            source_strategy,
+            target_strategy,
+        } => {
+            executers::reinvest_to_locked(deps, info, id, amount, source_strategy, target_strategy)
+        }
         ExecuteMsg::UpdateConfig {
             new_owner,
             new_registrar,
             max_general_category_id,
+            referral,
         } => executers::update_config(
             deps,
             env,
@@ -129,6 +145,7 @@ pub fn execute(
             new_owner,
             new_registrar,
             max_general_category_id,
+            referral,
         ),
         ExecuteMsg::CloseEndowment { id, beneficiary } => {
             executers::close_endowment(deps, env, info, id, beneficiary)
@@ -155,6 +172,16 @@ pub fn execute(
             endowment_id,
             asset,
         } => executers::spend_allowance(deps, env, info, endowment_id, asset),
+        ExecuteMsg::AllocateToChild {
+            child_id,
+            acct_type,
+            assets,
+        } => executers::allocate_to_child(deps, info, child_id, acct_type, assets),
+        ExecuteMsg::SweepFromChild {
+            child_id,
+            acct_type,
+            assets,
+        } => executers::sweep_from_child(deps, info, child_id, acct_type, assets),
     }
 }
 
@@ -210,6 +237,7 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
         0 => executers::cw3_reply(deps, env, msg.result),
         // 1 => executers::dao_reply(deps, env, msg.result),
         // 2 => executers::donation_match_reply(deps, env, msg.result),
+        3 => executers::reinvest_reply(deps, env, msg.result),
         _ => Err(ContractError::Unauthorized {}),
     }
 }
@@ -223,9 +251,24 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             &queriers::query_endowment_by_proposal_link(deps, proposal_link)?,
         ),
         QueryMsg::Endowment { id } => to_binary(&queriers::query_endowment_details(deps, id)?),
+        QueryMsg::Endowments(msg) => to_binary(&queriers::query_endowments(deps, msg)?),
         QueryMsg::Allowances { id, spender } => {
             to_binary(&queriers::query_allowances(deps, id, spender)?)
         }
+        QueryMsg::StrategyPositions { strategy_key } => {
+            to_binary(&queriers::query_strategy_positions(deps, strategy_key)?)
+        }
+        QueryMsg::FamilyBalance { id } => to_binary(&queriers::query_family_balance(deps, id)?),
+        QueryMsg::Referrals {
+            referrer_id,
+            start_after,
+            limit,
+        } => to_binary(&queriers::query_referrals(
+            deps,
+            referrer_id,
+            start_after,
+            limit,
+        )?),
     }
 }
 
@@ -255,36 +298,56 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
         .ok_or_else(|| StdError::not_found("Config not found"))?;
     let config: Config = from_slice(&data)?;
 
-    // setup the new Endowment struct and save to storage for all existing Accounts
+    // re-save all existing Endowments to build up their secondary indexes (owner, status, sdg &
+    // proposal link) & recount the strategy positions and child Endowments they hold
+    STRATEGY_POSITIONS.clear(deps.storage);
+    CHILDREN.clear(deps.storage);
     for endow_id in 1..config.next_account_id {
         let key = ENDOWMENTS.key(endow_id);
         let data = deps.storage.get(&key).ok_or_else(|| {
             StdError::not_found(format!("Endowment not found for ID {}", endow_id))
         })?;
-        let old_endow: OldEndowment = from_slice(&data)?;
-        ENDOWMENTS.save(
-            deps.storage,
-            endow_id,
-            &Endowment {
-                owner: old_endow.owner,
-                name: old_endow.name,
-                categories: old_endow.categories,
-                tier: old_endow.tier,
-                endow_type: old_endow.endow_type,
-                logo: old_endow.logo,
-                image: old_endow.image,
-                status: old_endow.status,
-                deposit_approved: old_endow.deposit_approved,
-                withdraw_approved: old_endow.withdraw_approved,
-                maturity_time: old_endow.maturity_time,
-                rebalance: old_endow.rebalance,
-                kyc_donors_only: old_endow.kyc_donors_only,
-                pending_redemptions: old_endow.pending_redemptions,
-                proposal_link: old_endow.proposal_link,
-                invested_strategies: Investments::default(),
-                referral_id: None,
-            },
-        )?;
+        // Endowments stored before v2.0.3 still have the old layout
+        let endowment = match from_slice::<Endowment>(&data) {
+            Ok(endowment) => endowment,
+            Err(_) => {
+                let old_endow: OldEndowment = from_slice(&data)?;
+                Endowment {
+                    owner: old_endow.owner,
+                    name: old_endow.name,
+                    categories: old_endow.categories,
+                    tier: old_endow.tier,
+                    endow_type: old_endow.endow_type,
+                    logo: old_endow.logo,
+                    image: old_endow.image,
+                    status: old_endow.status,
+                    deposit_approved: old_endow.deposit_approved,
+                    withdraw_approved: old_endow.withdraw_approved,
+                    maturity_time: old_endow.maturity_time,
+                    rebalance: old_endow.rebalance,
+                    kyc_donors_only: old_endow.kyc_donors_only,
+                    pending_redemptions: old_endow.pending_redemptions,
+                    proposal_link: old_endow.proposal_link,
+                    invested_strategies: Investments::default(),
+                    referral_id: None,
+                    parent: None,
+                }
+            }
+        };
+        for strategy_key in endowment
+            .invested_strategies
+            .locked
+            .iter()
+            .chain(endowment.invested_strategies.liquid.iter())
+        {
+            add_strategy_position(deps.storage, strategy_key)?;
+        }
+        if let Some(parent) = endowment.parent {
+            CHILDREN.save(deps.storage, (parent, endow_id), &Empty {})?;
+        }
+        // drop the stored record first, as saving to the indexed map loads what it replaces
+        deps.storage.remove(&key);
+        ENDOWMENTS.save(deps.storage, endow_id, &endowment)?;
     }
 
     Ok(Response::default())
@@ -1,8 +1,13 @@
 use crate::ibc::{AxelarGeneralMessage, MsgTransfer};
-use crate::state::{Endowment, State, ALLOWANCES, CONFIG, ENDOWMENTS, STATES};
+use crate::state::{
+    add_strategy_position, load_children, remove_strategy_position, Endowment, PendingReinvest,
+    Referral, State, ALLOWANCES, CHILDREN, CONFIG, ENDOWMENTS, PENDING_REINVEST, REFERRALS,
+    REFERRAL_REWARDS, STATES,
+};
 use angel_core::errors::core::ContractError;
 use angel_core::msgs::accounts::*;
 use angel_core::msgs::accounts_settings_controller::CreateEndowSettingsMsg;
+use angel_core::msgs::accounts_settings_controller::ExecuteMsg as SettingsControllerExecuteMsg;
 use angel_core::msgs::accounts_settings_controller::{
     EndowmentPermissionsResponse, EndowmentSettingsResponse,
 };
@@ -14,15 +19,18 @@ use angel_core::msgs::registrar::{
 };
 use angel_core::msgs::swap_router::ExecuteMsg as SwapRouterExecuteMsg;
 use angel_core::structs::{
-    AccountType, Allowances, BalanceInfo, Beneficiary, DonationsReceived, EndowmentController,
-    EndowmentFee, EndowmentStatus, EndowmentType, GenericBalance, Investments, RebalanceDetails,
-    SplitDetails, StrategyApprovalState, StrategyInvestment, StrategyLocale, StrategyParams,
-    SwapOperation, VaultActionData,
+    AccountType, Allowances, BalanceInfo, Beneficiary, ChildEndowmentsOnClose, DonationsReceived,
+    EndowmentController, EndowmentFee, EndowmentStatus, EndowmentType, GenericBalance, Investments,
+    RebalanceDetails, ReferralConfig, SplitDetails, StrategyApprovalState, StrategyInvestment,
+    StrategyLocale, StrategyParams, SwapOperation, VaultActionData,
+};
+use angel_core::utils::{
+    check_splits, percentage_checks, validate_deposit_fund, vault_endowment_balance,
 };
-use angel_core::utils::{check_splits, validate_deposit_fund, vault_endowment_balance};
 use cosmwasm_std::{
-    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, QueryRequest,
-    ReplyOn, Response, StdError, SubMsg, SubMsgResult, Uint128, WasmMsg, WasmQuery,
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Empty, Env, MessageInfo,
+    QueryRequest, ReplyOn, Response, StdError, StdResult, Storage, SubMsg, SubMsgResult, Uint128,
+    WasmMsg, WasmQuery,
 };
 use cw20::{Balance, Cw20Coin, Cw20CoinVerified, Cw20ExecuteMsg};
 use cw4::Member;
@@ -66,7 +74,7 @@ pub fn cw3_reply(deps: DepsMut, _env: Env, msg: SubMsgResult) -> Result<Response
 
 pub fn create_endowment(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     mut msg: CreateEndowmentMsg,
 ) -> Result<Response, ContractError> {
@@ -78,6 +86,24 @@ pub fn create_endowment(
             msg: to_binary(&RegistrarQuerier::ConfigExtension {})?,
         }))?;
 
+    // Child endowments are created by the owner of a top-level parent of the same type
+    // (Charity children go through the CW3 Review Applications like any other Charity)
+    if let Some(parent_id) = msg.parent {
+        let parent = ENDOWMENTS.load(deps.storage, parent_id)?;
+        if msg.endow_type != EndowmentType::Charity && info.sender != parent.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        if parent.status == EndowmentStatus::Closed {
+            return Err(ContractError::AccountClosed {});
+        }
+        if parent.parent.is_some() {
+            return Err(ContractError::NestedChildEndowment {});
+        }
+        if parent.endow_type != msg.endow_type {
+            return Err(ContractError::InvalidInputs {});
+        }
+    }
+
     // Charity endowments must be created through the CW3 Review Applications
     match msg.endow_type {
         EndowmentType::Charity => {
@@ -134,9 +160,28 @@ pub fn create_endowment(
                 image: msg.image.clone(),
                 proposal_link: msg.proposal_link,
                 referral_id: msg.referral_id,
+                parent: msg.parent,
             }),
         },
     )?;
+    if let Some(parent_id) = msg.parent {
+        CHILDREN.save(deps.storage, (parent_id, config.next_account_id), &Empty {})?;
+    }
+    // referrers earn rewards for the referral period configured at the time of creation
+    // (without one configured, the period starts with the first reward instead)
+    if let Some(referrer_id) = msg.referral_id {
+        ENDOWMENTS.load(deps.storage, referrer_id)?;
+        if let Some(referral) = config.referral.as_ref() {
+            REFERRALS.save(
+                deps.storage,
+                (referrer_id, config.next_account_id),
+                &Referral {
+                    expires_at: env.block.time.seconds() + referral.period,
+                    rewards: GenericBalance::default(),
+                },
+            )?;
+        }
+    }
 
     STATES.save(
         deps.storage,
@@ -333,9 +378,25 @@ pub fn update_endowment_status(
         // Has been liquidated or terminated. Remove from Funds and lockdown money flows
         EndowmentStatus::Closed => {
             // set a Beneficiary for the newly closed Endowment to send all funds to
+            let open_parent = match endowment.parent {
+                Some(parent_id)
+                    if ENDOWMENTS.load(deps.storage, parent_id)?.status
+                        != EndowmentStatus::Closed =>
+                {
+                    Some(parent_id)
+                }
+                _ => None,
+            };
             let beneficiary: Beneficiary;
             if msg.beneficiary.is_some() {
                 beneficiary = msg.beneficiary.unwrap();
+                // a closing Endowment cannot be the beneficiary of its own funds
+                if beneficiary == (Beneficiary::Endowment { id: endowment_id }) {
+                    return Err(ContractError::InvalidInputs {});
+                }
+            } else if let Some(parent_id) = open_parent {
+                // child Endowments fall back to their parent
+                beneficiary = Beneficiary::Endowment { id: parent_id };
             } else {
                 // query the Index Fund SC to find the Fund that this Endowment is a member of
                 let fund_list: angel_core::msgs::index_fund::FundListResponse =
@@ -360,29 +421,24 @@ pub fn update_endowment_status(
             }
             endowment.deposit_approved = false;
             endowment.withdraw_approved = false;
-            vec![
-                // trigger the removal of this endowment from all Index Funds
-                SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: index_fund_contract,
-                    msg: to_binary(&angel_core::msgs::index_fund::ExecuteMsg::RemoveMember(
-                        angel_core::msgs::index_fund::RemoveMemberMsg {
-                            member: msg.endowment_id,
-                        },
-                    ))
-                    .unwrap(),
-                    funds: vec![],
-                })),
-                // start redemption of Account SC's Strategy holdings to final beneficiary/index fund
-                SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: env.contract.address.to_string(),
-                    msg: to_binary(&angel_core::msgs::accounts::ExecuteMsg::CloseEndowment {
-                        id: endowment_id,
-                        beneficiary,
-                    })
-                    .unwrap(),
-                    funds: vec![],
-                })),
-            ]
+            let mut msgs = close_endowment_msgs(
+                &env,
+                &index_fund_contract,
+                endowment_id,
+                beneficiary.clone(),
+            );
+
+            // cascade the closure down to, or re-parent, any children of this endowment
+            msgs.extend(close_children(
+                deps.storage,
+                &env,
+                &index_fund_contract,
+                registrar_config_ext.accounts_settings_controller.unwrap(),
+                endowment_id,
+                beneficiary,
+                msg.children,
+            )?);
+            msgs
         }
         _ => vec![],
     };
@@ -396,6 +452,117 @@ pub fn update_endowment_status(
         .add_attribute("action", "update_endowment_status"))
 }
 
+fn close_children(
+    storage: &mut dyn Storage,
+    env: &Env,
+    index_fund_contract: &str,
+    settings_controller: String,
+    id: u32,
+    beneficiary: Beneficiary,
+    action: Option<ChildEndowmentsOnClose>,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let endow_type = ENDOWMENTS.load(storage, id)?.endow_type;
+    let mut msgs: Vec<SubMsg> = vec![];
+    let children = load_children(storage, id)?;
+    let action = match action {
+        Some(action) => action,
+        None => {
+            // children are never left without a parent, so the closer has to say where they go
+            for child_id in children {
+                if ENDOWMENTS.load(storage, child_id)?.status != EndowmentStatus::Closed {
+                    return Err(ContractError::OpenChildEndowments { id });
+                }
+            }
+            return Ok(msgs);
+        }
+    };
+    match action {
+        ChildEndowmentsOnClose::Cascade => {
+            // the closing family cannot be the beneficiary of its own funds
+            if let Beneficiary::Endowment { id: beneficiary_id } = beneficiary {
+                if beneficiary_id == id || children.contains(&beneficiary_id) {
+                    return Err(ContractError::InvalidInputs {});
+                }
+            }
+            for child_id in children {
+                let mut child = ENDOWMENTS.load(storage, child_id)?;
+                if child.status == EndowmentStatus::Closed {
+                    continue;
+                }
+                child.status = EndowmentStatus::Closed;
+                child.deposit_approved = false;
+                child.withdraw_approved = false;
+                ENDOWMENTS.save(storage, child_id, &child)?;
+                msgs.extend(close_endowment_msgs(
+                    env,
+                    index_fund_contract,
+                    child_id,
+                    beneficiary.clone(),
+                ));
+            }
+        }
+        ChildEndowmentsOnClose::Reparent { new_parent } => {
+            // children may only move under another open, top-level endowment of the same type
+            let new_parent_endow = ENDOWMENTS.load(storage, new_parent)?;
+            if new_parent == id
+                || new_parent_endow.status == EndowmentStatus::Closed
+                || new_parent_endow.parent.is_some()
+                || new_parent_endow.endow_type != endow_type
+            {
+                return Err(ContractError::InvalidInputs {});
+            }
+            for child_id in children {
+                let mut child = ENDOWMENTS.load(storage, child_id)?;
+                child.parent = Some(new_parent);
+                ENDOWMENTS.save(storage, child_id, &child)?;
+                CHILDREN.remove(storage, (id, child_id));
+                CHILDREN.save(storage, (new_parent, child_id), &Empty {})?;
+                // keep the parent recorded in the Endowment's settings in sync
+                msgs.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: settings_controller.clone(),
+                    msg: to_binary(&SettingsControllerExecuteMsg::UpdateEndowmentParent {
+                        id: child_id,
+                        parent: Some(new_parent),
+                    })?,
+                    funds: vec![],
+                })));
+            }
+        }
+    }
+    Ok(msgs)
+}
+
+/// Messages removing a closed endowment from all Index Funds & starting the
+/// redemption of its Strategy holdings to the final beneficiary
+fn close_endowment_msgs(
+    env: &Env,
+    index_fund_contract: &str,
+    id: u32,
+    beneficiary: Beneficiary,
+) -> Vec<SubMsg> {
+    vec![
+        // trigger the removal of this endowment from all Index Funds
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: index_fund_contract.to_string(),
+            msg: to_binary(&angel_core::msgs::index_fund::ExecuteMsg::RemoveMember(
+                angel_core::msgs::index_fund::RemoveMemberMsg { member: id },
+            ))
+            .unwrap(),
+            funds: vec![],
+        })),
+        // start redemption of Account SC's Strategy holdings to final beneficiary/index fund
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&angel_core::msgs::accounts::ExecuteMsg::CloseEndowment {
+                id,
+                beneficiary,
+            })
+            .unwrap(),
+            funds: vec![],
+        })),
+    ]
+}
+
 pub fn update_config(
     deps: DepsMut,
     _env: Env,
@@ -403,6 +570,7 @@ pub fn update_config(
     new_owner: Option<String>,
     new_registrar: Option<String>,
     max_general_category_id: Option<u8>,
+    referral: Option<ReferralConfig>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -424,6 +592,11 @@ pub fn update_config(
         Some(id) => id,
         None => config.max_general_category_id,
     };
+    if let Some(referral) = referral {
+        percentage_checks(referral.protocol_fee_share)?;
+        percentage_checks(referral.deposit_fee_share)?;
+        config.referral = Some(referral);
+    }
 
     CONFIG.save(deps.storage, &config)?;
 
@@ -928,6 +1101,19 @@ pub fn vault_receipt(
 
     STATES.save(deps.storage, id, &state)?;
 
+    // a closing Endowment gives up its position(s) in the Strategy once its funds are back
+    if state.closing_endowment {
+        for strategies in [
+            &mut endowment.invested_strategies.locked,
+            &mut endowment.invested_strategies.liquid,
+        ] {
+            if let Some(index) = strategies.iter().position(|key| *key == sender) {
+                strategies.remove(index);
+                remove_strategy_position(deps.storage, &sender)?;
+            }
+        }
+    }
+
     let mut msgs: Vec<CosmosMsg> = vec![];
     match endowment.pending_redemptions {
         // nothing pending, no action needed
@@ -954,7 +1140,7 @@ pub fn vault_receipt(
 
 pub fn deposit(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     sender_addr: Addr,
     msg: DepositMsg,
@@ -998,12 +1184,27 @@ pub fn deposit(
             active,
         } = endowment_settings.deposit_fee.clone().unwrap();
         if active {
-            let deposit_fee_amount = deposit_amount * fee_percentage;
+            let mut deposit_fee_amount = deposit_amount * fee_percentage;
 
             deposit_amount -= deposit_fee_amount;
             deposit_token.amount -= deposit_fee_amount;
 
+            // the referrer's cut of the fee stays in the contract, credited to its liquid balance
+            if let Some(referral) = config.referral.as_ref() {
+                deposit_fee_amount -= credit_referral_reward(
+                    deps.storage,
+                    &env,
+                    msg.id,
+                    endowment.referral_id,
+                    referral,
+                    &deposit_token.info,
+                    deposit_fee_amount * referral.deposit_fee_share,
+                )?;
+            }
+
             match deposit_token.info {
+                // nothing left to send once the referrer has taken its cut
+                _ if deposit_fee_amount.is_zero() => (),
                 AssetInfoBase::Native(ref token) => {
                     let deposit_fee: Coin = Coin {
                         denom: token.to_string(),
@@ -1222,11 +1423,12 @@ pub fn strategies_invest(
                 .locked
                 .iter()
                 .position(|s| s == &investment.strategy_key);
-            if pos.is_some() {
+            if pos.is_none() {
                 endowment
                     .invested_strategies
                     .locked
                     .push(investment.strategy_key.clone());
+                add_strategy_position(deps.storage, &investment.strategy_key)?;
             }
         }
 
@@ -1240,11 +1442,12 @@ pub fn strategies_invest(
                 .liquid
                 .iter()
                 .position(|s| s == &investment.strategy_key);
-            if pos.is_some() {
+            if pos.is_none() {
                 endowment
                     .invested_strategies
                     .liquid
                     .push(investment.strategy_key.clone());
+                add_strategy_position(deps.storage, &investment.strategy_key)?;
             }
         }
 
@@ -1396,6 +1599,7 @@ pub fn strategies_redeem(
                     .invested_strategies
                     .locked
                     .swap_remove(pos.unwrap());
+                remove_strategy_position(deps.storage, &investment.strategy_key)?;
             }
         }
         if investment.liquid_amount > Uint128::zero() {
@@ -1409,6 +1613,7 @@ pub fn strategies_redeem(
                     .invested_strategies
                     .liquid
                     .swap_remove(pos.unwrap());
+                remove_strategy_position(deps.storage, &investment.strategy_key)?;
             }
         }
 
@@ -1466,6 +1671,272 @@ pub fn strategies_redeem(
     Ok(res)
 }
 
+/// Redemptions from some native Strategies (ie. staking) are only paid out after an
+/// unbonding period. Endowment owners claim the matured ones via the native Vault Router.
+pub fn strategies_claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u32,
+    strategies: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let endowment = ENDOWMENTS.load(deps.storage, id)?;
+
+    if endowment.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if strategies.is_empty() {
+        return Err(ContractError::InvalidInputs {});
+    }
+
+    let mut res = Response::new().add_attribute("action", "strategies_claim");
+    for strategy_key in strategies.into_iter() {
+        let strategy_params: StrategyParams = deps
+            .querier
+            .query_wasm_smart::<StrategyDetailResponse>(
+                config.registrar_contract.to_string(),
+                &RegistrarQuerier::Strategy {
+                    strategy_key: strategy_key.clone(),
+                },
+            )?
+            .strategy;
+        if strategy_params.locale != StrategyLocale::Native {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: "Claiming is only supported for native Strategies".to_string(),
+            }));
+        }
+
+        let chain_info: NetworkConnectionResponse = deps.querier.query_wasm_smart(
+            config.registrar_contract.to_string(),
+            &RegistrarQuerier::NetworkConnection {
+                chain_id: strategy_params.chain.clone(),
+            },
+        )?;
+        let router_contract = match chain_info.network_connection.router_contract {
+            Some(router) => router,
+            None => {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: "Vault Router not set for chain in the Registrar Network Connection."
+                        .to_string(),
+                }))
+            }
+        };
+
+        let claim_msg = angel_core::msgs::vault_router::ExecuteMsg::Claim {
+            action: VaultActionData {
+                destination_chain: strategy_params.chain,
+                strategy_id: strategy_key,
+                selector: "claim".to_string(),
+                account_ids: vec![id],
+                token: strategy_params.input_denom,
+                lock_amt: Uint128::zero(),
+                liq_amt: Uint128::zero(),
+            },
+        };
+        res = res.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: router_contract,
+            msg: to_binary(&claim_msg)?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(res)
+}
+
+/// Allow Endowment owners to move some amount of their vault tokens from a Liquid Strategy
+/// position over to an approved Locked Strategy, via the native Vault Router contract.
+pub fn reinvest_to_locked(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u32,
+    amount: Uint128,
+    source_strategy: String,
+    target_strategy: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let endowment = ENDOWMENTS.load(deps.storage, id)?;
+
+    if endowment.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if amount.is_zero() {
+        return Err(ContractError::InvalidInputs {});
+    }
+
+    if !endowment
+        .invested_strategies
+        .liquid
+        .iter()
+        .any(|s| s == &source_strategy)
+    {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Endowment has no liquid investment in the source Strategy".to_string(),
+        }));
+    }
+
+    let source_params: StrategyParams = deps
+        .querier
+        .query_wasm_smart::<StrategyDetailResponse>(
+            config.registrar_contract.to_string(),
+            &RegistrarQuerier::Strategy {
+                strategy_key: source_strategy.clone(),
+            },
+        )?
+        .strategy;
+    if source_params.approval_state != StrategyApprovalState::Approved
+        && source_params.approval_state != StrategyApprovalState::WithdrawOnly
+    {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Strategy is either not approved or is depreciated".to_string(),
+        }));
+    }
+
+    let target_params: StrategyParams = deps
+        .querier
+        .query_wasm_smart::<StrategyDetailResponse>(
+            config.registrar_contract.to_string(),
+            &RegistrarQuerier::Strategy {
+                strategy_key: target_strategy.clone(),
+            },
+        )?
+        .strategy;
+    if target_params.approval_state != StrategyApprovalState::Approved {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Strategy is not approved to accept deposits".to_string(),
+        }));
+    }
+
+    // both positions must live on this chain and be handled by the same native Vault Router
+    if source_params.locale != StrategyLocale::Native
+        || target_params.locale != StrategyLocale::Native
+        || source_params.chain != target_params.chain
+    {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Reinvesting is only supported between native Strategies".to_string(),
+        }));
+    }
+    if source_params.input_denom != target_params.input_denom {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Strategies must share the same input token".to_string(),
+        }));
+    }
+    let source_liquid_vault = match source_params.liquid_addr {
+        Some(addr) => addr,
+        None => {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: "Source Strategy has no liquid vault".to_string(),
+            }))
+        }
+    };
+    if target_params.locked_addr.is_none() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Target Strategy has no locked vault".to_string(),
+        }));
+    }
+
+    let chain_info: NetworkConnectionResponse = deps.querier.query_wasm_smart(
+        config.registrar_contract.to_string(),
+        &RegistrarQuerier::NetworkConnection {
+            chain_id: source_params.chain.clone(),
+        },
+    )?;
+    let router_contract = match chain_info.network_connection.router_contract {
+        Some(router) => router,
+        None => {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: "Vault Router not set for chain in the Registrar Network Connection."
+                    .to_string(),
+            }))
+        }
+    };
+
+    // check the vault token(VT) balance held in the source liquid vault
+    let liquid_balance: Uint128 = deps.querier.query_wasm_smart(
+        source_liquid_vault.to_string(),
+        &angel_core::msgs::vault::QueryMsg::Balance { endowment_id: id },
+    )?;
+    if amount > liquid_balance {
+        return Err(ContractError::BalanceTooSmall {});
+    }
+
+    // invested strategies are only updated once the Vault Router round-trip succeeds
+    PENDING_REINVEST.save(
+        deps.storage,
+        &PendingReinvest {
+            id,
+            source_strategy: source_strategy.clone(),
+            target_strategy: target_strategy.clone(),
+            source_depleted: amount == liquid_balance,
+        },
+    )?;
+
+    let reinvest_msg = angel_core::msgs::vault_router::ExecuteMsg::ReinvestToLocked {
+        action: VaultActionData {
+            destination_chain: source_params.chain,
+            strategy_id: source_strategy,
+            selector: "reinvest_to_locked".to_string(),
+            account_ids: vec![id],
+            token: source_params.input_denom,
+            lock_amt: Uint128::zero(),
+            liq_amt: amount,
+        },
+        target_strategy,
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "reinvest_to_locked")
+        .add_submessage(SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: router_contract,
+                msg: to_binary(&reinvest_msg)?,
+                funds: vec![],
+            }),
+            3,
+        )))
+}
+
+pub fn reinvest_reply(
+    deps: DepsMut,
+    _env: Env,
+    _msg: SubMsgResult,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_REINVEST.load(deps.storage)?;
+    PENDING_REINVEST.remove(deps.storage);
+
+    let mut endowment = ENDOWMENTS.load(deps.storage, pending.id)?;
+    // a fully depleted source position is dropped from the liquid invested-strategies list
+    if pending.source_depleted {
+        if let Some(pos) = endowment
+            .invested_strategies
+            .liquid
+            .iter()
+            .position(|s| s == &pending.source_strategy)
+        {
+            endowment.invested_strategies.liquid.swap_remove(pos);
+            remove_strategy_position(deps.storage, &pending.source_strategy)?;
+        }
+    }
+    if !endowment
+        .invested_strategies
+        .locked
+        .iter()
+        .any(|s| s == &pending.target_strategy)
+    {
+        endowment
+            .invested_strategies
+            .locked
+            .push(pending.target_strategy.clone());
+        add_strategy_position(deps.storage, &pending.target_strategy)?;
+    }
+    ENDOWMENTS.save(deps.storage, pending.id, &endowment)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reinvest_to_locked_reply")
+        .add_attribute("endowment_id", pending.id.to_string()))
+}
+
 pub fn withdraw(
     deps: DepsMut,
     env: Env,
@@ -1597,42 +2068,57 @@ pub fn withdraw(
             return Err(ContractError::InsufficientFunds {});
         }
 
-        // calculate withdraw fee
-        let withdraw_fee = asset.amount * withdraw_rate;
+        // calculate withdraw fee (inter-endowment transfers are not charged one)
+        let withdraw_fee = if inter_endow_transfer {
+            Uint128::zero()
+        } else {
+            asset.amount * withdraw_rate
+        };
+        // the referrer's cut of the fee stays in the contract, credited to its liquid balance
+        let referral_reward = match config.referral.as_ref() {
+            Some(referral) => credit_referral_reward(
+                deps.storage,
+                &env,
+                id,
+                endowment.referral_id,
+                referral,
+                &asset.info.check(deps.api, None)?,
+                withdraw_fee * referral.protocol_fee_share,
+            )?,
+            None => Uint128::zero(),
+        };
+        let treasury_fee = withdraw_fee - referral_reward;
         // build message based on asset type and update state balance with deduction
         match asset.info.clone() {
             AssetInfoBase::Native(denom) => {
-                if inter_endow_transfer {
-                    // add Coin to the native coins vector to have a message built
-                    // but we take no fee deductions
-                    native_coins.push(Coin {
-                        denom: denom.clone(),
-                        amount: asset.amount,
-                    });
-                } else {
-                    // add Coin to the native coins vector to have a message built
-                    // and all deductions against the state balance done at the end
-                    native_coins.push(Coin {
-                        denom: denom.clone(),
-                        amount: asset.amount - withdraw_fee,
-                    });
-                }
+                // add Coin to the native coins vector to have a message built
+                // and all deductions against the state balance done at the end
+                native_coins.push(Coin {
+                    denom: denom.clone(),
+                    amount: asset.amount - withdraw_fee,
+                });
                 // don't push a fee asset in unless we need to send something
-                if withdraw_fee > Uint128::zero() {
+                if treasury_fee > Uint128::zero() {
                     native_coins_fees.push(Coin {
                         denom: denom.clone(),
-                        amount: withdraw_fee,
+                        amount: treasury_fee,
                     });
                 }
+                if referral_reward > Uint128::zero() {
+                    state_bal.deduct_tokens(Balance::from(vec![Coin {
+                        denom: denom.clone(),
+                        amount: referral_reward,
+                    }]));
+                }
             }
             AssetInfoBase::Cw20(addr) => {
-                if withdraw_fee > Uint128::zero() {
+                if treasury_fee > Uint128::zero() {
                     // Build message to AP treasury for withdraw fee owned
                     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
                         contract_addr: addr.to_string(),
                         msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
                             recipient: registrar_config.treasury.to_string(),
-                            amount: withdraw_fee,
+                            amount: treasury_fee,
                         })
                         .unwrap(),
                         funds: vec![],
@@ -1793,6 +2279,7 @@ pub fn close_endowment(
         })));
     }
 
+    // the Endowment keeps its positions in the Strategies until their redemptions land
     endowment.pending_redemptions = redeem_messages.len() as u8;
     endowment.deposit_approved = false;
     ENDOWMENTS.save(deps.storage, id, &endowment)?;
@@ -1966,3 +2453,198 @@ pub fn spend_allowance(
         .add_submessages(messages)
         .add_attribute("action", "spend_allowance"))
 }
+
+/// Credits the referrer of an Endowment with a reward taken out of a fee, as long as
+/// the referral period is still running. Returns the amount actually credited.
+fn credit_referral_reward(
+    storage: &mut dyn Storage,
+    env: &Env,
+    id: u32,
+    referrer_id: Option<u32>,
+    referral_config: &ReferralConfig,
+    token: &AssetInfo,
+    reward: Uint128,
+) -> Result<Uint128, ContractError> {
+    let referrer_id = match referrer_id {
+        Some(referrer_id) if !reward.is_zero() => referrer_id,
+        _ => return Ok(Uint128::zero()),
+    };
+    // Endowments referred before a referral period was configured start theirs with the first reward
+    let mut referral = REFERRALS
+        .may_load(storage, (referrer_id, id))?
+        .unwrap_or_else(|| Referral {
+            expires_at: env.block.time.seconds() + referral_config.period,
+            rewards: GenericBalance::default(),
+        });
+    if env.block.time.seconds() >= referral.expires_at {
+        return Ok(Uint128::zero());
+    }
+    if ENDOWMENTS.load(storage, referrer_id)?.status == EndowmentStatus::Closed {
+        return Ok(Uint128::zero());
+    }
+
+    let reward_bal = match token {
+        AssetInfoBase::Native(denom) => Balance::from(vec![Coin {
+            denom: denom.to_string(),
+            amount: reward,
+        }]),
+        AssetInfoBase::Cw20(address) => Balance::Cw20(Cw20CoinVerified {
+            address: address.clone(),
+            amount: reward,
+        }),
+        _ => return Ok(Uint128::zero()),
+    };
+    let mut referrer_state = STATES.load(storage, referrer_id)?;
+    referrer_state
+        .balances
+        .liquid
+        .add_tokens(reward_bal.clone());
+    STATES.save(storage, referrer_id, &referrer_state)?;
+    referral.rewards.add_tokens(reward_bal.clone());
+    REFERRALS.save(storage, (referrer_id, id), &referral)?;
+    REFERRAL_REWARDS.update(storage, referrer_id, |rewards| -> StdResult<_> {
+        let mut rewards = rewards.unwrap_or_default();
+        rewards.add_tokens(reward_bal);
+        Ok(rewards)
+    })?;
+
+    Ok(reward)
+}
+
+// Parent Endowment owners can shuffle TOH balances between the parent and
+// its children directly, without a withdraw & deposit round-trip.
+pub fn allocate_to_child(
+    deps: DepsMut,
+    info: MessageInfo,
+    child_id: u32,
+    acct_type: AccountType,
+    assets: Vec<AssetUnchecked>,
+) -> Result<Response, ContractError> {
+    let parent_id = assert_parent_owner(&deps, &info, child_id)?;
+    move_family_funds(deps, parent_id, child_id, acct_type, assets)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "allocate_to_child")
+        .add_attribute("parent_id", parent_id.to_string())
+        .add_attribute("child_id", child_id.to_string()))
+}
+
+pub fn sweep_from_child(
+    deps: DepsMut,
+    info: MessageInfo,
+    child_id: u32,
+    acct_type: AccountType,
+    assets: Vec<AssetUnchecked>,
+) -> Result<Response, ContractError> {
+    let parent_id = assert_parent_owner(&deps, &info, child_id)?;
+    move_family_funds(deps, child_id, parent_id, acct_type, assets)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sweep_from_child")
+        .add_attribute("parent_id", parent_id.to_string())
+        .add_attribute("child_id", child_id.to_string()))
+}
+
+/// Returns the parent ID of a child Endowment, if the sender owns that parent
+fn assert_parent_owner(
+    deps: &DepsMut,
+    info: &MessageInfo,
+    child_id: u32,
+) -> Result<u32, ContractError> {
+    let child = ENDOWMENTS.load(deps.storage, child_id)?;
+    let parent_id = child
+        .parent
+        .ok_or(ContractError::NotChildEndowment { id: child_id })?;
+    let parent = ENDOWMENTS.load(deps.storage, parent_id)?;
+    if info.sender != parent.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(parent_id)
+}
+
+fn move_family_funds(
+    deps: DepsMut,
+    from: u32,
+    to: u32,
+    acct_type: AccountType,
+    assets: Vec<AssetUnchecked>,
+) -> Result<(), ContractError> {
+    if assets.is_empty() {
+        return Err(ContractError::InvalidInputs {});
+    }
+
+    let from_endow = ENDOWMENTS.load(deps.storage, from)?;
+    let to_endow = ENDOWMENTS.load(deps.storage, to)?;
+    if from_endow.status == EndowmentStatus::Closed || to_endow.status == EndowmentStatus::Closed {
+        return Err(ContractError::AccountClosed {});
+    }
+    if !from_endow.withdraw_approved {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Withdraws are not approved for this endowment".to_string(),
+        }));
+    }
+    if !to_endow.deposit_approved {
+        return Err(ContractError::AccountNotApproved {});
+    }
+    // Locked funds may not end up somewhere they mature (ie. become withdrawable) sooner
+    if acct_type == AccountType::Locked {
+        let matures_later = match (to_endow.maturity_time, from_endow.maturity_time) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(to_time), Some(from_time)) => to_time >= from_time,
+        };
+        if !matures_later {
+            return Err(ContractError::InaccessableLockedBalance {});
+        }
+    }
+
+    let mut from_state = STATES.load(deps.storage, from)?;
+    let mut to_state = STATES.load(deps.storage, to)?;
+    let mut from_bal: GenericBalance = from_state.balances.get(&acct_type);
+    let mut to_bal: GenericBalance = to_state.balances.get(&acct_type);
+    for asset in assets.iter() {
+        asset.check(deps.api, None)?;
+        if asset.amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+        let (available, moved) = match asset.info.clone() {
+            AssetInfoBase::Native(denom) => (
+                from_bal.get_denom_amount(denom.clone()).amount,
+                Balance::from(vec![Coin {
+                    denom,
+                    amount: asset.amount,
+                }]),
+            ),
+            AssetInfoBase::Cw20(addr) => {
+                let address = deps.api.addr_validate(&addr)?;
+                (
+                    from_bal.get_token_amount(address.clone()).amount,
+                    Balance::Cw20(Cw20CoinVerified {
+                        address,
+                        amount: asset.amount,
+                    }),
+                )
+            }
+            _ => return Err(ContractError::InvalidInputs {}),
+        };
+        if asset.amount > available {
+            return Err(ContractError::InsufficientFunds {});
+        }
+        from_bal.deduct_tokens(moved.clone());
+        to_bal.add_tokens(moved);
+    }
+
+    match acct_type {
+        AccountType::Locked => {
+            from_state.balances.locked = from_bal;
+            to_state.balances.locked = to_bal;
+        }
+        AccountType::Liquid => {
+            from_state.balances.liquid = from_bal;
+            to_state.balances.liquid = to_bal;
+        }
+    }
+    STATES.save(deps.storage, from, &from_state)?;
+    STATES.save(deps.storage, to, &to_state)?;
+    Ok(())
+}
@@ -1,8 +1,16 @@
-use crate::state::{Endowment, ALLOWANCES, CONFIG, ENDOWMENTS, STATES};
+use crate::state::{
+    load_children, Endowment, ALLOWANCES, CONFIG, ENDOWMENTS, REFERRALS, REFERRAL_REWARDS, STATES,
+    STRATEGY_POSITIONS,
+};
 use angel_core::msgs::accounts::*;
 use angel_core::structs::Allowances;
 use cosmwasm_std::{Deps, Order, StdResult};
 use cw2::get_contract_version;
+use cw_storage_plus::Bound;
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
 
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
@@ -17,6 +25,7 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         registrar_contract: config.registrar_contract.to_string(),
         next_account_id: config.next_account_id,
         max_general_category_id: config.max_general_category_id,
+        referral: config.referral,
     })
 }
 
@@ -35,12 +44,13 @@ pub fn query_endowment_by_proposal_link(
     deps: Deps,
     proposal_link: u64,
 ) -> StdResult<EndowmentDetailsResponse> {
-    let endowments: Vec<Endowment> = ENDOWMENTS
-        .range(deps.storage, None, None, Order::Ascending)
-        .filter(|e| e.as_ref().unwrap().1.proposal_link == Some(proposal_link))
-        .map(|item| item.unwrap().1)
-        .collect();
-    if endowments.len() != 1 {
+    let ids: Vec<u32> = ENDOWMENTS
+        .idx
+        .proposal_link
+        .ids(deps.storage, proposal_link, None)
+        .take(2)
+        .collect::<StdResult<_>>()?;
+    if ids.len() != 1 {
         return Err(cosmwasm_std::StdError::NotFound {
             kind: "endowment".to_string(),
         });
@@ -63,7 +73,8 @@ pub fn query_endowment_by_proposal_link(
         pending_redemptions,
         proposal_link,
         referral_id,
-    } = endowments[0].clone();
+        parent,
+    } = ENDOWMENTS.load(deps.storage, ids[0])?;
 
     Ok(EndowmentDetailsResponse {
         owner,
@@ -83,6 +94,7 @@ pub fn query_endowment_by_proposal_link(
         pending_redemptions,
         proposal_link,
         referral_id,
+        parent,
     })
 }
 
@@ -107,6 +119,89 @@ pub fn query_endowment_details(deps: Deps, id: u32) -> StdResult<EndowmentDetail
         pending_redemptions: endowment.pending_redemptions,
         proposal_link: endowment.proposal_link,
         referral_id: endowment.referral_id,
+        parent: endowment.parent,
+    })
+}
+
+pub fn query_endowments(deps: Deps, msg: EndowmentsQueryMsg) -> StdResult<EndowmentListResponse> {
+    let limit = msg.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = msg.start_after.map(Bound::exclusive);
+    let owner = msg
+        .owner
+        .map(|owner| deps.api.addr_validate(&owner))
+        .transpose()?;
+
+    // walk the most selective index available & check the remaining filters within the page,
+    // so a filter matching few Endowments doesn't scan them all. `next_start_after` tells
+    // where the next page starts
+    let ids = match (&owner, msg.sdg, &msg.status) {
+        (Some(owner), _, _) => ENDOWMENTS.idx.owner.prefix(owner.clone()).keys(
+            deps.storage,
+            start,
+            None,
+            Order::Ascending,
+        ),
+        (None, Some(sdg), _) => ENDOWMENTS.idx.sdg.ids(deps.storage, sdg, msg.start_after),
+        (None, None, Some(status)) => ENDOWMENTS.idx.status.prefix(status.clone() as u8).keys(
+            deps.storage,
+            start,
+            None,
+            Order::Ascending,
+        ),
+        (None, None, None) => ENDOWMENTS.keys(deps.storage, start, None, Order::Ascending),
+    };
+    let page = ids.take(limit).collect::<StdResult<Vec<_>>>()?;
+    let next_start_after = match page.last() {
+        Some(id) if page.len() == limit => Some(*id),
+        _ => None,
+    };
+
+    let mut endowments = vec![];
+    for id in page {
+        let endowment = ENDOWMENTS.load(deps.storage, id)?;
+        if let Some(owner) = &owner {
+            if endowment.owner != *owner {
+                continue;
+            }
+        }
+        if let Some(sdg) = msg.sdg {
+            if !endowment.categories.sdgs.contains(&sdg) {
+                continue;
+            }
+        }
+        if let Some(status) = &msg.status {
+            if endowment.status != *status {
+                continue;
+            }
+        }
+        if let Some(endow_type) = &msg.endow_type {
+            if endowment.endow_type != *endow_type {
+                continue;
+            }
+        }
+        if let Some(tier) = msg.tier {
+            if endowment.tier != Some(tier) {
+                continue;
+            }
+        }
+        endowments.push(EndowmentEntry {
+            id,
+            owner: endowment.owner,
+            name: endowment.name,
+            categories: endowment.categories,
+            tier: endowment.tier,
+            endow_type: endowment.endow_type,
+            logo: endowment.logo,
+            image: endowment.image,
+            status: endowment.status,
+            proposal_link: endowment.proposal_link,
+            parent: endowment.parent,
+        });
+    }
+
+    Ok(EndowmentListResponse {
+        endowments,
+        next_start_after,
     })
 }
 
@@ -116,3 +211,68 @@ pub fn query_allowances(deps: Deps, id: u32, spender: String) -> StdResult<Allow
     let allowances = ALLOWANCES.may_load(deps.storage, (&endowment.owner, &spender))?;
     Ok(allowances.unwrap_or_default())
 }
+
+pub fn query_strategy_positions(
+    deps: Deps,
+    strategy_key: String,
+) -> StdResult<StrategyPositionsResponse> {
+    let positions = STRATEGY_POSITIONS
+        .may_load(deps.storage, &strategy_key)?
+        .unwrap_or_default();
+    Ok(StrategyPositionsResponse {
+        strategy_key,
+        positions,
+    })
+}
+
+pub fn query_family_balance(deps: Deps, id: u32) -> StdResult<FamilyBalanceResponse> {
+    // this fails if no account is found
+    let state = STATES.load(deps.storage, id)?;
+    let children = load_children(deps.storage, id)?;
+
+    let mut locked = state.balances.locked;
+    let mut liquid = state.balances.liquid;
+    for child in children.iter() {
+        let child_state = STATES.load(deps.storage, *child)?;
+        locked.receive_generic_balance(child_state.balances.locked);
+        liquid.receive_generic_balance(child_state.balances.liquid);
+    }
+
+    Ok(FamilyBalanceResponse {
+        id,
+        children,
+        locked,
+        liquid,
+    })
+}
+
+pub fn query_referrals(
+    deps: Deps,
+    referrer_id: u32,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<ReferralsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let referrals = REFERRALS
+        .prefix(referrer_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (endowment_id, referral) = item?;
+            Ok(ReferralResponse {
+                endowment_id,
+                expires_at: referral.expires_at,
+                rewards: referral.rewards,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ReferralsResponse {
+        referrer_id,
+        referrals,
+        total_rewards: REFERRAL_REWARDS
+            .may_load(deps.storage, referrer_id)?
+            .unwrap_or_default(),
+    })
+}
@@ -1,10 +1,14 @@
 use angel_core::structs::{
     AccountStrategies, Allowances, BalanceInfo, Beneficiary, Categories, DonationsReceived,
-    EndowmentStatus, EndowmentType, Investments, OneOffVaults, RebalanceDetails,
+    EndowmentStatus, EndowmentType, GenericBalance, Investments, OneOffVaults, RebalanceDetails,
+    ReferralConfig,
 };
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Env, Timestamp};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Empty, Env, Order, StdResult, Storage, Timestamp};
+use cw_storage_plus::{
+    Bound, Index, IndexList, IndexedMap, Item, KeyDeserialize, Map, MultiIndex, Prefixer,
+    PrimaryKey,
+};
 
 #[cw_serde]
 pub struct Config {
@@ -12,6 +16,7 @@ pub struct Config {
     pub registrar_contract: Addr,
     pub next_account_id: u32,
     pub max_general_category_id: u8,
+    pub referral: Option<ReferralConfig>,
 }
 
 #[cw_serde]
@@ -55,6 +60,7 @@ pub struct Endowment {
     pub pending_redemptions: u8, // number of vault redemptions currently pending for this endowment
     pub proposal_link: Option<u64>, // link back the CW3 Proposal that created an endowment
     pub referral_id: Option<u32>, // at time of creation, the Endowment ID that referred them can be noted, fixed value
+    pub parent: Option<u32>,      // top-level Endowment that created this one as a child (if any)
 }
 
 impl Endowment {
@@ -76,7 +82,142 @@ pub struct State {
     pub closing_beneficiary: Option<Beneficiary>,
 }
 
+#[cw_serde]
+pub struct Referral {
+    pub expires_at: u64, // time (in seconds) after which no more rewards are credited
+    pub rewards: GenericBalance, // cumulative rewards credited to the referrer
+}
+
+#[cw_serde]
+pub struct PendingReinvest {
+    pub id: u32,
+    pub source_strategy: String,
+    pub target_strategy: String,
+    pub source_depleted: bool, // whole liquid position in the source strategy is moved
+}
+
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const STATES: Map<u32, State> = Map::new("states");
-pub const ENDOWMENTS: Map<u32, Endowment> = Map::new("endowments");
+// liquid-to-locked reinvestment awaiting its Vault Router round-trip
+pub const PENDING_REINVEST: Item<PendingReinvest> = Item::new("pending_reinvest");
+pub const ENDOWMENTS: IndexedMap<u32, Endowment, EndowmentIndexes> = IndexedMap::new(
+    "endowments",
+    EndowmentIndexes {
+        owner: MultiIndex::new(
+            |_pk, endowment| endowment.owner.clone(),
+            "endowments",
+            "endowments__owner",
+        ),
+        status: MultiIndex::new(
+            |_pk, endowment| endowment.status.clone() as u8,
+            "endowments",
+            "endowments__status",
+        ),
+        sdg: ListIndex::new(
+            |endowment| endowment.categories.sdgs.clone(),
+            "endowments__sdg",
+        ),
+        proposal_link: ListIndex::new(
+            |endowment| endowment.proposal_link.into_iter().collect(),
+            "endowments__proposal_link",
+        ),
+    },
+);
 pub const ALLOWANCES: Map<(&Addr, &Addr), Allowances> = Map::new("allowances");
+// (referrer ID, referred ID) to the rewards credited for that referral
+pub const REFERRALS: Map<(u32, u32), Referral> = Map::new("referrals");
+// referrer ID to the rewards credited across all of its referrals
+pub const REFERRAL_REWARDS: Map<u32, GenericBalance> = Map::new("referral_rewards");
+// (parent ID, child ID) index of child Endowments
+pub const CHILDREN: Map<(u32, u32), Empty> = Map::new("children");
+// number of locked & liquid endowment positions held in each strategy
+pub const STRATEGY_POSITIONS: Map<&str, u64> = Map::new("strategy_positions");
+
+pub fn add_strategy_position(storage: &mut dyn Storage, strategy_key: &str) -> StdResult<u64> {
+    STRATEGY_POSITIONS.update(storage, strategy_key, |positions| -> StdResult<_> {
+        Ok(positions.unwrap_or_default() + 1)
+    })
+}
+
+pub fn load_children(storage: &dyn Storage, parent: u32) -> StdResult<Vec<u32>> {
+    CHILDREN
+        .prefix(parent)
+        .keys(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+pub fn remove_strategy_position(storage: &mut dyn Storage, strategy_key: &str) -> StdResult<u64> {
+    STRATEGY_POSITIONS.update(storage, strategy_key, |positions| -> StdResult<_> {
+        Ok(positions.unwrap_or_default().saturating_sub(1))
+    })
+}
+
+pub struct EndowmentIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, Endowment, u32>,
+    pub status: MultiIndex<'a, u8, Endowment, u32>,
+    pub sdg: ListIndex<'a, u8>,
+    pub proposal_link: ListIndex<'a, u64>,
+}
+
+impl<'a> IndexList<Endowment> for EndowmentIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Endowment>> + '_> {
+        let v: Vec<&dyn Index<Endowment>> =
+            vec![&self.owner, &self.status, &self.sdg, &self.proposal_link];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Secondary index for values an Endowment can hold any number of (including none),
+/// which a `MultiIndex` can't express. Stores (value, endowment ID) pairs.
+pub struct ListIndex<'a, K> {
+    idx_fn: fn(&Endowment) -> Vec<K>,
+    idx_map: Map<'a, (K, u32), Empty>,
+}
+
+impl<'a, K> ListIndex<'a, K>
+where
+    K: PrimaryKey<'a> + Prefixer<'a> + KeyDeserialize,
+{
+    pub const fn new(idx_fn: fn(&Endowment) -> Vec<K>, idx_namespace: &'a str) -> Self {
+        ListIndex {
+            idx_fn,
+            idx_map: Map::new(idx_namespace),
+        }
+    }
+
+    /// IDs of the Endowments indexed under a value, in ascending order
+    pub fn ids<'c>(
+        &self,
+        storage: &'c dyn Storage,
+        value: K,
+        start_after: Option<u32>,
+    ) -> Box<dyn Iterator<Item = StdResult<u32>> + 'c> {
+        self.idx_map.prefix(value).keys(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+    }
+}
+
+impl<'a, K> Index<Endowment> for ListIndex<'a, K>
+where
+    K: PrimaryKey<'a> + Prefixer<'a> + KeyDeserialize,
+{
+    fn save(&self, store: &mut dyn Storage, pk: &[u8], data: &Endowment) -> StdResult<()> {
+        let id = u32::from_slice(pk)?;
+        for value in (self.idx_fn)(data) {
+            self.idx_map.save(store, (value, id), &Empty {})?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, store: &mut dyn Storage, pk: &[u8], old_data: &Endowment) -> StdResult<()> {
+        let id = u32::from_slice(pk)?;
+        for value in (self.idx_fn)(old_data) {
+            self.idx_map.remove(store, (value, id));
+        }
+        Ok(())
+    }
+}
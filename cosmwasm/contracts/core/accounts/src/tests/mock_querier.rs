@@ -142,6 +142,32 @@ impl WasmMockQuerier {
                         })
                         .unwrap(),
                     )),
+                    "strategy-native-2" => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&StrategyDetailResponse {
+                            strategy: StrategyParams {
+                                approval_state: StrategyApprovalState::Approved,
+                                locale: StrategyLocale::Native,
+                                chain: "juno".to_string(),
+                                input_denom: "ibc/B3504E092456BA618CC28AC671A71FB08C6CA0FD0BE7C8A5B5A3E2DD933CC9E4".to_string(),
+                                locked_addr: Some(Addr::unchecked("vault2-locked-contract")),
+                                liquid_addr: Some(Addr::unchecked("vault2-liquid-contract")),
+                            },
+                        })
+                        .unwrap(),
+                    )),
+                    "strategy-native-locked-only" => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&StrategyDetailResponse {
+                            strategy: StrategyParams {
+                                approval_state: StrategyApprovalState::Approved,
+                                locale: StrategyLocale::Native,
+                                chain: "juno".to_string(),
+                                input_denom: "ibc/B3504E092456BA618CC28AC671A71FB08C6CA0FD0BE7C8A5B5A3E2DD933CC9E4".to_string(),
+                                locked_addr: Some(Addr::unchecked("vault3-locked-contract")),
+                                liquid_addr: None,
+                            },
+                        })
+                        .unwrap(),
+                    )),
                     "strategy-ethereum" => SystemResult::Ok(ContractResult::Ok(
                         to_binary(&StrategyDetailResponse {
                             strategy: StrategyParams {
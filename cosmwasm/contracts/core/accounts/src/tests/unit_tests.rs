@@ -1,19 +1,27 @@
 use super::mock_querier::{mock_dependencies, WasmMockQuerier};
-use crate::contract::{execute, instantiate, migrate, query};
+use crate::contract::{execute, instantiate, migrate, query, reply};
+use crate::state::{Endowment, CONFIG, ENDOWMENTS};
 use angel_core::errors::core::*;
-use angel_core::msgs::accounts::{ConfigResponse, EndowmentDetailsResponse, StateResponse};
 use angel_core::msgs::accounts::{
-    CreateEndowmentMsg, DepositMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiveMsg,
-    UpdateEndowmentDetailsMsg, UpdateEndowmentStatusMsg,
+    ConfigResponse, EndowmentDetailsResponse, EndowmentListResponse, FamilyBalanceResponse,
+    ReferralResponse, ReferralsResponse, StateResponse, StrategyPositionsResponse,
+};
+use angel_core::msgs::accounts::{
+    CreateEndowmentMsg, DepositMsg, EndowmentsQueryMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    QueryMsg, ReceiveMsg, UpdateEndowmentDetailsMsg, UpdateEndowmentStatusMsg,
 };
 use angel_core::structs::{
-    AccountType, Allowances, Beneficiary, Categories, EndowmentType, SplitDetails,
+    AccountType, Allowances, Beneficiary, Categories, ChildEndowmentsOnClose, EndowmentStatus,
+    EndowmentType, GenericBalance, Investments, RebalanceDetails, ReferralConfig, SplitDetails,
     StrategyInvestment, SwapOperation,
 };
 use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    coins, from_binary, to_binary, Addr, Coin, Decimal, Env, OwnedDeps, StdError, Uint128,
+    coins, from_binary, to_binary, to_vec, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, Env,
+    OwnedDeps, Reply, ReplyOn, StdError, StdResult, Storage, SubMsgResponse, SubMsgResult, Uint128,
+    WasmMsg,
 };
+use cw2::set_contract_version;
 use cw20::Cw20ReceiveMsg;
 use cw_asset::{Asset, AssetInfo, AssetInfoBase, AssetUnchecked};
 use cw_utils::{Expiration, Threshold};
@@ -185,6 +193,7 @@ fn test_update_endowment_status() {
         endowment_id: CHARITY_ID,
         status: 1,
         beneficiary: None,
+        children: None,
     };
     let info = mock_info("non-registrar", &[]);
     let err = execute(
@@ -232,6 +241,7 @@ fn test_change_configs() {
             new_owner: None,
             new_registrar: Some(PLEB.to_string()),
             max_general_category_id: Some(2),
+            referral: None,
         },
     )
     .unwrap();
@@ -247,6 +257,7 @@ fn test_change_configs() {
         new_owner: None,
         new_registrar: Some(PLEB.to_string()),
         max_general_category_id: Some(100),
+        referral: None,
     };
     let info = mock_info(PLEB, &coins(100000, "earth "));
     // This should fail with an error!
@@ -268,6 +279,7 @@ fn test_change_admin() {
             new_owner: Some(PLEB.to_string()),
             new_registrar: None,
             max_general_category_id: None,
+            referral: None,
         },
     )
     .unwrap();
@@ -283,6 +295,7 @@ fn test_change_admin() {
         new_owner: Some(CHARITY_ADDR.to_string()),
         new_registrar: None,
         max_general_category_id: None,
+        referral: None,
     };
     let info = mock_info(AP_TEAM, &coins(100000, "earth "));
     // This should fail with an error!
@@ -300,6 +313,7 @@ fn test_donate() {
         endowment_id: CHARITY_ID,
         status: 1,
         beneficiary: None,
+        children: None,
     });
     let _res = execute(deps.as_mut(), env.clone(), info, update_status_msg).unwrap();
 
@@ -359,6 +373,7 @@ fn test_deposit_cw20() {
         endowment_id: CHARITY_ID,
         status: 1,
         beneficiary: None,
+        children: None,
     });
     let _res = execute(deps.as_mut(), env.clone(), info, update_status_msg).unwrap();
 
@@ -390,6 +405,7 @@ fn test_withdraw_locked() {
         endowment_id: CHARITY_ID,
         status: 1,
         beneficiary: None,
+        children: None,
     });
     let _res = execute(deps.as_mut(), env.clone(), info, update_status_msg).unwrap();
 
@@ -530,6 +546,7 @@ fn test_withdraw_liquid() {
         endowment_id: CHARITY_ID,
         status: 1,
         beneficiary: None,
+        children: None,
     });
     let _res = execute(deps.as_mut(), env.clone(), info, update_status_msg).unwrap();
 
@@ -605,6 +622,7 @@ fn test_vault_receipt() {
         endowment_id: CHARITY_ID,
         status: 1,
         beneficiary: None,
+        children: None,
     });
     let _res = execute(deps.as_mut(), env.clone(), info, update_status_msg).unwrap();
 
@@ -702,6 +720,7 @@ fn test_close_endowment() {
         endowment_id: CHARITY_ID,
         status: 1,
         beneficiary: None,
+        children: None,
     });
     let _res = execute(deps.as_mut(), env.clone(), info, update_status_msg).unwrap();
 
@@ -1158,6 +1177,130 @@ fn test_strategies_invest() {
         state.tokens_on_hand.liquid.native[0].amount,
         Uint128::from(3000_u128)
     );
+
+    // The first investment records the strategy in both the locked & liquid lists
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Endowment { id: CHARITY_ID },
+    )
+    .unwrap();
+    let endowment: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        endowment.invested_strategies.locked,
+        vec![STRATEGY_KEY.to_string()]
+    );
+    assert_eq!(
+        endowment.invested_strategies.liquid,
+        vec![STRATEGY_KEY.to_string()]
+    );
+
+    // Both the locked & liquid positions are counted against the strategy
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::StrategyPositions {
+            strategy_key: STRATEGY_KEY.to_string(),
+        },
+    )
+    .unwrap();
+    let positions: StrategyPositionsResponse = from_binary(&res).unwrap();
+    assert_eq!(positions.positions, 2);
+
+    // Investing in the same strategy again records it only once per account
+    let info = mock_info(CHARITY_ADDR, &[]);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::StrategiesInvest {
+            id: CHARITY_ID,
+            strategies: vec![StrategyInvestment {
+                strategy_key: STRATEGY_KEY.to_string(),
+                locked_amount: Uint128::from(1000_u128),
+                liquid_amount: Uint128::from(1000_u128),
+            }],
+        },
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Endowment { id: CHARITY_ID },
+    )
+    .unwrap();
+    let endowment: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        endowment.invested_strategies.locked,
+        vec![STRATEGY_KEY.to_string()]
+    );
+    assert_eq!(
+        endowment.invested_strategies.liquid,
+        vec![STRATEGY_KEY.to_string()]
+    );
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::StrategyPositions {
+            strategy_key: STRATEGY_KEY.to_string(),
+        },
+    )
+    .unwrap();
+    let positions: StrategyPositionsResponse = from_binary(&res).unwrap();
+    assert_eq!(positions.positions, 2);
+
+    // Closing the endowment redeems the strategy, keeping both positions until it's received
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::CloseEndowment {
+            id: CHARITY_ID,
+            beneficiary: Beneficiary::Wallet {
+                address: CHARITY_ADDR.to_string(),
+            },
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    let positions = |deps: Deps| -> u64 {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::StrategyPositions {
+                strategy_key: STRATEGY_KEY.to_string(),
+            },
+        )
+        .unwrap();
+        from_binary::<StrategyPositionsResponse>(&res)
+            .unwrap()
+            .positions
+    };
+    assert_eq!(positions(deps.as_ref()), 2);
+
+    // The redeemed funds land & release both positions
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(STRATEGY_KEY, &coins(2000, USDC)),
+        ExecuteMsg::VaultReceipt {
+            id: CHARITY_ID,
+            acct_type: AccountType::Locked,
+        },
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Endowment { id: CHARITY_ID },
+    )
+    .unwrap();
+    let endowment: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert!(endowment.invested_strategies.locked.is_empty());
+    assert!(endowment.invested_strategies.liquid.is_empty());
+    assert_eq!(endowment.pending_redemptions, 0);
+    assert_eq!(positions(deps.as_ref()), 0);
 }
 
 #[test]
@@ -1252,188 +1395,461 @@ fn test_strategies_redeem() {
     assert_eq!(res.messages.len(), 1);
 }
 
+fn reinvest_reply() -> Reply {
+    Reply {
+        id: 3,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    }
+}
+
 #[test]
-fn test_distribute_to_beneficiary() {
+fn test_reinvest_to_locked() {
     let (mut deps, _, _, _) = create_endowment();
 
-    // Only contract itself can call this entry. In other words, it is internal entry.
-    let info = mock_info("anyone", &[]);
-    let err = execute(
+    // deposit & invest some of the liquid balance in a native strategy
+    let info = mock_info(DEPOSITOR, &coins(10000_u128, USDC));
+    let deposit_msg = ExecuteMsg::Deposit(DepositMsg {
+        id: CHARITY_ID,
+        locked_percentage: Decimal::percent(50),
+        liquid_percentage: Decimal::percent(50),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), info, deposit_msg).unwrap();
+    let info = mock_info(CHARITY_ADDR, &[]);
+    let _res = execute(
         deps.as_mut(),
         mock_env(),
         info,
-        ExecuteMsg::DistributeToBeneficiary { id: CHARITY_ID },
+        ExecuteMsg::StrategiesInvest {
+            id: CHARITY_ID,
+            strategies: vec![StrategyInvestment {
+                strategy_key: STRATEGY_KEY.to_string(),
+                locked_amount: Uint128::zero(),
+                liquid_amount: Uint128::from(2000_u128),
+            }],
+        },
     )
-    .unwrap_err();
-    assert_eq!(err, ContractError::Unauthorized {});
+    .unwrap();
 
-    // Since "state.closing_beneficiary" is None, it just defaults the "state.balances".
-    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
-    let _ = execute(
+    // Fail to reinvest since no endowment owner calls
+    let info = mock_info("anyone", &[]);
+    let err = execute(
         deps.as_mut(),
         mock_env(),
         info,
-        ExecuteMsg::DistributeToBeneficiary { id: CHARITY_ID },
+        ExecuteMsg::ReinvestToLocked {
+            id: CHARITY_ID,
+            amount: Uint128::from(1000_u128),
+            source_strategy: STRATEGY_KEY.to_string(),
+            target_strategy: "strategy-native-2".to_string(),
+        },
     )
-    .unwrap();
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
 
-    // Set the "closing_beneficiary" for the tests
-    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
-    let _ = execute(
+    // Fail to reinvest from a strategy the liquid account is not invested in
+    let info = mock_info(CHARITY_ADDR, &[]);
+    let err = execute(
         deps.as_mut(),
         mock_env(),
         info,
-        ExecuteMsg::CloseEndowment {
+        ExecuteMsg::ReinvestToLocked {
             id: CHARITY_ID,
-            beneficiary: Beneficiary::Wallet {
-                address: CHARITY_ADDR.to_string(),
-            },
+            amount: Uint128::from(1000_u128),
+            source_strategy: "strategy-native-2".to_string(),
+            target_strategy: STRATEGY_KEY.to_string(),
         },
     )
-    .unwrap();
-    let res = query(
-        deps.as_ref(),
-        mock_env(),
-        QueryMsg::State { id: CHARITY_ID },
-    )
-    .unwrap();
-    let state: StateResponse = from_binary(&res).unwrap();
+    .unwrap_err();
     assert_eq!(
-        state.closing_beneficiary,
-        Some(Beneficiary::Wallet {
-            address: CHARITY_ADDR.to_string()
+        err,
+        ContractError::Std(StdError::GenericErr {
+            msg: "Endowment has no liquid investment in the source Strategy".to_string(),
         })
     );
 
-    // Succeed to distribute to "wallet beneficiary"
-    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
-    let res = execute(
+    // Fail to reinvest into a non-native strategy
+    let info = mock_info(CHARITY_ADDR, &[]);
+    let err = execute(
         deps.as_mut(),
         mock_env(),
         info,
-        ExecuteMsg::DistributeToBeneficiary { id: CHARITY_ID },
+        ExecuteMsg::ReinvestToLocked {
+            id: CHARITY_ID,
+            amount: Uint128::from(1000_u128),
+            source_strategy: STRATEGY_KEY.to_string(),
+            target_strategy: "strategy-ethereum".to_string(),
+        },
     )
-    .unwrap();
-    assert_eq!(res.messages.len(), 0);
-}
-
-#[test]
-fn test_manage_allowances() {
-    let (mut deps, env, _, _) = create_endowment();
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::GenericErr {
+            msg: "Reinvesting is only supported between native Strategies".to_string(),
+        })
+    );
 
-    // Only endowment owner can execute the entry
-    let info = mock_info("anyone", &[]);
+    // Fail to reinvest more vault tokens than the endowment holds
+    let info = mock_info(CHARITY_ADDR, &[]);
     let err = execute(
         deps.as_mut(),
         mock_env(),
         info,
-        ExecuteMsg::Allowance {
-            endowment_id: CHARITY_ID,
-            action: "add".to_string(),
-            spender: "spender".to_string(),
-            asset: Asset {
-                info: AssetInfoBase::Native(USDC.to_string()),
-                amount: Uint128::from(100_u128),
-            },
-            expires: None,
+        ExecuteMsg::ReinvestToLocked {
+            id: CHARITY_ID,
+            amount: Uint128::from(1000001_u128),
+            source_strategy: STRATEGY_KEY.to_string(),
+            target_strategy: "strategy-native-2".to_string(),
         },
     )
     .unwrap_err();
-    assert_eq!(err, ContractError::Unauthorized {});
+    assert_eq!(err, ContractError::BalanceTooSmall {});
 
-    // Invalid query(no owner || no spender) just returns EMPTY
-    let res = query(
-        deps.as_ref(),
+    // Succeed to reinvest a part of the position: source strategy is kept in the liquid list
+    let info = mock_info(CHARITY_ADDR, &[]);
+    let res = execute(
+        deps.as_mut(),
         mock_env(),
-        QueryMsg::Allowances {
+        info,
+        ExecuteMsg::ReinvestToLocked {
             id: CHARITY_ID,
-            spender: "spender".to_string(),
+            amount: Uint128::from(1000_u128),
+            source_strategy: STRATEGY_KEY.to_string(),
+            target_strategy: "strategy-native-2".to_string(),
         },
     )
     .unwrap();
-    let allowances: Allowances = from_binary(&res).unwrap();
-    assert!(allowances.assets.is_empty());
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].reply_on, ReplyOn::Success);
 
-    // Endowment owner can "add" the allowance
-    let info = mock_info(CHARITY_ADDR, &[]);
-    let _ = execute(
-        deps.as_mut(),
+    // Invested strategies are left as they are until the Vault Router round-trip succeeds
+    let res = query(
+        deps.as_ref(),
         mock_env(),
-        info,
-        ExecuteMsg::Allowance {
-            endowment_id: CHARITY_ID,
-            action: "add".to_string(),
-            spender: "spender".to_string(),
-            asset: Asset {
-                info: AssetInfoBase::Native(USDC.to_string()),
-                amount: Uint128::from(100_u128),
-            },
-            expires: None,
-        },
+        QueryMsg::Endowment { id: CHARITY_ID },
     )
     .unwrap();
+    let endowment: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert!(endowment.invested_strategies.locked.is_empty());
+    let _res = reply(deps.as_mut(), mock_env(), reinvest_reply()).unwrap();
 
     let res = query(
         deps.as_ref(),
         mock_env(),
-        QueryMsg::Allowances {
-            id: CHARITY_ID,
-            spender: "spender".to_string(),
-        },
+        QueryMsg::Endowment { id: CHARITY_ID },
     )
     .unwrap();
-    let allowances: Allowances = from_binary(&res).unwrap();
-    assert_eq!(allowances.assets.len(), 1);
-    assert_eq!(allowances.assets[0].amount, Uint128::from(100_u128));
+    let endowment: EndowmentDetailsResponse = from_binary(&res).unwrap();
     assert_eq!(
-        allowances.assets[0].info.to_string(),
-        format!("native:{}", USDC)
+        endowment.invested_strategies.liquid,
+        vec![STRATEGY_KEY.to_string()]
+    );
+    assert_eq!(
+        endowment.invested_strategies.locked,
+        vec!["strategy-native-2".to_string()]
     );
-    assert_eq!(allowances.expires[0], Expiration::Never {});
 
-    // Try to re-"add" the allowance
+    // Succeed to reinvest the rest of the position: source strategy is removed
     let info = mock_info(CHARITY_ADDR, &[]);
-    let _ = execute(
+    let _res = execute(
         deps.as_mut(),
         mock_env(),
         info,
-        ExecuteMsg::Allowance {
-            endowment_id: CHARITY_ID,
-            action: "add".to_string(),
-            spender: "spender".to_string(),
-            asset: Asset {
-                info: AssetInfoBase::Native(USDC.to_string()),
-                amount: Uint128::from(100_u128),
-            },
-            expires: Some(Expiration::AtHeight(env.block.height + 100)),
+        ExecuteMsg::ReinvestToLocked {
+            id: CHARITY_ID,
+            amount: Uint128::from(1000000_u128),
+            source_strategy: STRATEGY_KEY.to_string(),
+            target_strategy: "strategy-native-2".to_string(),
         },
     )
     .unwrap();
+    let _res = reply(deps.as_mut(), mock_env(), reinvest_reply()).unwrap();
 
     let res = query(
         deps.as_ref(),
         mock_env(),
-        QueryMsg::Allowances {
-            id: CHARITY_ID,
-            spender: "spender".to_string(),
-        },
+        QueryMsg::Endowment { id: CHARITY_ID },
     )
     .unwrap();
-    let allowances: Allowances = from_binary(&res).unwrap();
-    assert_eq!(allowances.assets.len(), 1);
-    assert_eq!(allowances.assets[0].amount, Uint128::from(200_u128));
+    let endowment: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert!(endowment.invested_strategies.liquid.is_empty());
     assert_eq!(
-        allowances.assets[0].info.to_string(),
-        format!("native:{}", USDC)
-    );
-    assert_eq!(
-        allowances.expires[0],
-        Expiration::AtHeight(env.block.height + 100)
+        endowment.invested_strategies.locked,
+        vec!["strategy-native-2".to_string()]
     );
 
-    // Cannot "add/remove" the invalid asset amount
+    // Fail to reinvest from a strategy without a liquid vault
     let info = mock_info(CHARITY_ADDR, &[]);
-    let _err = execute(
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::StrategiesInvest {
+            id: CHARITY_ID,
+            strategies: vec![StrategyInvestment {
+                strategy_key: "strategy-native-locked-only".to_string(),
+                locked_amount: Uint128::zero(),
+                liquid_amount: Uint128::from(500_u128),
+            }],
+        },
+    )
+    .unwrap();
+    let info = mock_info(CHARITY_ADDR, &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ReinvestToLocked {
+            id: CHARITY_ID,
+            amount: Uint128::from(500_u128),
+            source_strategy: "strategy-native-locked-only".to_string(),
+            target_strategy: "strategy-native-2".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::GenericErr {
+            msg: "Source Strategy has no liquid vault".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_strategies_claim() {
+    let (mut deps, _, _, _) = create_endowment();
+
+    // Fail to claim since no endowment owner calls
+    let info = mock_info("anyone", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::StrategiesClaim {
+            id: CHARITY_ID,
+            strategies: vec![STRATEGY_KEY.to_string()],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fail to claim from a non-native strategy
+    let info = mock_info(CHARITY_ADDR, &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::StrategiesClaim {
+            id: CHARITY_ID,
+            strategies: vec!["strategy-ethereum".to_string()],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::GenericErr {
+            msg: "Claiming is only supported for native Strategies".to_string(),
+        })
+    );
+
+    // Succeed to send a claim per strategy to the vault router
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::StrategiesClaim {
+            id: CHARITY_ID,
+            strategies: vec![STRATEGY_KEY.to_string(), "strategy-native-2".to_string()],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+}
+
+#[test]
+fn test_distribute_to_beneficiary() {
+    let (mut deps, _, _, _) = create_endowment();
+
+    // Only contract itself can call this entry. In other words, it is internal entry.
+    let info = mock_info("anyone", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::DistributeToBeneficiary { id: CHARITY_ID },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Since "state.closing_beneficiary" is None, it just defaults the "state.balances".
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::DistributeToBeneficiary { id: CHARITY_ID },
+    )
+    .unwrap();
+
+    // Set the "closing_beneficiary" for the tests
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::CloseEndowment {
+            id: CHARITY_ID,
+            beneficiary: Beneficiary::Wallet {
+                address: CHARITY_ADDR.to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::State { id: CHARITY_ID },
+    )
+    .unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        state.closing_beneficiary,
+        Some(Beneficiary::Wallet {
+            address: CHARITY_ADDR.to_string()
+        })
+    );
+
+    // Succeed to distribute to "wallet beneficiary"
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::DistributeToBeneficiary { id: CHARITY_ID },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 0);
+}
+
+#[test]
+fn test_manage_allowances() {
+    let (mut deps, env, _, _) = create_endowment();
+
+    // Only endowment owner can execute the entry
+    let info = mock_info("anyone", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Allowance {
+            endowment_id: CHARITY_ID,
+            action: "add".to_string(),
+            spender: "spender".to_string(),
+            asset: Asset {
+                info: AssetInfoBase::Native(USDC.to_string()),
+                amount: Uint128::from(100_u128),
+            },
+            expires: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Invalid query(no owner || no spender) just returns EMPTY
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Allowances {
+            id: CHARITY_ID,
+            spender: "spender".to_string(),
+        },
+    )
+    .unwrap();
+    let allowances: Allowances = from_binary(&res).unwrap();
+    assert!(allowances.assets.is_empty());
+
+    // Endowment owner can "add" the allowance
+    let info = mock_info(CHARITY_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Allowance {
+            endowment_id: CHARITY_ID,
+            action: "add".to_string(),
+            spender: "spender".to_string(),
+            asset: Asset {
+                info: AssetInfoBase::Native(USDC.to_string()),
+                amount: Uint128::from(100_u128),
+            },
+            expires: None,
+        },
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Allowances {
+            id: CHARITY_ID,
+            spender: "spender".to_string(),
+        },
+    )
+    .unwrap();
+    let allowances: Allowances = from_binary(&res).unwrap();
+    assert_eq!(allowances.assets.len(), 1);
+    assert_eq!(allowances.assets[0].amount, Uint128::from(100_u128));
+    assert_eq!(
+        allowances.assets[0].info.to_string(),
+        format!("native:{}", USDC)
+    );
+    assert_eq!(allowances.expires[0], Expiration::Never {});
+
+    // Try to re-"add" the allowance
+    let info = mock_info(CHARITY_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Allowance {
+            endowment_id: CHARITY_ID,
+            action: "add".to_string(),
+            spender: "spender".to_string(),
+            asset: Asset {
+                info: AssetInfoBase::Native(USDC.to_string()),
+                amount: Uint128::from(100_u128),
+            },
+            expires: Some(Expiration::AtHeight(env.block.height + 100)),
+        },
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Allowances {
+            id: CHARITY_ID,
+            spender: "spender".to_string(),
+        },
+    )
+    .unwrap();
+    let allowances: Allowances = from_binary(&res).unwrap();
+    assert_eq!(allowances.assets.len(), 1);
+    assert_eq!(allowances.assets[0].amount, Uint128::from(200_u128));
+    assert_eq!(
+        allowances.assets[0].info.to_string(),
+        format!("native:{}", USDC)
+    );
+    assert_eq!(
+        allowances.expires[0],
+        Expiration::AtHeight(env.block.height + 100)
+    );
+
+    // Cannot "add/remove" the invalid asset amount
+    let info = mock_info(CHARITY_ADDR, &[]);
+    let _err = execute(
         deps.as_mut(),
         mock_env(),
         info,
@@ -1653,15 +2069,1045 @@ fn test_spend_allowance() {
     );
 }
 
-#[test]
-fn test_migrate() {
-    let (mut deps, env, _acct_contract, _endow_details) = create_endowment();
-
-    let err = migrate(deps.as_mut(), env, MigrateMsg {}).unwrap_err();
-    assert_eq!(
-        err,
+fn child_endowment_msg(parent: Option<u32>, maturity_time: Option<u64>) -> CreateEndowmentMsg {
+    CreateEndowmentMsg {
+        owner: CHARITY_ADDR.to_string(),
+        name: "Test Child Endowment".to_string(),
+        endow_type: EndowmentType::Normal,
+        categories: Categories {
+            sdgs: vec![],
+            general: vec![],
+        },
+        tier: Some(3),
+        logo: None,
+        image: None,
+        maturity_time,
+        cw4_members: vec![],
+        kyc_donors_only: false,
+        cw3_threshold: Threshold::AbsolutePercentage {
+            percentage: Decimal::percent(10),
+        },
+        cw3_max_voting_period: 60,
+        beneficiaries_allowlist: vec![],
+        contributors_allowlist: vec![],
+        earnings_fee: None,
+        withdraw_fee: None,
+        deposit_fee: None,
+        aum_fee: None,
+        dao: None,
+        proposal_link: None,
+        endowment_controller: None,
+        parent,
+        split_to_liquid: Some(SplitDetails::default()),
+        ignore_user_splits: false,
+        referral_id: None,
+    }
+}
+
+#[test]
+fn test_child_endowments() {
+    let (mut deps, env, _acct_contract, endow_details) = create_endowment();
+    let child_id = CHARITY_ID + 1;
+
+    // Only the parent's owner can create a child under it
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(PLEB, &[]),
+        ExecuteMsg::CreateEndowment(child_endowment_msg(Some(CHARITY_ID), None)),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Child matures before its parent, so it cannot receive the parent's locked funds
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::CreateEndowment(child_endowment_msg(
+            Some(CHARITY_ID),
+            Some(endow_details.maturity_time.unwrap() - 5),
+        )),
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Endowment { id: child_id },
+    )
+    .unwrap();
+    let child: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert_eq!(child.parent, Some(CHARITY_ID));
+
+    // Children cannot have children of their own
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::CreateEndowment(child_endowment_msg(Some(child_id), None)),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NestedChildEndowment {});
+
+    // Fund the parent with 10000 locked & 10000 liquid
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(DEPOSITOR, &coins(20000, USDC)),
+        ExecuteMsg::Deposit(DepositMsg {
+            id: CHARITY_ID,
+            locked_percentage: Decimal::percent(50),
+            liquid_percentage: Decimal::percent(50),
+        }),
+    )
+    .unwrap();
+
+    let usdc = |amount: u128| AssetUnchecked {
+        info: AssetInfoBase::Native(USDC.to_string()),
+        amount: Uint128::from(amount),
+    };
+
+    // Only the parent's owner can move family funds
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(PLEB, &[]),
+        ExecuteMsg::AllocateToChild {
+            child_id,
+            acct_type: AccountType::Liquid,
+            assets: vec![usdc(4000)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Top-level endowments are not anyone's child
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::AllocateToChild {
+            child_id: CHARITY_ID,
+            acct_type: AccountType::Liquid,
+            assets: vec![usdc(4000)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotChildEndowment { id: CHARITY_ID });
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::AllocateToChild {
+            child_id,
+            acct_type: AccountType::Locked,
+            assets: vec![usdc(4000)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InaccessableLockedBalance {});
+
+    // Succeed to allocate liquid funds without any messages being sent
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::AllocateToChild {
+            child_id,
+            acct_type: AccountType::Liquid,
+            assets: vec![usdc(4000)],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 0);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::SweepFromChild {
+            child_id,
+            acct_type: AccountType::Liquid,
+            assets: vec![usdc(5000)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds {});
+
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::SweepFromChild {
+            child_id,
+            acct_type: AccountType::Liquid,
+            assets: vec![usdc(1000)],
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::State { id: child_id }).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.tokens_on_hand.liquid.native, coins(3000, USDC));
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::State { id: CHARITY_ID },
+    )
+    .unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.tokens_on_hand.liquid.native, coins(7000, USDC));
+
+    // The family balance consolidates the parent & its children
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::FamilyBalance { id: CHARITY_ID },
+    )
+    .unwrap();
+    let family: FamilyBalanceResponse = from_binary(&res).unwrap();
+    assert_eq!(family.children, vec![child_id]);
+    assert_eq!(family.liquid.native, coins(10000, USDC));
+    assert_eq!(family.locked.native, coins(10000, USDC));
+}
+
+#[test]
+fn test_charity_child_endowments() {
+    let (mut deps, env, _acct_contract, _endow_details) = create_endowment();
+    let parent_id = CHARITY_ID + 1;
+    let mut create_msg = child_endowment_msg(None, None);
+    create_msg.endow_type = EndowmentType::Charity;
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("applications-review", &[]),
+        ExecuteMsg::CreateEndowment(create_msg.clone()),
+    )
+    .unwrap();
+
+    // Charity children are reviewed like any other Charity, even for the parent's owner
+    create_msg.parent = Some(parent_id);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::CreateEndowment(create_msg.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("applications-review", &[]),
+        ExecuteMsg::CreateEndowment(create_msg),
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::Endowment { id: parent_id + 1 },
+    )
+    .unwrap();
+    let child: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert_eq!(child.parent, Some(parent_id));
+}
+
+#[test]
+fn test_close_parent_endowment() {
+    let (mut deps, env, _acct_contract, _endow_details) = create_endowment();
+    for _ in 0..2 {
+        let _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CHARITY_ADDR, &[]),
+            ExecuteMsg::CreateEndowment(child_endowment_msg(Some(CHARITY_ID), None)),
+        )
+        .unwrap();
+    }
+    // another top-level endowment to re-parent children under
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(PLEB, &[]),
+        ExecuteMsg::CreateEndowment(child_endowment_msg(None, None)),
+    )
+    .unwrap();
+
+    // Closing a child defaults to its parent as the beneficiary
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(AP_TEAM, &[]),
+        ExecuteMsg::UpdateEndowmentStatus(UpdateEndowmentStatusMsg {
+            endowment_id: 2,
+            status: 3,
+            beneficiary: None,
+            children: None,
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    match &res.messages[1].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => assert_eq!(
+            from_binary::<ExecuteMsg>(msg).unwrap(),
+            ExecuteMsg::CloseEndowment {
+                id: 2,
+                beneficiary: Beneficiary::Endowment { id: CHARITY_ID },
+            }
+        ),
+        _ => panic!("unexpected message"),
+    }
+
+    // The open child has to be cascaded to or re-parented, never left without a parent
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(AP_TEAM, &[]),
+        ExecuteMsg::UpdateEndowmentStatus(UpdateEndowmentStatusMsg {
+            endowment_id: CHARITY_ID,
+            status: 3,
+            beneficiary: Some(Beneficiary::Wallet {
+                address: CHARITY_ADDR.to_string(),
+            }),
+            children: None,
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OpenChildEndowments { id: CHARITY_ID });
+
+    // The closing parent & its children cannot be the beneficiary
+    for beneficiary_id in [CHARITY_ID, 3] {
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(AP_TEAM, &[]),
+            ExecuteMsg::UpdateEndowmentStatus(UpdateEndowmentStatusMsg {
+                endowment_id: CHARITY_ID,
+                status: 3,
+                beneficiary: Some(Beneficiary::Endowment { id: beneficiary_id }),
+                children: Some(ChildEndowmentsOnClose::Cascade),
+            }),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidInputs {});
+    }
+
+    // Children can only be re-parented under an open, top-level endowment
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(AP_TEAM, &[]),
+        ExecuteMsg::UpdateEndowmentStatus(UpdateEndowmentStatusMsg {
+            endowment_id: CHARITY_ID,
+            status: 3,
+            beneficiary: Some(Beneficiary::Wallet {
+                address: CHARITY_ADDR.to_string(),
+            }),
+            children: Some(ChildEndowmentsOnClose::Reparent { new_parent: 3 }),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidInputs {});
+
+    // Cascading closes the remaining open child with the parent's beneficiary
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(AP_TEAM, &[]),
+        ExecuteMsg::UpdateEndowmentStatus(UpdateEndowmentStatusMsg {
+            endowment_id: CHARITY_ID,
+            status: 3,
+            beneficiary: Some(Beneficiary::Wallet {
+                address: CHARITY_ADDR.to_string(),
+            }),
+            children: Some(ChildEndowmentsOnClose::Cascade),
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 4);
+    match &res.messages[3].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => assert_eq!(
+            from_binary::<ExecuteMsg>(msg).unwrap(),
+            ExecuteMsg::CloseEndowment {
+                id: 3,
+                beneficiary: Beneficiary::Wallet {
+                    address: CHARITY_ADDR.to_string(),
+                },
+            }
+        ),
+        _ => panic!("unexpected message"),
+    }
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Endowment { id: 3 }).unwrap();
+    let child: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert_eq!(child.status, EndowmentStatus::Closed);
+}
+
+#[test]
+fn test_reparent_children_on_close() {
+    let (mut deps, env, _acct_contract, _endow_details) = create_endowment();
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::CreateEndowment(child_endowment_msg(Some(CHARITY_ID), None)),
+    )
+    .unwrap();
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(PLEB, &[]),
+        ExecuteMsg::CreateEndowment(child_endowment_msg(None, None)),
+    )
+    .unwrap();
+
+    // Children move under the new parent & the settings controller is told about it
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(AP_TEAM, &[]),
+        ExecuteMsg::UpdateEndowmentStatus(UpdateEndowmentStatusMsg {
+            endowment_id: CHARITY_ID,
+            status: 3,
+            beneficiary: Some(Beneficiary::Wallet {
+                address: CHARITY_ADDR.to_string(),
+            }),
+            children: Some(ChildEndowmentsOnClose::Reparent { new_parent: 3 }),
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 3);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Endowment { id: 2 }).unwrap();
+    let child: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert_eq!(child.parent, Some(3));
+    assert_eq!(child.status, EndowmentStatus::Approved);
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::FamilyBalance { id: CHARITY_ID },
+    )
+    .unwrap();
+    let family: FamilyBalanceResponse = from_binary(&res).unwrap();
+    assert!(family.children.is_empty());
+    let res = query(deps.as_ref(), env, QueryMsg::FamilyBalance { id: 3 }).unwrap();
+    let family: FamilyBalanceResponse = from_binary(&res).unwrap();
+    assert_eq!(family.children, vec![2]);
+}
+
+#[test]
+fn test_referral_rewards() {
+    let (mut deps, env, _acct_contract, _endow_details) = create_endowment();
+    let referred_id = CHARITY_ID + 1;
+
+    // Shares are percentages
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(AP_TEAM, &[]),
+        ExecuteMsg::UpdateConfig {
+            new_owner: None,
+            new_registrar: None,
+            max_general_category_id: None,
+            referral: Some(ReferralConfig {
+                protocol_fee_share: Decimal::percent(150),
+                deposit_fee_share: Decimal::zero(),
+                period: 100,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidInputs {});
+
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(AP_TEAM, &[]),
+        ExecuteMsg::UpdateConfig {
+            new_owner: None,
+            new_registrar: None,
+            max_general_category_id: None,
+            referral: Some(ReferralConfig {
+                protocol_fee_share: Decimal::percent(50),
+                deposit_fee_share: Decimal::percent(50),
+                period: 100,
+            }),
+        },
+    )
+    .unwrap();
+
+    // Create the referred endowment & fund its liquid account
+    let mut create_msg = child_endowment_msg(None, None);
+    create_msg.referral_id = Some(CHARITY_ID);
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(PLEB, &[]),
+        ExecuteMsg::CreateEndowment(create_msg),
+    )
+    .unwrap();
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(DEPOSITOR, &coins(2000, USDC)),
+        ExecuteMsg::Deposit(DepositMsg {
+            id: referred_id,
+            locked_percentage: Decimal::zero(),
+            liquid_percentage: Decimal::one(),
+        }),
+    )
+    .unwrap();
+
+    // The 10% withdraw fee is split between the AP Treasury & the referrer
+    let withdraw_msg = ExecuteMsg::Withdraw {
+        id: referred_id,
+        acct_type: AccountType::Liquid,
+        beneficiary_wallet: Some("beneficiary".to_string()),
+        beneficiary_endow: None,
+        assets: vec![AssetUnchecked {
+            info: AssetInfoBase::Native(USDC.to_string()),
+            amount: Uint128::from(1000_u128),
+        }],
+    };
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        withdraw_msg.clone(),
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::State { id: referred_id },
+    )
+    .unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.tokens_on_hand.liquid.native, coins(1000, USDC));
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::State { id: CHARITY_ID },
+    )
+    .unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.tokens_on_hand.liquid.native, coins(50, USDC));
+
+    // No more rewards once the referral period is over
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(100);
+    let _ = execute(
+        deps.as_mut(),
+        later.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        withdraw_msg,
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        later,
+        QueryMsg::Referrals {
+            referrer_id: CHARITY_ID,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let referrals: ReferralsResponse = from_binary(&res).unwrap();
+    assert_eq!(referrals.referrals.len(), 1);
+    assert_eq!(referrals.referrals[0].endowment_id, referred_id);
+    assert_eq!(
+        referrals.referrals[0].expires_at,
+        env.block.time.seconds() + 100
+    );
+    assert_eq!(referrals.total_rewards.native, coins(50, USDC));
+}
+
+#[test]
+fn test_inter_endowment_transfer_pays_no_referral_reward() {
+    let (mut deps, env, _acct_contract, _endow_details) = create_endowment();
+    let referred_id = CHARITY_ID + 1;
+
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(AP_TEAM, &[]),
+        ExecuteMsg::UpdateConfig {
+            new_owner: None,
+            new_registrar: None,
+            max_general_category_id: None,
+            referral: Some(ReferralConfig {
+                protocol_fee_share: Decimal::percent(50),
+                deposit_fee_share: Decimal::percent(50),
+                period: 100,
+            }),
+        },
+    )
+    .unwrap();
+    let mut create_msg = child_endowment_msg(None, None);
+    create_msg.referral_id = Some(CHARITY_ID);
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(PLEB, &[]),
+        ExecuteMsg::CreateEndowment(create_msg),
+    )
+    .unwrap();
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(DEPOSITOR, &coins(2000, USDC)),
+        ExecuteMsg::Deposit(DepositMsg {
+            id: referred_id,
+            locked_percentage: Decimal::zero(),
+            liquid_percentage: Decimal::one(),
+        }),
+    )
+    .unwrap();
+
+    // No withdraw fee is charged on a transfer to another Endowment, so there is no reward
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::Withdraw {
+            id: referred_id,
+            acct_type: AccountType::Liquid,
+            beneficiary_wallet: Some("beneficiary".to_string()),
+            beneficiary_endow: Some(CHARITY_ID),
+            assets: vec![AssetUnchecked {
+                info: AssetInfoBase::Native(USDC.to_string()),
+                amount: Uint128::from(1000_u128),
+            }],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "beneficiary".to_string(),
+            amount: coins(1000, USDC),
+        })
+    );
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::State { id: referred_id },
+    )
+    .unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.tokens_on_hand.liquid.native, coins(1000, USDC));
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::State { id: CHARITY_ID },
+    )
+    .unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert!(state.tokens_on_hand.liquid.native.is_empty());
+
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::Referrals {
+            referrer_id: CHARITY_ID,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let referrals: ReferralsResponse = from_binary(&res).unwrap();
+    assert_eq!(referrals.referrals[0].rewards, GenericBalance::default());
+    assert_eq!(referrals.total_rewards, GenericBalance::default());
+}
+
+#[test]
+fn test_referral_period_starts_with_first_reward() {
+    let (mut deps, env, _acct_contract, _endow_details) = create_endowment();
+    let referred_id = CHARITY_ID + 1;
+
+    // Two endowments are referred before any referral rewards are configured
+    for _ in 0..2 {
+        let mut create_msg = child_endowment_msg(None, None);
+        create_msg.referral_id = Some(CHARITY_ID);
+        let _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(PLEB, &[]),
+            ExecuteMsg::CreateEndowment(create_msg),
+        )
+        .unwrap();
+    }
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(DEPOSITOR, &coins(2000, USDC)),
+        ExecuteMsg::Deposit(DepositMsg {
+            id: referred_id,
+            locked_percentage: Decimal::zero(),
+            liquid_percentage: Decimal::one(),
+        }),
+    )
+    .unwrap();
+
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(50);
+    let _ = execute(
+        deps.as_mut(),
+        later.clone(),
+        mock_info(AP_TEAM, &[]),
+        ExecuteMsg::UpdateConfig {
+            new_owner: None,
+            new_registrar: None,
+            max_general_category_id: None,
+            referral: Some(ReferralConfig {
+                protocol_fee_share: Decimal::percent(50),
+                deposit_fee_share: Decimal::percent(50),
+                period: 100,
+            }),
+        },
+    )
+    .unwrap();
+    let _ = execute(
+        deps.as_mut(),
+        later.clone(),
+        mock_info(CHARITY_ADDR, &[]),
+        ExecuteMsg::Withdraw {
+            id: referred_id,
+            acct_type: AccountType::Liquid,
+            beneficiary_wallet: Some("beneficiary".to_string()),
+            beneficiary_endow: None,
+            assets: vec![AssetUnchecked {
+                info: AssetInfoBase::Native(USDC.to_string()),
+                amount: Uint128::from(1000_u128),
+            }],
+        },
+    )
+    .unwrap();
+
+    // The referral period runs from the first reward
+    let res = query(
+        deps.as_ref(),
+        later.clone(),
+        QueryMsg::Referrals {
+            referrer_id: CHARITY_ID,
+            start_after: None,
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    let referrals: ReferralsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        referrals.referrals,
+        vec![ReferralResponse {
+            endowment_id: referred_id,
+            expires_at: later.block.time.seconds() + 100,
+            rewards: GenericBalance {
+                native: coins(50, USDC),
+                cw20: vec![],
+            },
+        }]
+    );
+    assert_eq!(referrals.total_rewards.native, coins(50, USDC));
+
+    // The other referral has no rewards yet, so it isn't recorded
+    let res = query(
+        deps.as_ref(),
+        later,
+        QueryMsg::Referrals {
+            referrer_id: CHARITY_ID,
+            start_after: Some(referred_id),
+            limit: None,
+        },
+    )
+    .unwrap();
+    let referrals: ReferralsResponse = from_binary(&res).unwrap();
+    assert!(referrals.referrals.is_empty());
+    assert_eq!(referrals.total_rewards.native, coins(50, USDC));
+}
+
+#[test]
+fn test_endowments_query() {
+    let (mut deps, env, _acct_contract, _endow_details) = create_endowment();
+
+    // Endowments #2, #3 & #4 with a mix of owners, SDGs & tiers
+    let endowments = vec![
+        (PLEB, vec![1, 3], Some(3), Some(7)),
+        (CHARITY_ADDR, vec![3], Some(2), None),
+        (PLEB, vec![], Some(3), None),
+    ];
+    for (owner, sdgs, tier, proposal_link) in endowments {
+        let mut create_msg = child_endowment_msg(None, None);
+        create_msg.owner = owner.to_string();
+        create_msg.categories.sdgs = sdgs;
+        create_msg.tier = tier;
+        create_msg.proposal_link = proposal_link;
+        let _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            ExecuteMsg::CreateEndowment(create_msg),
+        )
+        .unwrap();
+    }
+    let query_page = |deps: Deps, msg: EndowmentsQueryMsg| -> (Vec<u32>, Option<u32>) {
+        let res = query(deps, env.clone(), QueryMsg::Endowments(msg)).unwrap();
+        let list: EndowmentListResponse = from_binary(&res).unwrap();
+        (
+            list.endowments.iter().map(|e| e.id).collect(),
+            list.next_start_after,
+        )
+    };
+    let query_ids = |deps: Deps, msg: EndowmentsQueryMsg| -> Vec<u32> { query_page(deps, msg).0 };
+    let page = |start_after, limit| EndowmentsQueryMsg {
+        start_after,
+        limit,
+        ..Default::default()
+    };
+
+    // Paginate through all Endowments
+    assert_eq!(
+        query_page(deps.as_ref(), page(None, None)),
+        (vec![1, 2, 3, 4], None)
+    );
+    assert_eq!(
+        query_page(deps.as_ref(), page(None, Some(2))),
+        (vec![1, 2], Some(2))
+    );
+    assert_eq!(
+        query_page(deps.as_ref(), page(Some(2), Some(2))),
+        (vec![3, 4], Some(4))
+    );
+    assert_eq!(
+        query_page(deps.as_ref(), page(Some(4), Some(2))),
+        (vec![], None)
+    );
+
+    // Filters apply within the page of Endowments scanned
+    let tier_page = |start_after| EndowmentsQueryMsg {
+        start_after,
+        limit: Some(2),
+        tier: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(
+        query_page(deps.as_ref(), tier_page(None)),
+        (vec![1, 2], Some(2))
+    );
+    assert_eq!(
+        query_page(deps.as_ref(), tier_page(Some(2))),
+        (vec![4], Some(4))
+    );
+
+    // Filter by each index, combined with the remaining filters
+    let filtered = |owner: Option<&str>, sdg, tier, start_after| EndowmentsQueryMsg {
+        start_after,
+        limit: None,
+        status: Some(EndowmentStatus::Approved),
+        endow_type: Some(EndowmentType::Normal),
+        tier,
+        sdg,
+        owner: owner.map(|o| o.to_string()),
+    };
+    assert_eq!(
+        query_ids(deps.as_ref(), filtered(Some(PLEB), None, None, None)),
+        vec![2, 4]
+    );
+    assert_eq!(
+        query_ids(deps.as_ref(), filtered(Some(PLEB), None, None, Some(2))),
+        vec![4]
+    );
+    assert_eq!(
+        query_ids(deps.as_ref(), filtered(None, Some(3), None, None)),
+        vec![2, 3]
+    );
+    assert_eq!(
+        query_ids(deps.as_ref(), filtered(None, Some(3), None, Some(2))),
+        vec![3]
+    );
+    assert_eq!(
+        query_ids(
+            deps.as_ref(),
+            filtered(Some(CHARITY_ADDR), Some(3), None, None)
+        ),
+        vec![3]
+    );
+    assert_eq!(
+        query_ids(deps.as_ref(), filtered(None, None, Some(3), None)),
+        vec![1, 2, 4]
+    );
+    assert_eq!(
+        query_ids(deps.as_ref(), filtered(None, Some(2), None, None)),
+        Vec::<u32>::new()
+    );
+
+    // Closed Endowments drop out of the status index
+    let _ = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(AP_TEAM, &[]),
+        ExecuteMsg::UpdateEndowmentStatus(UpdateEndowmentStatusMsg {
+            endowment_id: 4,
+            status: 3,
+            beneficiary: Some(Beneficiary::Wallet {
+                address: PLEB.to_string(),
+            }),
+            children: None,
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        query_ids(deps.as_ref(), filtered(None, None, None, None)),
+        vec![1, 2, 3]
+    );
+
+    // Endowments can be looked up by their proposal link
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::EndowmentByProposalLink { proposal_link: 7 },
+    )
+    .unwrap();
+    let endowment: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert_eq!(endowment.owner, Addr::unchecked(PLEB));
+    assert_eq!(endowment.proposal_link, Some(7));
+    let err = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::EndowmentByProposalLink { proposal_link: 8 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::NotFound {
+            kind: "endowment".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_migrate() {
+    let (mut deps, env, _acct_contract, _endow_details) = create_endowment();
+
+    let err = migrate(deps.as_mut(), env, MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err,
         ContractError::Std(StdError::GenericErr {
             msg: "Cannot upgrade from a newer version".to_string(),
         })
     );
 }
+
+#[test]
+fn test_migrate_indexes_endowments() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(AP_TEAM, &[]),
+        InstantiateMsg {
+            owner_sc: AP_TEAM.to_string(),
+            registrar_contract: REGISTRAR_CONTRACT.to_string(),
+        },
+    )
+    .unwrap();
+
+    // store an Endowment invested in a Strategy as v2.0.3 did, without any indexes
+    let endowment = Endowment {
+        owner: Addr::unchecked(CHARITY_ADDR),
+        name: "Invested Endowment".to_string(),
+        categories: Categories {
+            sdgs: vec![3],
+            general: vec![],
+        },
+        tier: Some(2),
+        endow_type: EndowmentType::Charity,
+        logo: None,
+        image: None,
+        status: EndowmentStatus::Approved,
+        deposit_approved: true,
+        withdraw_approved: true,
+        maturity_time: None,
+        invested_strategies: Investments {
+            locked: vec![STRATEGY_KEY.to_string()],
+            liquid: vec![STRATEGY_KEY.to_string()],
+        },
+        rebalance: RebalanceDetails::default(),
+        kyc_donors_only: false,
+        pending_redemptions: 0,
+        proposal_link: Some(5),
+        referral_id: None,
+        parent: None,
+    };
+    let data = String::from_utf8(to_vec(&endowment).unwrap())
+        .unwrap()
+        .replace(",\"parent\":null", "");
+    deps.storage
+        .set(&ENDOWMENTS.key(CHARITY_ID), data.as_bytes());
+    CONFIG
+        .update(deps.as_mut().storage, |mut config| -> StdResult<_> {
+            config.next_account_id = 2;
+            Ok(config)
+        })
+        .unwrap();
+    set_contract_version(deps.as_mut().storage, "accounts", "2.0.3").unwrap();
+
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+    // the Endowment keeps its investments & their positions are counted
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Endowment { id: CHARITY_ID },
+    )
+    .unwrap();
+    let details: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert_eq!(details.invested_strategies, endowment.invested_strategies);
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::StrategyPositions {
+            strategy_key: STRATEGY_KEY.to_string(),
+        },
+    )
+    .unwrap();
+    let positions: StrategyPositionsResponse = from_binary(&res).unwrap();
+    assert_eq!(positions.positions, 2);
+
+    // & it can be found through each of the secondary indexes
+    for msg in [
+        EndowmentsQueryMsg {
+            owner: Some(CHARITY_ADDR.to_string()),
+            ..Default::default()
+        },
+        EndowmentsQueryMsg {
+            sdg: Some(3),
+            ..Default::default()
+        },
+        EndowmentsQueryMsg {
+            status: Some(EndowmentStatus::Approved),
+            ..Default::default()
+        },
+    ] {
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Endowments(msg)).unwrap();
+        let list: EndowmentListResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            list.endowments.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![CHARITY_ID]
+        );
+    }
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::EndowmentByProposalLink { proposal_link: 5 },
+    )
+    .unwrap();
+    let details: EndowmentDetailsResponse = from_binary(&res).unwrap();
+    assert_eq!(details.owner, Addr::unchecked(CHARITY_ADDR));
+}
@@ -102,10 +102,28 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Strategy { strategy_key } => {
             to_binary(&queriers::query_strategy(deps, strategy_key)?)
         }
+        QueryMsg::Strategies {
+            start_after,
+            limit,
+            approval_state,
+            locale,
+            chain,
+        } => to_binary(&queriers::query_strategies(
+            deps,
+            start_after,
+            limit,
+            approval_state,
+            locale,
+            chain,
+        )?),
         QueryMsg::NetworkConnection { chain_id } => {
             to_binary(&queriers::query_network_connection(deps, chain_id)?)
         }
+        QueryMsg::NetworkConnections { start_after, limit } => to_binary(
+            &queriers::query_network_connections(deps, start_after, limit)?,
+        ),
         QueryMsg::Fee { name } => to_binary(&queriers::query_fee(deps, name)?),
+        QueryMsg::Fees {} => to_binary(&queriers::query_fees(deps)?),
     }
 }
 
@@ -1,5 +1,6 @@
 use crate::state::{CONFIG, FEES, NETWORK_CONNECTIONS, STRATEGIES};
 use angel_core::errors::core::ContractError;
+use angel_core::msgs::accounts::{QueryMsg as AccountsQueryMsg, StrategyPositionsResponse};
 use angel_core::msgs::registrar::*;
 use angel_core::structs::{NetworkInfo, StrategyApprovalState, StrategyParams};
 use angel_core::utils::{percentage_checks, split_checks};
@@ -136,6 +137,33 @@ pub fn strategy_remove(
     if strat_bytes.len() != 4 {
         return Err(ContractError::InvalidInputs {});
     }
+    // only a Strategy that never took deposits, or is being wound down, can be removed
+    let strategy = STRATEGIES.load(deps.storage, strat_bytes)?;
+    if !strategy.approval_state.is_removable() {
+        return Err(ContractError::StrategyTransitionNotAllowed {
+            from: strategy.approval_state.to_string(),
+            to: "removed".to_string(),
+        });
+    }
+
+    // refuse to remove a Strategy while endowments on this chain still hold positions in it
+    let accounts_contract = NETWORK_CONNECTIONS
+        .may_load(deps.storage, &config.axelar_chain_id)?
+        .and_then(|network| network.accounts_contract);
+    if let Some(accounts_contract) = accounts_contract {
+        let res: StrategyPositionsResponse = deps.querier.query_wasm_smart(
+            accounts_contract,
+            &AccountsQueryMsg::StrategyPositions {
+                strategy_key: strategy_key.clone(),
+            },
+        )?;
+        if res.positions > 0 {
+            return Err(ContractError::StrategyHasOpenPositions {
+                positions: res.positions,
+            });
+        }
+    }
+
     // remove the Strategy from storage
     STRATEGIES.remove(deps.storage, &strat_bytes);
     Ok(Response::default())
@@ -162,6 +190,14 @@ pub fn strategy_update(
     // try to look up the given strategy in Storage
     let mut strategy = STRATEGIES.load(deps.storage, &strat_bytes)?;
 
+    // only allow the legal moves through the Strategy lifecycle
+    if !strategy.approval_state.can_transition_to(&approval_state) {
+        return Err(ContractError::StrategyTransitionNotAllowed {
+            from: strategy.approval_state.to_string(),
+            to: approval_state.to_string(),
+        });
+    }
+
     // update strategy with approval state from passed arg
     strategy.approval_state = approval_state;
     STRATEGIES.save(deps.storage, strategy_key.as_bytes(), &strategy)?;
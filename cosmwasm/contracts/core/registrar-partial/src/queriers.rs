@@ -1,9 +1,15 @@
 use crate::state::{CONFIG, FEES, NETWORK_CONNECTIONS, STRATEGIES};
 use angel_core::msgs::registrar::{
-    ConfigResponse, NetworkConnectionResponse, StrategyDetailResponse,
+    ConfigResponse, FeeListResponse, NetworkConnectionListResponse, NetworkConnectionResponse,
+    StrategyDetailResponse, StrategyListItem, StrategyListResponse,
 };
-use cosmwasm_std::{Decimal, Deps, StdError, StdResult};
+use angel_core::structs::{StrategyApprovalState, StrategyLocale};
+use cosmwasm_std::{Decimal, Deps, Order, StdError, StdResult};
 use cw2::get_contract_version;
+use cw_storage_plus::Bound;
+
+const MAX_LIMIT: u64 = 30;
+const DEFAULT_LIMIT: u64 = 10;
 
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
@@ -39,6 +45,62 @@ pub fn query_strategy(deps: Deps, strategy_key: String) -> StdResult<StrategyDet
     })
 }
 
+pub fn query_strategies(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u64>,
+    approval_state: Option<StrategyApprovalState>,
+    locale: Option<StrategyLocale>,
+    chain: Option<String>,
+) -> StdResult<StrategyListResponse> {
+    let start = start_after
+        .as_ref()
+        .map(|key| Bound::exclusive(key.as_bytes()));
+    // the filters apply within the page, so a filter matching few strategies doesn't
+    // scan the whole map. `next_start_after` tells where the next page starts
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let page = STRATEGIES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let next_start_after = match page.last() {
+        Some((key, _)) if page.len() == limit => Some(String::from_utf8(key.clone())?),
+        _ => None,
+    };
+    let strategies = page
+        .into_iter()
+        .filter(|(_, strategy)| {
+            if let Some(state) = &approval_state {
+                if *state != strategy.approval_state {
+                    return false;
+                }
+            }
+            if let Some(locale) = &locale {
+                if *locale != strategy.locale {
+                    return false;
+                }
+            }
+            if let Some(chain) = &chain {
+                if *chain != strategy.chain {
+                    return false;
+                }
+            }
+            true
+        })
+        .map(|(key, strategy)| {
+            Ok(StrategyListItem {
+                strategy_key: String::from_utf8(key)?,
+                strategy,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(StrategyListResponse {
+        strategies,
+        next_start_after,
+    })
+}
+
 pub fn query_network_connection(
     deps: Deps,
     chain_id: String,
@@ -49,6 +111,37 @@ pub fn query_network_connection(
     })
 }
 
+pub fn query_network_connections(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<NetworkConnectionListResponse> {
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let network_connections = NETWORK_CONNECTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize)
+        .map(|item| {
+            let (chain, network_connection) = item?;
+            Ok(NetworkConnectionResponse {
+                chain,
+                network_connection,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(NetworkConnectionListResponse {
+        network_connections,
+    })
+}
+
 pub fn query_fee(deps: Deps, name: String) -> StdResult<Decimal> {
     Ok(FEES.load(deps.storage, &name).unwrap_or(Decimal::zero()))
 }
+
+pub fn query_fees(deps: Deps) -> StdResult<FeeListResponse> {
+    Ok(FeeListResponse {
+        fees: FEES
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?,
+    })
+}
@@ -1,8 +1,9 @@
 use crate::contract::{execute, instantiate, query};
 use angel_core::errors::core::*;
+use angel_core::msgs::accounts::StrategyPositionsResponse;
 use angel_core::msgs::registrar::{
-    ConfigResponse, InstantiateMsg, NetworkConnectionResponse, StrategyDetailResponse,
-    UpdateConfigMsg,
+    ConfigResponse, FeeListResponse, InstantiateMsg, NetworkConnectionListResponse,
+    NetworkConnectionResponse, StrategyDetailResponse, StrategyListResponse, UpdateConfigMsg,
 };
 use angel_core::msgs::registrar_partial::{ExecuteMsg, QueryMsg};
 use angel_core::structs::{
@@ -10,7 +11,9 @@ use angel_core::structs::{
     StrategyLocale, StrategyParams,
 };
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{coins, from_binary, Addr, Decimal, StdError};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, ContractResult, Decimal, StdError, SystemResult, WasmQuery,
+};
 use std::str::Utf8Error;
 
 const AP_TEAM: &str = "juno1rcznds2le2eflj3y4e8ep3e4upvq04sc65wdly";
@@ -236,7 +239,16 @@ fn test_add_update_and_remove_strategy() {
         strategy_detail_response.strategy.approval_state
     );
 
-    // remove strategy
+    // remove strategy, once no endowment holds a position in it
+    deps.querier.update_wasm(|_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_binary(&StrategyPositionsResponse {
+                strategy_key: STRATEGY_KEY.unwrap().to_string(),
+                positions: 0,
+            })
+            .unwrap(),
+        ))
+    });
     let info = mock_info(ap_team.as_ref(), &coins(1000, "earth"));
     let msg = ExecuteMsg::StrategyRemove {
         strategy_key: STRATEGY_KEY.unwrap().to_string(),
@@ -473,3 +485,295 @@ fn test_update_fees() {
     let fee: Decimal = from_binary(&res).unwrap();
     assert_eq!(fee, Decimal::from_ratio(20_u128, 100_u128));
 }
+
+#[test]
+fn test_list_strategies_network_connections_and_fees() {
+    let mut deps = mock_dependencies();
+    let info = mock_info(AP_TEAM, &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), instantiate_msg()).unwrap();
+
+    for chain_id in ["osmosis", "ethereum"] {
+        let msg = ExecuteMsg::UpdateNetworkConnections {
+            chain_id: chain_id.to_string(),
+            network_info: NetworkInfo {
+                router_contract: None,
+                accounts_contract: None,
+            },
+            action: "post".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let strategies = [
+        (
+            "aaa1",
+            StrategyApprovalState::Approved,
+            StrategyLocale::Native,
+            "osmosis",
+        ),
+        (
+            "aaa2",
+            StrategyApprovalState::NotApproved,
+            StrategyLocale::Native,
+            "osmosis",
+        ),
+        (
+            "aaa3",
+            StrategyApprovalState::Approved,
+            StrategyLocale::Evm,
+            "ethereum",
+        ),
+    ];
+    for (strategy_key, approval_state, locale, chain) in strategies {
+        let msg = ExecuteMsg::StrategyAdd {
+            strategy_key: strategy_key.to_string(),
+            strategy: StrategyParams {
+                approval_state,
+                locale,
+                chain: chain.to_string(),
+                input_denom: USDC.to_string(),
+                locked_addr: None,
+                liquid_addr: None,
+            },
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let query_strategies = |start_after: Option<&str>,
+                            limit: Option<u64>,
+                            approval_state: Option<StrategyApprovalState>,
+                            locale: Option<StrategyLocale>,
+                            chain: Option<&str>| {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Strategies {
+                start_after: start_after.map(|key| key.to_string()),
+                limit,
+                approval_state,
+                locale,
+                chain: chain.map(|chain| chain.to_string()),
+            },
+        )
+        .unwrap();
+        let res: StrategyListResponse = from_binary(&res).unwrap();
+        res.strategies
+            .into_iter()
+            .map(|item| item.strategy_key)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        query_strategies(None, None, None, None, None),
+        vec!["aaa1", "aaa2", "aaa3"]
+    );
+    assert_eq!(
+        query_strategies(Some("aaa1"), Some(1), None, None, None),
+        vec!["aaa2"]
+    );
+    assert_eq!(
+        query_strategies(
+            None,
+            None,
+            Some(StrategyApprovalState::Approved),
+            None,
+            None
+        ),
+        vec!["aaa1", "aaa3"]
+    );
+    assert_eq!(
+        query_strategies(
+            None,
+            None,
+            Some(StrategyApprovalState::Approved),
+            Some(StrategyLocale::Native),
+            None
+        ),
+        vec!["aaa1"]
+    );
+    assert_eq!(
+        query_strategies(None, None, None, None, Some("ethereum")),
+        vec!["aaa3"]
+    );
+
+    // a filtered page only looks at `limit` strategies, & tells where to go on from
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Strategies {
+            start_after: None,
+            limit: Some(2),
+            approval_state: None,
+            locale: None,
+            chain: Some("ethereum".to_string()),
+        },
+    )
+    .unwrap();
+    let res: StrategyListResponse = from_binary(&res).unwrap();
+    assert_eq!(res.strategies, vec![]);
+    assert_eq!(res.next_start_after, Some("aaa2".to_string()));
+    assert_eq!(
+        query_strategies(Some("aaa2"), Some(2), None, None, Some("ethereum")),
+        vec!["aaa3"]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::NetworkConnections {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let res: NetworkConnectionListResponse = from_binary(&res).unwrap();
+    let chains: Vec<String> = res
+        .network_connections
+        .into_iter()
+        .map(|connection| connection.chain)
+        .collect();
+    assert_eq!(chains, vec!["ethereum", "juno", "osmosis"]);
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::NetworkConnections {
+            start_after: Some("ethereum".to_string()),
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    let res: NetworkConnectionListResponse = from_binary(&res).unwrap();
+    assert_eq!(res.network_connections.len(), 1);
+    assert_ne!(res.network_connections[0].chain, "ethereum");
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+    let res: FeeListResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        res.fees,
+        vec![
+            ("accounts_withdraw".to_string(), Decimal::permille(2)),
+            ("vaults_harvest".to_string(), Decimal::percent(20)),
+        ]
+    );
+}
+
+#[test]
+fn test_strategy_lifecycle() {
+    let mut deps = mock_dependencies();
+    let info = mock_info(AP_TEAM, &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::StrategyAdd {
+        strategy_key: STRATEGY_KEY.unwrap().to_string(),
+        strategy: StrategyParams {
+            approval_state: StrategyApprovalState::Approved,
+            locale: StrategyLocale::Native,
+            chain: CHAIN_ID.to_string(),
+            input_denom: USDC.to_string(),
+            locked_addr: None,
+            liquid_addr: None,
+        },
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // An approved strategy cannot go back to not approved, nor be removed
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::StrategyUpdate {
+            strategy_key: STRATEGY_KEY.unwrap().to_string(),
+            approval_state: StrategyApprovalState::NotApproved,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StrategyTransitionNotAllowed {
+            from: "approved".to_string(),
+            to: "not_approved".to_string(),
+        }
+    );
+    let remove_strategy_msg = ExecuteMsg::StrategyRemove {
+        strategy_key: STRATEGY_KEY.unwrap().to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        remove_strategy_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StrategyTransitionNotAllowed {
+            from: "approved".to_string(),
+            to: "removed".to_string(),
+        }
+    );
+
+    // Deprecated -> WithdrawOnly -> removable
+    for approval_state in [
+        StrategyApprovalState::Deprecated,
+        StrategyApprovalState::WithdrawOnly,
+    ] {
+        let msg = ExecuteMsg::StrategyUpdate {
+            strategy_key: STRATEGY_KEY.unwrap().to_string(),
+            approval_state,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    // Endowments on this chain still hold positions in the strategy
+    let add_network_info_msg = ExecuteMsg::UpdateNetworkConnections {
+        chain_id: CHAIN_ID.to_string(),
+        network_info: NetworkInfo {
+            router_contract: None,
+            accounts_contract: Some("accounts_contract_addr".to_string()),
+        },
+        action: "post".to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        add_network_info_msg,
+    )
+    .unwrap();
+    let mock_positions = |positions: u64| {
+        move |_: &WasmQuery| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&StrategyPositionsResponse {
+                    strategy_key: STRATEGY_KEY.unwrap().to_string(),
+                    positions,
+                })
+                .unwrap(),
+            ))
+        }
+    };
+    deps.querier.update_wasm(mock_positions(1));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        remove_strategy_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StrategyHasOpenPositions { positions: 1 }
+    );
+
+    // All positions are redeemed
+    deps.querier.update_wasm(mock_positions(0));
+    execute(deps.as_mut(), mock_env(), info, remove_strategy_msg).unwrap();
+    query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Strategy {
+            strategy_key: STRATEGY_KEY.unwrap().to_string(),
+        },
+    )
+    .unwrap_err();
+}
@@ -9,8 +9,8 @@ use angel_core::structs::{
 };
 use angel_core::utils::{percentage_checks, split_checks};
 use cosmwasm_std::{
-    entry_point, from_slice, to_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
-    StdError, StdResult,
+    entry_point, from_slice, to_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply,
+    Response, StdError, StdResult,
 };
 use cw2::{get_contract_version, set_contract_version};
 
@@ -124,7 +124,26 @@ pub fn execute(
             network_info,
             action,
         } => executers::update_network_connections(deps, env, info, chain_id, network_info, action),
-        ExecuteMsg::UpdateFees { fees } => executers::update_fees(deps, info, fees),
+        ExecuteMsg::UpdateFees { fees } => executers::update_fees(deps, env, info, fees),
+        ExecuteMsg::UpdateValidators { add, remove } => {
+            executers::update_validators(deps, info, add, remove)
+        }
+        ExecuteMsg::UpdateSubscribers { add, remove } => {
+            executers::update_subscribers(deps, info, add, remove)
+        }
+        ExecuteMsg::UpdateTimelock { period } => {
+            executers::update_timelock(deps, env, info, period)
+        }
+        ExecuteMsg::ExecuteQueuedChange { id } => executers::execute_queued_change(deps, env, id),
+        ExecuteMsg::CancelQueuedChange { id } => executers::cancel_queued_change(deps, info, id),
+    }
+}
+
+#[entry_point]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        executers::REGISTRAR_HOOK_REPLY_ID => executers::registrar_hook_reply(msg.result),
+        _ => Err(ContractError::Unauthorized {}),
     }
 }
 
@@ -136,10 +155,35 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Strategy { strategy_key } => {
             to_binary(&queriers::query_strategy(deps, strategy_key)?)
         }
+        QueryMsg::Strategies {
+            start_after,
+            limit,
+            approval_state,
+            locale,
+            chain,
+        } => to_binary(&queriers::query_strategies(
+            deps,
+            start_after,
+            limit,
+            approval_state,
+            locale,
+            chain,
+        )?),
         QueryMsg::NetworkConnection { chain_id } => {
             to_binary(&queriers::query_network_connection(deps, chain_id)?)
         }
+        QueryMsg::NetworkConnections { start_after, limit } => to_binary(
+            &queriers::query_network_connections(deps, start_after, limit)?,
+        ),
         QueryMsg::Fee { name } => to_binary(&queriers::query_fee(deps, name)?),
+        QueryMsg::Fees {} => to_binary(&queriers::query_fees(deps)?),
+        QueryMsg::Validators {} => to_binary(&queriers::query_validators(deps)?),
+        QueryMsg::Timelock {} => to_binary(&queriers::query_timelock(deps)?),
+        QueryMsg::QueuedChanges { start_after, limit } => {
+            to_binary(&queriers::query_queued_changes(deps, start_after, limit)?)
+        }
+        QueryMsg::PendingOwner {} => to_binary(&queriers::query_pending_owner(deps)?),
+        QueryMsg::Subscribers {} => to_binary(&queriers::query_subscribers(deps)?),
     }
 }
 
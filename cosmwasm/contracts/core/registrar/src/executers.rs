@@ -1,61 +1,261 @@
-use crate::state::{CONFIG, CONFIG_EXTENSION, FEES, NETWORK_CONNECTIONS, STRATEGIES};
+use crate::state::{
+    PendingOwner, QueuedChange, CONFIG, CONFIG_EXTENSION, FEES, NETWORK_CONNECTIONS, PENDING_OWNER,
+    QUEUED_CHANGES, QUEUED_CHANGE_SEQ, STRATEGIES, SUBSCRIBERS, TIMELOCK_PERIOD, VALIDATORS,
+};
 use angel_core::errors::core::ContractError;
+use angel_core::msgs::accounts::{QueryMsg as AccountsQueryMsg, StrategyPositionsResponse};
 use angel_core::msgs::registrar::*;
 use angel_core::structs::{NetworkInfo, StrategyApprovalState, StrategyParams};
 use angel_core::utils::{percentage_checks, split_checks};
-use cosmwasm_std::{Decimal, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{Decimal, Deps, DepsMut, Env, MessageInfo, Response, SubMsg, SubMsgResult};
+
+/// Number of blocks the proposed owner has to accept the ownership
+const PENDING_OWNER_DEADLINE: u64 = 42069;
+
+/// Reply ID of the `RegistrarUpdated` hooks sent to subscribers
+pub const REGISTRAR_HOOK_REPLY_ID: u64 = 0;
 
 pub fn update_owner(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     new_owner: String,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    let new_owner_addr = deps.api.addr_validate(&new_owner)?;
+
+    // proposed owner accepts the ownership before the deadline
+    if let Some(pending) = PENDING_OWNER.may_load(deps.storage)? {
+        if info.sender == pending.owner && new_owner_addr == pending.owner {
+            if env.block.height > pending.deadline {
+                return Err(ContractError::Expired {});
+            }
+            config.owner = pending.owner;
+            CONFIG.save(deps.storage, &config)?;
+            PENDING_OWNER.remove(deps.storage);
+            return Ok(Response::new()
+                .add_attribute("action", "accept_owner")
+                .add_attribute("owner", config.owner));
+        }
+    }
 
     if info.sender.ne(&config.owner) {
         return Err(ContractError::Unauthorized {});
     }
+    // current owner proposes the new owner through the timelock, who must then accept it
+    queue_or_apply(
+        deps,
+        env,
+        ConfigChange::UpdateOwner {
+            new_owner: new_owner_addr.to_string(),
+        },
+    )
+}
+
+fn apply_update_owner(
+    deps: DepsMut,
+    env: Env,
+    new_owner: String,
+) -> Result<Response, ContractError> {
     let new_owner_addr = deps.api.addr_validate(&new_owner)?;
-    // update config attributes with newly passed owner
-    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
-        config.owner = new_owner_addr;
-        Ok(config)
-    })?;
+    // the acceptance deadline runs from when the proposal takes effect
+    let deadline = env.block.height + PENDING_OWNER_DEADLINE;
+    PENDING_OWNER.save(
+        deps.storage,
+        &PendingOwner {
+            owner: new_owner_addr.clone(),
+            deadline,
+        },
+    )?;
 
-    Ok(Response::new().add_attribute("action", "update_owner"))
+    Ok(Response::new()
+        .add_attribute("action", "update_owner")
+        .add_attribute("pending_owner", new_owner_addr)
+        .add_attribute("deadline", deadline.to_string()))
 }
 
-pub fn update_fees(
+pub fn update_timelock(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    fees: Vec<(String, Decimal)>,
+    period: u64,
 ) -> Result<Response, ContractError> {
+    assert_owner(deps.as_ref(), &info)?;
+    queue_or_apply(deps, env, ConfigChange::UpdateTimelock { period })
+}
+
+pub fn execute_queued_change(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let queued = QUEUED_CHANGES.load(deps.storage, id)?;
+    if env.block.time.seconds() < queued.eta {
+        return Err(ContractError::TimelockNotExpired { eta: queued.eta });
+    }
+    QUEUED_CHANGES.remove(deps.storage, id);
+
+    Ok(apply_config_change(deps, env, queued.change)?.add_attribute("change_id", id.to_string()))
+}
+
+pub fn cancel_queued_change(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.as_ref(), &info)?;
+    QUEUED_CHANGES.load(deps.storage, id)?;
+    QUEUED_CHANGES.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_queued_change")
+        .add_attribute("change_id", id.to_string()))
+}
+
+fn assert_owner(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if info.sender.ne(&config.owner) {
         return Err(ContractError::Unauthorized {});
     }
+    Ok(())
+}
+
+/// Applies the change right away when no timelock is set, otherwise queues it
+/// until the timelock period has passed.
+fn queue_or_apply(
+    deps: DepsMut,
+    env: Env,
+    change: ConfigChange,
+) -> Result<Response, ContractError> {
+    let period = TIMELOCK_PERIOD.may_load(deps.storage)?.unwrap_or_default();
+    if period == 0 {
+        return apply_config_change(deps, env, change);
+    }
+
+    let id = QUEUED_CHANGE_SEQ
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    QUEUED_CHANGE_SEQ.save(deps.storage, &(id + 1))?;
+    let eta = env.block.time.seconds() + period;
+    QUEUED_CHANGES.save(deps.storage, id, &QueuedChange { change, eta })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "queue_change")
+        .add_attribute("change_id", id.to_string())
+        .add_attribute("eta", eta.to_string()))
+}
+
+fn apply_config_change(
+    deps: DepsMut,
+    env: Env,
+    change: ConfigChange,
+) -> Result<Response, ContractError> {
+    match change {
+        ConfigChange::UpdateOwner { new_owner } => apply_update_owner(deps, env, new_owner),
+        ConfigChange::UpdateConfig(msg) => apply_update_config(deps, msg),
+        ConfigChange::UpdateConfigExtension(msg) => apply_update_config_extension(deps, msg),
+        ConfigChange::UpdateFees { fees } => apply_update_fees(deps, fees),
+        ConfigChange::UpdateTimelock { period } => {
+            TIMELOCK_PERIOD.save(deps.storage, &period)?;
+            Ok(Response::new()
+                .add_attribute("action", "update_timelock")
+                .add_attribute("period", period.to_string()))
+        }
+    }
+}
 
+pub fn update_fees(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    fees: Vec<(String, Decimal)>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.as_ref(), &info)?;
+    // check percentages are valid before the change is queued
     for fee in fees.iter() {
-        // check percentage is valid
         percentage_checks(fee.1)?;
+    }
+    queue_or_apply(deps, env, ConfigChange::UpdateFees { fees })
+}
+
+fn apply_update_fees(
+    deps: DepsMut,
+    fees: Vec<(String, Decimal)>,
+) -> Result<Response, ContractError> {
+    for fee in fees.iter() {
         // save|update fee set in storage
         FEES.save(deps.storage, &fee.0, &fee.1)?;
     }
     Ok(Response::new().add_attribute("action", "update_fees"))
 }
 
-pub fn update_config(
+pub fn update_validators(
     deps: DepsMut,
-    _env: Env,
     info: MessageInfo,
-    msg: UpdateConfigMsg,
+    add: Vec<String>,
+    remove: Vec<String>,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
-
+    let config = CONFIG.load(deps.storage)?;
     if info.sender.ne(&config.owner) {
         return Err(ContractError::Unauthorized {});
     }
+
+    let mut validators = VALIDATORS.may_load(deps.storage)?.unwrap_or_default();
+    for validator in add.into_iter() {
+        // validator operator addresses use a different bech32 prefix, so only check for blanks here
+        if validator.trim().is_empty() {
+            return Err(ContractError::InvalidInputs {});
+        }
+        if !validators.contains(&validator) {
+            validators.push(validator);
+        }
+    }
+    validators.retain(|v| !remove.contains(v));
+    VALIDATORS.save(deps.storage, &validators)?;
+
+    Ok(Response::new().add_attribute("action", "update_validators"))
+}
+
+/// Only called when a subscriber errored on the `RegistrarUpdated` hook. The error is noted
+/// and swallowed so the config update still goes through.
+pub fn registrar_hook_reply(msg: SubMsgResult) -> Result<Response, ContractError> {
+    match msg {
+        SubMsgResult::Err(err) => Ok(Response::new()
+            .add_attribute("action", "registrar_hook_failed")
+            .add_attribute("error", err)),
+        SubMsgResult::Ok(_) => Ok(Response::default()),
+    }
+}
+
+pub fn update_subscribers(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.as_ref(), &info)?;
+
+    let mut subscribers = SUBSCRIBERS.may_load(deps.storage)?.unwrap_or_default();
+    for subscriber in add.iter() {
+        let subscriber = deps.api.addr_validate(subscriber)?;
+        if !subscribers.contains(&subscriber) {
+            subscribers.push(subscriber);
+        }
+    }
+    subscribers.retain(|s| !remove.contains(&s.to_string()));
+    SUBSCRIBERS.save(deps.storage, &subscribers)?;
+
+    Ok(Response::new().add_attribute("action", "update_subscribers"))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: UpdateConfigMsg,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.as_ref(), &info)?;
+    queue_or_apply(deps, env, ConfigChange::UpdateConfig(msg))
+}
+
+fn apply_update_config(deps: DepsMut, msg: UpdateConfigMsg) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
     // update config attributes with newly passed configs
     config.treasury = deps
         .api
@@ -91,16 +291,20 @@ pub fn update_config(
 
 pub fn update_config_extension(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: UpdateConfigExtensionMsg,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let mut extension = CONFIG_EXTENSION.load(deps.storage)?;
+    assert_owner(deps.as_ref(), &info)?;
+    queue_or_apply(deps, env, ConfigChange::UpdateConfigExtension(msg))
+}
 
-    if info.sender.ne(&config.owner) {
-        return Err(ContractError::Unauthorized {});
-    }
+fn apply_update_config_extension(
+    deps: DepsMut,
+    msg: UpdateConfigExtensionMsg,
+) -> Result<Response, ContractError> {
+    let mut extension = CONFIG_EXTENSION.load(deps.storage)?;
+    let prev = extension.clone();
     // update config attributes with newly passed configs
     extension.applications_review = match msg.applications_review {
         Some(addr) => deps.api.addr_validate(&addr)?,
@@ -181,7 +385,66 @@ pub fn update_config_extension(
 
     CONFIG_EXTENSION.save(deps.storage, &extension)?;
 
-    Ok(Response::new().add_attribute("action", "update_config"))
+    // let the subscribed contracts know which addresses changed
+    let changed: Vec<String> = [
+        (
+            "applications_review",
+            prev.applications_review != extension.applications_review,
+        ),
+        (
+            "accounts_contract",
+            prev.accounts_contract != extension.accounts_contract,
+        ),
+        ("swaps_router", prev.swaps_router != extension.swaps_router),
+        (
+            "charity_shares_contract",
+            prev.charity_shares_contract != extension.charity_shares_contract,
+        ),
+        (
+            "index_fund_contract",
+            prev.index_fund_contract != extension.index_fund_contract,
+        ),
+        (
+            "donation_match_charites_contract",
+            prev.donation_match_charites_contract != extension.donation_match_charites_contract,
+        ),
+        (
+            "fundraising_contract",
+            prev.fundraising_contract != extension.fundraising_contract,
+        ),
+        ("swap_factory", prev.swap_factory != extension.swap_factory),
+        ("halo_token", prev.halo_token != extension.halo_token),
+        (
+            "halo_token_lp_contract",
+            prev.halo_token_lp_contract != extension.halo_token_lp_contract,
+        ),
+        (
+            "accounts_settings_controller",
+            prev.accounts_settings_controller != extension.accounts_settings_controller,
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(field, _)| field.to_string())
+    .collect();
+
+    // a subscriber failing to handle the hook must not block the config update
+    let mut hooks = vec![];
+    if !changed.is_empty() {
+        for subscriber in SUBSCRIBERS.may_load(deps.storage)?.unwrap_or_default() {
+            hooks.push(SubMsg::reply_on_error(
+                RegistrarHookMsg::RegistrarUpdated {
+                    changed: changed.clone(),
+                }
+                .into_cosmos_msg(subscriber)?,
+                REGISTRAR_HOOK_REPLY_ID,
+            ));
+        }
+    }
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_attribute("action", "update_config"))
 }
 
 pub fn strategy_add(
@@ -231,6 +494,30 @@ pub fn strategy_remove(
     if strat_bytes.len() != 4 {
         return Err(ContractError::InvalidInputs {});
     }
+    // only a Strategy that never took deposits, or is being wound down, can be removed
+    let strategy = STRATEGIES.load(deps.storage, strat_bytes)?;
+    if !strategy.approval_state.is_removable() {
+        return Err(ContractError::StrategyTransitionNotAllowed {
+            from: strategy.approval_state.to_string(),
+            to: "removed".to_string(),
+        });
+    }
+
+    // refuse to remove a Strategy while endowments still hold positions in it
+    if let Some(accounts_contract) = CONFIG_EXTENSION.load(deps.storage)?.accounts_contract {
+        let res: StrategyPositionsResponse = deps.querier.query_wasm_smart(
+            accounts_contract.to_string(),
+            &AccountsQueryMsg::StrategyPositions {
+                strategy_key: strategy_key.clone(),
+            },
+        )?;
+        if res.positions > 0 {
+            return Err(ContractError::StrategyHasOpenPositions {
+                positions: res.positions,
+            });
+        }
+    }
+
     // remove the Strategy from storage
     STRATEGIES.remove(deps.storage, &strat_bytes);
     Ok(Response::default())
@@ -257,6 +544,14 @@ pub fn strategy_update(
     // try to look up the given strategy in Storage
     let mut strategy = STRATEGIES.load(deps.storage, &strat_bytes)?;
 
+    // only allow the legal moves through the Strategy lifecycle
+    if !strategy.approval_state.can_transition_to(&approval_state) {
+        return Err(ContractError::StrategyTransitionNotAllowed {
+            from: strategy.approval_state.to_string(),
+            to: approval_state.to_string(),
+        });
+    }
+
     // update strategy with approval state from passed arg
     strategy.approval_state = approval_state;
     STRATEGIES.save(deps.storage, strategy_key.as_bytes(), &strategy)?;
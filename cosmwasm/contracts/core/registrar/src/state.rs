@@ -1,3 +1,4 @@
+use angel_core::msgs::registrar::ConfigChange;
 use angel_core::structs::{
     AcceptedTokens, NetworkInfo, RebalanceDetails, RegistrarConfigCore, RegistrarConfigExtension,
     SplitDetails, StrategyParams,
@@ -30,3 +31,22 @@ pub const CONFIG_EXTENSION: Item<RegistrarConfigExtension> = Item::new("config_e
 pub const STRATEGIES: Map<&[u8], StrategyParams> = Map::new("strategies");
 pub const NETWORK_CONNECTIONS: Map<&str, NetworkInfo> = Map::new("network_connections");
 pub const FEES: Map<&str, Decimal> = Map::new("fee");
+pub const VALIDATORS: Item<Vec<String>> = Item::new("validators");
+pub const SUBSCRIBERS: Item<Vec<Addr>> = Item::new("subscribers");
+
+#[cw_serde]
+pub struct QueuedChange {
+    pub change: ConfigChange,
+    pub eta: u64, // block time (seconds) after which the change can be applied
+}
+
+#[cw_serde]
+pub struct PendingOwner {
+    pub owner: Addr,
+    pub deadline: u64, // block height until which the `owner` can accept the ownership
+}
+
+pub const TIMELOCK_PERIOD: Item<u64> = Item::new("timelock_period");
+pub const QUEUED_CHANGE_SEQ: Item<u64> = Item::new("queued_change_seq");
+pub const QUEUED_CHANGES: Map<u64, QueuedChange> = Map::new("queued_changes");
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
@@ -1,12 +1,17 @@
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, query, reply};
+use crate::state::CONFIG_EXTENSION;
 use angel_core::errors::core::*;
+use angel_core::msgs::accounts::StrategyPositionsResponse;
 use angel_core::msgs::registrar::*;
 use angel_core::structs::{
     AcceptedTokens, NetworkInfo, RebalanceDetails, SplitDetails, StrategyApprovalState,
     StrategyLocale, StrategyParams,
 };
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{coins, from_binary, Addr, Decimal, StdError};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, ContractResult, Decimal, Reply, StdError, StdResult,
+    SubMsg, SubMsgResult, SystemResult, WasmQuery,
+};
 use std::str::Utf8Error;
 
 const MOCK_CW3_CODE_ID: u64 = 18;
@@ -76,6 +81,31 @@ fn update_owner() {
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     assert_eq!(0, res.messages.len());
+
+    // ownership only moves once the proposed owner accepts it
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+    let pending: PendingOwnerResponse = from_binary(&res).unwrap();
+    assert_eq!(Some("alice".to_string()), pending.pending_owner);
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(ap_team, config_response.owner);
+
+    // the proposed owner cannot accept after the deadline
+    let mut env = mock_env();
+    env.block.height = pending.deadline.unwrap() + 1;
+    let msg = ExecuteMsg::UpdateOwner {
+        new_owner: String::from("alice"),
+    };
+    let err = execute(deps.as_mut(), env, mock_info("alice", &[]), msg.clone()).unwrap_err();
+    assert_eq!(ContractError::Expired {}, err);
+
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!("alice", config_response.owner);
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+    let pending: PendingOwnerResponse = from_binary(&res).unwrap();
+    assert_eq!(None, pending.pending_owner);
 }
 
 #[test]
@@ -511,3 +541,606 @@ fn test_update_fees() {
     let fee: Decimal = from_binary(&res).unwrap();
     assert_eq!(fee, Decimal::from_ratio(20_u128, 100_u128));
 }
+
+#[test]
+fn test_update_validators() {
+    let mut deps = mock_dependencies();
+    let ap_team = AP_TEAM.to_string();
+    let instantiate_msg = instantiate_msg();
+    let info = mock_info(ap_team.as_ref(), &coins(1000, "earth"));
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+    // No validators are set by default
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Validators {}).unwrap();
+    let validators: ValidatorsResponse = from_binary(&res).unwrap();
+    assert!(validators.validators.is_empty());
+
+    // Only the config.owner can update the validator set
+    let info = mock_info("anyone", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UpdateValidators {
+            add: vec!["junovaloper1".to_string()],
+            remove: vec![],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Duplicates are only added once
+    let info = mock_info(ap_team.as_str(), &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UpdateValidators {
+            add: vec![
+                "junovaloper1".to_string(),
+                "junovaloper2".to_string(),
+                "junovaloper1".to_string(),
+            ],
+            remove: vec![],
+        },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UpdateValidators {
+            add: vec!["junovaloper3".to_string()],
+            remove: vec!["junovaloper1".to_string()],
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Validators {}).unwrap();
+    let validators: ValidatorsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        validators.validators,
+        vec!["junovaloper2".to_string(), "junovaloper3".to_string()]
+    );
+}
+
+#[test]
+fn test_list_strategies_network_connections_and_fees() {
+    let mut deps = mock_dependencies();
+    let info = mock_info(AP_TEAM, &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), instantiate_msg()).unwrap();
+
+    for chain_id in ["osmosis", "ethereum"] {
+        let msg = ExecuteMsg::UpdateNetworkConnections {
+            chain_id: chain_id.to_string(),
+            network_info: NetworkInfo {
+                router_contract: None,
+                accounts_contract: None,
+            },
+            action: "post".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let strategies = [
+        (
+            "aaa1",
+            StrategyApprovalState::Approved,
+            StrategyLocale::Native,
+            "osmosis",
+        ),
+        (
+            "aaa2",
+            StrategyApprovalState::NotApproved,
+            StrategyLocale::Native,
+            "osmosis",
+        ),
+        (
+            "aaa3",
+            StrategyApprovalState::Approved,
+            StrategyLocale::Evm,
+            "ethereum",
+        ),
+    ];
+    for (strategy_key, approval_state, locale, chain) in strategies {
+        let msg = ExecuteMsg::StrategyAdd {
+            strategy_key: strategy_key.to_string(),
+            strategy: StrategyParams {
+                approval_state,
+                locale,
+                chain: chain.to_string(),
+                input_denom: USDC.to_string(),
+                locked_addr: None,
+                liquid_addr: None,
+            },
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let query_strategies = |start_after: Option<&str>,
+                            limit: Option<u64>,
+                            approval_state: Option<StrategyApprovalState>,
+                            locale: Option<StrategyLocale>,
+                            chain: Option<&str>| {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Strategies {
+                start_after: start_after.map(|key| key.to_string()),
+                limit,
+                approval_state,
+                locale,
+                chain: chain.map(|chain| chain.to_string()),
+            },
+        )
+        .unwrap();
+        let res: StrategyListResponse = from_binary(&res).unwrap();
+        res.strategies
+            .into_iter()
+            .map(|item| item.strategy_key)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        query_strategies(None, None, None, None, None),
+        vec!["aaa1", "aaa2", "aaa3"]
+    );
+    assert_eq!(
+        query_strategies(Some("aaa1"), Some(1), None, None, None),
+        vec!["aaa2"]
+    );
+    assert_eq!(
+        query_strategies(
+            None,
+            None,
+            Some(StrategyApprovalState::Approved),
+            None,
+            None
+        ),
+        vec!["aaa1", "aaa3"]
+    );
+    assert_eq!(
+        query_strategies(
+            None,
+            None,
+            Some(StrategyApprovalState::Approved),
+            Some(StrategyLocale::Native),
+            None
+        ),
+        vec!["aaa1"]
+    );
+    assert_eq!(
+        query_strategies(None, None, None, None, Some("ethereum")),
+        vec!["aaa3"]
+    );
+
+    // a filtered page only looks at `limit` strategies, & tells where to go on from
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Strategies {
+            start_after: None,
+            limit: Some(2),
+            approval_state: None,
+            locale: None,
+            chain: Some("ethereum".to_string()),
+        },
+    )
+    .unwrap();
+    let res: StrategyListResponse = from_binary(&res).unwrap();
+    assert_eq!(res.strategies, vec![]);
+    assert_eq!(res.next_start_after, Some("aaa2".to_string()));
+    assert_eq!(
+        query_strategies(Some("aaa2"), Some(2), None, None, Some("ethereum")),
+        vec!["aaa3"]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::NetworkConnections {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let res: NetworkConnectionListResponse = from_binary(&res).unwrap();
+    let chains: Vec<String> = res
+        .network_connections
+        .into_iter()
+        .map(|connection| connection.chain)
+        .collect();
+    assert_eq!(chains, vec!["ethereum", "juno", "osmosis"]);
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::NetworkConnections {
+            start_after: Some("ethereum".to_string()),
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    let res: NetworkConnectionListResponse = from_binary(&res).unwrap();
+    assert_eq!(res.network_connections.len(), 1);
+    assert_ne!(res.network_connections[0].chain, "ethereum");
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+    let res: FeeListResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        res.fees,
+        vec![
+            ("accounts_withdraw".to_string(), Decimal::permille(2)),
+            ("vaults_harvest".to_string(), Decimal::percent(20)),
+        ]
+    );
+}
+
+#[test]
+fn test_strategy_lifecycle() {
+    let mut deps = mock_dependencies();
+    let info = mock_info(AP_TEAM, &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::StrategyAdd {
+        strategy_key: STRATEGY_KEY.unwrap().to_string(),
+        strategy: StrategyParams {
+            approval_state: StrategyApprovalState::Approved,
+            locale: StrategyLocale::Native,
+            chain: CHAIN_ID.to_string(),
+            input_denom: USDC.to_string(),
+            locked_addr: None,
+            liquid_addr: None,
+        },
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // An approved strategy cannot go back to not approved, nor be removed
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::StrategyUpdate {
+            strategy_key: STRATEGY_KEY.unwrap().to_string(),
+            approval_state: StrategyApprovalState::NotApproved,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StrategyTransitionNotAllowed {
+            from: "approved".to_string(),
+            to: "not_approved".to_string(),
+        }
+    );
+    let remove_strategy_msg = ExecuteMsg::StrategyRemove {
+        strategy_key: STRATEGY_KEY.unwrap().to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        remove_strategy_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StrategyTransitionNotAllowed {
+            from: "approved".to_string(),
+            to: "removed".to_string(),
+        }
+    );
+
+    // Deprecated -> WithdrawOnly -> removable
+    for approval_state in [
+        StrategyApprovalState::Deprecated,
+        StrategyApprovalState::WithdrawOnly,
+    ] {
+        let msg = ExecuteMsg::StrategyUpdate {
+            strategy_key: STRATEGY_KEY.unwrap().to_string(),
+            approval_state,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    // Endowments still hold positions in the strategy
+    CONFIG_EXTENSION
+        .update(deps.as_mut().storage, |mut extension| -> StdResult<_> {
+            extension.accounts_contract = Some(Addr::unchecked("accounts_contract_addr"));
+            Ok(extension)
+        })
+        .unwrap();
+    let mock_positions = |positions: u64| {
+        move |_: &WasmQuery| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&StrategyPositionsResponse {
+                    strategy_key: STRATEGY_KEY.unwrap().to_string(),
+                    positions,
+                })
+                .unwrap(),
+            ))
+        }
+    };
+    deps.querier.update_wasm(mock_positions(2));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        remove_strategy_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StrategyHasOpenPositions { positions: 2 }
+    );
+
+    // All positions are redeemed
+    deps.querier.update_wasm(mock_positions(0));
+    execute(deps.as_mut(), mock_env(), info, remove_strategy_msg).unwrap();
+    query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Strategy {
+            strategy_key: STRATEGY_KEY.unwrap().to_string(),
+        },
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_config_timelock() {
+    let mut deps = mock_dependencies();
+    let info = mock_info(AP_TEAM, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), instantiate_msg()).unwrap();
+
+    // without a timelock the first change is applied right away
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UpdateTimelock { period: 3600 },
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Timelock {}).unwrap();
+    let timelock: TimelockResponse = from_binary(&res).unwrap();
+    assert_eq!(3600, timelock.period);
+
+    // later changes are queued
+    let fees_msg = ExecuteMsg::UpdateFees {
+        fees: vec![("endowment_closure".to_string(), Decimal::percent(2))],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pleb", &[]),
+        fees_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), fees_msg.clone()).unwrap();
+    assert_eq!(res.attributes[0].value, "queue_change");
+    execute(deps.as_mut(), mock_env(), info.clone(), fees_msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+    let fees: FeeListResponse = from_binary(&res).unwrap();
+    assert!(!fees
+        .fees
+        .iter()
+        .any(|(name, _)| name == "endowment_closure"));
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QueuedChanges {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let queued: QueuedChangesResponse = from_binary(&res).unwrap();
+    assert_eq!(2, queued.changes.len());
+    let eta = queued.changes[0].eta;
+    assert_eq!(mock_env().block.time.seconds() + 3600, eta);
+
+    // nobody can apply a change before its ETA
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pleb", &[]),
+        ExecuteMsg::ExecuteQueuedChange { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::TimelockNotExpired { eta }, err);
+
+    // only the owner can cancel a queued change
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pleb", &[]),
+        ExecuteMsg::CancelQueuedChange { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::CancelQueuedChange { id: 1 },
+    )
+    .unwrap();
+
+    // anyone can apply it after the ETA
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(3600);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("pleb", &[]),
+        ExecuteMsg::ExecuteQueuedChange { id: 0 },
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Fee {
+            name: "endowment_closure".to_string(),
+        },
+    )
+    .unwrap();
+    let fee: Decimal = from_binary(&res).unwrap();
+    assert_eq!(Decimal::percent(2), fee);
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::QueuedChanges {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let queued: QueuedChangesResponse = from_binary(&res).unwrap();
+    assert!(queued.changes.is_empty());
+
+    // a new owner is only proposed once the queued proposal is applied
+    let owner_msg = ExecuteMsg::UpdateOwner {
+        new_owner: "alice".to_string(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, owner_msg.clone()).unwrap();
+    assert_eq!(res.attributes[0].value, "queue_change");
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::PendingOwner {}).unwrap();
+    let pending: PendingOwnerResponse = from_binary(&res).unwrap();
+    assert_eq!(None, pending.pending_owner);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        owner_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    env.block.time = env.block.time.plus_seconds(3600);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("pleb", &[]),
+        ExecuteMsg::ExecuteQueuedChange { id: 2 },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        owner_msg,
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!("alice", config_response.owner);
+}
+
+#[test]
+fn test_subscribers_get_registrar_updated_hook() {
+    let mut deps = mock_dependencies();
+    let info = mock_info(AP_TEAM, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), instantiate_msg()).unwrap();
+
+    // only the owner can manage the subscribers
+    let msg = ExecuteMsg::UpdateSubscribers {
+        add: vec!["swap-router".to_string(), "index-fund".to_string()],
+        remove: vec![],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pleb", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UpdateSubscribers {
+            add: vec![],
+            remove: vec!["index-fund".to_string()],
+        },
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Subscribers {}).unwrap();
+    let subscribers: SubscribersResponse = from_binary(&res).unwrap();
+    assert_eq!(vec!["swap-router".to_string()], subscribers.subscribers);
+
+    // changing an address pushes the hook to every subscriber
+    let mut update_msg = UpdateConfigExtensionMsg {
+        accounts_contract: Some("accounts".to_string()),
+        swaps_router: None,
+        index_fund_contract: None,
+        cw3_code: Some(MOCK_CW3_CODE_ID),
+        cw4_code: None,
+        subdao_gov_code: None,
+        subdao_cw20_token_code: None,
+        subdao_bonding_token_code: None,
+        subdao_cw900_code: None,
+        subdao_distributor_code: None,
+        donation_match_code: None,
+        donation_match_charites_contract: None,
+        halo_token: None,
+        halo_token_lp_contract: None,
+        gov_contract: None,
+        collector_addr: None,
+        charity_shares_contract: None,
+        fundraising_contract: None,
+        swap_factory: None,
+        applications_review: None,
+        accounts_settings_controller: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UpdateConfigExtension(update_msg.clone()),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![SubMsg::reply_on_error(
+            RegistrarHookMsg::RegistrarUpdated {
+                changed: vec!["accounts_contract".to_string()],
+            }
+            .into_cosmos_msg("swap-router")
+            .unwrap(),
+            0
+        )],
+        res.messages
+    );
+
+    // a subscriber that fails on the hook doesn't revert the update
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 0,
+            result: SubMsgResult::Err("unknown variant `registrar_updated`".to_string()),
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::ConfigExtension {}).unwrap();
+    let extension: ConfigExtensionResponse = from_binary(&res).unwrap();
+    assert_eq!(Some("accounts".to_string()), extension.accounts_contract);
+
+    // no hook when only code ids change
+    update_msg.accounts_contract = None;
+    update_msg.cw4_code = Some(MOCK_CW4_CODE_ID);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UpdateConfigExtension(update_msg),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+}
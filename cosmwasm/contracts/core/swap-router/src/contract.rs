@@ -1,5 +1,5 @@
 use crate::operations::{assert_minium_receive, execute_swap_operation, send_swap_receipt};
-use crate::state::{pair_key, Config, CONFIG, PAIRS};
+use crate::state::{pair_key, read_accounts_contract, Config, OldConfig, CONFIG, PAIRS};
 use angel_core::errors::core::ContractError;
 use angel_core::msgs::dexs::{
     InfoResponse, JunoSwapQueryMsg, LoopQueryMsg, SimulationResponse, Token1ForToken2PriceResponse,
@@ -15,8 +15,8 @@ use angel_core::msgs::swap_router::{
 };
 use angel_core::structs::{AccountType, Pair, StrategyApprovalState, SwapOperation};
 use cosmwasm_std::{
-    entry_point, from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+    entry_point, from_binary, from_slice, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::set_contract_version;
 use cw20::{Cw20ReceiveMsg, Denom};
@@ -39,7 +39,6 @@ pub fn instantiate(
         deps.storage,
         &Config {
             registrar_contract: msg.registrar_contract,
-            accounts_contract: msg.accounts_contract,
         },
     )?;
 
@@ -174,14 +173,19 @@ pub fn execute_swap_operations(
     let config = CONFIG.load(deps.storage)?;
     // Swaps are restricted to the Accounts contract (endowments) & approved Strategy's Vault contracts
     let mut vault_addr = None;
-    if sender != config.accounts_contract && strategy_key != None {
+    let accounts_contract = read_accounts_contract(deps.as_ref(), &config)?;
+    if accounts_contract.as_ref() != Some(&sender) {
+        // this is NOT the accounts contract, so a strategy key must be provided to
+        // attempt to validate the sender is a valid vault contract
+        let strategy_key = match strategy_key {
+            Some(key) => key,
+            None => return Err(ContractError::Unauthorized {}),
+        };
         // check that the deposit token came from an approved Strategy's Vault SC
         let strategy_res: StrategyDetailResponse =
             deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
                 contract_addr: config.registrar_contract.to_string(),
-                msg: to_binary(&RegistrarQuerier::Strategy {
-                    strategy_key: strategy_key.unwrap(),
-                })?,
+                msg: to_binary(&RegistrarQuerier::Strategy { strategy_key })?,
             }))?;
         if strategy_res.strategy.approval_state != StrategyApprovalState::Approved {
             return Err(ContractError::Unauthorized {});
@@ -190,10 +194,6 @@ pub fn execute_swap_operations(
             AccountType::Locked => strategy_res.strategy.locked_addr,
             AccountType::Liquid => strategy_res.strategy.liquid_addr,
         };
-    } else {
-        // this is NOT the accounts contract, nor is there a strategy key provided to
-        // attempt to validate the sender is a valid vault contract
-        return Err(ContractError::Unauthorized {});
     }
 
     let operations_len = operations.len();
@@ -264,8 +264,8 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage).unwrap();
     Ok(ConfigResponse {
+        accounts_contract: read_accounts_contract(deps, &config)?,
         registrar_contract: config.registrar_contract,
-        accounts_contract: config.accounts_contract,
     })
 }
 
@@ -395,7 +395,20 @@ fn assert_operations(operations: &[SwapOperation]) -> StdResult<()> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    // drop the cached `accounts_contract`; it is now read from the Registrar
+    let data = deps
+        .storage
+        .get("config".as_bytes())
+        .ok_or_else(|| StdError::not_found("Config not found"))?;
+    if let Ok(old_config) = from_slice::<OldConfig>(&data) {
+        CONFIG.save(
+            deps.storage,
+            &Config {
+                registrar_contract: old_config.registrar_contract,
+            },
+        )?;
+    }
     Ok(Response::default())
 }
 
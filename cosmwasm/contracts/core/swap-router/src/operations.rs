@@ -1,4 +1,4 @@
-use crate::state::{pair_key, CONFIG, PAIRS};
+use crate::state::{pair_key, read_accounts_contract, CONFIG, PAIRS};
 use angel_core::errors::core::ContractError;
 use angel_core::msgs::accounts::ExecuteMsg as AccountsExecuteMsg;
 use angel_core::msgs::dexs::{
@@ -37,7 +37,9 @@ pub fn send_swap_receipt(
     //   - `vault` contract should receive the operation result
     let message = match vault_addr {
         None => CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: config.accounts_contract.to_string(),
+            contract_addr: read_accounts_contract(deps, &config)?
+                .ok_or(ContractError::ContractNotConfigured {})?
+                .to_string(),
             msg: to_binary(&AccountsExecuteMsg::SwapReceipt {
                 id: endowment_id,
                 acct_type,
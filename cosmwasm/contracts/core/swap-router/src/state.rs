@@ -1,16 +1,24 @@
 use angel_core::errors::core::ContractError;
+use angel_core::msgs::registrar::{
+    ConfigExtensionResponse as RegistrarConfigExtensionResponse, QueryMsg as RegistrarQueryMsg,
+};
 use angel_core::structs::Pair;
-use cosmwasm_schema::{cw_serde};
-use cosmwasm_std::{to_binary, Addr, Deps, StdError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_binary, Addr, Deps, StdError, StdResult};
 use cw_asset::AssetInfo;
 use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
-pub struct Config {
+pub struct OldConfig {
     pub registrar_contract: Addr,
     pub accounts_contract: Addr,
 }
 
+#[cw_serde]
+pub struct Config {
+    pub registrar_contract: Addr,
+}
+
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const PAIRS: Map<&[u8], Pair> = Map::new("pairs");
 
@@ -26,3 +34,15 @@ pub fn read_pair(deps: Deps, asset_infos: &[AssetInfo; 2]) -> Result<Pair, Contr
         Err(_e) => Err(StdError::generic_err("no pair data stored").into()),
     }
 }
+
+/// Accounts contract is looked up from the Registrar on use, so it is never out of sync
+pub fn read_accounts_contract(deps: Deps, config: &Config) -> StdResult<Option<Addr>> {
+    let registrar_config: RegistrarConfigExtensionResponse = deps.querier.query_wasm_smart(
+        config.registrar_contract.to_string(),
+        &RegistrarQueryMsg::ConfigExtension {},
+    )?;
+    registrar_config
+        .accounts_contract
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()
+}
@@ -1,5 +1,7 @@
 use angel_core::msgs::dexs::InfoResponse;
-use angel_core::msgs::registrar::{ConfigResponse, StrategyDetailResponse};
+use angel_core::msgs::registrar::{
+    ConfigExtensionResponse, ConfigResponse, StrategyDetailResponse,
+};
 use angel_core::structs::{
     AcceptedTokens, AccountType, RebalanceDetails, SplitDetails, StrategyApprovalState,
     StrategyLocale, StrategyParams,
@@ -7,7 +9,7 @@ use angel_core::structs::{
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, from_slice, to_binary, Addr, Api, BankQuery, Coin, ContractResult, Decimal, Empty,
+    from_binary, from_slice, to_binary, Addr, Api, BankQuery, Coin, ContractResult, Empty,
     OwnedDeps, Querier, QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
 use cw20::{BalanceResponse, Denom};
@@ -17,6 +19,7 @@ use std::marker::PhantomData;
 #[cw_serde]
 pub enum QueryMsg {
     Config {},
+    ConfigExtension {},
     Strategy {
         strategy_key: String,
     },
@@ -97,7 +100,10 @@ impl WasmMockQuerier {
                 QueryMsg::Info {} => SystemResult::Ok(ContractResult::Ok(
                     to_binary(&InfoResponse {
                         token1_reserve: Uint128::from(1000000_u128),
-                        token1_denom: Denom::Native("usdc".to_string()),
+                        token1_denom: Denom::Native(
+                            "ibc/B3504E092456BA618CC28AC671A71FB08C6CA0FD0BE7C8A5B5A3E2DD933CC9E4"
+                                .to_string(),
+                        ),
                         token2_reserve: Uint128::from(1000000_u128),
                         token2_denom: Denom::Cw20(Addr::unchecked("asset0000")),
                         lp_token_supply: Uint128::from(1000000_u128),
@@ -116,22 +122,29 @@ impl WasmMockQuerier {
                     to_binary(&ConfigResponse {
                         owner: "registrar-owner".to_string(),
                         version: "v1.0".to_string(),
-                        accounts_contract: None,
                         treasury: "treasury".to_string(),
                         rebalance: RebalanceDetails::default(),
-                        index_fund: None,
                         split_to_liquid: SplitDetails::default(),
+                        accepted_tokens: AcceptedTokens::default(),
+                        axelar_gateway: "axelar-gateway".to_string(),
+                        axelar_ibc_channel: "channel-1".to_string(),
+                        axelar_chain_id: "juno".to_string(),
+                    })
+                    .unwrap(),
+                )),
+                QueryMsg::ConfigExtension {} => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&ConfigExtensionResponse {
+                        accounts_contract: Some("accounts_contract_addr".to_string()),
+                        index_fund: None,
                         halo_token: None,
                         gov_contract: None,
                         charity_shares_contract: None,
                         cw3_code: None,
                         cw4_code: None,
-                        accepted_tokens: AcceptedTokens::default(),
                         applications_review: "applications-review".to_string(),
                         swaps_router: None,
                         donation_match_charites_contract: Some(MOCK_CONTRACT_ADDR.to_string()),
                         collector_addr: "collector-addr".to_string(),
-                        collector_share: Decimal::percent(50),
                         swap_factory: Some("swap-factory".to_string()),
                         accounts_settings_controller: Some(
                             "accounts-settings-controller".to_string(),
@@ -143,8 +156,6 @@ impl WasmMockQuerier {
                         subdao_distributor_code: None,
                         donation_match_code: None,
                         halo_token_lp_contract: None,
-                        axelar_gateway: "axelar-gateway".to_string(),
-                        axelar_ibc_channel: "channel-1".to_string(),
                     })
                     .unwrap(),
                 )),
@@ -26,7 +26,6 @@ fn proper_initialization() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
-        accounts_contract: Addr::unchecked(ACCOUNTS_CONTRACT),
         registrar_contract: Addr::unchecked(REGISTRAR_CONTRACT),
         pairs: vec![],
     };
@@ -40,14 +39,18 @@ fn proper_initialization() {
     // it worked, let's query the state
     let config: ConfigResponse =
         from_binary(&query(deps.as_ref(), env, QueryMsg::Config {}).unwrap()).unwrap();
-    assert_eq!(ACCOUNTS_CONTRACT, config.accounts_contract.as_str());
+    assert_eq!(REGISTRAR_CONTRACT, config.registrar_contract.as_str());
+    // the accounts contract is looked up from the Registrar
+    assert_eq!(
+        Some(Addr::unchecked(ACCOUNTS_CONTRACT)),
+        config.accounts_contract
+    );
 }
 
 #[test]
 fn execute_swap_operations() {
     let mut deps = mock_dependencies(&[]);
     let msg = InstantiateMsg {
-        accounts_contract: Addr::unchecked(ACCOUNTS_CONTRACT),
         registrar_contract: Addr::unchecked(REGISTRAR_CONTRACT),
         pairs: vec![
             Pair {
@@ -179,10 +182,10 @@ fn execute_swap_operations() {
     );
 
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: "vault-1".into(),
+        sender: "vault1-locked-contract".into(),
         amount: Uint128::from(1000000u128),
         msg: to_binary(&Cw20HookMsg::ExecuteSwapOperations {
-            strategy_key: None,
+            strategy_key: Some("vault-1".to_string()),
             operations: vec![
                 SwapOperation::JunoSwap {
                     offer_asset_info: AssetInfo::Native(USDC.to_string()),
@@ -251,7 +254,7 @@ fn execute_swap_operations() {
                     prev_balance: Uint128::from(1000000_u128),
                     endowment_id: 1,
                     acct_type: AccountType::Locked,
-                    vault_addr: Some(Addr::unchecked("vault-1")),
+                    vault_addr: Some(Addr::unchecked("vault1-locked-contract")),
                 })
                 .unwrap(),
             })),
@@ -265,7 +268,6 @@ fn test_execute_swap_operation() {
 
     // Instantiate the contract
     let msg = InstantiateMsg {
-        accounts_contract: Addr::unchecked(ACCOUNTS_CONTRACT),
         registrar_contract: Addr::unchecked(REGISTRAR_CONTRACT),
         pairs: vec![
             Pair {
@@ -356,7 +358,6 @@ fn query_buy_with_routes() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
-        accounts_contract: Addr::unchecked(ACCOUNTS_CONTRACT),
         registrar_contract: Addr::unchecked(REGISTRAR_CONTRACT),
         pairs: vec![
             Pair {
@@ -482,7 +483,6 @@ fn test_update_pairs() {
 
     // Instantiate the contract
     let msg = InstantiateMsg {
-        accounts_contract: Addr::unchecked(ACCOUNTS_CONTRACT),
         registrar_contract: Addr::unchecked(REGISTRAR_CONTRACT),
         pairs: vec![],
     };
@@ -511,7 +511,6 @@ fn test_migrate() {
 
     // Instantiate the contract
     let msg = InstantiateMsg {
-        accounts_contract: Addr::unchecked(ACCOUNTS_CONTRACT),
         registrar_contract: Addr::unchecked(REGISTRAR_CONTRACT),
         pairs: vec![],
     };
@@ -530,7 +529,6 @@ fn test_send_swap_receipt() {
 
     // Instantiate the contract
     let msg = InstantiateMsg {
-        accounts_contract: Addr::unchecked(ACCOUNTS_CONTRACT),
         registrar_contract: Addr::unchecked(REGISTRAR_CONTRACT),
         pairs: vec![
             Pair {
@@ -1,14 +1,22 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-use crate::state::{read_config, store_config, Config};
+use crate::state::{
+    read_config, read_grants, store_config, Config, Grant, GRANTS, GRANTS_COMMITTED, GRANT_SEQ,
+};
 
 use cosmwasm_std::{
     to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
     Uint128, WasmMsg,
 };
 
-use halo_token::community::{ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use angel_core::utils::query_token_balance;
+
+use halo_token::common::OrderBy;
+use halo_token::community::{
+    ConfigResponse, ExecuteMsg, GrantResponse, GrantsResponse, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use halo_token::vesting::{assert_vesting_schedules, VestingInfo};
 
 use cw20::Cw20ExecuteMsg;
 
@@ -27,23 +35,25 @@ pub fn instantiate(
             spend_limit: msg.spend_limit,
         },
     )?;
+    GRANTS_COMMITTED.save(deps.storage, &Uint128::zero())?;
 
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    msg: ExecuteMsg,
-) -> StdResult<Response> {
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
     match msg {
         ExecuteMsg::UpdateConfig {
             spend_limit,
             gov_contract,
         } => update_config(deps, info, spend_limit, gov_contract),
-        ExecuteMsg::Spend { recipient, amount } => spend(deps, info, recipient, amount),
+        ExecuteMsg::Spend { recipient, amount } => spend(deps, env, info, recipient, amount),
+        ExecuteMsg::CreateGrant {
+            recipient,
+            schedules,
+        } => create_grant(deps, env, info, recipient, schedules),
+        ExecuteMsg::ClaimGrant { grant_id } => claim_grant(deps, env, info, grant_id),
+        ExecuteMsg::CancelGrant { grant_id } => cancel_grant(deps, env, info, grant_id),
     }
 }
 
@@ -73,9 +83,11 @@ pub fn update_config(
 
 /// Spend
 /// Owner can execute spend operation to send
-/// `amount` of HALO token to `recipient` for community purpose
+/// `amount` of HALO token to `recipient` for community purpose.
+/// HALO committed to open grants can't be spent.
 pub fn spend(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
@@ -88,6 +100,11 @@ pub fn spend(
     if config.spend_limit < amount {
         return Err(StdError::generic_err("Cannot spend more than spend_limit"));
     }
+    if uncommitted_balance(deps.as_ref(), &env, &config)? < amount {
+        return Err(StdError::generic_err(
+            "Cannot spend HALO committed to open grants",
+        ));
+    }
 
     let halo_token = config.halo_token.to_string();
     Ok(Response::new()
@@ -106,10 +123,193 @@ pub fn spend(
         ]))
 }
 
+/// CreateGrant
+/// Owner can stream HALO to `recipient` over the given `(start, end, amount)`
+/// schedules. The whole grant counts against the spend limit, and is committed
+/// out of the HALO not already committed to other grants.
+pub fn create_grant(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    schedules: Vec<(u64, u64, Uint128)>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.gov_contract != info.sender {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if schedules.is_empty() {
+        return Err(StdError::generic_err(
+            "Grant must have at least one schedule",
+        ));
+    }
+    assert_vesting_schedules(&schedules)?;
+
+    let amount: Uint128 = schedules.iter().map(|s| s.2).sum();
+    if config.spend_limit < amount {
+        return Err(StdError::generic_err("Cannot spend more than spend_limit"));
+    }
+    if uncommitted_balance(deps.as_ref(), &env, &config)? < amount {
+        return Err(StdError::generic_err(
+            "Cannot grant HALO committed to open grants",
+        ));
+    }
+    GRANTS_COMMITTED.update(deps.storage, |committed| -> StdResult<_> {
+        Ok(committed + amount)
+    })?;
+
+    let grant_id = GRANT_SEQ.may_load(deps.storage)?.unwrap_or_default() + 1;
+    GRANT_SEQ.save(deps.storage, &grant_id)?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let start_time = schedules.iter().map(|s| s.0).min().unwrap_or_default();
+    GRANTS.save(
+        deps.storage,
+        grant_id,
+        &Grant {
+            recipient: recipient.clone(),
+            info: VestingInfo {
+                schedules,
+                last_claim_time: start_time,
+            },
+            claimed: Uint128::zero(),
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "create_grant"),
+        ("grant_id", &grant_id.to_string()),
+        ("recipient", recipient.as_str()),
+        ("amount", &amount.to_string()),
+    ]))
+}
+
+/// ClaimGrant
+/// Recipient claims the part of the grant released since its last claim
+pub fn claim_grant(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grant_id: u64,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let mut grant = GRANTS.load(deps.storage, grant_id)?;
+    if grant.recipient != info.sender {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let current_time = env.block.time.seconds();
+    let claim_amount = grant.info.compute_claim_amount(current_time);
+    let balance = query_token_balance(
+        deps.as_ref(),
+        config.halo_token.to_string(),
+        env.contract.address.to_string(),
+    )?;
+    if balance < claim_amount {
+        return Err(StdError::generic_err(
+            "Community pool balance cannot cover the grant claim",
+        ));
+    }
+    grant.info.last_claim_time = current_time;
+
+    // drop the grant once everything has been released, along with the commitment
+    // to it (including the dust the claims round off)
+    let released = if grant.info.schedules.iter().all(|s| s.1 <= current_time) {
+        GRANTS.remove(deps.storage, grant_id);
+        grant.outstanding()
+    } else {
+        grant.claimed += claim_amount;
+        GRANTS.save(deps.storage, grant_id, &grant)?;
+        claim_amount
+    };
+    GRANTS_COMMITTED.update(deps.storage, |committed| -> StdResult<_> {
+        Ok(committed.checked_sub(released)?)
+    })?;
+
+    Ok(Response::new()
+        .add_messages(grant_transfer_msg(&config, &grant, claim_amount)?)
+        .add_attributes(vec![
+            ("action", "claim_grant"),
+            ("grant_id", &grant_id.to_string()),
+            ("claim_amount", &claim_amount.to_string()),
+        ]))
+}
+
+/// CancelGrant
+/// Owner can cancel a grant. What has been released so far is paid to the
+/// recipient and the unvested remainder stays in the community pool.
+pub fn cancel_grant(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grant_id: u64,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.gov_contract != info.sender {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let grant = GRANTS.load(deps.storage, grant_id)?;
+    GRANTS.remove(deps.storage, grant_id);
+    GRANTS_COMMITTED.update(deps.storage, |committed| -> StdResult<_> {
+        Ok(committed.checked_sub(grant.outstanding())?)
+    })?;
+
+    let current_time = env.block.time.seconds();
+    let claim_amount = grant.info.compute_claim_amount(current_time);
+    let returned_amount = grant.info.compute_unvested_amount(current_time);
+
+    Ok(Response::new()
+        .add_messages(grant_transfer_msg(&config, &grant, claim_amount)?)
+        .add_attributes(vec![
+            ("action", "cancel_grant"),
+            ("grant_id", &grant_id.to_string()),
+            ("claim_amount", &claim_amount.to_string()),
+            ("returned_amount", &returned_amount.to_string()),
+        ]))
+}
+
+/// HALO held by the contract that isn't committed to open grants
+fn uncommitted_balance(deps: Deps, env: &Env, config: &Config) -> StdResult<Uint128> {
+    let balance = query_token_balance(
+        deps,
+        config.halo_token.to_string(),
+        env.contract.address.to_string(),
+    )?;
+    let committed = GRANTS_COMMITTED.may_load(deps.storage)?.unwrap_or_default();
+    Ok(balance.saturating_sub(committed))
+}
+
+fn grant_transfer_msg(
+    config: &Config,
+    grant: &Grant,
+    amount: Uint128,
+) -> StdResult<Option<CosmosMsg>> {
+    if amount.is_zero() {
+        return Ok(None);
+    }
+
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.halo_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: grant.recipient.to_string(),
+            amount,
+        })?,
+    })))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Grant { grant_id } => to_binary(&query_grant(deps, grant_id)?),
+        QueryMsg::Grants {
+            start_after,
+            limit,
+            order_by,
+        } => to_binary(&query_grants(deps, start_after, limit, order_by)?),
     }
 }
 
@@ -124,7 +324,38 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     Ok(resp)
 }
 
+pub fn query_grant(deps: Deps, grant_id: u64) -> StdResult<GrantResponse> {
+    let grant = GRANTS.load(deps.storage, grant_id)?;
+    Ok(GrantResponse {
+        grant_id,
+        recipient: grant.recipient.to_string(),
+        info: grant.info,
+    })
+}
+
+pub fn query_grants(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    order_by: Option<OrderBy>,
+) -> StdResult<GrantsResponse> {
+    let grants = read_grants(deps.storage, start_after, limit, order_by)?
+        .into_iter()
+        .map(|(grant_id, grant)| GrantResponse {
+            grant_id,
+            recipient: grant.recipient.to_string(),
+            info: grant.info,
+        })
+        .collect();
+
+    Ok(GrantsResponse { grants })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    // there are no grants before this version, so no HALO is committed to them yet
+    if GRANTS_COMMITTED.may_load(deps.storage)?.is_none() {
+        GRANTS_COMMITTED.save(deps.storage, &Uint128::zero())?;
+    }
     Ok(Response::default())
 }
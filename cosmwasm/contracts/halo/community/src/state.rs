@@ -1,7 +1,9 @@
 use cosmwasm_schema::cw_serde;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Bound, Item, Map};
 
 use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use halo_token::common::OrderBy;
+use halo_token::vesting::VestingInfo;
 
 #[cw_serde]
 pub struct Config {
@@ -19,3 +21,44 @@ pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()>
 pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
     CONFIG.load(storage)
 }
+
+#[cw_serde]
+pub struct Grant {
+    pub recipient: Addr,
+    pub info: VestingInfo,
+    /// HALO paid out of the grant so far
+    pub claimed: Uint128,
+}
+
+impl Grant {
+    /// HALO the grant has yet to pay out
+    pub fn outstanding(&self) -> Uint128 {
+        let amount: Uint128 = self.info.schedules.iter().map(|s| s.2).sum();
+        amount.saturating_sub(self.claimed)
+    }
+}
+
+pub const GRANT_SEQ: Item<u64> = Item::new("grant_seq");
+pub const GRANTS: Map<u64, Grant> = Map::new("grants");
+// HALO committed to the open grants, which `spend` & new grants can't use
+pub const GRANTS_COMMITTED: Item<Uint128> = Item::new("grants_committed");
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+pub fn read_grants(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    order_by: Option<OrderBy>,
+) -> StdResult<Vec<(u64, Grant)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let (start, end, order_by) = match order_by {
+        Some(OrderBy::Asc) => (start_after.map(Bound::exclusive), None, OrderBy::Asc),
+        _ => (None, start_after.map(Bound::exclusive), OrderBy::Desc),
+    };
+
+    GRANTS
+        .range(storage, start, end, order_by.into())
+        .take(limit)
+        .collect()
+}
@@ -1,9 +1,30 @@
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, migrate, query};
+use crate::state::GRANTS_COMMITTED;
 
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{from_binary, to_binary, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg};
-use cw20::Cw20ExecuteMsg;
-use halo_token::community::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, ContractResult, CosmosMsg, OwnedDeps, StdError, SubMsg,
+    SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg};
+use halo_token::community::{
+    ConfigResponse, ExecuteMsg, GrantResponse, GrantsResponse, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+
+/// Answers the HALO balance queries with `balance`
+fn mock_halo_balance(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, balance: u128) {
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { .. } => SystemResult::Ok(ContractResult::Ok(
+            to_binary(&BalanceResponse {
+                balance: Uint128::from(balance),
+            })
+            .unwrap(),
+        )),
+        _ => panic!("DO NOT ENTER HERE"),
+    });
+}
 
 #[test]
 fn proper_initialization() {
@@ -90,6 +111,7 @@ fn test_spend() {
 
     // we can just call .unwrap() to assert this was a success
     let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    mock_halo_balance(&mut deps, 1000000);
 
     // permission failed
     let msg = ExecuteMsg::Spend {
@@ -139,3 +161,247 @@ fn test_spend() {
         }))]
     );
 }
+
+#[test]
+fn test_grants() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        gov_contract: "gov".to_string(),
+        halo_token: "halo".to_string(),
+        spend_limit: Uint128::from(1000000u128),
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    mock_halo_balance(&mut deps, 1000000);
+
+    let start = mock_env().block.time.seconds();
+    let msg = ExecuteMsg::CreateGrant {
+        recipient: "addr0000".to_string(),
+        schedules: vec![(start, start + 100, Uint128::from(1000000u128))],
+    };
+
+    // permission failed
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // failed due to spend limit
+    let info = mock_info("gov", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::CreateGrant {
+            recipient: "addr0000".to_string(),
+            schedules: vec![
+                (start, start + 100, Uint128::from(1000000u128)),
+                (start + 100, start + 200, Uint128::from(1u128)),
+            ],
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Cannot spend more than spend_limit")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let info = mock_info("gov", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_eq!(
+        GRANTS_COMMITTED.load(&deps.storage).unwrap(),
+        Uint128::from(1000000u128)
+    );
+
+    // the whole balance is committed to the grant, so it can't be spent or granted again
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov", &[]),
+        ExecuteMsg::Spend {
+            recipient: "addr0001".to_string(),
+            amount: Uint128::from(1u128),
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Cannot spend HALO committed to open grants")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Cannot grant HALO committed to open grants")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // only the recipient can claim
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(25);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::ClaimGrant { grant_id: 1 },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ClaimGrant { grant_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "halo".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(250000u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    let grant: GrantResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Grant { grant_id: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(grant.info.last_claim_time, start + 25);
+    mock_halo_balance(&mut deps, 750000);
+    assert_eq!(
+        GRANTS_COMMITTED.load(&deps.storage).unwrap(),
+        Uint128::from(750000u128)
+    );
+
+    // gov cancels halfway, the released part is paid and the rest returned
+    env.block.time = env.block.time.plus_seconds(25);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("gov", &[]),
+        ExecuteMsg::CancelGrant { grant_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "halo".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(250000u128),
+            })
+            .unwrap(),
+        }))]
+    );
+    assert_eq!(res.attributes[3], attr("returned_amount", "500000"));
+    assert_eq!(
+        GRANTS_COMMITTED.load(&deps.storage).unwrap(),
+        Uint128::zero()
+    );
+
+    let grants: GrantsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Grants {
+                start_after: None,
+                limit: None,
+                order_by: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(grants.grants, vec![]);
+}
+
+#[test]
+fn test_grant_claims_need_balance() {
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        gov_contract: "gov".to_string(),
+        halo_token: "halo".to_string(),
+        spend_limit: Uint128::from(1000000u128),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    mock_halo_balance(&mut deps, 1000000);
+
+    let start = mock_env().block.time.seconds();
+    let msg = ExecuteMsg::CreateGrant {
+        recipient: "addr0000".to_string(),
+        schedules: vec![(start, start + 100, Uint128::from(1000000u128))],
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), msg).unwrap();
+
+    // the pool lost HALO it had committed, so the grant can't be paid
+    mock_halo_balance(&mut deps, 100000);
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(25);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ClaimGrant { grant_id: 1 },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Community pool balance cannot cover the grant claim")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    mock_halo_balance(&mut deps, 1000000);
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ClaimGrant { grant_id: 1 },
+    )
+    .unwrap();
+
+    // migration keeps the HALO committed to the open grants
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        GRANTS_COMMITTED.load(&deps.storage).unwrap(),
+        Uint128::from(750000u128)
+    );
+}
+
+#[test]
+fn test_migrate_starts_without_committed_halo() {
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        gov_contract: "gov".to_string(),
+        halo_token: "halo".to_string(),
+        spend_limit: Uint128::from(1000000u128),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // the previous version doesn't track the HALO committed to grants
+    GRANTS_COMMITTED.remove(&mut deps.storage);
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        GRANTS_COMMITTED.load(&deps.storage).unwrap(),
+        Uint128::zero()
+    );
+}
@@ -1,17 +1,23 @@
+use crate::delegation::{
+    delegate, delegated_balances, lock_delegated_votes, query_delegation, query_delegators,
+    revoke_delegated_vote, undelegate,
+};
 use crate::error::ContractError;
 use crate::staking::{
     claim_voting_tokens, query_staker, stake_voting_tokens, withdraw_voting_tokens,
 };
 use crate::state::{
-    read_bank, read_config, read_poll, read_poll_voter, read_poll_voters, read_polls, read_state,
-    read_tmp_poll_id, remove_poll_indexer, store_bank, store_config, store_poll,
-    store_poll_indexer, store_poll_voter, store_state, store_tmp_poll_id, Config, ExecuteData,
-    Poll, State, CLAIMS,
+    read_bank, read_config, read_poll, read_poll_voter, read_poll_voters, read_polls,
+    read_share_at_height, read_state, read_tmp_poll_id, read_total_share_at_height,
+    remove_poll_indexer, store_bank, store_config, store_poll, store_poll_indexer,
+    store_poll_voter, store_state, store_tmp_poll_id, Config, ExecuteData, Poll, State, BANK,
+    CLAIMS, OLD_POLL, SHARE_SNAPSHOT, TOTAL_SHARE_SNAPSHOT,
 };
 use cosmwasm_std::{
     entry_point, from_binary, to_binary, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Reply, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
+use cw2::{get_contract_version, set_contract_version};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use cw_utils::Duration;
 use halo_token::common::OrderBy;
@@ -30,6 +36,10 @@ const POLL_EXECUTE_REPLY_ID: u64 = 1;
 const MIN_LINK_LENGTH: usize = 12;
 const MAX_LINK_LENGTH: usize = 128;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "halo-gov";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
@@ -38,14 +48,17 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     let quorum = Decimal::percent(msg.quorum);
     let threshold = Decimal::percent(msg.threshold);
+    let veto_threshold = Decimal::percent(msg.veto_threshold);
     validate_decimal(quorum)?;
     validate_decimal(threshold)?;
+    validate_decimal(veto_threshold)?;
 
     let config = Config {
         halo_token: deps.api.addr_validate(&msg.halo_token)?,
         owner: info.sender,
         quorum,
         threshold,
+        veto_threshold,
         voting_period: msg.voting_period,
         timelock_period: msg.timelock_period,
         proposal_deposit: msg.proposal_deposit,
@@ -55,6 +68,8 @@ pub fn instantiate(
         gov_hodler: deps.api.addr_validate(&msg.gov_hodler)?,
     };
 
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     let state = State {
         poll_count: 0,
         total_share: Uint128::zero(),
@@ -81,6 +96,7 @@ pub fn execute(
             owner,
             quorum,
             threshold,
+            veto_threshold,
             voting_period,
             timelock_period,
             proposal_deposit,
@@ -93,6 +109,7 @@ pub fn execute(
             owner,
             quorum,
             threshold,
+            veto_threshold,
             voting_period,
             timelock_period,
             proposal_deposit,
@@ -111,6 +128,8 @@ pub fn execute(
         } => cast_vote(deps, env, info, poll_id, vote, amount),
         ExecuteMsg::EndPoll { poll_id } => end_poll(deps, env, poll_id),
         ExecuteMsg::ExecutePoll { poll_id } => execute_poll(deps, env, poll_id),
+        ExecuteMsg::Delegate { to } => delegate(deps, info, to),
+        ExecuteMsg::Undelegate {} => undelegate(deps, info),
     }
 }
 
@@ -191,6 +210,7 @@ pub fn update_config(
     owner: Option<String>,
     quorum: Option<u64>,
     threshold: Option<u64>,
+    veto_threshold: Option<u64>,
     voting_period: Option<u64>,
     timelock_period: Option<u64>,
     proposal_deposit: Option<Uint128>,
@@ -217,6 +237,12 @@ pub fn update_config(
         config.threshold = Decimal::percent(threshold);
     }
 
+    if let Some(veto_threshold) = veto_threshold {
+        let veto_threshold = Decimal::percent(veto_threshold);
+        validate_decimal(veto_threshold)?;
+        config.veto_threshold = veto_threshold;
+    }
+
     if let Some(voting_period) = voting_period {
         config.voting_period = voting_period;
     }
@@ -324,23 +350,14 @@ pub fn create_poll(
     // Increase poll count & total deposit amount
     state.poll_count += 1;
     state.total_deposit += deposit_amount;
-    let contract = if proposal_type == Some("registrar".to_string()) {
-        config.registrar_contract
-    } else {
-        env.contract.address.clone()
-    };
-
     let mut data_list: Vec<ExecuteData> = vec![];
     let all_execute_data = if let Some(exe_msgs) = options {
         for msgs in exe_msgs {
             let execute_data = ExecuteData {
                 order: msgs.order,
-                contract: contract.clone(),
+                contract: deps.api.addr_validate(&msgs.contract)?,
                 msg: msgs.msg,
-                funding_goal: msgs.funding_goal,
-                fund_rotation: msgs.fund_rotation,
-                split_to_liquid: msgs.split_to_liquid,
-                treasury_tax_rate: msgs.treasury_tax_rate,
+                funds: msgs.funds,
             };
             data_list.push(execute_data)
         }
@@ -350,10 +367,17 @@ pub fn create_poll(
     };
 
     let sender_address_raw = deps.api.addr_validate(&proposer)?;
-    // store the current staked amount for quorum calculation
-    let staked_amount =
+    // snapshot the voting power at the start of the block, valuing the total share snapshot
+    // at the current share price so both are read at the same height
+    let total_balance =
         query_token_balance(&deps.querier, config.halo_token, env.contract.address)?
             .checked_sub(state.total_deposit)?;
+    let total_share = read_total_share_at_height(deps.storage, Some(env.block.height))?;
+    let staked_amount = if state.total_share.is_zero() {
+        total_balance
+    } else {
+        total_share.multiply_ratio(total_balance, state.total_share)
+    };
 
     let new_poll = Poll {
         id: poll_id,
@@ -361,6 +385,8 @@ pub fn create_poll(
         status: PollStatus::InProgress,
         yes_votes: Uint128::zero(),
         no_votes: Uint128::zero(),
+        abstain_votes: Uint128::zero(),
+        veto_votes: Uint128::zero(),
         end_height: env.block.height + config.voting_period,
         title,
         description,
@@ -370,6 +396,8 @@ pub fn create_poll(
         deposit_amount,
         total_balance_at_end_poll: None,
         staked_amount: Some(staked_amount),
+        start_height: Some(env.block.height),
+        total_share: Some(total_share),
     };
 
     store_poll(deps.storage, &poll_id.to_be_bytes(), &new_poll)?;
@@ -401,8 +429,11 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Contr
 
     let no = a_poll.no_votes.u128();
     let yes = a_poll.yes_votes.u128();
+    let abstain = a_poll.abstain_votes.u128();
+    let veto = a_poll.veto_votes.u128();
 
-    let tallied_weight = yes + no;
+    // abstain votes count toward the quorum, but not toward the threshold
+    let tallied_weight = yes + no + abstain + veto;
 
     let mut poll_status = PollStatus::Rejected;
     let mut rejected_reason = "";
@@ -437,8 +468,21 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Contr
         // Quorum: More than quorum of the total staked tokens at the end of the voting
         // period need to have participated in the vote.
         rejected_reason = "Quorum not reached";
+    } else if Decimal::from_ratio(veto, tallied_weight) > config.veto_threshold {
+        // Veto: More than the veto threshold of the tokens that participated in the vote
+        // vetoed the proposal. The poll is rejected and its deposit burned.
+        rejected_reason = "Vetoed";
+        if !a_poll.deposit_amount.is_zero() {
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.halo_token.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: a_poll.deposit_amount,
+                })?,
+            }))
+        }
     } else {
-        if Decimal::from_ratio(yes, tallied_weight) > config.threshold {
+        if yes + no + veto != 0 && Decimal::from_ratio(yes, yes + no + veto) > config.threshold {
             //Threshold: More than 50% of the tokens that participated in the vote
             // (after excluding “Abstain” votes) need to have voted in favor of the proposal (“Yes”).
             poll_status = PollStatus::Passed;
@@ -537,7 +581,7 @@ pub fn execute_poll_messages(
             messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: msg.contract.to_string(),
                 msg: msg.msg,
-                funds: vec![],
+                funds: msg.funds,
             }));
         }
     }
@@ -593,35 +637,71 @@ pub fn cast_vote(
     let key = &info.sender.as_bytes();
     let mut token_manager = read_bank(deps.storage, key)?.unwrap_or_default();
 
-    // convert share to amount
-    let total_share = state.total_share;
+    // voting directly overrides the vote the sender's delegate cast with their stake
+    revoke_delegated_vote(deps.storage, &mut a_poll, &info.sender, &mut token_manager)?;
+
     let total_balance =
         query_token_balance(&deps.querier, config.halo_token, env.contract.address)?
             .checked_sub(state.total_deposit)?;
 
-    if token_manager
-        .share
-        .multiply_ratio(total_balance, total_share)
-        < amount
-    {
+    // convert share to amount, as staked at the start of the poll
+    let (total_share, staked_balance) = match a_poll.start_height {
+        Some(start_height) => (
+            match a_poll.total_share {
+                Some(total_share) => total_share,
+                None => read_total_share_at_height(deps.storage, Some(start_height))?,
+            },
+            a_poll.staked_amount.unwrap_or_default(),
+        ),
+        None => (state.total_share, total_balance),
+    };
+
+    // the sender's own stake is voted first, then the stake delegated to the sender
+    let own_balance = if total_share.is_zero() {
+        Uint128::zero()
+    } else {
+        read_share_at_height(deps.storage, key, a_poll.start_height)?
+            .multiply_ratio(staked_balance, total_share)
+    };
+    let delegated = delegated_balances(
+        deps.storage,
+        &a_poll,
+        &info.sender,
+        total_share,
+        staked_balance,
+    )?;
+    let delegated_balance: Uint128 = delegated.iter().map(|(_, balance)| *balance).sum();
+
+    if own_balance + delegated_balance < amount {
         return Err(ContractError::InsufficientStaked {});
     }
+    let own_amount = own_balance.min(amount);
+    let delegated_amount = amount - own_amount;
 
     // update tally info
-    if VoteOption::Yes == vote {
-        a_poll.yes_votes += amount;
-    } else {
-        a_poll.no_votes += amount;
-    }
+    *a_poll.votes_mut(&vote) += amount;
+
+    token_manager.locked_balance.push((
+        poll_id,
+        VoterInfo {
+            vote: vote.clone(),
+            balance: own_amount,
+        },
+    ));
+    store_bank(deps.storage, key, &token_manager)?;
+    lock_delegated_votes(
+        deps.storage,
+        poll_id,
+        &info.sender,
+        &vote,
+        delegated,
+        delegated_amount,
+    )?;
 
     let vote_info = VoterInfo {
         vote,
         balance: amount,
     };
-    token_manager
-        .locked_balance
-        .push((poll_id, vote_info.clone()));
-    store_bank(deps.storage, key, &token_manager)?;
 
     // store poll voter && and update poll data
     store_poll_voter(deps.storage, poll_id, info.sender.clone(), &vote_info)?;
@@ -635,13 +715,17 @@ pub fn cast_vote(
 
     store_poll(deps.storage, &poll_id.to_be_bytes(), &a_poll)?;
 
-    Ok(Response::new().add_attributes(vec![
+    let mut res = Response::new().add_attributes(vec![
         ("action", "cast_vote"),
         ("poll_id", poll_id.to_string().as_str()),
         ("amount", amount.to_string().as_str()),
         ("voter", info.sender.as_str()),
         ("vote_option", vote_info.vote.to_string().as_str()),
-    ]))
+    ]);
+    if !delegated_amount.is_zero() {
+        res = res.add_attribute("delegated_amount", delegated_amount.to_string());
+    }
+    Ok(res)
 }
 
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
@@ -677,6 +761,20 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
             limit,
             order_by,
         )?)?),
+        QueryMsg::Delegation { address } => Ok(to_binary(&query_delegation(deps, address)?)?),
+        QueryMsg::Delegators {
+            address,
+            start_after,
+            limit,
+            order_by,
+        } => Ok(to_binary(&query_delegators(
+            deps,
+            env,
+            address,
+            start_after,
+            limit,
+            order_by,
+        )?)?),
     }
 }
 
@@ -687,6 +785,7 @@ fn query_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
         halo_token: config.halo_token.to_string(),
         quorum: config.quorum,
         threshold: config.threshold,
+        veto_threshold: config.veto_threshold,
         voting_period: config.voting_period,
         timelock_period: config.timelock_period,
         proposal_deposit: config.proposal_deposit,
@@ -727,11 +826,9 @@ fn query_poll(deps: Deps, poll_id: u64) -> Result<PollResponse, ContractError> {
             for msg in exe_msgs {
                 let execute_data = PollExecuteMsg {
                     order: msg.order,
+                    contract: msg.contract.to_string(),
                     msg: msg.msg,
-                    funding_goal: msg.funding_goal,
-                    fund_rotation: msg.fund_rotation,
-                    split_to_liquid: msg.split_to_liquid,
-                    treasury_tax_rate: msg.treasury_tax_rate,
+                    funds: msg.funds,
                 };
                 data_list.push(execute_data)
             }
@@ -741,6 +838,8 @@ fn query_poll(deps: Deps, poll_id: u64) -> Result<PollResponse, ContractError> {
         },
         yes_votes: poll.yes_votes,
         no_votes: poll.no_votes,
+        abstain_votes: poll.abstain_votes,
+        veto_votes: poll.veto_votes,
         staked_amount: poll.staked_amount,
         total_balance_at_end_poll: poll.total_balance_at_end_poll,
     })
@@ -774,11 +873,9 @@ fn query_polls(
                     for msg in exe_msgs {
                         let execute_data = PollExecuteMsg {
                             order: msg.order,
+                            contract: msg.contract.to_string(),
                             msg: msg.msg,
-                            funding_goal: msg.funding_goal,
-                            fund_rotation: msg.fund_rotation,
-                            split_to_liquid: msg.split_to_liquid,
-                            treasury_tax_rate: msg.treasury_tax_rate,
+                            funds: msg.funds,
                         };
                         data_list.push(execute_data)
                     }
@@ -788,6 +885,8 @@ fn query_polls(
                 },
                 yes_votes: poll.yes_votes,
                 no_votes: poll.no_votes,
+                abstain_votes: poll.abstain_votes,
+                veto_votes: poll.veto_votes,
                 staked_amount: poll.staked_amount,
                 total_balance_at_end_poll: poll.total_balance_at_end_poll,
             })
@@ -844,6 +943,71 @@ fn query_voters(
 }
 
 #[entry_point]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // contracts deployed before the version was tracked still store the polls
+    // with the deprecated special-case execute data fields
+    if get_contract_version(deps.storage).is_err() {
+        migrate_poll_execute_data(deps.storage)?;
+    }
+    // stakes from before the voting power snapshots need a starting snapshot
+    if TOTAL_SHARE_SNAPSHOT.may_load(deps.storage)?.is_none() {
+        migrate_share_snapshots(deps.storage, env.block.height)?;
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     Ok(Response::default())
 }
+
+/// Snapshot the current stakes, so polls created from now on can vote with them.
+fn migrate_share_snapshots(storage: &mut dyn Storage, height: u64) -> StdResult<()> {
+    let shares = BANK
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(key, token_manager)| (key, token_manager.share)))
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, share) in shares {
+        SHARE_SNAPSHOT.save(storage, &key, &share, height)?;
+    }
+    let total_share = read_state(storage)?.total_share;
+    TOTAL_SHARE_SNAPSHOT.save(storage, &total_share, height)
+}
+
+/// Drop the deprecated `funding_goal`, `fund_rotation`, `split_to_liquid` & `treasury_tax_rate`
+/// fields from the stored polls. None of those messages sent any funds along.
+fn migrate_poll_execute_data(storage: &mut dyn Storage) -> StdResult<()> {
+    let old_polls = OLD_POLL
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, old_poll) in old_polls {
+        let poll = Poll {
+            id: old_poll.id,
+            creator: old_poll.creator,
+            status: old_poll.status,
+            yes_votes: old_poll.yes_votes,
+            no_votes: old_poll.no_votes,
+            abstain_votes: Uint128::zero(),
+            veto_votes: Uint128::zero(),
+            end_height: old_poll.end_height,
+            title: old_poll.title,
+            description: old_poll.description,
+            link: old_poll.link,
+            proposal_type: old_poll.proposal_type,
+            execute_data: old_poll.execute_data.map(|data| {
+                data.into_iter()
+                    .map(|d| ExecuteData {
+                        order: d.order,
+                        contract: d.contract,
+                        msg: d.msg,
+                        funds: vec![],
+                    })
+                    .collect()
+            }),
+            deposit_amount: old_poll.deposit_amount,
+            total_balance_at_end_poll: old_poll.total_balance_at_end_poll,
+            staked_amount: old_poll.staked_amount,
+            start_height: None,
+            total_share: None,
+        };
+        store_poll(storage, &key, &poll)?;
+    }
+    Ok(())
+}
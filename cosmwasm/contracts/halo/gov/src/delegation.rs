@@ -0,0 +1,198 @@
+use crate::error::ContractError;
+use crate::state::{
+    read_all_delegators, read_bank, read_config, read_delegated_vote, read_delegation,
+    read_delegators, read_poll_voter, read_share_at_height, read_state, remove_delegated_vote,
+    remove_delegation, store_bank, store_delegated_vote, store_delegation, store_poll_voter,
+    Config, DelegatedVote, Poll, State, TokenManager, MAX_DELEGATORS,
+};
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+use halo_token::common::OrderBy;
+use halo_token::gov::{
+    DelegationResponse, DelegatorsResponse, DelegatorsResponseItem, VoteOption, VoterInfo,
+};
+use terraswap::querier::query_token_balance;
+
+pub fn delegate(deps: DepsMut, info: MessageInfo, to: String) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&to)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegation {});
+    }
+
+    let token_manager = read_bank(deps.storage, &info.sender.as_bytes())?.unwrap_or_default();
+    if token_manager.share.is_zero() {
+        return Err(ContractError::NothingStaked {});
+    }
+
+    // a staker has a single delegate at a time
+    if let Some(prev_delegate) = read_delegation(deps.storage, &info.sender)? {
+        remove_delegation(deps.storage, &info.sender, &prev_delegate);
+    }
+    if read_all_delegators(deps.storage, &delegate)?.len() >= MAX_DELEGATORS {
+        return Err(ContractError::TooManyDelegators {});
+    }
+    store_delegation(deps.storage, &info.sender, &delegate)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "delegate"),
+        ("delegator", info.sender.as_str()),
+        ("delegate", delegate.as_str()),
+    ]))
+}
+
+pub fn undelegate(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let delegate = match read_delegation(deps.storage, &info.sender)? {
+        Some(delegate) => delegate,
+        None => return Err(ContractError::NothingDelegated {}),
+    };
+    remove_delegation(deps.storage, &info.sender, &delegate);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "undelegate"),
+        ("delegator", info.sender.as_str()),
+        ("delegate", delegate.as_str()),
+    ]))
+}
+
+/// Staked balances the `delegate` can vote with on a poll through the delegations it received,
+/// as staked at the start of the poll. Delegators who have voted on the poll themselves, or whose
+/// stake was already voted by a previous delegate, are left out.
+pub fn delegated_balances(
+    storage: &dyn Storage,
+    poll: &Poll,
+    delegate: &Addr,
+    total_share: Uint128,
+    total_balance: Uint128,
+) -> StdResult<Vec<(Addr, Uint128)>> {
+    if total_share.is_zero() {
+        return Ok(vec![]);
+    }
+
+    let mut balances = vec![];
+    for delegator in read_all_delegators(storage, delegate)? {
+        if read_poll_voter(storage, poll.id, delegator.clone()).is_ok()
+            || read_delegated_vote(storage, poll.id, &delegator)?.is_some()
+        {
+            continue;
+        }
+        let share = read_share_at_height(storage, delegator.as_bytes(), poll.start_height)?;
+        if !share.is_zero() {
+            balances.push((delegator, share.multiply_ratio(total_balance, total_share)));
+        }
+    }
+    Ok(balances)
+}
+
+/// Lock the `amount` voted with delegated stake on the delegators' balances,
+/// drawing on each delegator in turn until the amount is covered
+pub fn lock_delegated_votes(
+    storage: &mut dyn Storage,
+    poll_id: u64,
+    delegate: &Addr,
+    vote: &VoteOption,
+    delegated: Vec<(Addr, Uint128)>,
+    amount: Uint128,
+) -> StdResult<()> {
+    let mut remaining = amount;
+    for (delegator, balance) in delegated {
+        if remaining.is_zero() {
+            break;
+        }
+        let used = balance.min(remaining);
+        remaining -= used;
+
+        let key = delegator.as_bytes();
+        let mut token_manager = read_bank(storage, &key)?.unwrap_or_default();
+        token_manager.locked_balance.push((
+            poll_id,
+            VoterInfo {
+                vote: vote.clone(),
+                balance: used,
+            },
+        ));
+        store_bank(storage, &key, &token_manager)?;
+
+        store_delegated_vote(
+            storage,
+            poll_id,
+            &delegator,
+            &DelegatedVote {
+                delegate: delegate.clone(),
+                balance: used,
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// Take the delegator's stake back out of the vote its delegate has cast on the poll,
+/// so that the delegator can vote on it directly
+pub fn revoke_delegated_vote(
+    storage: &mut dyn Storage,
+    poll: &mut Poll,
+    delegator: &Addr,
+    token_manager: &mut TokenManager,
+) -> StdResult<()> {
+    let delegated_vote = match read_delegated_vote(storage, poll.id, delegator)? {
+        Some(delegated_vote) => delegated_vote,
+        None => return Ok(()),
+    };
+
+    let mut vote_info = read_poll_voter(storage, poll.id, delegated_vote.delegate.clone())?;
+    let votes = poll.votes_mut(&vote_info.vote);
+    *votes = votes.checked_sub(delegated_vote.balance)?;
+    vote_info.balance = vote_info.balance.checked_sub(delegated_vote.balance)?;
+    store_poll_voter(storage, poll.id, delegated_vote.delegate, &vote_info)?;
+
+    token_manager
+        .locked_balance
+        .retain(|(poll_id, _)| *poll_id != poll.id);
+    remove_delegated_vote(storage, poll.id, delegator);
+    Ok(())
+}
+
+pub fn query_delegation(deps: Deps, address: String) -> StdResult<DelegationResponse> {
+    let delegator = deps.api.addr_validate(&address)?;
+    Ok(DelegationResponse {
+        delegate: read_delegation(deps.storage, &delegator)?.map(|addr| addr.to_string()),
+    })
+}
+
+pub fn query_delegators(
+    deps: Deps,
+    env: Env,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    order_by: Option<OrderBy>,
+) -> StdResult<DelegatorsResponse> {
+    let delegate = deps.api.addr_validate(&address)?;
+    let start_after = match start_after {
+        Some(start_after) => Some(deps.api.addr_validate(&start_after)?),
+        None => None,
+    };
+    let config: Config = read_config(deps.storage)?;
+    let state: State = read_state(deps.storage)?;
+
+    let total_balance =
+        query_token_balance(&deps.querier, config.halo_token, env.contract.address)?
+            .checked_sub(state.total_deposit)?;
+
+    let delegators = read_delegators(deps.storage, &delegate, start_after, limit, order_by)?
+        .into_iter()
+        .map(|delegator| {
+            let share = read_bank(deps.storage, &delegator.as_bytes())?
+                .unwrap_or_default()
+                .share;
+            Ok(DelegatorsResponseItem {
+                delegator: delegator.to_string(),
+                balance: if !state.total_share.is_zero() {
+                    share.multiply_ratio(total_balance, state.total_share)
+                } else {
+                    Uint128::zero()
+                },
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(DelegatorsResponse { delegators })
+}
@@ -74,4 +74,13 @@ pub enum ContractError {
 
     #[error("Invalid inputs")]
     InvalidInputs {},
+
+    #[error("Cannot delegate voting power to yourself")]
+    SelfDelegation {},
+
+    #[error("Nothing delegated")]
+    NothingDelegated {},
+
+    #[error("Delegate has reached the maximum number of delegators")]
+    TooManyDelegators {},
 }
@@ -1,5 +1,6 @@
 pub mod contract;
 
+mod delegation;
 mod error;
 mod staking;
 mod state;
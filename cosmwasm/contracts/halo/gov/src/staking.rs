@@ -1,7 +1,7 @@
 use crate::error::ContractError;
 use crate::state::{
-    read_bank, read_config, read_poll, read_state, remove_poll_voter, store_bank, store_state,
-    Config, Poll, State, TokenManager, CLAIMS,
+    read_bank, read_config, read_poll, read_state, remove_delegated_vote, remove_poll_voter,
+    store_bank, store_share_snapshot, store_state, Config, Poll, State, TokenManager, CLAIMS,
 };
 use cosmwasm_std::{
     to_binary, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage,
@@ -42,6 +42,13 @@ pub fn stake_voting_tokens(
 
     store_state(deps.storage, &state)?;
     store_bank(deps.storage, &key, &token_manager)?;
+    store_share_snapshot(
+        deps.storage,
+        key,
+        token_manager.share,
+        state.total_share,
+        env.block.height,
+    )?;
 
     Ok(Response::new().add_attributes(vec![
         ("action", "staking"),
@@ -101,6 +108,13 @@ pub fn withdraw_voting_tokens(
 
             state.total_share = Uint128::from(total_share - withdraw_share);
             store_state(deps.storage, &state)?;
+            store_share_snapshot(
+                deps.storage,
+                key,
+                token_manager.share,
+                state.total_share,
+                env.block.height,
+            )?;
 
             // create claim on withdrawn HALO tokens
             CLAIMS.create_claim(
@@ -159,6 +173,7 @@ fn compute_locked_balance(
         if poll.status != PollStatus::InProgress {
             // remove voter info from the poll
             remove_poll_voter(storage, *poll_id, voter);
+            remove_delegated_vote(storage, *poll_id, voter);
         }
 
         poll.status == PollStatus::InProgress
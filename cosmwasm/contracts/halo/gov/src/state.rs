@@ -1,10 +1,10 @@
 use angel_core::utils::{calc_range_end, calc_range_start};
-use cosmwasm_std::{Addr, Binary, Decimal, Deps, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Deps, Order, StdResult, Storage, Uint128};
 use cw_controllers::Claims;
-use cw_storage_plus::{Bound, Item, Map};
+use cw_storage_plus::{Bound, Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
 use halo_token::common::OrderBy;
-use halo_token::gov::{PollStatus, VoterInfo};
+use halo_token::gov::{PollStatus, VoteOption, VoterInfo};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
@@ -17,6 +17,9 @@ pub struct Config {
     pub halo_token: Addr,
     pub quorum: Decimal,
     pub threshold: Decimal,
+    /// Share of the tallied weight that has to veto a poll to reject it & burn its deposit
+    #[serde(default = "default_veto_threshold")]
+    pub veto_threshold: Decimal,
     pub voting_period: u64,
     pub timelock_period: u64,
     pub proposal_deposit: Uint128,
@@ -29,6 +32,11 @@ pub struct Config {
     pub gov_hodler: Addr,
 }
 
+/// Veto threshold of the configs stored before it could be configured
+fn default_veto_threshold() -> Decimal {
+    Decimal::permille(334)
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
     pub poll_count: u64,
@@ -49,6 +57,10 @@ pub struct Poll {
     pub status: PollStatus,
     pub yes_votes: Uint128,
     pub no_votes: Uint128,
+    #[serde(default)]
+    pub abstain_votes: Uint128,
+    #[serde(default)]
+    pub veto_votes: Uint128,
     pub end_height: u64,
     pub title: String,
     pub description: String,
@@ -59,6 +71,25 @@ pub struct Poll {
     /// Total balance at the end poll
     pub total_balance_at_end_poll: Option<Uint128>,
     pub staked_amount: Option<Uint128>,
+    /// Block height the voting power is snapshotted at. Polls created before the
+    /// snapshots were introduced have none & vote with the current stake.
+    #[serde(default)]
+    pub start_height: Option<u64>,
+    /// Total staked share at `start_height`, which `staked_amount` is the value of
+    #[serde(default)]
+    pub total_share: Option<Uint128>,
+}
+
+impl Poll {
+    /// Tally of the votes cast with the given option
+    pub fn votes_mut(&mut self, vote: &VoteOption) -> &mut Uint128 {
+        match vote {
+            VoteOption::Yes => &mut self.yes_votes,
+            VoteOption::No => &mut self.no_votes,
+            VoteOption::Abstain => &mut self.abstain_votes,
+            VoteOption::Veto => &mut self.veto_votes,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
@@ -66,10 +97,7 @@ pub struct ExecuteData {
     pub order: u64,
     pub contract: Addr,
     pub msg: Binary,
-    pub funding_goal: Option<Uint128>,
-    pub fund_rotation: Option<u64>,
-    pub split_to_liquid: Option<Decimal>,
-    pub treasury_tax_rate: Option<Decimal>,
+    pub funds: Vec<Coin>,
 }
 impl Eq for ExecuteData {}
 
@@ -91,6 +119,36 @@ impl PartialEq for ExecuteData {
     }
 }
 
+/// Poll as stored before each execute msg named its own target contract & funds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OldPoll {
+    pub id: u64,
+    pub creator: Addr,
+    pub status: PollStatus,
+    pub yes_votes: Uint128,
+    pub no_votes: Uint128,
+    pub end_height: u64,
+    pub title: String,
+    pub description: String,
+    pub link: Option<String>,
+    pub proposal_type: Option<String>,
+    pub execute_data: Option<Vec<OldExecuteData>>,
+    pub deposit_amount: Uint128,
+    pub total_balance_at_end_poll: Option<Uint128>,
+    pub staked_amount: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OldExecuteData {
+    pub order: u64,
+    pub contract: Addr,
+    pub msg: Binary,
+    pub funding_goal: Option<Uint128>,      // Deprecated
+    pub fund_rotation: Option<u64>,         // Deprecated
+    pub split_to_liquid: Option<Decimal>,   // Deprecated
+    pub treasury_tax_rate: Option<Decimal>, // Deprecated
+}
+
 // "Config" storage item
 pub const CONFIG: Item<Config> = Item::new("config");
 
@@ -132,6 +190,8 @@ pub fn read_poll(storage: &dyn Storage, poll_id: &[u8]) -> StdResult<Poll> {
     POLL.load(storage, poll_id)
 }
 
+pub const OLD_POLL: Map<&[u8], OldPoll> = Map::new("poll");
+
 // "poll_indexer_store" storage map
 pub const POLL_INDEXER_STORE: Map<(&str, &[u8]), bool> = Map::new("poll_indexer_store");
 pub fn store_poll_indexer(
@@ -247,6 +307,62 @@ pub fn read_polls(
     }
 }
 
+// staked share of each voter, snapshotted on every change
+pub const SHARE_SNAPSHOT: SnapshotMap<&[u8], Uint128> = SnapshotMap::new(
+    "share_snapshot",
+    "share_snapshot__checkpoint",
+    "share_snapshot__changelog",
+    Strategy::EveryBlock,
+);
+
+// total staked share, snapshotted on every change
+pub const TOTAL_SHARE_SNAPSHOT: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_share_snapshot",
+    "total_share_snapshot__checkpoint",
+    "total_share_snapshot__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Staked share of the voter at the start of the given block.
+/// Without a height the current share is read from the bank.
+pub fn read_share_at_height(
+    storage: &dyn Storage,
+    key: &[u8],
+    height: Option<u64>,
+) -> StdResult<Uint128> {
+    match height {
+        Some(height) => Ok(SHARE_SNAPSHOT
+            .may_load_at_height(storage, key, height)?
+            .unwrap_or_default()),
+        None => Ok(read_bank(storage, &key)?.unwrap_or_default().share),
+    }
+}
+
+/// Total staked share at the start of the given block.
+/// Without a height the current total share is read from the state.
+pub fn read_total_share_at_height(
+    storage: &dyn Storage,
+    height: Option<u64>,
+) -> StdResult<Uint128> {
+    match height {
+        Some(height) => Ok(TOTAL_SHARE_SNAPSHOT
+            .may_load_at_height(storage, height)?
+            .unwrap_or_default()),
+        None => Ok(read_state(storage)?.total_share),
+    }
+}
+
+pub fn store_share_snapshot(
+    storage: &mut dyn Storage,
+    key: &[u8],
+    share: Uint128,
+    total_share: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    SHARE_SNAPSHOT.save(storage, key, &share, height)?;
+    TOTAL_SHARE_SNAPSHOT.save(storage, &total_share, height)
+}
+
 // "bank" storage map
 pub const BANK: Map<&[u8], TokenManager> = Map::new("bank");
 pub fn store_bank(storage: &mut dyn Storage, key: &&[u8], data: &TokenManager) -> StdResult<()> {
@@ -256,3 +372,94 @@ pub fn store_bank(storage: &mut dyn Storage, key: &&[u8], data: &TokenManager) -
 pub fn read_bank(storage: &dyn Storage, key: &&[u8]) -> StdResult<Option<TokenManager>> {
     BANK.may_load(storage, key)
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegatedVote {
+    pub delegate: Addr,
+    pub balance: Uint128, // part of the delegator's stake counted in the delegate's vote
+}
+
+// "delegation" storage map: delegator => delegate
+pub const DELEGATION: Map<&[u8], Addr> = Map::new("delegation");
+pub fn read_delegation(storage: &dyn Storage, delegator: &Addr) -> StdResult<Option<Addr>> {
+    DELEGATION.may_load(storage, delegator.as_bytes())
+}
+
+// "delegator" storage map: (delegate, delegator) => true
+pub const DELEGATOR: Map<(&[u8], &[u8]), bool> = Map::new("delegator");
+pub fn store_delegation(
+    storage: &mut dyn Storage,
+    delegator: &Addr,
+    delegate: &Addr,
+) -> StdResult<()> {
+    DELEGATION.save(storage, delegator.as_bytes(), delegate)?;
+    DELEGATOR.save(storage, (delegate.as_bytes(), delegator.as_bytes()), &true)
+}
+
+pub fn remove_delegation(storage: &mut dyn Storage, delegator: &Addr, delegate: &Addr) {
+    DELEGATION.remove(storage, delegator.as_bytes());
+    DELEGATOR.remove(storage, (delegate.as_bytes(), delegator.as_bytes()));
+}
+
+pub fn read_delegators(
+    storage: &dyn Storage,
+    delegate: &Addr,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+    order_by: Option<OrderBy>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(|v| Bound::exclusive(v.as_bytes()));
+    let (start, end, order_by) = match order_by {
+        Some(OrderBy::Asc) => (start, None, OrderBy::Asc),
+        _ => (None, start, OrderBy::Desc),
+    };
+
+    DELEGATOR
+        .prefix(delegate.as_bytes())
+        .keys(storage, start, end, order_by.into())
+        .take(limit)
+        .map(|k| Ok(Addr::unchecked(String::from_utf8(k?)?)))
+        .collect()
+}
+
+/// Most stakers that can delegate to a single delegate, bounding the delegators
+/// a delegate's vote has to walk through
+pub const MAX_DELEGATORS: usize = 100;
+
+/// All the stakers delegating to the `delegate`, in ascending order
+pub fn read_all_delegators(storage: &dyn Storage, delegate: &Addr) -> StdResult<Vec<Addr>> {
+    DELEGATOR
+        .prefix(delegate.as_bytes())
+        .keys(storage, None, None, Order::Ascending)
+        .take(MAX_DELEGATORS)
+        .map(|k| Ok(Addr::unchecked(String::from_utf8(k?)?)))
+        .collect()
+}
+
+// "delegated_vote" storage map: (poll_id, delegator) => vote cast with the delegator's stake
+pub const DELEGATED_VOTE: Map<(&[u8], &[u8]), DelegatedVote> = Map::new("delegated_vote");
+pub fn read_delegated_vote(
+    storage: &dyn Storage,
+    poll_id: u64,
+    delegator: &Addr,
+) -> StdResult<Option<DelegatedVote>> {
+    DELEGATED_VOTE.may_load(storage, (&poll_id.to_be_bytes(), delegator.as_bytes()))
+}
+
+pub fn store_delegated_vote(
+    storage: &mut dyn Storage,
+    poll_id: u64,
+    delegator: &Addr,
+    data: &DelegatedVote,
+) -> StdResult<()> {
+    DELEGATED_VOTE.save(
+        storage,
+        (&poll_id.to_be_bytes(), delegator.as_bytes()),
+        data,
+    )
+}
+
+pub fn remove_delegated_vote(storage: &mut dyn Storage, poll_id: u64, delegator: &Addr) {
+    DELEGATED_VOTE.remove(storage, (&poll_id.to_be_bytes(), delegator.as_bytes()))
+}
@@ -1,9 +1,10 @@
-use crate::contract::{execute, instantiate, query, reply};
+use crate::contract::{execute, instantiate, migrate, query, reply};
 use crate::error::ContractError;
 use crate::mock_querier::mock_dependencies;
 use crate::state::{
-    read_bank, read_config, read_poll_voter, read_state, store_bank, store_poll, store_poll_voter,
-    Config, Poll, State, TokenManager,
+    read_bank, read_config, read_poll, read_poll_voter, read_state, store_bank, store_poll,
+    store_poll_voter, store_state, Config, OldExecuteData, OldPoll, Poll, State, TokenManager,
+    MAX_DELEGATORS, OLD_POLL,
 };
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
@@ -14,8 +15,9 @@ use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use cw_utils::Duration;
 use halo_token::common::OrderBy;
 use halo_token::gov::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, PollExecuteMsg, PollResponse,
-    PollStatus, PollsResponse, QueryMsg, StakerResponse, VoteOption, VoterInfo, VotersResponse,
+    ConfigResponse, Cw20HookMsg, DelegationResponse, DelegatorsResponse, DelegatorsResponseItem,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PollExecuteMsg, PollResponse, PollStatus,
+    PollsResponse, QueryMsg, StakerResponse, VoteOption, VoterInfo, VotersResponse,
     VotersResponseItem,
 };
 use terraswap::querier::query_token_balance;
@@ -30,6 +32,7 @@ const HALO_TOKEN: &str = "halo_token";
 const GOV_HODLER: &str = "gov_hodler";
 const DEFAULT_QUORUM: u64 = 30u64;
 const DEFAULT_THRESHOLD: u64 = 50u64;
+const DEFAULT_VETO_THRESHOLD: u64 = 33u64;
 const DEFAULT_VOTING_PERIOD: u64 = 20000u64;
 const DEFAULT_FIX_PERIOD: u64 = 10u64;
 const DEFAULT_TIMELOCK_PERIOD: u64 = 10000u64;
@@ -40,6 +43,7 @@ fn mock_instantiate(deps: DepsMut) {
     let msg = InstantiateMsg {
         quorum: DEFAULT_QUORUM,
         threshold: DEFAULT_THRESHOLD,
+        veto_threshold: DEFAULT_VETO_THRESHOLD,
         voting_period: DEFAULT_VOTING_PERIOD,
         timelock_period: DEFAULT_TIMELOCK_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
@@ -75,6 +79,7 @@ fn instantiate_msg() -> InstantiateMsg {
     InstantiateMsg {
         quorum: DEFAULT_QUORUM,
         threshold: DEFAULT_THRESHOLD,
+        veto_threshold: DEFAULT_VETO_THRESHOLD,
         voting_period: DEFAULT_VOTING_PERIOD,
         timelock_period: DEFAULT_TIMELOCK_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
@@ -86,6 +91,7 @@ fn instantiate_msg() -> InstantiateMsg {
     }
 }
 
+#[test]
 fn proper_initialization() {
     let mut deps = mock_dependencies(&[]);
 
@@ -102,6 +108,7 @@ fn proper_initialization() {
             owner: deps.api.addr_validate(TEST_CREATOR).unwrap(),
             quorum: Decimal::percent(DEFAULT_QUORUM),
             threshold: Decimal::percent(DEFAULT_THRESHOLD),
+            veto_threshold: Decimal::percent(DEFAULT_VETO_THRESHOLD),
             voting_period: DEFAULT_VOTING_PERIOD,
             timelock_period: DEFAULT_TIMELOCK_PERIOD,
             proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
@@ -133,6 +140,7 @@ fn proper_initialization() {
     );
 }
 
+#[test]
 fn poll_not_found() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -147,12 +155,14 @@ fn poll_not_found() {
     }
 }
 
+#[test]
 fn fails_init_invalid_quorum() {
     let mut deps = mock_dependencies(&[]);
     let info = mock_info("voter", &coins(11, VOTING_TOKEN));
     let msg = InstantiateMsg {
         quorum: 101,
         threshold: DEFAULT_THRESHOLD,
+        veto_threshold: DEFAULT_VETO_THRESHOLD,
         voting_period: DEFAULT_VOTING_PERIOD,
         timelock_period: DEFAULT_TIMELOCK_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
@@ -174,12 +184,14 @@ fn fails_init_invalid_quorum() {
     }
 }
 
+#[test]
 fn fails_init_invalid_threshold() {
     let mut deps = mock_dependencies(&[]);
     let info = mock_info("voter", &coins(11, VOTING_TOKEN));
     let msg = InstantiateMsg {
         quorum: DEFAULT_QUORUM,
         threshold: 101,
+        veto_threshold: DEFAULT_VETO_THRESHOLD,
         voting_period: DEFAULT_VOTING_PERIOD,
         timelock_period: DEFAULT_TIMELOCK_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
@@ -201,12 +213,14 @@ fn fails_init_invalid_threshold() {
     }
 }
 
+#[test]
 fn fails_contract_register_from_non_owner() {
     let mut deps = mock_dependencies(&[]);
     let info = mock_info("voter", &coins(11, VOTING_TOKEN));
     let msg = InstantiateMsg {
         quorum: DEFAULT_QUORUM,
         threshold: DEFAULT_THRESHOLD,
+        veto_threshold: DEFAULT_VETO_THRESHOLD,
         voting_period: DEFAULT_VOTING_PERIOD,
         timelock_period: DEFAULT_TIMELOCK_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
@@ -232,6 +246,7 @@ fn fails_contract_register_from_non_owner() {
     }
 }
 
+#[test]
 fn fails_create_poll_invalid_title() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -264,6 +279,7 @@ fn fails_create_poll_invalid_title() {
     }
 }
 
+#[test]
 fn fails_create_poll_invalid_description() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -296,6 +312,7 @@ fn fails_create_poll_invalid_description() {
     }
 }
 
+#[test]
 fn fails_create_poll_invalid_link() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -334,6 +351,7 @@ fn fails_create_poll_invalid_link() {
     }
 }
 
+#[test]
 fn fails_create_poll_invalid_deposit() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -381,6 +399,7 @@ fn create_poll_msg(
     })
 }
 
+#[test]
 fn happy_days_create_poll() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -407,6 +426,7 @@ fn happy_days_create_poll() {
     );
 }
 
+#[test]
 fn query_polls() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -440,27 +460,21 @@ fn query_polls() {
     let execute_msgs: Vec<PollExecuteMsg> = vec![
         PollExecuteMsg {
             order: 1u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz,
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 3u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz3,
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 2u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz2,
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
     ];
 
@@ -504,6 +518,8 @@ fn query_polls() {
                 execute_data: Some(execute_msgs.clone()),
                 yes_votes: Uint128::zero(),
                 no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
+                veto_votes: Uint128::zero(),
                 staked_amount: Some(Uint128::from(DEFAULT_PROPOSAL_DEPOSIT)),
                 total_balance_at_end_poll: None,
             },
@@ -520,6 +536,8 @@ fn query_polls() {
                 execute_data: None,
                 yes_votes: Uint128::zero(),
                 no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
+                veto_votes: Uint128::zero(),
                 staked_amount: Some(Uint128::zero()),
                 total_balance_at_end_poll: None,
             },
@@ -553,6 +571,8 @@ fn query_polls() {
             execute_data: None,
             yes_votes: Uint128::zero(),
             no_votes: Uint128::zero(),
+            abstain_votes: Uint128::zero(),
+            veto_votes: Uint128::zero(),
             staked_amount: Some(Uint128::zero()),
             total_balance_at_end_poll: None,
         },]
@@ -586,6 +606,8 @@ fn query_polls() {
                 execute_data: None,
                 yes_votes: Uint128::zero(),
                 no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
+                veto_votes: Uint128::zero(),
                 staked_amount: Some(Uint128::zero()),
                 total_balance_at_end_poll: None,
             },
@@ -602,6 +624,8 @@ fn query_polls() {
                 execute_data: Some(execute_msgs),
                 yes_votes: Uint128::zero(),
                 no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
+                veto_votes: Uint128::zero(),
                 staked_amount: Some(Uint128::from(DEFAULT_PROPOSAL_DEPOSIT)),
                 total_balance_at_end_poll: None,
             }
@@ -635,6 +659,8 @@ fn query_polls() {
             execute_data: None,
             yes_votes: Uint128::zero(),
             no_votes: Uint128::zero(),
+            abstain_votes: Uint128::zero(),
+            veto_votes: Uint128::zero(),
             staked_amount: Some(Uint128::zero()),
             total_balance_at_end_poll: None,
         },]
@@ -655,6 +681,7 @@ fn query_polls() {
     assert_eq!(response.polls, vec![]);
 }
 
+#[test]
 fn create_poll_no_quorum() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -682,6 +709,7 @@ fn create_poll_no_quorum() {
     );
 }
 
+#[test]
 fn fails_end_poll_before_end_height() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -724,6 +752,7 @@ fn fails_end_poll_before_end_height() {
     }
 }
 
+#[test]
 fn happy_days_end_poll() {
     const POLL_START_HEIGHT: u64 = 1000;
     const POLL_ID: u64 = 1;
@@ -736,7 +765,25 @@ fn happy_days_end_poll() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((stake_amount + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(stake_amount),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(stake_amount),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(stake_amount, 0, stake_amount, 0, execute_res, deps.as_ref());
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(stake_amount + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
 
@@ -762,27 +809,21 @@ fn happy_days_end_poll() {
     let execute_msgs: Vec<PollExecuteMsg> = vec![
         PollExecuteMsg {
             order: 3u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz3.clone(),
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 2u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz2.clone(),
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 1u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz.clone(),
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
     ];
 
@@ -802,29 +843,17 @@ fn happy_days_end_poll() {
     )
     .unwrap();
 
-    assert_create_poll_result(
-        1,
-        creator_env.block.height + DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
-        deps.as_ref(),
-    );
-
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(stake_amount as u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        stake_amount,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        stake_amount,
-        1,
-        execute_res,
-        deps.as_ref(),
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr(
+                "end_height",
+                (creator_env.block.height + DEFAULT_VOTING_PERIOD).to_string()
+            ),
+        ]
     );
 
     let msg = ExecuteMsg::CastVote {
@@ -900,7 +929,7 @@ fn happy_days_end_poll() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from(stake_amount as u128),
+            &Uint128::from(stake_amount),
         )],
     )]);
 
@@ -1065,6 +1094,7 @@ fn happy_days_end_poll() {
     );
 }
 
+#[test]
 fn fail_poll() {
     const POLL_START_HEIGHT: u64 = 1000;
     const POLL_ID: u64 = 1;
@@ -1077,7 +1107,25 @@ fn fail_poll() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((stake_amount + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(stake_amount),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(stake_amount),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(stake_amount, 0, stake_amount, 0, execute_res, deps.as_ref());
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(stake_amount + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
 
@@ -1090,11 +1138,9 @@ fn fail_poll() {
     .unwrap();
     let execute_msgs: Vec<PollExecuteMsg> = vec![PollExecuteMsg {
         order: 1u64,
+        contract: VOTING_TOKEN.to_string(),
         msg: exec_msg_bz.clone(),
-        funding_goal: None,
-        fund_rotation: None,
-        split_to_liquid: None,
-        treasury_tax_rate: None,
+        funds: vec![],
     }];
     let msg = create_poll_msg(
         "test".to_string(),
@@ -1112,29 +1158,17 @@ fn fail_poll() {
     )
     .unwrap();
 
-    assert_create_poll_result(
-        1,
-        creator_env.block.height + DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
-        deps.as_ref(),
-    );
-
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(stake_amount as u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        stake_amount,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        stake_amount,
-        1,
-        execute_res,
-        deps.as_ref(),
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr(
+                "end_height",
+                (creator_env.block.height + DEFAULT_VOTING_PERIOD).to_string()
+            ),
+        ]
     );
 
     let msg = ExecuteMsg::CastVote {
@@ -1257,6 +1291,7 @@ fn fail_poll() {
     assert_eq!(polls_res.polls[0], poll_res);
 }
 
+#[test]
 fn end_poll_zero_quorum() {
     let mut deps = mock_dependencies(&coins(1000, VOTING_TOKEN));
     mock_instantiate(deps.as_mut());
@@ -1275,14 +1310,12 @@ fn end_poll_zero_quorum() {
 
     let execute_msgs: Vec<PollExecuteMsg> = vec![PollExecuteMsg {
         order: 1u64,
+        contract: VOTING_TOKEN.to_string(),
         msg: to_binary(&Cw20ExecuteMsg::Burn {
             amount: Uint128::new(123),
         })
         .unwrap(),
-        funding_goal: None,
-        fund_rotation: None,
-        split_to_liquid: None,
-        treasury_tax_rate: None,
+        funds: vec![],
     }];
 
     let msg = create_poll_msg(
@@ -1379,11 +1412,27 @@ fn end_poll_zero_quorum() {
     assert_eq!(response.polls.len(), 0);
 }
 
+#[test]
 fn end_poll_quorum_rejected() {
     let mut deps = mock_dependencies(&coins(100, VOTING_TOKEN));
     mock_instantiate(deps.as_mut());
     mock_register_voting_token(deps.as_mut());
     let stake_amount = 100;
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100u128))],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(stake_amount),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(stake_amount, 0, stake_amount, 0, execute_res, deps.as_ref());
+
     deps.querier.with_token_balances(&[(
         &VOTING_TOKEN.to_string(),
         &[(
@@ -1412,23 +1461,6 @@ fn end_poll_quorum_rejected() {
         ]
     );
 
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(stake_amount as u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        stake_amount,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        stake_amount,
-        1,
-        execute_res,
-        deps.as_ref(),
-    );
-
     let msg = ExecuteMsg::CastVote {
         poll_id: 1,
         vote: VoteOption::Yes,
@@ -1465,6 +1497,7 @@ fn end_poll_quorum_rejected() {
     );
 }
 
+#[test]
 fn end_poll_quorum_rejected_nothing_staked() {
     let mut deps = mock_dependencies(&coins(100, VOTING_TOKEN));
     mock_instantiate(deps.as_mut());
@@ -1514,6 +1547,7 @@ fn end_poll_quorum_rejected_nothing_staked() {
     );
 }
 
+#[test]
 fn end_poll_nay_rejected() {
     let voter1_stake = 100;
     let voter2_stake = 1000;
@@ -1524,74 +1558,75 @@ fn end_poll_nay_rejected() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((voter1_stake + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(voter1_stake),
         )],
     )]);
 
-    let mut creator_env = mock_env();
-    let mut creator_info = mock_info(VOTING_TOKEN, &coins(2, VOTING_TOKEN));
-
-    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
-
-    let execute_res = execute(
-        deps.as_mut(),
-        creator_env.clone(),
-        creator_info.clone(),
-        msg,
-    )
-    .unwrap();
-    assert_eq!(
-        execute_res.attributes,
-        vec![
-            attr("action", "create_poll"),
-            attr("creator", TEST_CREATOR),
-            attr("poll_id", "1"),
-            attr("end_height", "32345"),
-        ]
-    );
-
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(voter1_stake as u128),
+        amount: Uint128::from(voter1_stake),
         msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
     });
 
     let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        voter1_stake,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        voter1_stake,
-        1,
-        execute_res,
-        deps.as_ref(),
-    );
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(voter1_stake, 0, voter1_stake, 0, execute_res, deps.as_ref());
 
     deps.querier.with_token_balances(&[(
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((voter1_stake + voter2_stake + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(voter1_stake + voter2_stake),
         )],
     )]);
 
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: TEST_VOTER_2.to_string(),
-        amount: Uint128::from(voter2_stake as u128),
+        amount: Uint128::from(voter2_stake),
         msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
     });
 
     let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
     assert_stake_tokens_result(
         voter1_stake + voter2_stake,
-        DEFAULT_PROPOSAL_DEPOSIT,
+        0,
         voter2_stake,
-        1,
+        0,
         execute_res,
         deps.as_ref(),
     );
 
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(voter1_stake + voter2_stake + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+
+    let mut creator_env = mock_env();
+    let mut creator_info = mock_info(VOTING_TOKEN, &coins(2, VOTING_TOKEN));
+
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
+
+    let execute_res = execute(
+        deps.as_mut(),
+        creator_env.clone(),
+        creator_info.clone(),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr("end_height", "32345"),
+        ]
+    );
+
     let info = mock_info(TEST_VOTER_2, &[]);
     let msg = ExecuteMsg::CastVote {
         poll_id: 1,
@@ -1617,6 +1652,7 @@ fn end_poll_nay_rejected() {
     );
 }
 
+#[test]
 fn fails_cast_vote_not_enough_staked() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -1678,31 +1714,16 @@ fn fails_cast_vote_not_enough_staked() {
     }
 }
 
+#[test]
 fn happy_days_cast_vote() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
     mock_register_voting_token(deps.as_mut());
     deps.querier.with_token_balances(&[(
         &VOTING_TOKEN.to_string(),
-        &[(
-            &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from(11u128 + DEFAULT_PROPOSAL_DEPOSIT),
-        )],
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(11u128))],
     )]);
 
-    let env = mock_env_height(0, 10000);
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
-
-    let execute_res = execute(deps.as_mut(), env, info, msg).unwrap();
-    assert_create_poll_result(
-        1,
-        DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
-        deps.as_ref(),
-    );
-
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: TEST_VOTER.to_string(),
         amount: Uint128::from(11u128),
@@ -1710,17 +1731,32 @@ fn happy_days_cast_vote() {
     });
 
     let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(11, 0, 11, 0, execute_res, deps.as_ref());
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(11u128 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
+
     let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        11,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        11,
-        1,
-        execute_res,
-        deps.as_ref(),
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr("end_height", "32345"),
+        ]
     );
 
-    let env = mock_env_height(0, 10000);
+    let env = mock_env();
     let info = mock_info(TEST_VOTER, &coins(11, VOTING_TOKEN));
     let amount = 10u128;
     let msg = ExecuteMsg::CastVote {
@@ -1804,6 +1840,7 @@ fn happy_days_cast_vote() {
     assert_eq!(response.voters.len(), 0);
 }
 
+#[test]
 fn happy_days_withdraw_voting_tokens() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -1846,7 +1883,7 @@ fn happy_days_withdraw_voting_tokens() {
     };
 
     let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    let msg = execute_res.messages.get(0).expect("no message");
+    let msg = execute_res.messages.first().expect("no message");
 
     assert_eq!(
         msg,
@@ -1872,6 +1909,7 @@ fn happy_days_withdraw_voting_tokens() {
     );
 }
 
+#[test]
 fn happy_days_withdraw_voting_tokens_all() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -1914,7 +1952,7 @@ fn happy_days_withdraw_voting_tokens_all() {
     };
 
     let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    let msg = execute_res.messages.get(0).expect("no message");
+    let msg = execute_res.messages.first().expect("no message");
 
     assert_eq!(
         msg,
@@ -1940,6 +1978,7 @@ fn happy_days_withdraw_voting_tokens_all() {
     );
 }
 
+#[test]
 fn withdraw_voting_tokens_remove_not_in_progress_poll_voter_info() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -1970,6 +2009,8 @@ fn withdraw_voting_tokens_remove_not_in_progress_poll_voter_info() {
             status: PollStatus::InProgress,
             yes_votes: Uint128::zero(),
             no_votes: Uint128::zero(),
+            abstain_votes: Uint128::zero(),
+            veto_votes: Uint128::zero(),
             end_height: 0u64,
             title: "title".to_string(),
             description: "description".to_string(),
@@ -1979,6 +2020,8 @@ fn withdraw_voting_tokens_remove_not_in_progress_poll_voter_info() {
             execute_data: None,
             total_balance_at_end_poll: None,
             staked_amount: None,
+            start_height: None,
+            total_share: None,
         },
     )
     .unwrap();
@@ -1992,6 +2035,8 @@ fn withdraw_voting_tokens_remove_not_in_progress_poll_voter_info() {
             status: PollStatus::Passed,
             yes_votes: Uint128::zero(),
             no_votes: Uint128::zero(),
+            abstain_votes: Uint128::zero(),
+            veto_votes: Uint128::zero(),
             end_height: 0u64,
             title: "title".to_string(),
             description: "description".to_string(),
@@ -2001,6 +2046,8 @@ fn withdraw_voting_tokens_remove_not_in_progress_poll_voter_info() {
             execute_data: None,
             total_balance_at_end_poll: None,
             staked_amount: None,
+            start_height: None,
+            total_share: None,
         },
     )
     .unwrap();
@@ -2083,6 +2130,7 @@ fn withdraw_voting_tokens_remove_not_in_progress_poll_voter_info() {
     );
 }
 
+#[test]
 fn fails_withdraw_voting_tokens_no_stake() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -2102,6 +2150,7 @@ fn fails_withdraw_voting_tokens_no_stake() {
     }
 }
 
+#[test]
 fn fails_withdraw_too_many_tokens() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -2136,10 +2185,26 @@ fn fails_withdraw_too_many_tokens() {
     }
 }
 
+#[test]
 fn fails_cast_vote_twice() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
     mock_register_voting_token(deps.as_mut());
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(11u128))],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(11u128),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(11, 0, 11, 0, execute_res, deps.as_ref());
+
     deps.querier.with_token_balances(&[(
         &VOTING_TOKEN.to_string(),
         &[(
@@ -2148,36 +2213,23 @@ fn fails_cast_vote_twice() {
         )],
     )]);
 
-    let env = mock_env_height(0, 10000);
+    let env = mock_env();
     let info = mock_info(VOTING_TOKEN, &coins(2, VOTING_TOKEN));
 
     let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
     let execute_res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-
-    assert_create_poll_result(
-        1,
-        env.block.height + DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
-        deps.as_ref(),
-    );
-
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(11u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        11,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        11,
-        1,
-        execute_res,
-        deps.as_ref(),
-    );
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr(
+                "end_height",
+                (env.block.height + DEFAULT_VOTING_PERIOD).to_string()
+            ),
+        ]
+    );
 
     let amount = 1u128;
     let msg = ExecuteMsg::CastVote {
@@ -2185,7 +2237,6 @@ fn fails_cast_vote_twice() {
         vote: VoteOption::Yes,
         amount: Uint128::from(amount),
     };
-    let env = mock_env_height(0, 10000);
     let info = mock_info(TEST_VOTER, &[]);
     let execute_res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
     assert_cast_vote_success(TEST_VOTER, amount, 1, VoteOption::Yes, execute_res);
@@ -2204,6 +2255,7 @@ fn fails_cast_vote_twice() {
     }
 }
 
+#[test]
 fn fails_cast_vote_without_poll() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -2225,6 +2277,7 @@ fn fails_cast_vote_without_poll() {
     }
 }
 
+#[test]
 fn happy_days_stake_voting_tokens() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -2246,6 +2299,7 @@ fn happy_days_stake_voting_tokens() {
     assert_stake_tokens_result(11, 0, 11, 0, execute_res, deps.as_ref());
 }
 
+#[test]
 fn fails_insufficient_funds() {
     let mut deps = mock_dependencies(&[]);
 
@@ -2270,6 +2324,7 @@ fn fails_insufficient_funds() {
     }
 }
 
+#[test]
 fn fails_staking_wrong_token() {
     let mut deps = mock_dependencies(&[]);
 
@@ -2299,6 +2354,7 @@ fn fails_staking_wrong_token() {
     }
 }
 
+#[test]
 fn share_calculation() {
     let mut deps = mock_dependencies(&[]);
 
@@ -2454,6 +2510,7 @@ fn assert_cast_vote_success(
     );
 }
 
+#[test]
 fn update_config() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -2465,6 +2522,7 @@ fn update_config() {
         owner: Some("addr0001".to_string()),
         quorum: None,
         threshold: None,
+        veto_threshold: None,
         voting_period: None,
         timelock_period: None,
         proposal_deposit: None,
@@ -2492,6 +2550,7 @@ fn update_config() {
         owner: None,
         quorum: Some(20),
         threshold: Some(75),
+        veto_threshold: None,
         voting_period: Some(20000u64),
         timelock_period: Some(20000u64),
         proposal_deposit: Some(Uint128::from(123u128)),
@@ -2520,6 +2579,7 @@ fn update_config() {
         owner: None,
         quorum: None,
         threshold: None,
+        veto_threshold: None,
         voting_period: None,
         timelock_period: None,
         proposal_deposit: None,
@@ -2535,6 +2595,7 @@ fn update_config() {
     }
 }
 
+#[test]
 fn add_several_execute_msgs() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
@@ -2569,27 +2630,21 @@ fn add_several_execute_msgs() {
     let execute_msgs: Vec<PollExecuteMsg> = vec![
         PollExecuteMsg {
             order: 1u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz,
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 3u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz3,
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 2u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz2,
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
     ];
 
@@ -2618,6 +2673,7 @@ fn add_several_execute_msgs() {
     assert_eq!(response_execute_data, execute_msgs);
 }
 
+#[test]
 fn execute_poll_with_order() {
     const POLL_START_HEIGHT: u64 = 1000;
     const POLL_ID: u64 = 1;
@@ -2630,7 +2686,25 @@ fn execute_poll_with_order() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((stake_amount + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(stake_amount),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(stake_amount),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(stake_amount, 0, stake_amount, 0, execute_res, deps.as_ref());
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(stake_amount + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
 
@@ -2664,43 +2738,33 @@ fn execute_poll_with_order() {
     let execute_msgs: Vec<PollExecuteMsg> = vec![
         PollExecuteMsg {
             order: 3u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz3.clone(),
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 4u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz4.clone(),
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 2u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz2.clone(),
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 5u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz5.clone(),
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 1u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz.clone(),
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
     ];
 
@@ -2720,29 +2784,17 @@ fn execute_poll_with_order() {
     )
     .unwrap();
 
-    assert_create_poll_result(
-        1,
-        creator_env.block.height + DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
-        deps.as_ref(),
-    );
-
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(stake_amount as u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        stake_amount,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        stake_amount,
-        1,
-        execute_res,
-        deps.as_ref(),
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr(
+                "end_height",
+                (creator_env.block.height + DEFAULT_VOTING_PERIOD).to_string()
+            ),
+        ]
     );
 
     let msg = ExecuteMsg::CastVote {
@@ -2804,7 +2856,7 @@ fn execute_poll_with_order() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from(stake_amount as u128),
+            &Uint128::from(stake_amount),
         )],
     )]);
 
@@ -2862,6 +2914,7 @@ fn execute_poll_with_order() {
     );
 }
 
+#[test]
 fn poll_with_empty_execute_data_marked_as_executed() {
     const POLL_START_HEIGHT: u64 = 1000;
     const POLL_ID: u64 = 1;
@@ -2874,7 +2927,25 @@ fn poll_with_empty_execute_data_marked_as_executed() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((stake_amount + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(stake_amount),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(stake_amount),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(stake_amount, 0, stake_amount, 0, execute_res, deps.as_ref());
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(stake_amount + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
 
@@ -2897,29 +2968,17 @@ fn poll_with_empty_execute_data_marked_as_executed() {
     )
     .unwrap();
 
-    assert_create_poll_result(
-        1,
-        creator_env.block.height + DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
-        deps.as_ref(),
-    );
-
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(stake_amount as u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        stake_amount,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        stake_amount,
-        1,
-        execute_res,
-        deps.as_ref(),
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr(
+                "end_height",
+                (creator_env.block.height + DEFAULT_VOTING_PERIOD).to_string()
+            ),
+        ]
     );
 
     let msg = ExecuteMsg::CastVote {
@@ -2981,7 +3040,7 @@ fn poll_with_empty_execute_data_marked_as_executed() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from(stake_amount as u128),
+            &Uint128::from(stake_amount),
         )],
     )]);
 
@@ -3029,6 +3088,7 @@ fn poll_with_empty_execute_data_marked_as_executed() {
     assert_eq!(polls_res.polls[0], poll_res);
 }
 
+#[test]
 fn poll_with_none_execute_data_marked_as_executed() {
     const POLL_START_HEIGHT: u64 = 1000;
     const POLL_ID: u64 = 1;
@@ -3041,7 +3101,25 @@ fn poll_with_none_execute_data_marked_as_executed() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((stake_amount + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(stake_amount),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(stake_amount),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(stake_amount, 0, stake_amount, 0, execute_res, deps.as_ref());
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(stake_amount + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
 
@@ -3058,29 +3136,17 @@ fn poll_with_none_execute_data_marked_as_executed() {
     )
     .unwrap();
 
-    assert_create_poll_result(
-        1,
-        creator_env.block.height + DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
-        deps.as_ref(),
-    );
-
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(stake_amount as u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        stake_amount,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        stake_amount,
-        1,
-        execute_res,
-        deps.as_ref(),
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr(
+                "end_height",
+                (creator_env.block.height + DEFAULT_VOTING_PERIOD).to_string()
+            ),
+        ]
     );
 
     let msg = ExecuteMsg::CastVote {
@@ -3142,7 +3208,7 @@ fn poll_with_none_execute_data_marked_as_executed() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from(stake_amount as u128),
+            &Uint128::from(stake_amount),
         )],
     )]);
 
@@ -3190,50 +3256,45 @@ fn poll_with_none_execute_data_marked_as_executed() {
     assert_eq!(polls_res.polls[0], poll_res);
 }
 
+#[test]
 fn happy_days_cast_vote_with_snapshot() {
     let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
     mock_register_voting_token(deps.as_mut());
+
+    let mut total_staked = 0u128;
+    for voter in [TEST_VOTER, TEST_VOTER_2, TEST_VOTER_3] {
+        total_staked += 11;
+        deps.querier.with_token_balances(&[(
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(total_staked),
+            )],
+        )]);
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: voter.to_string(),
+            amount: Uint128::from(11u128),
+            msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+        });
+        let info = mock_info(VOTING_TOKEN, &[]);
+        let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+        assert_stake_tokens_result(total_staked, 0, 11, 0, execute_res, deps.as_ref());
+    }
+
     deps.querier.with_token_balances(&[(
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from(11u128 + DEFAULT_PROPOSAL_DEPOSIT),
+            &Uint128::from(33u128 + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
 
-    let env = mock_env_height(0, 10000);
     let info = mock_info(VOTING_TOKEN, &[]);
     let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
-
-    let execute_res = execute(deps.as_mut(), env, info, msg).unwrap();
-    assert_create_poll_result(
-        1,
-        DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
-        deps.as_ref(),
-    );
-
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(11u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        11,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        11,
-        1,
-        execute_res,
-        deps.as_ref(),
-    );
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     //cast_vote without snapshot
-    let env = mock_env_height(0, 10000);
     let info = mock_info(TEST_VOTER, &coins(11, VOTING_TOKEN));
     let amount = 10u128;
 
@@ -3243,7 +3304,7 @@ fn happy_days_cast_vote_with_snapshot() {
         amount: Uint128::from(amount),
     };
 
-    let execute_res = execute(deps.as_mut(), env, info, msg).unwrap();
+    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     assert_cast_vote_success(TEST_VOTER, amount, 1, VoteOption::Yes, execute_res);
 
     // balance be double
@@ -3251,25 +3312,15 @@ fn happy_days_cast_vote_with_snapshot() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from(22u128 + DEFAULT_PROPOSAL_DEPOSIT),
+            &Uint128::from(66u128 + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
     let value: PollResponse = from_binary(&res).unwrap();
-    assert_eq!(value.staked_amount, Some(Uint128::from(11u128)));
+    assert_eq!(value.staked_amount, Some(Uint128::from(33u128)));
     let end_height = value.end_height;
 
-    //cast another vote
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER_2.to_string(),
-        amount: Uint128::from(11u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let _execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
     // another voter cast a vote
     let msg = ExecuteMsg::CastVote {
         poll_id: 1,
@@ -3283,26 +3334,9 @@ fn happy_days_cast_vote_with_snapshot() {
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
     let value: PollResponse = from_binary(&res).unwrap();
-    assert_eq!(value.staked_amount, Some(Uint128::new(11)));
-
-    // balance be double
-    deps.querier.with_token_balances(&[(
-        &VOTING_TOKEN.to_string(),
-        &[(
-            &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from(33u128 + DEFAULT_PROPOSAL_DEPOSIT),
-        )],
-    )]);
+    assert_eq!(value.staked_amount, Some(Uint128::new(33)));
 
     // another voter cast a vote but the snapshot is already occurred
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER_3.to_string(),
-        amount: Uint128::from(11u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let _execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     let msg = ExecuteMsg::CastVote {
         poll_id: 1,
         vote: VoteOption::Yes,
@@ -3315,29 +3349,204 @@ fn happy_days_cast_vote_with_snapshot() {
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
     let value: PollResponse = from_binary(&res).unwrap();
-    assert_eq!(value.staked_amount, Some(Uint128::new(11)));
+    assert_eq!(value.staked_amount, Some(Uint128::new(33)));
 }
 
-fn fails_end_poll_quorum_inflation_without_snapshot_poll() {
-    const POLL_START_HEIGHT: u64 = 1000;
-    const POLL_ID: u64 = 1;
-    let stake_amount = 1000;
-
-    let mut deps = mock_dependencies(&coins(1000, VOTING_TOKEN));
+#[test]
+fn cast_vote_with_stake_at_poll_start() {
+    let mut deps = mock_dependencies(&[]);
     mock_instantiate(deps.as_mut());
     mock_register_voting_token(deps.as_mut());
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(10u128))],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(10u128),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+    let info = mock_info(VOTING_TOKEN, &[]);
+    execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+
     deps.querier.with_token_balances(&[(
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((stake_amount + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(10u128 + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    let mut creator_env = mock_env_height(POLL_START_HEIGHT, 10000);
-    let mut creator_info = mock_info(VOTING_TOKEN, &coins(2, VOTING_TOKEN));
-
-    let exec_msg_bz = to_binary(&Cw20ExecuteMsg::Burn {
+    // stake added once the poll started does not count towards its votes
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(20u128 + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER_2.to_string(),
+        amount: Uint128::from(10u128),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+    let info = mock_info(VOTING_TOKEN, &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(1u128),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientStaked {});
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(11u128),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientStaked {});
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(10u128),
+    };
+    let execute_res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    assert_cast_vote_success(TEST_VOTER, 10, 1, VoteOption::Yes, execute_res);
+
+    // later polls vote with the added stake
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
+    let mut env = mock_env();
+    env.block.height += 1;
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(20u128 + 2 * DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(VOTING_TOKEN, &[]),
+        msg,
+    )
+    .unwrap();
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 2,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(10u128),
+    };
+    let execute_res = execute(deps.as_mut(), env, mock_info(TEST_VOTER_2, &[]), msg).unwrap();
+    assert_cast_vote_success(TEST_VOTER_2, 10, 2, VoteOption::Yes, execute_res);
+}
+
+#[test]
+fn stake_in_poll_start_block_is_left_out_of_snapshot() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    // voter1 stakes in an earlier block, voter2 in the block the poll is created in
+    let mut total_staked = 0u128;
+    for (voter, env) in [
+        (TEST_VOTER, mock_env_height(0, 10000)),
+        (TEST_VOTER_2, mock_env()),
+    ] {
+        total_staked += 10;
+        deps.querier.with_token_balances(&[(
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(total_staked),
+            )],
+        )]);
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: voter.to_string(),
+            amount: Uint128::from(10u128),
+            msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+        });
+        execute(deps.as_mut(), env, mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+    }
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(total_staked + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
+    execute(deps.as_mut(), mock_env(), mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+
+    // the staked amount & the total share are both taken at the start of the block
+    let poll = read_poll(&deps.storage, &1u64.to_be_bytes()).unwrap();
+    assert_eq!(poll.total_share, Some(Uint128::from(10u128)));
+    assert_eq!(poll.staked_amount, Some(Uint128::from(10u128)));
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(11u128),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientStaked {});
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(10u128),
+    };
+    let execute_res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    assert_cast_vote_success(TEST_VOTER, 10, 1, VoteOption::Yes, execute_res);
+}
+
+#[test]
+fn fails_end_poll_quorum_inflation_without_snapshot_poll() {
+    const POLL_START_HEIGHT: u64 = 1000;
+    const POLL_ID: u64 = 1;
+    let stake_amount = 1000;
+
+    let mut deps = mock_dependencies(&coins(1000, VOTING_TOKEN));
+    mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(stake_amount),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(stake_amount),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(stake_amount, 0, stake_amount, 0, execute_res, deps.as_ref());
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(stake_amount + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+
+    let mut creator_env = mock_env_height(POLL_START_HEIGHT, 10000);
+    let mut creator_info = mock_info(VOTING_TOKEN, &coins(2, VOTING_TOKEN));
+
+    let exec_msg_bz = to_binary(&Cw20ExecuteMsg::Burn {
         amount: Uint128::new(123),
     })
     .unwrap();
@@ -3346,19 +3555,15 @@ fn fails_end_poll_quorum_inflation_without_snapshot_poll() {
     let execute_msgs: Vec<PollExecuteMsg> = vec![
         PollExecuteMsg {
             order: 1u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz.clone(),
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 2u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz,
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
     ];
 
@@ -3378,29 +3583,17 @@ fn fails_end_poll_quorum_inflation_without_snapshot_poll() {
     )
     .unwrap();
 
-    assert_create_poll_result(
-        1,
-        creator_env.block.height + DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
-        deps.as_ref(),
-    );
-
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(stake_amount as u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        stake_amount,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        stake_amount,
-        1,
-        execute_res,
-        deps.as_ref(),
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr(
+                "end_height",
+                (creator_env.block.height + DEFAULT_VOTING_PERIOD).to_string()
+            ),
+        ]
     );
 
     let msg = ExecuteMsg::CastVote {
@@ -3430,21 +3623,21 @@ fn fails_end_poll_quorum_inflation_without_snapshot_poll() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from(((10 * stake_amount) + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from((10 * stake_amount) + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
 
     //cast another vote
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: TEST_VOTER_2.to_string(),
-        amount: Uint128::from(8 * stake_amount as u128),
+        amount: Uint128::from(8 * stake_amount),
         msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
     });
 
     let info = mock_info(VOTING_TOKEN, &[]);
     let _execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    // another voter cast a vote
+    // another voter cannot vote with the stake added after the poll start
     let msg = ExecuteMsg::CastVote {
         poll_id: 1,
         vote: VoteOption::Yes,
@@ -3452,18 +3645,8 @@ fn fails_end_poll_quorum_inflation_without_snapshot_poll() {
     };
     let env = mock_env_height(creator_env.block.height, 10000);
     let info = mock_info(TEST_VOTER_2, &[]);
-    let execute_res = execute(deps.as_mut(), env, info, msg).unwrap();
-
-    assert_eq!(
-        execute_res.attributes,
-        vec![
-            attr("action", "cast_vote"),
-            attr("poll_id", POLL_ID.to_string().as_str()),
-            attr("amount", "1000"),
-            attr("voter", TEST_VOTER_2),
-            attr("vote_option", "yes"),
-        ]
-    );
+    let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientStaked {});
 
     creator_info.sender = Addr::unchecked(TEST_CREATOR);
     creator_env.block.height += 10;
@@ -3490,6 +3673,7 @@ fn fails_end_poll_quorum_inflation_without_snapshot_poll() {
     );
 }
 
+#[test]
 fn happy_days_end_poll_with_controlled_quorum() {
     const POLL_START_HEIGHT: u64 = 1000;
     const POLL_ID: u64 = 1;
@@ -3502,7 +3686,25 @@ fn happy_days_end_poll_with_controlled_quorum() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from((stake_amount + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from(stake_amount),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_VOTER.to_string(),
+        amount: Uint128::from(stake_amount),
+        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+    });
+
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let execute_res = execute(deps.as_mut(), mock_env_height(0, 10000), info, msg).unwrap();
+    assert_stake_tokens_result(stake_amount, 0, stake_amount, 0, execute_res, deps.as_ref());
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(stake_amount + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
 
@@ -3518,19 +3720,15 @@ fn happy_days_end_poll_with_controlled_quorum() {
     let execute_msgs: Vec<PollExecuteMsg> = vec![
         PollExecuteMsg {
             order: 1u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz.clone(),
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
         PollExecuteMsg {
             order: 2u64,
+            contract: VOTING_TOKEN.to_string(),
             msg: exec_msg_bz,
-            funding_goal: None,
-            fund_rotation: None,
-            split_to_liquid: None,
-            treasury_tax_rate: None,
+            funds: vec![],
         },
     ];
 
@@ -3550,29 +3748,17 @@ fn happy_days_end_poll_with_controlled_quorum() {
     )
     .unwrap();
 
-    assert_create_poll_result(
-        1,
-        creator_env.block.height + DEFAULT_VOTING_PERIOD,
-        TEST_CREATOR,
-        execute_res,
-        deps.as_ref(),
-    );
-
-    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
-        sender: TEST_VOTER.to_string(),
-        amount: Uint128::from(stake_amount as u128),
-        msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
-    });
-
-    let info = mock_info(VOTING_TOKEN, &[]);
-    let execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    assert_stake_tokens_result(
-        stake_amount,
-        DEFAULT_PROPOSAL_DEPOSIT,
-        stake_amount,
-        1,
-        execute_res,
-        deps.as_ref(),
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr(
+                "end_height",
+                (creator_env.block.height + DEFAULT_VOTING_PERIOD).to_string()
+            ),
+        ]
     );
 
     let msg = ExecuteMsg::CastVote {
@@ -3602,20 +3788,21 @@ fn happy_days_end_poll_with_controlled_quorum() {
         &VOTING_TOKEN.to_string(),
         &[(
             &MOCK_CONTRACT_ADDR.to_string(),
-            &Uint128::from(((10 * stake_amount) + DEFAULT_PROPOSAL_DEPOSIT) as u128),
+            &Uint128::from((10 * stake_amount) + DEFAULT_PROPOSAL_DEPOSIT),
         )],
     )]);
 
     //cast another vote
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: TEST_VOTER_2.to_string(),
-        amount: Uint128::from(8 * stake_amount as u128),
+        amount: Uint128::from(8 * stake_amount),
         msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
     });
 
     let info = mock_info(VOTING_TOKEN, &[]);
     let _execute_res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
+    // the stake added after the poll start cannot vote on it
     let msg = ExecuteMsg::CastVote {
         poll_id: 1,
         vote: VoteOption::Yes,
@@ -3623,18 +3810,8 @@ fn happy_days_end_poll_with_controlled_quorum() {
     };
     let env = mock_env_height(creator_env.block.height, 10000);
     let info = mock_info(TEST_VOTER_2, &[]);
-    let execute_res = execute(deps.as_mut(), env, info, msg).unwrap();
-
-    assert_eq!(
-        execute_res.attributes,
-        vec![
-            attr("action", "cast_vote"),
-            attr("poll_id", POLL_ID.to_string().as_str()),
-            attr("amount", "8000"),
-            attr("voter", TEST_VOTER_2),
-            attr("vote_option", "yes"),
-        ]
-    );
+    let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientStaked {});
 
     creator_info.sender = Addr::unchecked(TEST_CREATOR);
     creator_env.block.height += 10;
@@ -3672,7 +3849,7 @@ fn happy_days_end_poll_with_controlled_quorum() {
         value.total_balance_at_end_poll.unwrap().u128()
     );
 
-    assert_eq!(value.yes_votes.u128(), 9 * stake_amount);
+    assert_eq!(value.yes_votes.u128(), stake_amount);
 
     // actual staked amount is 10 times bigger than staked amount
     let actual_staked_weight = query_token_balance(
@@ -3686,3 +3863,442 @@ fn happy_days_end_poll_with_controlled_quorum() {
 
     assert_eq!(actual_staked_weight.u128(), (10 * stake_amount))
 }
+
+#[test]
+fn migrate_drops_deprecated_poll_execute_data() {
+    let mut deps = mock_dependencies(&[]);
+    let exec_msg_bz = to_binary(&Cw20ExecuteMsg::Burn {
+        amount: Uint128::new(123),
+    })
+    .unwrap();
+
+    let old_poll = OldPoll {
+        id: 1u64,
+        creator: Addr::unchecked(TEST_CREATOR),
+        status: PollStatus::InProgress,
+        yes_votes: Uint128::zero(),
+        no_votes: Uint128::zero(),
+        end_height: DEFAULT_VOTING_PERIOD,
+        title: "test".to_string(),
+        description: "test".to_string(),
+        link: None,
+        proposal_type: Some("registrar".to_string()),
+        execute_data: Some(vec![OldExecuteData {
+            order: 1u64,
+            contract: Addr::unchecked(REGISTRAR_CONTRACT),
+            msg: exec_msg_bz.clone(),
+            funding_goal: Some(Uint128::new(1000)),
+            fund_rotation: Some(10u64),
+            split_to_liquid: Some(Decimal::percent(50)),
+            treasury_tax_rate: Some(Decimal::percent(20)),
+        }]),
+        deposit_amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+        total_balance_at_end_poll: None,
+        staked_amount: None,
+    };
+    store_state(
+        deps.as_mut().storage,
+        &State {
+            poll_count: 1,
+            total_share: Uint128::zero(),
+            total_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+        },
+    )
+    .unwrap();
+    OLD_POLL
+        .save(deps.as_mut().storage, &1u64.to_be_bytes(), &old_poll)
+        .unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let poll = read_poll(deps.as_ref().storage, &1u64.to_be_bytes()).unwrap();
+    let execute_data = poll.execute_data.unwrap();
+    assert_eq!(execute_data.len(), 1);
+    assert_eq!(execute_data[0].order, 1u64);
+    assert_eq!(
+        execute_data[0].contract,
+        Addr::unchecked(REGISTRAR_CONTRACT)
+    );
+    assert_eq!(execute_data[0].msg, exec_msg_bz);
+    assert!(execute_data[0].funds.is_empty());
+    assert_eq!(poll.proposal_type, Some("registrar".to_string()));
+
+    // a second migration finds the polls already converted
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let poll = read_poll(deps.as_ref().storage, &1u64.to_be_bytes()).unwrap();
+    assert!(poll.execute_data.unwrap()[0].funds.is_empty());
+}
+
+#[test]
+fn delegate_voting_power() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+
+    // stake 10, 20 & 5 tokens from voter1, voter2 & voter3
+    let mut total_staked = 0u128;
+    for (voter, amount) in [(TEST_VOTER, 10u128), (TEST_VOTER_2, 20), (TEST_VOTER_3, 5)] {
+        total_staked += amount;
+        deps.querier.with_token_balances(&[(
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(total_staked),
+            )],
+        )]);
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: voter.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+        });
+        let env = mock_env_height(0, 10000);
+        execute(deps.as_mut(), env, mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+    }
+
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(total_staked + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // cannot delegate to yourself
+    let msg = ExecuteMsg::Delegate {
+        to: TEST_VOTER_2.to_string(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::SelfDelegation {});
+
+    // nothing to delegate without stake
+    let msg = ExecuteMsg::Delegate {
+        to: TEST_VOTER.to_string(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::NothingStaked {});
+
+    for delegator in [TEST_VOTER_2, TEST_VOTER_3] {
+        let msg = ExecuteMsg::Delegate {
+            to: TEST_VOTER.to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(delegator, &[]), msg).unwrap();
+    }
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Delegation {
+            address: TEST_VOTER_2.to_string(),
+        },
+    )
+    .unwrap();
+    let response: DelegationResponse = from_binary(&res).unwrap();
+    assert_eq!(response.delegate, Some(TEST_VOTER.to_string()));
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Delegators {
+            address: TEST_VOTER.to_string(),
+            start_after: None,
+            limit: None,
+            order_by: Some(OrderBy::Asc),
+        },
+    )
+    .unwrap();
+    let response: DelegatorsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        response.delegators,
+        vec![
+            DelegatorsResponseItem {
+                delegator: TEST_VOTER_2.to_string(),
+                balance: Uint128::from(20u128),
+            },
+            DelegatorsResponseItem {
+                delegator: TEST_VOTER_3.to_string(),
+                balance: Uint128::from(5u128),
+            },
+        ]
+    );
+
+    // the delegate can vote with its own & the delegated stake, but no more
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(36u128),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientStaked {});
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::from(35u128),
+    };
+    let execute_res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    assert!(execute_res
+        .attributes
+        .contains(&attr("delegated_amount", "25")));
+
+    // the delegated stake is locked on the delegators
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Staker {
+            address: TEST_VOTER_2.to_string(),
+        },
+    )
+    .unwrap();
+    let response: StakerResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        response.locked_balance,
+        vec![(
+            1u64,
+            VoterInfo {
+                vote: VoteOption::Yes,
+                balance: Uint128::from(20u128),
+            }
+        )]
+    );
+    let msg = ExecuteMsg::WithdrawVotingTokens {
+        amount: Some(Uint128::from(1u128)),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidWithdrawAmount {});
+
+    // voter2 overrides its delegate by voting directly
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::No,
+        amount: Uint128::from(20u128),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let response: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(response.yes_votes, Uint128::from(15u128));
+    assert_eq!(response.no_votes, Uint128::from(20u128));
+    assert_eq!(
+        read_poll_voter(&deps.storage, 1, Addr::unchecked(TEST_VOTER))
+            .unwrap()
+            .balance,
+        Uint128::from(15u128)
+    );
+    let token_manager = read_bank(&deps.storage, &TEST_VOTER_2.as_bytes())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        token_manager.locked_balance,
+        vec![(
+            1u64,
+            VoterInfo {
+                vote: VoteOption::No,
+                balance: Uint128::from(20u128),
+            }
+        )]
+    );
+
+    // voter3's stake was already voted by voter1, so a new delegate cannot vote with it again
+    let msg = ExecuteMsg::Delegate {
+        to: TEST_CREATOR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_3, &[]), msg).unwrap();
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::No,
+        amount: Uint128::from(5u128),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientStaked {});
+
+    // undelegate
+    let msg = ExecuteMsg::Undelegate {};
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_3, &[]), msg).unwrap();
+    let msg = ExecuteMsg::Undelegate {};
+    let err = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_3, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::NothingDelegated {});
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Delegation {
+            address: TEST_VOTER_3.to_string(),
+        },
+    )
+    .unwrap();
+    let response: DelegationResponse = from_binary(&res).unwrap();
+    assert_eq!(response.delegate, None);
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Delegators {
+            address: TEST_VOTER.to_string(),
+            start_after: None,
+            limit: None,
+            order_by: None,
+        },
+    )
+    .unwrap();
+    let response: DelegatorsResponse = from_binary(&res).unwrap();
+    assert_eq!(response.delegators.len(), 1);
+}
+
+#[test]
+fn fails_delegate_over_max_delegators() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+
+    for i in 0..=MAX_DELEGATORS {
+        let delegator = format!("delegator{:03}", i);
+        store_bank(
+            &mut deps.storage,
+            &delegator.as_bytes(),
+            &TokenManager {
+                share: Uint128::from(1u128),
+                locked_balance: vec![],
+            },
+        )
+        .unwrap();
+        let msg = ExecuteMsg::Delegate {
+            to: TEST_VOTER.to_string(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(&delegator, &[]), msg);
+        if i < MAX_DELEGATORS {
+            res.unwrap();
+        } else {
+            assert_eq!(res.unwrap_err(), ContractError::TooManyDelegators {});
+        }
+    }
+}
+
+#[test]
+fn end_poll_with_abstain_and_veto_votes() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_voting_token(deps.as_mut());
+    let mut total_staked = 0u128;
+    for (voter, amount) in [(TEST_VOTER, 600u128), (TEST_VOTER_2, 400)] {
+        total_staked += amount;
+        deps.querier.with_token_balances(&[(
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(total_staked),
+            )],
+        )]);
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: voter.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::StakeVotingTokens {}).unwrap(),
+        });
+        let env = mock_env_height(0, 10000);
+        execute(deps.as_mut(), env, mock_info(VOTING_TOKEN, &[]), msg).unwrap();
+    }
+
+    let creator_env = mock_env();
+    for poll_id in 1..=2 {
+        deps.querier.with_token_balances(&[(
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(total_staked + poll_id * DEFAULT_PROPOSAL_DEPOSIT),
+            )],
+        )]);
+        let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None, None);
+        execute(
+            deps.as_mut(),
+            creator_env.clone(),
+            mock_info(VOTING_TOKEN, &[]),
+            msg,
+        )
+        .unwrap();
+    }
+
+    // poll 1 only reaches the quorum with the abstain votes
+    // poll 2 is vetoed by half of the tallied weight
+    for (poll_id, voter, vote, amount) in [
+        (1u64, TEST_VOTER, VoteOption::Abstain, 600u128),
+        (1u64, TEST_VOTER_2, VoteOption::Yes, 200u128),
+        (2u64, TEST_VOTER, VoteOption::Veto, 400u128),
+        (2u64, TEST_VOTER_2, VoteOption::Yes, 400u128),
+    ] {
+        let msg = ExecuteMsg::CastVote {
+            poll_id,
+            vote: vote.clone(),
+            amount: Uint128::from(amount),
+        };
+        let execute_res = execute(
+            deps.as_mut(),
+            creator_env.clone(),
+            mock_info(voter, &[]),
+            msg,
+        )
+        .unwrap();
+        assert_cast_vote_success(voter, amount, poll_id, vote, execute_res);
+    }
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let response: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(response.yes_votes, Uint128::from(200u128));
+    assert_eq!(response.abstain_votes, Uint128::from(600u128));
+    assert_eq!(response.veto_votes, Uint128::zero());
+
+    let mut end_env = creator_env.clone();
+    end_env.block.height += DEFAULT_VOTING_PERIOD;
+
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    let execute_res = execute(
+        deps.as_mut(),
+        end_env.clone(),
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", ""),
+            attr("passed", "true"),
+        ]
+    );
+
+    // the deposit of poll 1 is refunded
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(total_staked + DEFAULT_PROPOSAL_DEPOSIT),
+        )],
+    )]);
+
+    let msg = ExecuteMsg::EndPoll { poll_id: 2 };
+    let execute_res = execute(deps.as_mut(), end_env, mock_info(TEST_CREATOR, &[]), msg).unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("poll_id", "2"),
+            attr("rejected_reason", "Vetoed"),
+            attr("passed", "false"),
+        ]
+    );
+    assert_eq!(
+        execute_res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let state = read_state(&deps.storage).unwrap();
+    assert_eq!(state.total_deposit, Uint128::zero());
+}
@@ -3,14 +3,14 @@ use crate::state::{
 };
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, Uint128, WasmMsg,
+    to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
 use halo_token::common::OrderBy;
 use halo_token::vesting::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, VestingAccount,
-    VestingAccountResponse, VestingAccountsResponse, VestingInfo,
+    assert_vesting_schedules, ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    VestingAccount, VestingAccountResponse, VestingAccountsResponse, VestingInfo,
 };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -84,18 +84,6 @@ pub fn update_config(
     Ok(Response::new().add_attributes(vec![("action", "update_config")]))
 }
 
-fn assert_vesting_schedules(vesting_schedules: &[(u64, u64, Uint128)]) -> StdResult<()> {
-    for vesting_schedule in vesting_schedules.iter() {
-        if vesting_schedule.0 >= vesting_schedule.1 {
-            return Err(StdError::generic_err(
-                "end_time must greater than start_time",
-            ));
-        }
-    }
-
-    Ok(())
-}
-
 pub fn register_vesting_accounts(
     deps: DepsMut,
     info: MessageInfo,
@@ -185,7 +173,7 @@ pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response>
     let config: Config = read_config(deps.storage)?;
     let mut vesting_info: VestingInfo = read_vesting_info(deps.storage, &address)?;
 
-    let claim_amount = compute_claim_amount(current_time, &vesting_info);
+    let claim_amount = vesting_info.compute_claim_amount(current_time);
     let messages: Vec<CosmosMsg> = if claim_amount.is_zero() {
         vec![]
     } else {
@@ -210,27 +198,6 @@ pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response>
     ]))
 }
 
-fn compute_claim_amount(current_time: u64, vesting_info: &VestingInfo) -> Uint128 {
-    let mut claimable_amount: Uint128 = Uint128::zero();
-    for s in vesting_info.schedules.iter() {
-        if s.0 > current_time || s.1 < vesting_info.last_claim_time {
-            continue;
-        }
-
-        // min(s.1, current_time) - max(s.0, last_claim_time)
-        let passed_time =
-            std::cmp::min(s.1, current_time) - std::cmp::max(s.0, vesting_info.last_claim_time);
-
-        // prevent zero time_period case
-        let time_period = s.1 - s.0;
-        let release_amount_per_time: Decimal = Decimal::from_ratio(s.2, time_period);
-
-        claimable_amount += Uint128::from(passed_time as u128) * release_amount_per_time;
-    }
-
-    claimable_amount
-}
-
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -145,6 +145,10 @@ fn execute_donor_match(
     if endow_detail.status != EndowmentStatus::Approved {
         return Err(ContractError::Unauthorized {});
     }
+    let endowment_dao = endow_settings
+        .dao
+        .clone()
+        .ok_or(ContractError::EndowmentDaoNotSet {})?;
 
     // Validation 2. Check if the correct endowment is calling this entry
     match endow_detail.endow_type {
@@ -210,7 +214,7 @@ fn execute_donor_match(
             msg: to_binary(&DaoTokenHookMsg::DonorMatch {
                 amount: reserve_token_amount,
                 donor,
-                accounts_contract,
+                endowment_dao: endowment_dao.to_string(),
                 endowment_id,
             })?,
         })?,
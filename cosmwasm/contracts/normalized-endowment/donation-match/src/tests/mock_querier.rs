@@ -134,6 +134,7 @@ impl WasmMockQuerier {
                         image: None,
                         logo: None,
                         referral_id: None,
+                        parent: None,
                     }).unwrap()
                 )),
                 QueryMsg::Simulation { offer_asset: _ } => SystemResult::Ok(ContractResult::Ok(
@@ -159,6 +160,7 @@ impl WasmMockQuerier {
                                 registrar_contract: "registrar-contract".to_string(),
                                 next_account_id: 2,
                                 max_general_category_id: 1,
+                                referral: None,
                             }).unwrap())),
                         "registrar-contract" => SystemResult::Ok(ContractResult::Ok(
                             to_binary(&RegistrarConfigResponse {
@@ -225,7 +227,7 @@ impl WasmMockQuerier {
                 )),
                 QueryMsg::EndowmentSettings { id: _ } => SystemResult::Ok(ContractResult::Ok(
                     to_binary(&EndowmentSettingsResponse {
-                        dao: None,
+                        dao: Some(Addr::unchecked("endowment-dao")),
                         dao_token: None,
                         donation_match_active: false,
                         donation_match_contract: Some(Addr::unchecked("donation-match-contract")),
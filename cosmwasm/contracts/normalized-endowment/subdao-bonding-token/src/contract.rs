@@ -1,6 +1,6 @@
 use cosmwasm_std::{
-    attr, entry_point, from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+    attr, entry_point, from_binary, to_binary, Addr, Api, Binary, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
@@ -15,10 +15,12 @@ use crate::state::{
     Config, CurveState, MinterData, TokenInfo, BALANCES, CLAIMS, CONFIG, CURVE_STATE, CURVE_TYPE,
     TOKEN_INFO,
 };
-use angel_core::curves::DecimalPlaces;
+use angel_core::curves::{Curve, DecimalPlaces};
 use angel_core::errors::core::ContractError;
+use angel_core::msgs::accounts::{DepositMsg, ReceiveMsg as AccountsReceiveMsg};
 use angel_core::msgs::subdao_bonding_token::{
     CurveFn, CurveInfoResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+    SimulateBuyResponse, SimulateSellResponse, TradingFees,
 };
 use angel_core::utils::{must_pay, nonpayable};
 
@@ -36,6 +38,15 @@ pub fn instantiate(
     nonpayable(&info)?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    let donor_match_split = msg.donor_match_split.unwrap_or_default();
+    if donor_match_split.donor + donor_match_split.endowment + donor_match_split.burn
+        != Decimal::one()
+    {
+        return Err(ContractError::InvalidInputs {});
+    }
+    validate_trading_fees(deps.api, &msg.fees)?;
+    msg.curve_type.validate()?;
+
     // store token info using cw20 standard format
     TOKEN_INFO.save(
         deps.storage,
@@ -56,6 +67,9 @@ pub fn instantiate(
         deps.storage,
         &Config {
             unbonding_period: Duration::Time(msg.unbonding_period), // secconds of unbonding
+            donor_match_split,
+            owner: Some(info.sender),
+            fees: msg.fees,
         },
     )?;
 
@@ -85,16 +99,17 @@ pub fn receive_cw20(
 
     let token_holder_address = deps.api.addr_validate(&cw20_msg.sender)?;
     match from_binary(&cw20_msg.msg) {
-        Ok(Cw20HookMsg::Buy {}) => execute_buy_cw20(
+        Ok(Cw20HookMsg::Buy { min_tokens_out }) => execute_buy_cw20(
             deps,
             env,
             token_holder_address, // addr of HALO holder who's purchasing
             cw20_msg.amount,      // how much HALO sending
+            min_tokens_out,
         ),
         Ok(Cw20HookMsg::DonorMatch {
             amount,
             donor,
-            accounts_contract,
+            endowment_dao,
             endowment_id,
         }) => execute_donor_match(
             deps,
@@ -103,7 +118,7 @@ pub fn receive_cw20(
             cw20_msg.amount,
             amount,
             donor,
-            accounts_contract,
+            endowment_dao,
             endowment_id,
         ),
         _ => Err(ContractError::Unauthorized {}),
@@ -140,11 +155,34 @@ pub fn do_execute(
         }
         // We only accept CW20 tokens for reseve asset curve at this time (see receive_cw20 > ExecuteMsg::Buy).
         // ExecuteMsg::Buy {} => execute_buy(deps, env, info, curve_fn),
-        ExecuteMsg::Burn { amount } => Ok(execute_sell(deps, env, info, curve_fn, amount)?),
-        ExecuteMsg::BurnFrom { owner, amount } => {
-            Ok(execute_sell_from(deps, env, info, curve_fn, owner, amount)?)
-        }
+        ExecuteMsg::Burn {
+            amount,
+            min_reserve_out,
+        } => Ok(execute_sell(
+            deps,
+            env,
+            info,
+            curve_fn,
+            amount,
+            min_reserve_out,
+        )?),
+        ExecuteMsg::BurnFrom {
+            owner,
+            amount,
+            min_reserve_out,
+        } => Ok(execute_sell_from(
+            deps,
+            env,
+            info,
+            curve_fn,
+            owner,
+            amount,
+            min_reserve_out,
+        )?),
         ExecuteMsg::ClaimTokens {} => Ok(claim_tokens(deps, env, info)?),
+        ExecuteMsg::UpdateTradingFees { fees } => {
+            Ok(execute_update_trading_fees(deps, info, fees)?)
+        }
 
         // these all come from cw20-base to implement the cw20 standard
         ExecuteMsg::Transfer { recipient, amount } => {
@@ -192,21 +230,28 @@ pub fn execute_buy_cw20(
     env: Env,
     buyer_addr: Addr,
     buyer_amount: Uint128,
+    min_tokens_out: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     let curve_type = CURVE_TYPE.load(deps.storage)?;
     let curve_fn = curve_type.to_curve_fn();
     let mut state = CURVE_STATE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
 
-    // calculate how many tokens can be purchased with this and mint them
+    // calculate how many tokens can be purchased with this (less the buy fee) and mint them
     let curve = curve_fn(state.decimals);
-    state.reserve += buyer_amount;
-    let new_supply = curve.supply(state.reserve);
-    let minted = new_supply
-        .checked_sub(state.supply)
-        .map_err(StdError::overflow)?;
-    state.supply = new_supply;
+    let (fee_amount, minted) =
+        simulate_buy(&state, curve.as_ref(), config.fees.as_ref(), buyer_amount)?;
+    assert_minimum_receive(min_tokens_out, minted)?;
+    state.reserve += buyer_amount - fee_amount;
+    state.supply += minted;
     CURVE_STATE.save(deps.storage, &state)?;
 
+    // send the buy fee to the endowment's liquid account
+    let mut messages = vec![];
+    if let Some(fees) = config.fees.filter(|_| !fee_amount.is_zero()) {
+        messages.push(fee_deposit_msg(&state.reserve_denom, &fees, fee_amount)?);
+    }
+
     // call into cw20-base to mint the token, call as self as no one else is allowed
     let sub_info = MessageInfo {
         sender: env.contract.address.clone(),
@@ -216,10 +261,12 @@ pub fn execute_buy_cw20(
 
     // bond them to the validator
     let res = Response::new()
+        .add_messages(messages)
         .add_attribute("action", "buy")
         .add_attribute("from", buyer_addr.to_string())
         .add_attribute("reserve", buyer_amount)
-        .add_attribute("supply", minted);
+        .add_attribute("supply", minted)
+        .add_attribute("fee", fee_amount);
     Ok(res)
 }
 
@@ -230,40 +277,53 @@ pub fn execute_donor_match(
     sent_reserve_token_amount: Uint128,
     amount: Uint128,
     donor: String,
-    accounts_contract: String,
-    _endowment_id: u32, // FIXME: Should be used for minting the subdao token
+    endowment_dao: String,
+    endowment_id: u32,
 ) -> Result<Response, ContractError> {
     // Validation: Check if the correct amount of tokens are sent
     if sent_reserve_token_amount != amount {
         return Err(ContractError::InsufficientFunds {});
     }
+    let endowment_dao = deps.api.addr_validate(&endowment_dao)?;
 
-    // Calculate the amounts of dao-token to be minted and burned
-    // Minted for the "donor": 40%
-    // Minted for the "endowment_contract": 40%
-    // Burned: 20% (This action is just needed for calculation purposes, no real action is needed.)
-    let donor_amount = amount.multiply_ratio(40_u128, 100_u128);
-    let endowment_amount = amount.multiply_ratio(40_u128, 100_u128);
-    let burn_amount = amount.multiply_ratio(20_u128, 100_u128);
+    // Calculate the amounts of dao-token to be minted and burned, by the configured split
+    // Minted for the "donor": 40% by default
+    // Minted for the endowment's subdao: 40% by default
+    // Burned: the rest (This action is just needed for calculation purposes, no real action is needed.)
+    let split = CONFIG.load(deps.storage)?.donor_match_split;
+    let donor_amount = amount * split.donor;
+    let endowment_amount = amount * split.endowment;
+    let burn_amount = amount - donor_amount - endowment_amount;
 
     let sub_info = MessageInfo {
         sender: env.contract.address.clone(),
         funds: vec![],
     };
 
-    // Mint to "donor": 40%
-    execute_mint(
-        deps.branch(),
-        env.clone(),
-        sub_info.clone(),
-        donor,
-        donor_amount,
-    )?;
-    // Mint to "endowment_contract": 40%
-    execute_mint(deps, env, sub_info, accounts_contract, endowment_amount)?; // FIXME: How to handle `accounts_contract` & `endowment_id` together
+    // Mint to "donor"
+    if !donor_amount.is_zero() {
+        execute_mint(
+            deps.branch(),
+            env.clone(),
+            sub_info.clone(),
+            donor,
+            donor_amount,
+        )?;
+    }
+    // Mint to the endowment's subdao
+    if !endowment_amount.is_zero() {
+        execute_mint(
+            deps,
+            env,
+            sub_info,
+            endowment_dao.to_string(),
+            endowment_amount,
+        )?;
+    }
 
     Ok(Response::default().add_attributes(vec![
         attr("method", "donor_match"),
+        attr("endowment_id", endowment_id.to_string()),
         attr("donor_amount", donor_amount),
         attr("endowment_amount", endowment_amount),
         attr("burnt_amount", burn_amount),
@@ -283,7 +343,7 @@ pub fn execute_buy(
     // calculate how many tokens can be purchased with this and mint them
     let curve = curve_fn(state.decimals);
     state.reserve += payment;
-    let new_supply = curve.supply(state.reserve);
+    let new_supply = curve.supply(state.reserve)?;
     let minted = new_supply
         .checked_sub(state.supply)
         .map_err(StdError::overflow)?;
@@ -312,11 +372,12 @@ pub fn execute_sell(
     info: MessageInfo,
     curve_fn: CurveFn,
     amount: Uint128,
+    min_reserve_out: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     nonpayable(&info)?;
     let receiver = info.sender.clone();
     // do all the work
-    let mut res = do_sell(deps, env, info, curve_fn, receiver, amount)?;
+    let mut res = do_sell(deps, env, info, curve_fn, receiver, amount, min_reserve_out)?;
 
     // add our custom attributes
     res.attributes.push(attr("action", "burn"));
@@ -330,6 +391,7 @@ pub fn execute_sell_from(
     curve_fn: CurveFn,
     owner: String,
     amount: Uint128,
+    min_reserve_out: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     nonpayable(&info)?;
     let owner_addr = deps.api.addr_validate(&owner)?;
@@ -351,6 +413,7 @@ pub fn execute_sell_from(
         curve_fn,
         receiver_addr.clone(),
         amount,
+        min_reserve_out,
     )?;
 
     // add our custom attributes
@@ -368,42 +431,142 @@ fn do_sell(
     // receiver is the one who gains (same for execute_sell, diff for execute_sell_from)
     _receiver: Addr,
     amount: Uint128,
+    min_reserve_out: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     let block = env.clone().block;
-    // burn from the caller, this ensures there are tokens to cover this
-    execute_burn(deps.branch(), env, info.clone(), amount)?;
 
-    // calculate how many tokens can be purchased with this and mint them
+    // calculate how many reserve tokens are released (less the sell fee)
     let mut state = CURVE_STATE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
     let curve = curve_fn(state.decimals);
-    state.supply = state
-        .supply
-        .checked_sub(amount)
-        .map_err(StdError::overflow)?;
-    let new_reserve = curve.reserve(state.supply);
-    let released = state
-        .reserve
-        .checked_sub(new_reserve)
-        .map_err(StdError::overflow)?;
-    state.reserve = new_reserve;
+    let (fee_amount, released) =
+        simulate_sell(&state, curve.as_ref(), config.fees.as_ref(), amount)?;
+    assert_minimum_receive(min_reserve_out, released)?;
+
+    // burn from the caller, this ensures there are tokens to cover this
+    execute_burn(deps.branch(), env, info.clone(), amount)?;
+    state.supply -= amount;
+    state.reserve -= released + fee_amount;
     CURVE_STATE.save(deps.storage, &state)?;
 
-    // create a new claim for the released reserve Amount
-    let config = CONFIG.load(deps.storage)?;
+    // create a new claim for the released reserve amount (not the burnt supply amount),
+    // which is what `claim_tokens` pays out in reserve tokens
     CLAIMS.create_claim(
         deps.storage,
         &info.sender,
-        amount,
+        released,
         config.unbonding_period.after(&block),
     )?;
 
+    // send the sell fee to the endowment's liquid account
+    let mut messages = vec![];
+    if let Some(fees) = config.fees.filter(|_| !fee_amount.is_zero()) {
+        messages.push(fee_deposit_msg(&state.reserve_denom, &fees, fee_amount)?);
+    }
+
     let res = Response::new()
+        .add_messages(messages)
         .add_attribute("from", info.sender)
         .add_attribute("supply", amount)
-        .add_attribute("reserve", released);
+        .add_attribute("reserve", released)
+        .add_attribute("fee", fee_amount);
     Ok(res)
 }
 
+pub fn execute_update_trading_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    fees: Option<TradingFees>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != Some(info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_trading_fees(deps.api, &fees)?;
+    config.fees = fees;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_trading_fees"))
+}
+
+/// Fees have to leave something to trade & be paid to a valid accounts contract
+fn validate_trading_fees(api: &dyn Api, fees: &Option<TradingFees>) -> Result<(), ContractError> {
+    if let Some(fees) = fees {
+        if fees.buy_fee >= Decimal::one() || fees.sell_fee >= Decimal::one() {
+            return Err(ContractError::InvalidInputs {});
+        }
+        api.addr_validate(&fees.accounts_contract)?;
+    }
+    Ok(())
+}
+
+fn assert_minimum_receive(minimum: Option<Uint128>, amount: Uint128) -> Result<(), ContractError> {
+    match minimum {
+        Some(receive) if amount < receive => {
+            Err(ContractError::AssertionMinimumReceive { receive, amount })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the buy fee & the supply tokens minted when buying with `reserve_amount`
+pub fn simulate_buy(
+    state: &CurveState,
+    curve: &dyn Curve,
+    fees: Option<&TradingFees>,
+    reserve_amount: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    let fee_amount = fees.map_or(Uint128::zero(), |fees| reserve_amount * fees.buy_fee);
+    let new_reserve = state.reserve + reserve_amount - fee_amount;
+    let minted = curve
+        .supply(new_reserve)?
+        .checked_sub(state.supply)
+        .map_err(StdError::overflow)?;
+    Ok((fee_amount, minted))
+}
+
+/// Returns the sell fee & the reserve tokens released when selling `token_amount`
+pub fn simulate_sell(
+    state: &CurveState,
+    curve: &dyn Curve,
+    fees: Option<&TradingFees>,
+    token_amount: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    let new_supply = state
+        .supply
+        .checked_sub(token_amount)
+        .map_err(StdError::overflow)?;
+    let released = state
+        .reserve
+        .checked_sub(curve.reserve(new_supply)?)
+        .map_err(StdError::overflow)?;
+    let fee_amount = fees.map_or(Uint128::zero(), |fees| released * fees.sell_fee);
+    Ok((fee_amount, released - fee_amount))
+}
+
+/// Deposits the fee in the endowment's liquid account
+fn fee_deposit_msg(
+    reserve_denom: &str,
+    fees: &TradingFees,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: reserve_denom.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: fees.accounts_contract.clone(),
+            amount,
+            msg: to_binary(&AccountsReceiveMsg::Deposit(DepositMsg {
+                id: fees.endowment_id,
+                locked_percentage: Decimal::zero(),
+                liquid_percentage: Decimal::one(),
+            }))?,
+        })?,
+        funds: vec![],
+    }))
+}
+
 // Claim all reserve tokens that are past the unbonding period for a user.
 pub fn claim_tokens(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
     // check how much to send - min(balance, claims[sender]), and reduce the claim
@@ -606,6 +769,12 @@ pub fn do_query(deps: Deps, _env: Env, msg: QueryMsg, curve_fn: CurveFn) -> StdR
         QueryMsg::Allowance { owner, spender } => {
             to_binary(&query_allowance(deps, owner, spender)?)
         }
+        QueryMsg::SimulateBuy { reserve_amount } => {
+            to_binary(&query_simulate_buy(deps, curve_fn, reserve_amount)?)
+        }
+        QueryMsg::SimulateSell { token_amount } => {
+            to_binary(&query_simulate_sell(deps, curve_fn, token_amount)?)
+        }
     }
 }
 
@@ -619,7 +788,7 @@ pub fn query_curve_info(deps: Deps, curve_fn: CurveFn) -> StdResult<CurveInfoRes
 
     // This we can get from the local digits stored in instantiate
     let curve = curve_fn(decimals);
-    let spot_price = curve.spot_price(supply);
+    let spot_price = curve.spot_price(supply)?;
 
     Ok(CurveInfoResponse {
         reserve,
@@ -628,3 +797,37 @@ pub fn query_curve_info(deps: Deps, curve_fn: CurveFn) -> StdResult<CurveInfoRes
         reserve_denom,
     })
 }
+
+pub fn query_simulate_buy(
+    deps: Deps,
+    curve_fn: CurveFn,
+    reserve_amount: Uint128,
+) -> StdResult<SimulateBuyResponse> {
+    let state = CURVE_STATE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let curve = curve_fn(state.decimals);
+    let (fee_amount, tokens_out) =
+        simulate_buy(&state, curve.as_ref(), config.fees.as_ref(), reserve_amount)?;
+
+    Ok(SimulateBuyResponse {
+        tokens_out,
+        fee_amount,
+    })
+}
+
+pub fn query_simulate_sell(
+    deps: Deps,
+    curve_fn: CurveFn,
+    token_amount: Uint128,
+) -> StdResult<SimulateSellResponse> {
+    let state = CURVE_STATE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let curve = curve_fn(state.decimals);
+    let (fee_amount, reserve_out) =
+        simulate_sell(&state, curve.as_ref(), config.fees.as_ref(), token_amount)?;
+
+    Ok(SimulateSellResponse {
+        reserve_out,
+        fee_amount,
+    })
+}
@@ -1,5 +1,5 @@
 use angel_core::curves::DecimalPlaces;
-use angel_core::msgs::subdao_bonding_token::CurveType;
+use angel_core::msgs::subdao_bonding_token::{CurveType, DonorMatchSplit, TradingFees};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128};
 use cw_controllers::Claims;
@@ -57,6 +57,16 @@ pub struct Config {
     /// This is the unbonding period of CS tokens
     /// We need this to only allow claims to be redeemed after this period
     pub unbonding_period: Duration,
+    /// Split of the tokens minted for a donor match
+    #[serde(default)]
+    pub donor_match_split: DonorMatchSplit,
+    /// Contract allowed to update the trading fees (the subdao that created the token).
+    /// Unset for tokens created before trading fees existed
+    #[serde(default)]
+    pub owner: Option<Addr>,
+    /// Fees charged on buys & sells, with the endowment receiving them
+    #[serde(default)]
+    pub fees: Option<TradingFees>,
 }
 
 pub const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
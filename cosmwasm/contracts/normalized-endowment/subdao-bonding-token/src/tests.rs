@@ -1,14 +1,20 @@
 use angel_core::errors::core::{ContractError, PaymentError};
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{coins, from_binary, to_binary, Decimal, OverflowError, StdError, Uint128};
-use cw20::{BalanceResponse, Cw20ReceiveMsg};
+use cosmwasm_std::{
+    attr, coins, from_binary, to_binary, Decimal, OverflowError, StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_controllers::ClaimsResponse;
 
 use crate::contract::{execute, instantiate, query};
+use angel_core::msgs::accounts::{DepositMsg, ReceiveMsg as AccountsReceiveMsg};
 use angel_core::msgs::subdao_bonding_token::{
-    CurveInfoResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+    CurveBreakpoint, CurveInfoResponse, CurveType, Cw20HookMsg, DonorMatchSplit, ExecuteMsg,
+    InstantiateMsg, QueryMsg, SimulateBuyResponse, SimulateSellResponse, TradingFees,
 };
 
 const ENDOWMENT_ID: u32 = 1_u32;
+const ENDOWMENT_DAO: &str = "endowment-dao";
 const ACCOUNTS_CONTRACT: &str = "accounts-contract";
 const DONATION_MATCH_AMOUNT: u128 = 100_u128;
 
@@ -27,6 +33,8 @@ fn test_proper_initialization() {
             scale: 1,
         },
         unbonding_period: 100_u64,
+        donor_match_split: None,
+        fees: None,
     };
 
     let res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
@@ -45,6 +53,73 @@ fn test_proper_initialization() {
     );
 }
 
+#[test]
+fn test_instantiate_with_invalid_curve() {
+    let mut deps = mock_dependencies();
+    let info = mock_info("creator", &[]);
+    let mut instantiate_msg = InstantiateMsg {
+        name: "Dao-Token".to_string(),
+        symbol: "DT".to_string(),
+        decimals: 6,
+        reserve_denom: "reserve-token-address".to_string(),
+        reserve_decimals: 6,
+        // breakpoints have to start at supply 0
+        curve_type: CurveType::PiecewiseLinear {
+            points: vec![
+                CurveBreakpoint {
+                    supply: Uint128::from(10_u128),
+                    price: Uint128::from(1_u128),
+                },
+                CurveBreakpoint {
+                    supply: Uint128::from(20_u128),
+                    price: Uint128::from(2_u128),
+                },
+            ],
+            scale: 0,
+        },
+        unbonding_period: 100_u64,
+        donor_match_split: None,
+        fees: None,
+    };
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("Invalid curve parameters"))
+    );
+
+    // the supply can't grow with a zero growth rate
+    instantiate_msg.curve_type = CurveType::Exponential {
+        initial_price: Uint128::from(1_u128),
+        growth: Uint128::zero(),
+        scale: 0,
+    };
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("Invalid curve parameters"))
+    );
+
+    instantiate_msg.curve_type = CurveType::Exponential {
+        initial_price: Uint128::from(1_u128),
+        growth: Uint128::from(1_u128),
+        scale: 1,
+    };
+    let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+}
+
 #[test]
 fn test_claim_tokens() {
     let mut deps = mock_dependencies();
@@ -60,9 +135,11 @@ fn test_claim_tokens() {
             scale: 1,
         },
         unbonding_period: 100_u64,
+        donor_match_split: None,
+        fees: None,
     };
 
-    let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+    let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg.clone()).unwrap();
 
     // Fails to "claim_tokens" because of the period
     let info = mock_info("claimer", &[]);
@@ -71,54 +148,77 @@ fn test_claim_tokens() {
 
     assert_eq!(err, ContractError::NothingToClaim {});
 
-    // Success to claim tokens
-    // TODO: How to test the "claim_tokens" logic
-
-    // // subaction 1: Buy the dao-token
-    // let info = mock_info("reserve-token-address", &[]);
-    // let cw20_receive_msg = Cw20ReceiveMsg {
-    //     sender: "buyer".to_string(),
-    //     amount: Uint128::from(10_u128),
-    //     msg: to_binary(&Cw20HookMsg::Buy {}).unwrap(),
-    // };
-    // let _res = execute(
-    //     deps.as_mut(),
-    //     mock_env(),
-    //     info,
-    //     ExecuteMsg::Receive(cw20_receive_msg),
-    // )
-    // .unwrap();
-
-    // // subaction 2. Succeed to "sell"
-    // let info = mock_info("buyer", &[]);
-    // let sell_msg = ExecuteMsg::Burn {
-    //     amount: Uint128::from(5_u128),
-    // };
-    // let _res = execute(deps.as_mut(), mock_env(), info, sell_msg).unwrap();
-
-    // // subaction 3: Claim the tokens
-    // let info = mock_info("reserve-token-address", &[]);
-    // let cw20_receive_msg = Cw20ReceiveMsg {
-    //     sender: "buyer".to_string(),
-    //     amount: Uint128::from(10_u128),
-    //     msg: to_binary(&Cw20HookMsg::Buy {}).unwrap(),
-    // };
-    // let _res = execute(
-    //     deps.as_mut(),
-    //     mock_env(),
-    //     info,
-    //     ExecuteMsg::Receive(cw20_receive_msg),
-    // )
-    // .unwrap();
-
-    // let mut env = mock_env();
-    // env.block.height += 100;
-    // let info = mock_info("claimer", &[]);
-    // let claim_token_msg = ExecuteMsg::ClaimTokens {};
-    // let res = execute(deps.as_mut(), env, info, claim_token_msg).unwrap();
-
-    // assert_eq!(res.messages.len(), 1);
-    // assert_eq!(res.attributes.len(), 2);
+    // Buy 5 dao-tokens at a price of 2 reserve tokens each
+    let mut deps = mock_dependencies();
+    let mut instantiate_msg = instantiate_msg;
+    instantiate_msg.curve_type = CurveType::Constant {
+        value: Uint128::from(20_u128),
+        scale: 1,
+    };
+    let _ = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        instantiate_msg,
+    )
+    .unwrap();
+    let info = mock_info("reserve-token-address", &[]);
+    let cw20_receive_msg = Cw20ReceiveMsg {
+        sender: "buyer".to_string(),
+        amount: Uint128::from(10_u128),
+        msg: to_binary(&Cw20HookMsg::Buy {
+            min_tokens_out: None,
+        })
+        .unwrap(),
+    };
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Receive(cw20_receive_msg),
+    )
+    .unwrap();
+
+    // Selling them claims the 10 reserve tokens released, not the 5 dao-tokens burned
+    let info = mock_info("buyer", &[]);
+    let sell_msg = ExecuteMsg::Burn {
+        amount: Uint128::from(5_u128),
+        min_reserve_out: None,
+    };
+    let _ = execute(deps.as_mut(), mock_env(), info, sell_msg).unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Claims {
+            address: "buyer".to_string(),
+        },
+    )
+    .unwrap();
+    let claims: ClaimsResponse = from_binary(&res).unwrap();
+    assert_eq!(claims.claims.len(), 1);
+    assert_eq!(claims.claims[0].amount, Uint128::from(10_u128));
+
+    // Nothing to claim before the unbonding period is over
+    let info = mock_info("buyer", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::ClaimTokens {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let info = mock_info("buyer", &[]);
+    let res = execute(deps.as_mut(), env, info, ExecuteMsg::ClaimTokens {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "reserve-token-address".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "buyer".to_string(),
+                amount: Uint128::from(10_u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
 }
 
 #[test]
@@ -136,6 +236,8 @@ fn test_buy() {
             scale: 1,
         },
         unbonding_period: 100_u64,
+        donor_match_split: None,
+        fees: None,
     };
 
     let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
@@ -145,7 +247,10 @@ fn test_buy() {
     let cw20_receive_msg = Cw20ReceiveMsg {
         sender: "buyer".to_string(),
         amount: Uint128::from(10_u128),
-        msg: to_binary(&Cw20HookMsg::Buy {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Buy {
+            min_tokens_out: None,
+        })
+        .unwrap(),
     };
     let err = execute(
         deps.as_mut(),
@@ -161,7 +266,10 @@ fn test_buy() {
     let cw20_receive_msg = Cw20ReceiveMsg {
         sender: "buyer".to_string(),
         amount: Uint128::from(0_u128),
-        msg: to_binary(&Cw20HookMsg::Buy {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Buy {
+            min_tokens_out: None,
+        })
+        .unwrap(),
     };
     let err = execute(
         deps.as_mut(),
@@ -177,7 +285,10 @@ fn test_buy() {
     let cw20_receive_msg = Cw20ReceiveMsg {
         sender: "buyer".to_string(),
         amount: Uint128::from(10_u128),
-        msg: to_binary(&Cw20HookMsg::Buy {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Buy {
+            min_tokens_out: None,
+        })
+        .unwrap(),
     };
     let res = execute(
         deps.as_mut(),
@@ -187,7 +298,7 @@ fn test_buy() {
     )
     .unwrap();
     assert_eq!(res.messages.len(), 0);
-    assert_eq!(res.attributes.len(), 4);
+    assert_eq!(res.attributes.len(), 5);
 
     // query the state
     let res = query(
@@ -202,6 +313,64 @@ fn test_buy() {
     assert_eq!(dao_token_bal.balance, Uint128::from(10_u128));
 }
 
+#[test]
+fn test_buy_past_sigmoid_supply_cap() {
+    let mut deps = mock_dependencies();
+    let info = mock_info("creator", &[]);
+    // price goes up to 2, passing 1 at 10 tokens. No more than 20 tokens can be issued
+    let instantiate_msg = InstantiateMsg {
+        name: "Dao-Token".to_string(),
+        symbol: "DT".to_string(),
+        decimals: 6,
+        reserve_denom: "reserve-token-address".to_string(),
+        reserve_decimals: 6,
+        curve_type: CurveType::Sigmoid {
+            max_price: Uint128::from(2_u128),
+            steepness: Uint128::from(1_u128),
+            midpoint: Uint128::from(10_u128),
+            supply_cap: Uint128::from(20_u128),
+            scale: 0,
+        },
+        unbonding_period: 100_u64,
+        donor_match_split: None,
+        fees: None,
+    };
+    let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+    let buy_msg = |amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "buyer".to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Buy {
+                min_tokens_out: None,
+            })
+            .unwrap(),
+        })
+    };
+    let info = mock_info("reserve-token-address", &[]);
+    let _ = execute(deps.as_mut(), mock_env(), info, buy_msg(10_000_000)).unwrap();
+
+    // the whole supply up to the cap is worth about 20 reserve tokens, so 20 more can't be taken
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateBuy {
+            reserve_amount: Uint128::from(20_000_000_u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Bonding curve supply cap exceeded")
+    );
+    let info = mock_info("reserve-token-address", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, buy_msg(20_000_000)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("Bonding curve supply cap exceeded"))
+    );
+}
+
 #[test]
 fn test_sell() {
     let mut deps = mock_dependencies();
@@ -217,6 +386,8 @@ fn test_sell() {
             scale: 1,
         },
         unbonding_period: 100_u64,
+        donor_match_split: None,
+        fees: None,
     };
 
     let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
@@ -226,7 +397,10 @@ fn test_sell() {
     let cw20_receive_msg = Cw20ReceiveMsg {
         sender: "buyer".to_string(),
         amount: Uint128::from(10_u128),
-        msg: to_binary(&Cw20HookMsg::Buy {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Buy {
+            min_tokens_out: None,
+        })
+        .unwrap(),
     };
     let _ = execute(
         deps.as_mut(),
@@ -240,6 +414,7 @@ fn test_sell() {
     let info = mock_info("buyer", &coins(100, "earth"));
     let sell_msg = ExecuteMsg::Burn {
         amount: Uint128::from(5_u128),
+        min_reserve_out: None,
     };
     let err = execute(deps.as_mut(), mock_env(), info, sell_msg).unwrap_err();
     assert_eq!(err, ContractError::Payment(PaymentError::NonPayable {}));
@@ -248,6 +423,7 @@ fn test_sell() {
     let info = mock_info("buyer", &[]);
     let sell_msg = ExecuteMsg::Burn {
         amount: Uint128::from(15_u128),
+        min_reserve_out: None,
     };
     let err = execute(deps.as_mut(), mock_env(), info, sell_msg).unwrap_err();
     assert_eq!(
@@ -263,10 +439,11 @@ fn test_sell() {
     let info = mock_info("buyer", &[]);
     let sell_msg = ExecuteMsg::Burn {
         amount: Uint128::from(5_u128),
+        min_reserve_out: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, sell_msg).unwrap();
     assert_eq!(res.messages.len(), 0);
-    assert_eq!(res.attributes.len(), 4);
+    assert_eq!(res.attributes.len(), 5);
 
     // query the state
     let res = query(
@@ -296,6 +473,8 @@ fn test_transfer() {
             scale: 1,
         },
         unbonding_period: 100_u64,
+        donor_match_split: None,
+        fees: None,
     };
 
     let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
@@ -305,7 +484,10 @@ fn test_transfer() {
     let cw20_receive_msg = Cw20ReceiveMsg {
         sender: "buyer".to_string(),
         amount: Uint128::from(10_u128),
-        msg: to_binary(&Cw20HookMsg::Buy {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Buy {
+            min_tokens_out: None,
+        })
+        .unwrap(),
     };
     let _res = execute(
         deps.as_mut(),
@@ -353,6 +535,8 @@ fn test_donor_match() {
             scale: 1,
         },
         unbonding_period: 100_u64,
+        donor_match_split: None,
+        fees: None,
     };
 
     let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
@@ -365,7 +549,7 @@ fn test_donor_match() {
         msg: to_binary(&Cw20HookMsg::DonorMatch {
             amount: Uint128::from(DONATION_MATCH_AMOUNT),
             donor: "donor".to_string(),
-            accounts_contract: ACCOUNTS_CONTRACT.to_string(),
+            endowment_dao: ENDOWMENT_DAO.to_string(),
             endowment_id: ENDOWMENT_ID,
         })
         .unwrap(),
@@ -387,7 +571,7 @@ fn test_donor_match() {
         msg: to_binary(&Cw20HookMsg::DonorMatch {
             amount: Uint128::from(DONATION_MATCH_AMOUNT),
             donor: "donor".to_string(),
-            accounts_contract: ACCOUNTS_CONTRACT.to_string(),
+            endowment_dao: ENDOWMENT_DAO.to_string(),
             endowment_id: ENDOWMENT_ID,
         })
         .unwrap(),
@@ -409,7 +593,7 @@ fn test_donor_match() {
         msg: to_binary(&Cw20HookMsg::DonorMatch {
             amount: Uint128::from(DONATION_MATCH_AMOUNT),
             donor: "donor".to_string(),
-            accounts_contract: ACCOUNTS_CONTRACT.to_string(),
+            endowment_dao: ENDOWMENT_DAO.to_string(),
             endowment_id: ENDOWMENT_ID,
         })
         .unwrap(),
@@ -421,7 +605,7 @@ fn test_donor_match() {
         ExecuteMsg::Receive(cw20_receive_msg),
     )
     .unwrap();
-    assert_eq!(res.attributes.len(), 4);
+    assert_eq!(res.attributes.len(), 5);
     assert_eq!(res.messages.len(), 0);
 
     // query the state
@@ -443,7 +627,7 @@ fn test_donor_match() {
         deps.as_ref(),
         mock_env(),
         QueryMsg::Balance {
-            address: ACCOUNTS_CONTRACT.to_string(),
+            address: ENDOWMENT_DAO.to_string(),
         },
     )
     .unwrap();
@@ -453,3 +637,265 @@ fn test_donor_match() {
         Uint128::from(DONATION_MATCH_AMOUNT) * Decimal::from_ratio(40_u128, 100_u128)
     );
 }
+
+#[test]
+fn test_donor_match_custom_split() {
+    let mut deps = mock_dependencies();
+    let info = mock_info("creator", &[]);
+    let mut instantiate_msg = InstantiateMsg {
+        name: "Dao-Token".to_string(),
+        symbol: "DT".to_string(),
+        decimals: 6,
+        reserve_denom: "reserve-token-address".to_string(),
+        reserve_decimals: 6,
+        curve_type: angel_core::msgs::subdao_bonding_token::CurveType::Constant {
+            value: Uint128::from(10_u128),
+            scale: 1,
+        },
+        unbonding_period: 100_u64,
+        donor_match_split: Some(DonorMatchSplit {
+            donor: Decimal::percent(50),
+            endowment: Decimal::percent(40),
+            burn: Decimal::percent(20),
+        }),
+        fees: None,
+    };
+
+    // Fails to instantiate with a split not adding up to 100%
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidInputs {});
+
+    instantiate_msg.donor_match_split = Some(DonorMatchSplit {
+        donor: Decimal::percent(50),
+        endowment: Decimal::percent(30),
+        burn: Decimal::percent(20),
+    });
+    let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+    let info = mock_info("reserve-token-address", &[]);
+    let cw20_receive_msg = Cw20ReceiveMsg {
+        sender: "executer".to_string(),
+        amount: Uint128::from(DONATION_MATCH_AMOUNT),
+        msg: to_binary(&Cw20HookMsg::DonorMatch {
+            amount: Uint128::from(DONATION_MATCH_AMOUNT),
+            donor: "donor".to_string(),
+            endowment_dao: ENDOWMENT_DAO.to_string(),
+            endowment_id: ENDOWMENT_ID,
+        })
+        .unwrap(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Receive(cw20_receive_msg),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes[1],
+        attr("endowment_id", ENDOWMENT_ID.to_string())
+    );
+
+    for (address, share) in [("donor", 50_u128), (ENDOWMENT_DAO, 30_u128)] {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+        let dao_token_bal: BalanceResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            dao_token_bal.balance,
+            Uint128::from(DONATION_MATCH_AMOUNT) * Decimal::from_ratio(share, 100_u128)
+        );
+    }
+}
+
+#[test]
+fn test_trading_fees_and_slippage() {
+    let mut deps = mock_dependencies();
+    let info = mock_info("creator", &[]);
+    let mut instantiate_msg = InstantiateMsg {
+        name: "Dao-Token".to_string(),
+        symbol: "DT".to_string(),
+        decimals: 6,
+        reserve_denom: "reserve-token-address".to_string(),
+        reserve_decimals: 6,
+        curve_type: angel_core::msgs::subdao_bonding_token::CurveType::Constant {
+            value: Uint128::from(10_u128),
+            scale: 1,
+        },
+        unbonding_period: 100_u64,
+        donor_match_split: None,
+        fees: Some(TradingFees {
+            buy_fee: Decimal::one(),
+            sell_fee: Decimal::percent(10),
+            endowment_id: ENDOWMENT_ID,
+            accounts_contract: ACCOUNTS_CONTRACT.to_string(),
+        }),
+    };
+
+    // Fails to instantiate since the buy fee would take everything
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidInputs {});
+
+    instantiate_msg.fees.as_mut().unwrap().buy_fee = Decimal::percent(10);
+    let _ = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateBuy {
+            reserve_amount: Uint128::from(100_u128),
+        },
+    )
+    .unwrap();
+    let simulation: SimulateBuyResponse = from_binary(&res).unwrap();
+    assert_eq!(simulation.tokens_out, Uint128::from(90_u128));
+    assert_eq!(simulation.fee_amount, Uint128::from(10_u128));
+
+    // Fails to "buy" since less tokens than asked would be minted
+    let buy_msg = |min_tokens_out: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "buyer".to_string(),
+            amount: Uint128::from(100_u128),
+            msg: to_binary(&Cw20HookMsg::Buy {
+                min_tokens_out: Some(Uint128::from(min_tokens_out)),
+            })
+            .unwrap(),
+        })
+    };
+    let info = mock_info("reserve-token-address", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), buy_msg(91)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AssertionMinimumReceive {
+            receive: Uint128::from(91_u128),
+            amount: Uint128::from(90_u128),
+        }
+    );
+
+    // Succeed to "buy", with the fee deposited to the endowment's liquid account
+    let res = execute(deps.as_mut(), mock_env(), info, buy_msg(90)).unwrap();
+    assert_eq!(res.messages, vec![fee_deposit_submsg(10)]);
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance {
+            address: "buyer".to_string(),
+        },
+    )
+    .unwrap();
+    let dao_token_bal: BalanceResponse = from_binary(&res).unwrap();
+    assert_eq!(dao_token_bal.balance, Uint128::from(90_u128));
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateSell {
+            token_amount: Uint128::from(50_u128),
+        },
+    )
+    .unwrap();
+    let simulation: SimulateSellResponse = from_binary(&res).unwrap();
+    assert_eq!(simulation.reserve_out, Uint128::from(45_u128));
+    assert_eq!(simulation.fee_amount, Uint128::from(5_u128));
+
+    // Fails to "sell" since less reserve than asked would be claimable
+    let info = mock_info("buyer", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::Burn {
+            amount: Uint128::from(50_u128),
+            min_reserve_out: Some(Uint128::from(46_u128)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AssertionMinimumReceive {
+            receive: Uint128::from(46_u128),
+            amount: Uint128::from(45_u128),
+        }
+    );
+
+    // Succeed to "sell", claiming the reserve less the fee
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Burn {
+            amount: Uint128::from(50_u128),
+            min_reserve_out: Some(Uint128::from(45_u128)),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![fee_deposit_submsg(5)]);
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Claims {
+            address: "buyer".to_string(),
+        },
+    )
+    .unwrap();
+    let claims: ClaimsResponse = from_binary(&res).unwrap();
+    assert_eq!(claims.claims[0].amount, Uint128::from(45_u128));
+
+    // Only the creator (subdao) can update the fees
+    let update_msg = ExecuteMsg::UpdateTradingFees { fees: None };
+    let info = mock_info("anyone", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, update_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info("creator", &[]);
+    let _ = execute(deps.as_mut(), mock_env(), info, update_msg).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateBuy {
+            reserve_amount: Uint128::from(100_u128),
+        },
+    )
+    .unwrap();
+    let simulation: SimulateBuyResponse = from_binary(&res).unwrap();
+    assert_eq!(simulation.tokens_out, Uint128::from(100_u128));
+    assert_eq!(simulation.fee_amount, Uint128::zero());
+}
+
+fn fee_deposit_submsg(amount: u128) -> SubMsg {
+    SubMsg::new(WasmMsg::Execute {
+        contract_addr: "reserve-token-address".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: ACCOUNTS_CONTRACT.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&AccountsReceiveMsg::Deposit(DepositMsg {
+                id: ENDOWMENT_ID,
+                locked_percentage: Decimal::zero(),
+                liquid_percentage: Decimal::one(),
+            }))
+            .unwrap(),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
@@ -2,26 +2,32 @@ use crate::querier::{
     query_address_voting_balance_at_timestamp, query_total_voting_balance_at_timestamp,
 };
 use crate::state::{
-    config_read, config_store, poll_indexer_store, poll_read, poll_store, poll_voter_read,
-    poll_voter_store, read_poll_voters, read_polls, state_read, state_store, Config, ExecuteData,
-    Poll, State,
+    config_read, config_store, default_veto_threshold, open_poll_count_read, open_poll_count_store,
+    poll_indexer_store, poll_read, poll_store, poll_voter_read, poll_voter_store, read_poll_voters,
+    read_polls, state_read, state_store, Config, ExecuteData, Poll, PollOption, State,
 };
 use angel_core::common::OrderBy;
 use angel_core::errors::dao::ContractError;
-use angel_core::msgs::registrar::ConfigResponse as RegistrarConfigResponse;
-use angel_core::msgs::registrar::QueryMsg::Config as RegistrarConfig;
+use angel_core::msgs::registrar::QueryMsg::{
+    Config as RegistrarConfig, ConfigExtension as RegistrarConfigExtension,
+};
+use angel_core::msgs::registrar::{
+    ConfigExtensionResponse as RegistrarConfigExtensionResponse,
+    ConfigResponse as RegistrarConfigResponse,
+};
 use angel_core::msgs::subdao::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PollExecuteMsg,
-    PollResponse, PollStatus, PollsResponse, QueryMsg, StateResponse, VoteOption, VoterInfo,
-    VotersResponse, VotersResponseItem,
+    ConfigResponse, Cw20HookMsg, DepositOutcome, DepositPolicy, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, PollExecuteMsg, PollOptionMsg, PollOptionResponse, PollResponse, PollStatus,
+    PollsResponse, QueryMsg, StateResponse, VoteOption, VoterInfo, VotersResponse,
+    VotersResponseItem,
 };
 use angel_core::msgs::subdao_bonding_token::InstantiateMsg as DaoTokenInstantiateMsg;
 use angel_core::structs::{DaoToken, EndowmentType};
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
-    QueryRequest, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, SubMsgResult, Uint128,
-    WasmMsg, WasmQuery,
+    from_binary, to_binary, Addr, Api, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    QueryRequest, Reply, ReplyOn, Response, StdError, StdResult, Storage, SubMsg, SubMsgResult,
+    Uint128, WasmMsg, WasmQuery,
 };
 use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg};
 
@@ -31,6 +37,8 @@ const MIN_DESC_LENGTH: usize = 4;
 const MAX_DESC_LENGTH: usize = 1024;
 const MIN_LINK_LENGTH: usize = 12;
 const MAX_LINK_LENGTH: usize = 128;
+const MIN_POLL_OPTIONS: usize = 2;
+const MAX_POLL_OPTIONS: usize = 16;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -41,6 +49,15 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     validate_quorum(msg.quorum)?;
     validate_threshold(msg.threshold)?;
+    let veto_threshold = msg.veto_threshold.unwrap_or_else(default_veto_threshold);
+    validate_threshold(veto_threshold)?;
+
+    let deposit_policy = msg.deposit_policy.unwrap_or_default();
+    let slash_recipient = msg
+        .slash_recipient
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    validate_deposit_policy(&deposit_policy, &slash_recipient)?;
 
     let config = Config {
         registrar_contract: deps.api.addr_validate(&msg.registrar_contract)?,
@@ -50,11 +67,15 @@ pub fn instantiate(
         swap_factory: Addr::unchecked(""),
         quorum: msg.quorum,
         threshold: msg.threshold,
+        veto_threshold,
         voting_period: msg.voting_period,
         timelock_period: msg.timelock_period,
         expiration_period: msg.expiration_period,
         proposal_deposit: msg.proposal_deposit,
         snapshot_period: msg.snapshot_period,
+        deposit_policy,
+        slash_recipient,
+        max_open_polls: msg.max_open_polls,
     };
 
     let state = State {
@@ -86,6 +107,11 @@ pub fn build_dao_token_messages(
             contract_addr: config.registrar_contract.to_string(),
             msg: to_binary(&RegistrarConfig {})?,
         }))?;
+    let registrar_config_ext: RegistrarConfigExtensionResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.registrar_contract.to_string(),
+            msg: to_binary(&RegistrarConfigExtension {})?,
+        }))?;
 
     let mut submsgs: Vec<SubMsg> = vec![];
     match (token, endow_type) {
@@ -113,7 +139,7 @@ pub fn build_dao_token_messages(
         ) => submsgs.push(SubMsg {
             id: 1,
             msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
-                code_id: registrar_config.subdao_cw20_token_code.unwrap(),
+                code_id: registrar_config_ext.subdao_cw20_token_code.unwrap(),
                 admin: None,
                 label: "new endowment dao token(cw20) contract".to_string(),
                 msg: to_binary(&cw20_base::msg::InstantiateMsg {
@@ -147,7 +173,7 @@ pub fn build_dao_token_messages(
         ) => submsgs.push(SubMsg {
             id: 1,
             msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
-                code_id: registrar_config.subdao_bonding_token_code.unwrap(),
+                code_id: registrar_config_ext.subdao_bonding_token_code.unwrap(),
                 admin: None,
                 label: "new endowment dao token(bonding curve) contract".to_string(),
                 msg: to_binary(&DaoTokenInstantiateMsg {
@@ -158,6 +184,8 @@ pub fn build_dao_token_messages(
                     reserve_denom,
                     reserve_decimals,
                     unbonding_period,
+                    donor_match_split: None,
+                    fees: None,
                 })?,
                 funds: vec![],
             }),
@@ -178,7 +206,7 @@ pub fn build_dao_token_messages(
             EndowmentType::Charity,
         ) => {
             // setup DAO token contract
-            let halo_token = match registrar_config.halo_token.clone() {
+            let halo_token = match registrar_config_ext.halo_token.clone() {
                 Some(addr) => addr,
                 None => {
                     return Err(ContractError::Std(StdError::GenericErr {
@@ -189,7 +217,7 @@ pub fn build_dao_token_messages(
             submsgs.push(SubMsg {
                 id: 1,
                 msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
-                    code_id: registrar_config.subdao_bonding_token_code.unwrap(),
+                    code_id: registrar_config_ext.subdao_bonding_token_code.unwrap(),
                     admin: None,
                     label: "new endowment dao token(bonding curve) contract".to_string(),
                     msg: to_binary(&DaoTokenInstantiateMsg {
@@ -200,6 +228,8 @@ pub fn build_dao_token_messages(
                         reserve_denom: halo_token,
                         reserve_decimals: 6,
                         unbonding_period: 21,
+                        donor_match_split: None,
+                        fees: None,
                     })?,
                     funds: vec![],
                 }),
@@ -229,22 +259,30 @@ pub fn execute(
             owner,
             quorum,
             threshold,
+            veto_threshold,
             voting_period,
             timelock_period,
             expiration_period,
             proposal_deposit,
             snapshot_period,
+            deposit_policy,
+            slash_recipient,
+            max_open_polls,
         } => update_config(
             deps,
             info,
             owner,
             quorum,
             threshold,
+            veto_threshold,
             voting_period,
             timelock_period,
             expiration_period,
             proposal_deposit,
             snapshot_period,
+            deposit_policy,
+            slash_recipient,
+            max_open_polls,
         ),
         ExecuteMsg::CastVote { poll_id, vote } => cast_vote(deps, env, info, poll_id, vote),
         ExecuteMsg::EndPoll { poll_id } => end_poll(deps, env, poll_id),
@@ -281,6 +319,23 @@ pub fn receive_cw20(
             description,
             link,
             execute_msgs,
+            vec![],
+        ),
+        Ok(Cw20HookMsg::CreateMultipleChoicePoll {
+            title,
+            description,
+            link,
+            options,
+        }) => create_poll(
+            deps,
+            env,
+            cw20_msg.sender,
+            cw20_msg.amount,
+            title,
+            description,
+            link,
+            None,
+            options,
         ),
         _ => Err(ContractError::DataShouldBeGiven {}),
     }
@@ -385,11 +440,15 @@ pub fn update_config(
     owner: Option<String>,
     quorum: Option<Decimal>,
     threshold: Option<Decimal>,
+    veto_threshold: Option<Decimal>,
     voting_period: Option<u64>,
     timelock_period: Option<u64>,
     expiration_period: Option<u64>,
     proposal_deposit: Option<Uint128>,
     snapshot_period: Option<u64>,
+    deposit_policy: Option<DepositPolicy>,
+    slash_recipient: Option<String>,
+    max_open_polls: Option<u64>,
 ) -> Result<Response, ContractError> {
     let api = deps.api;
     config_store(deps.storage).update(|mut config| {
@@ -409,6 +468,11 @@ pub fn update_config(
             config.threshold = threshold;
         }
 
+        if let Some(veto_threshold) = veto_threshold {
+            validate_threshold(veto_threshold)?;
+            config.veto_threshold = veto_threshold;
+        }
+
         if let Some(voting_period) = voting_period {
             config.voting_period = voting_period;
         }
@@ -429,6 +493,20 @@ pub fn update_config(
             config.snapshot_period = period;
         }
 
+        if let Some(deposit_policy) = deposit_policy {
+            config.deposit_policy = deposit_policy;
+        }
+
+        if let Some(slash_recipient) = slash_recipient {
+            config.slash_recipient = Some(api.addr_validate(&slash_recipient)?);
+        }
+
+        if let Some(max_open_polls) = max_open_polls {
+            config.max_open_polls = Some(max_open_polls);
+        }
+
+        validate_deposit_policy(&config.deposit_policy, &config.slash_recipient)?;
+
         Ok(config)
     })?;
 
@@ -472,6 +550,24 @@ fn validate_link(link: &Option<String>) -> StdResult<()> {
     }
 }
 
+/// validate_options returns an error if the options of a multiple-choice poll are invalid
+fn validate_options(options: &[PollOptionMsg]) -> StdResult<()> {
+    if options.len() < MIN_POLL_OPTIONS {
+        Err(StdError::generic_err("Too few options"))
+    } else if options.len() > MAX_POLL_OPTIONS {
+        Err(StdError::generic_err("Too many options"))
+    } else if options.iter().any(|option| option.label.is_empty()) {
+        Err(StdError::generic_err("Option label too short"))
+    } else if options
+        .iter()
+        .any(|option| option.label.len() > MAX_TITLE_LENGTH)
+    {
+        Err(StdError::generic_err("Option label too long"))
+    } else {
+        Ok(())
+    }
+}
+
 /// validate_quorum returns an error if the quorum is invalid
 /// (we require 0-1)
 fn validate_quorum(quorum: Decimal) -> StdResult<()> {
@@ -492,6 +588,20 @@ fn validate_threshold(threshold: Decimal) -> StdResult<()> {
     }
 }
 
+/// validate_deposit_policy returns an error if deposits are slashed without a recipient
+fn validate_deposit_policy(
+    deposit_policy: &DepositPolicy,
+    slash_recipient: &Option<Addr>,
+) -> StdResult<()> {
+    if deposit_policy.slashes() && slash_recipient.is_none() {
+        Err(StdError::generic_err(
+            "Slash recipient must be set to slash deposits",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 /// create a new poll
 pub fn create_poll(
@@ -503,10 +613,14 @@ pub fn create_poll(
     description: String,
     link: Option<String>,
     execute_msgs: Option<Vec<PollExecuteMsg>>,
+    options: Vec<PollOptionMsg>,
 ) -> Result<Response, ContractError> {
     validate_title(&title)?;
     validate_description(&description)?;
     validate_link(&link)?;
+    if !options.is_empty() {
+        validate_options(&options)?;
+    }
 
     let config: Config = config_store(deps.storage).load()?;
     if deposit_amount < config.proposal_deposit {
@@ -515,6 +629,16 @@ pub fn create_poll(
         ));
     }
 
+    // Limit the polls an address can have in progress
+    let sender_addr_raw = deps.api.addr_validate(&proposer)?;
+    let open_polls = open_poll_count_read(deps.storage)
+        .may_load(sender_addr_raw.as_bytes())?
+        .unwrap_or_default();
+    if matches!(config.max_open_polls, Some(max_open_polls) if open_polls >= max_open_polls) {
+        return Err(ContractError::TooManyOpenPolls {});
+    }
+    open_poll_count_store(deps.storage).save(sender_addr_raw.as_bytes(), &(open_polls + 1))?;
+
     let mut state: State = state_store(deps.storage).load()?;
     let poll_id = state.poll_count + 1;
 
@@ -522,20 +646,17 @@ pub fn create_poll(
     state.poll_count += 1;
     state.total_deposit += deposit_amount;
 
-    let mut data_list: Vec<ExecuteData> = vec![];
-    let all_execute_data = if let Some(exe_msgs) = execute_msgs {
-        for msgs in exe_msgs {
-            let execute_data = ExecuteData {
-                order: msgs.order,
-                contract: deps.api.addr_validate(&msgs.contract)?,
-                msg: msgs.msg,
-            };
-            data_list.push(execute_data)
-        }
-        Some(data_list)
-    } else {
-        None
-    };
+    let all_execute_data = to_execute_data(deps.api, execute_msgs)?;
+    let options = options
+        .into_iter()
+        .map(|option| {
+            Ok(PollOption {
+                label: option.label,
+                votes: Uint128::zero(),
+                execute_data: to_execute_data(deps.api, option.execute_msgs)?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
 
     let staked_amount = query_total_voting_balance_at_timestamp(
         &deps.querier,
@@ -543,13 +664,14 @@ pub fn create_poll(
         Some(env.block.time.seconds()),
     )?;
 
-    let sender_addr_raw = deps.api.addr_validate(&proposer)?;
     let new_poll = Poll {
         id: poll_id,
         creator: sender_addr_raw,
         status: PollStatus::InProgress,
         yes_votes: Uint128::zero(),
         no_votes: Uint128::zero(),
+        abstain_votes: Uint128::zero(),
+        veto_votes: Uint128::zero(),
         start_time: env.block.time.seconds(),
         end_height: env.block.height + config.voting_period,
         title,
@@ -559,6 +681,9 @@ pub fn create_poll(
         deposit_amount,
         total_balance_at_end_poll: None,
         staked_amount: Some(staked_amount),
+        options,
+        winning_option: None,
+        deposit_pending: false,
     };
 
     poll_store(deps.storage).save(&poll_id.to_be_bytes(), &new_poll)?;
@@ -575,6 +700,26 @@ pub fn create_poll(
     ]))
 }
 
+fn to_execute_data(
+    api: &dyn Api,
+    execute_msgs: Option<Vec<PollExecuteMsg>>,
+) -> StdResult<Option<Vec<ExecuteData>>> {
+    let mut data_list: Vec<ExecuteData> = vec![];
+    if let Some(exe_msgs) = execute_msgs {
+        for msgs in exe_msgs {
+            let execute_data = ExecuteData {
+                order: msgs.order,
+                contract: api.addr_validate(&msgs.contract)?,
+                msg: msgs.msg,
+            };
+            data_list.push(execute_data)
+        }
+        Ok(Some(data_list))
+    } else {
+        Ok(None)
+    }
+}
+
 /*
  * Ends a poll.
  */
@@ -591,8 +736,17 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Contr
 
     let no = a_poll.no_votes.u128();
     let yes = a_poll.yes_votes.u128();
+    let abstain = a_poll.abstain_votes.u128();
+    let veto = a_poll.veto_votes.u128();
+
+    let choices: u128 = a_poll
+        .options
+        .iter()
+        .map(|option| option.votes.u128())
+        .sum();
 
-    let tallied_weight = yes + no;
+    // abstain votes count toward the quorum, but not toward the threshold
+    let tallied_weight = yes + no + abstain + veto + choices;
 
     let mut poll_status = PollStatus::Rejected;
     let mut rejected_reason = "";
@@ -613,12 +767,29 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Contr
         )
     };
 
-    if tallied_weight == 0 || quorum < config.quorum {
+    let deposit_outcome = if tallied_weight == 0 || quorum < config.quorum {
         // Quorum: More than quorum of the total staked tokens at the end of the voting
         // period need to have participated in the vote.
         rejected_reason = "Quorum not reached";
+        Some(&config.deposit_policy.quorum_not_reached)
+    } else if Decimal::from_ratio(veto, tallied_weight) > config.veto_threshold {
+        // Veto: More than the veto threshold of the tokens that participated in the vote
+        // vetoed the proposal. The poll is rejected.
+        rejected_reason = "Vetoed";
+        Some(&config.deposit_policy.vetoed)
     } else {
-        if Decimal::from_ratio(yes, tallied_weight) > config.threshold {
+        if !a_poll.options.is_empty() {
+            // Plurality: the option with the most votes wins, a tie rejects the poll
+            a_poll.winning_option = a_poll.plurality_winner();
+            if a_poll.winning_option.is_some() {
+                poll_status = PollStatus::Passed;
+                passed = true;
+            } else {
+                rejected_reason = "No winning option";
+            }
+        } else if yes + no + veto != 0
+            && Decimal::from_ratio(yes, yes + no + veto) > config.threshold
+        {
             //Threshold: More than 50% of the tokens that participated in the vote
             // (after excluding “Abstain” votes) need to have voted in favor of the proposal (“Yes”).
             poll_status = PollStatus::Passed;
@@ -627,22 +798,28 @@ pub fn end_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Contr
             rejected_reason = "Threshold not reached";
         }
 
-        // Refunds deposit only when quorum is reached
-        if !a_poll.deposit_amount.is_zero() {
-            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: config.dao_token.to_string(),
-                funds: vec![],
-                msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: a_poll.creator.to_string(),
-                    amount: a_poll.deposit_amount,
-                })?,
-            }))
+        if !passed {
+            Some(&config.deposit_policy.rejected)
+        } else if a_poll.executable_data().is_some() {
+            // The deposit of a passed poll is settled once it is executed or expires
+            a_poll.deposit_pending = true;
+            None
+        } else {
+            Some(&config.deposit_policy.passed)
         }
+    };
+
+    if let Some(outcome) = deposit_outcome {
+        messages.extend(settle_deposit(&config, &a_poll, outcome)?);
+        // Decrease total deposit amount
+        state.total_deposit = state.total_deposit.checked_sub(a_poll.deposit_amount)?;
+        state_store(deps.storage).save(&state)?;
     }
 
-    // Decrease total deposit amount
-    state.total_deposit = state.total_deposit.checked_sub(a_poll.deposit_amount)?;
-    state_store(deps.storage).save(&state)?;
+    // The poll is no longer in progress for its creator
+    open_poll_count_store(deps.storage).update(a_poll.creator.as_bytes(), |count| {
+        Ok::<_, ContractError>(count.unwrap_or_default().saturating_sub(1))
+    })?;
 
     // Update poll indexer
     poll_indexer_store(deps.storage, &PollStatus::InProgress).remove(&a_poll.id.to_be_bytes());
@@ -679,11 +856,20 @@ pub fn execute_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, C
     poll_indexer_store(deps.storage, &PollStatus::Passed).remove(&poll_id.to_be_bytes());
     poll_indexer_store(deps.storage, &PollStatus::Executed).save(&poll_id.to_be_bytes(), &true)?;
 
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if a_poll.deposit_pending {
+        messages.extend(release_pending_deposit(
+            deps.storage,
+            &config,
+            &mut a_poll,
+            &config.deposit_policy.passed,
+        )?);
+    }
+
     a_poll.status = PollStatus::Executed;
     poll_store(deps.storage).save(&poll_id.to_be_bytes(), &a_poll)?;
 
-    let mut messages: Vec<CosmosMsg> = vec![];
-    if let Some(all_msgs) = a_poll.execute_data {
+    if let Some(all_msgs) = a_poll.executable_data().cloned() {
         let mut msgs = all_msgs;
         msgs.sort();
         for msg in msgs {
@@ -712,7 +898,7 @@ pub fn expire_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Co
         return Err(ContractError::PollNotPassed {});
     }
 
-    if a_poll.execute_data.is_none() {
+    if a_poll.executable_data().is_none() {
         return Err(ContractError::NoExecuteData {});
     }
 
@@ -723,15 +909,71 @@ pub fn expire_poll(deps: DepsMut, env: Env, poll_id: u64) -> Result<Response, Co
     poll_indexer_store(deps.storage, &PollStatus::Passed).remove(&poll_id.to_be_bytes());
     poll_indexer_store(deps.storage, &PollStatus::Expired).save(&poll_id.to_be_bytes(), &true)?;
 
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if a_poll.deposit_pending {
+        messages.extend(release_pending_deposit(
+            deps.storage,
+            &config,
+            &mut a_poll,
+            &config.deposit_policy.expired,
+        )?);
+    }
+
     a_poll.status = PollStatus::Expired;
     poll_store(deps.storage).save(&poll_id.to_be_bytes(), &a_poll)?;
 
-    Ok(Response::new().add_attributes(vec![
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
         ("action", "expire_poll"),
         ("poll_id", &poll_id.to_string()),
     ]))
 }
 
+/// Builds the message sending a poll's deposit where the outcome says, if anywhere
+fn settle_deposit(
+    config: &Config,
+    poll: &Poll,
+    outcome: &DepositOutcome,
+) -> StdResult<Option<CosmosMsg>> {
+    let amount = poll.deposit_amount;
+    let msg = match outcome {
+        _ if amount.is_zero() => return Ok(None),
+        DepositOutcome::Keep => return Ok(None),
+        DepositOutcome::Refund => Cw20ExecuteMsg::Transfer {
+            recipient: poll.creator.to_string(),
+            amount,
+        },
+        DepositOutcome::Slash => Cw20ExecuteMsg::Transfer {
+            recipient: config
+                .slash_recipient
+                .as_ref()
+                .ok_or_else(|| StdError::generic_err("Slash recipient is not set"))?
+                .to_string(),
+            amount,
+        },
+        DepositOutcome::Burn => Cw20ExecuteMsg::Burn { amount },
+    };
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.dao_token.to_string(),
+        msg: to_binary(&msg)?,
+        funds: vec![],
+    })))
+}
+
+/// Settles the deposit held since a poll passed
+fn release_pending_deposit(
+    storage: &mut dyn Storage,
+    config: &Config,
+    poll: &mut Poll,
+    outcome: &DepositOutcome,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    let mut state: State = state_read(storage).load()?;
+    state.total_deposit = state.total_deposit.checked_sub(poll.deposit_amount)?;
+    state_store(storage).save(&state)?;
+
+    poll.deposit_pending = false;
+    Ok(settle_deposit(config, poll, outcome)?)
+}
+
 pub fn cast_vote(
     deps: DepsMut,
     env: Env,
@@ -768,11 +1010,7 @@ pub fn cast_vote(
     )?;
 
     // update tally info
-    if VoteOption::Yes == vote {
-        a_poll.yes_votes += amount;
-    } else {
-        a_poll.no_votes += amount;
-    }
+    *a_poll.votes_mut(&vote)? += amount;
 
     let vote_info = VoterInfo {
         vote,
@@ -833,11 +1071,15 @@ fn query_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
         swap_factory: config.swap_factory.to_string(),
         quorum: config.quorum,
         threshold: config.threshold,
+        veto_threshold: config.veto_threshold,
         voting_period: config.voting_period,
         timelock_period: config.timelock_period,
         expiration_period: config.expiration_period,
         proposal_deposit: config.proposal_deposit,
         snapshot_period: config.snapshot_period,
+        deposit_policy: config.deposit_policy,
+        slash_recipient: config.slash_recipient.map(|addr| addr.to_string()),
+        max_open_polls: config.max_open_polls,
     })
 }
 
@@ -884,8 +1126,12 @@ fn query_poll(deps: Deps, poll_id: u64) -> Result<PollResponse, ContractError> {
         },
         yes_votes: poll.yes_votes,
         no_votes: poll.no_votes,
+        abstain_votes: poll.abstain_votes,
+        veto_votes: poll.veto_votes,
         staked_amount: poll.staked_amount,
         total_balance_at_end_poll: poll.total_balance_at_end_poll,
+        options: poll_options_response(&poll.options),
+        winning_option: poll.winning_option,
     })
 }
 
@@ -928,8 +1174,12 @@ fn query_polls(
                 },
                 yes_votes: poll.yes_votes,
                 no_votes: poll.no_votes,
+                abstain_votes: poll.abstain_votes,
+                veto_votes: poll.veto_votes,
                 staked_amount: poll.staked_amount,
                 total_balance_at_end_poll: poll.total_balance_at_end_poll,
+                options: poll_options_response(&poll.options),
+                winning_option: poll.winning_option,
             })
         })
         .collect();
@@ -939,6 +1189,26 @@ fn query_polls(
     })
 }
 
+fn poll_options_response(options: &[PollOption]) -> Vec<PollOptionResponse> {
+    options
+        .iter()
+        .map(|option| PollOptionResponse {
+            label: option.label.clone(),
+            votes: option.votes,
+            execute_data: option.execute_data.as_ref().map(|exe_msgs| {
+                exe_msgs
+                    .iter()
+                    .map(|msg| PollExecuteMsg {
+                        order: msg.order,
+                        contract: msg.contract.to_string(),
+                        msg: msg.msg.clone(),
+                    })
+                    .collect()
+            }),
+        })
+        .collect()
+}
+
 fn query_voters(
     deps: Deps,
     poll_id: u64,
@@ -1,3 +1,9 @@
 pub mod contract;
 pub mod querier;
 pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(test)]
+mod mock_querier;
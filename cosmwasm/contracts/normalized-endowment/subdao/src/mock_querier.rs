@@ -1,8 +1,13 @@
+use angel_core::msgs::registrar::{
+    ConfigExtensionResponse as RegistrarConfigExtensionResponse,
+    ConfigResponse as RegistrarConfigResponse,
+};
 use angel_core::msgs::ve_token::{StakerResponse, StateResponse};
-use cosmwasm_schema::{cw_serde, QueryResponses};
+use angel_core::structs::{AcceptedTokens, RebalanceDetails, SplitDetails};
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, from_slice, to_binary, Coin, ContractResult, Empty, OwnedDeps, Querier,
+    from_binary, from_slice, to_binary, Coin, ContractResult, Decimal, Empty, OwnedDeps, Querier,
     QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
@@ -90,6 +95,9 @@ pub enum QueryMsg {
         address: String,
         timestamp: Option<u64>,
     },
+    // Registrar queries
+    Config {},
+    ConfigExtension {},
 }
 
 impl WasmMockQuerier {
@@ -121,7 +129,7 @@ impl WasmMockQuerier {
             //     }
             // }
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
-                match from_binary(&msg) {
+                match from_binary(msg) {
                     // Ok(QueryMsg::Pair { asset_infos }) => {
                     //     let key = asset_infos[0].to_string() + asset_infos[1].to_string().as_str();
                     //     match self.terraswap_factory_querier.pairs.get(&key) {
@@ -199,7 +207,53 @@ impl WasmMockQuerier {
                         ))
                     }
 
-                    _ => match from_binary(&msg).unwrap() {
+                    Ok(QueryMsg::Config {}) => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&RegistrarConfigResponse {
+                            owner: "registrar_owner".to_string(),
+                            version: "0.1.0".to_string(),
+                            treasury: "treasury".to_string(),
+                            rebalance: RebalanceDetails::default(),
+                            split_to_liquid: SplitDetails {
+                                min: Decimal::zero(),
+                                max: Decimal::one(),
+                                default: Decimal::percent(50),
+                            },
+                            accepted_tokens: AcceptedTokens {
+                                native: vec!["ujuno".to_string()],
+                                cw20: vec!["test-cw20".to_string()],
+                            },
+                            axelar_gateway: "axelar-gateway".to_string(),
+                            axelar_ibc_channel: "channel-1".to_string(),
+                            axelar_chain_id: "juno".to_string(),
+                        })
+                        .unwrap(),
+                    )),
+                    Ok(QueryMsg::ConfigExtension {}) => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&RegistrarConfigExtensionResponse {
+                            index_fund: None,
+                            accounts_contract: Some("accounts-contract".to_string()),
+                            subdao_gov_code: Some(333),
+                            subdao_cw20_token_code: Some(4_u64),
+                            subdao_bonding_token_code: Some(3_u64),
+                            subdao_cw900_code: None,
+                            subdao_distributor_code: None,
+                            donation_match_code: None,
+                            donation_match_charites_contract: None,
+                            collector_addr: "collector-addr".to_string(),
+                            halo_token: Some("halo_token".to_string()),
+                            halo_token_lp_contract: None,
+                            gov_contract: None,
+                            charity_shares_contract: None,
+                            cw3_code: None,
+                            cw4_code: None,
+                            swap_factory: None,
+                            applications_review: "applications-review".to_string(),
+                            swaps_router: None,
+                            accounts_settings_controller: None,
+                        })
+                        .unwrap(),
+                    )),
+                    _ => match from_binary(msg).unwrap() {
                         Cw20QueryMsg::Balance { address } => {
                             let balances: &HashMap<String, Uint128> =
                                 match self.token_querier.balances.get(contract_addr) {
@@ -1,5 +1,6 @@
 use angel_core::common::OrderBy;
-use angel_core::msgs::subdao::{PollStatus, VoterInfo};
+use angel_core::errors::dao::ContractError;
+use angel_core::msgs::subdao::{DepositPolicy, PollStatus, VoteOption, VoterInfo};
 use angel_core::utils::{
     calc_range_end, calc_range_end_addr, calc_range_start, calc_range_start_addr,
 };
@@ -17,6 +18,7 @@ static KEY_STATE: &[u8] = b"state";
 static PREFIX_POLL_INDEXER: &[u8] = b"poll_indexer";
 static PREFIX_POLL_VOTER: &[u8] = b"poll_voter";
 static PREFIX_POLL: &[u8] = b"poll";
+static PREFIX_OPEN_POLL_COUNT: &[u8] = b"open_poll_count";
 
 #[cw_serde]
 pub struct Config {
@@ -27,11 +29,27 @@ pub struct Config {
     pub swap_factory: Addr,
     pub quorum: Decimal,
     pub threshold: Decimal,
+    /// Share of the tallied weight that has to veto a poll to reject it & burn its deposit
+    #[serde(default = "default_veto_threshold")]
+    pub veto_threshold: Decimal,
     pub voting_period: u64,
     pub timelock_period: u64,
     pub expiration_period: u64,
     pub proposal_deposit: Uint128,
     pub snapshot_period: u64,
+    /// What happens to the proposal deposit for each poll result
+    #[serde(default)]
+    pub deposit_policy: DepositPolicy,
+    #[serde(default)]
+    pub slash_recipient: Option<Addr>,
+    /// Most polls a single address can have in progress at once
+    #[serde(default)]
+    pub max_open_polls: Option<u64>,
+}
+
+/// Veto threshold of the configs stored before it could be configured, and of new DAOs that don't set one
+pub fn default_veto_threshold() -> Decimal {
+    Decimal::permille(334)
 }
 
 #[cw_serde]
@@ -54,6 +72,10 @@ pub struct Poll {
     pub status: PollStatus,
     pub yes_votes: Uint128,
     pub no_votes: Uint128,
+    #[serde(default)]
+    pub abstain_votes: Uint128,
+    #[serde(default)]
+    pub veto_votes: Uint128,
     pub start_time: u64,
     pub end_height: u64,
     pub title: String,
@@ -64,6 +86,67 @@ pub struct Poll {
     /// Total balance at the end poll
     pub total_balance_at_end_poll: Option<Uint128>,
     pub staked_amount: Option<Uint128>,
+    /// Options of a multiple-choice poll, empty for a yes/no poll
+    #[serde(default)]
+    pub options: Vec<PollOption>,
+    #[serde(default)]
+    pub winning_option: Option<u32>,
+    /// Deposit of a passed poll, held until the poll is executed or expires
+    #[serde(default)]
+    pub deposit_pending: bool,
+}
+
+impl Poll {
+    /// Tally of the votes cast with the given option. Multiple-choice polls take
+    /// a choice or an abstain vote, yes/no polls take the other options.
+    pub fn votes_mut(&mut self, vote: &VoteOption) -> Result<&mut Uint128, ContractError> {
+        match (vote, self.options.is_empty()) {
+            (VoteOption::Abstain, _) => Ok(&mut self.abstain_votes),
+            (VoteOption::Yes, true) => Ok(&mut self.yes_votes),
+            (VoteOption::No, true) => Ok(&mut self.no_votes),
+            (VoteOption::Veto, true) => Ok(&mut self.veto_votes),
+            (VoteOption::Choice(index), false) => self
+                .options
+                .get_mut(*index as usize)
+                .map(|option| &mut option.votes)
+                .ok_or(ContractError::InvalidVoteOption {}),
+            _ => Err(ContractError::InvalidVoteOption {}),
+        }
+    }
+
+    /// Index of the option of a multiple-choice poll with the most votes,
+    /// if a single option has them
+    pub fn plurality_winner(&self) -> Option<u32> {
+        let most_votes = self.options.iter().map(|option| option.votes).max()?;
+        let mut leaders = self
+            .options
+            .iter()
+            .enumerate()
+            .filter(|(_, option)| option.votes == most_votes);
+        match (leaders.next(), leaders.next()) {
+            (Some((index, _)), None) if !most_votes.is_zero() => Some(index as u32),
+            _ => None,
+        }
+    }
+
+    /// Messages run once the poll is executed: the winning option's ones
+    /// on a multiple-choice poll
+    pub fn executable_data(&self) -> Option<&Vec<ExecuteData>> {
+        match self.winning_option {
+            Some(index) => self
+                .options
+                .get(index as usize)
+                .and_then(|option| option.execute_data.as_ref()),
+            None => self.execute_data.as_ref(),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct PollOption {
+    pub label: String,
+    pub votes: Uint128,
+    pub execute_data: Option<Vec<ExecuteData>>,
 }
 
 #[cw_serde]
@@ -86,12 +169,6 @@ impl PartialOrd for ExecuteData {
     }
 }
 
-impl PartialEq for ExecuteData {
-    fn eq(&self, other: &Self) -> bool {
-        self.order == other.order
-    }
-}
-
 pub fn config_store(storage: &mut dyn Storage) -> Singleton<Config> {
     singleton(storage, KEY_CONFIG)
 }
@@ -126,6 +203,15 @@ pub fn poll_indexer_store<'a>(
     )
 }
 
+/// Number of polls in progress created by each address
+pub fn open_poll_count_store(storage: &mut dyn Storage) -> Bucket<u64> {
+    bucket(storage, PREFIX_OPEN_POLL_COUNT)
+}
+
+pub fn open_poll_count_read(storage: &dyn Storage) -> ReadonlyBucket<u64> {
+    bucket_read(storage, PREFIX_OPEN_POLL_COUNT)
+}
+
 pub fn poll_voter_store(storage: &mut dyn Storage, poll_id: u64) -> Bucket<VoterInfo> {
     Bucket::multilevel(storage, &[PREFIX_POLL_VOTER, &poll_id.to_be_bytes()])
 }
@@ -1,18 +1,19 @@
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, query, reply};
+use crate::mock_querier::mock_dependencies;
 use crate::state::{config_read, poll_voter_read, state_read, Config, State};
 use angel_core::common::OrderBy;
 use angel_core::errors::dao::ContractError;
 use angel_core::msgs::subdao::{
-    ConfigResponse, Cw20HookMsg, DaoBondingConfig, DaoToken, ExecuteMsg, InstantiateMsg,
-    PollExecuteMsg, PollResponse, PollStatus, PollsResponse, QueryMsg, VoteOption, VoterInfo,
-    VotersResponse, VotersResponseItem,
+    ConfigResponse, Cw20HookMsg, DepositOutcome, DepositPolicy, ExecuteMsg, InstantiateMsg,
+    PollExecuteMsg, PollOptionMsg, PollResponse, PollStatus, PollsResponse, QueryMsg, VoteOption,
+    VoterInfo, VotersResponse, VotersResponseItem,
 };
 use angel_core::msgs::subdao_bonding_token::CurveType;
-use angel_core::structs::EndowmentType;
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use angel_core::structs::{DaoToken, EndowmentType};
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    attr, coins, from_binary, to_binary, Addr, CosmosMsg, Decimal, Deps, DepsMut, Env, Response,
-    StdError, SubMsg, Timestamp, Uint128, WasmMsg,
+    attr, coins, from_binary, to_binary, Addr, CosmosMsg, Decimal, Deps, DepsMut, Env, Event,
+    Reply, Response, StdError, SubMsg, SubMsgResponse, SubMsgResult, Timestamp, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
@@ -25,25 +26,31 @@ const TEST_VOTER_2: &str = "voter2";
 // const TEST_VOTER_3: &str = "voter3";
 const DEFAULT_QUORUM: u64 = 30u64;
 const DEFAULT_THRESHOLD: u64 = 50u64;
+const DEFAULT_VETO_THRESHOLD: u64 = 33u64;
 const DEFAULT_VOTING_PERIOD: u64 = 10000u64;
 const DEFAULT_FIX_PERIOD: u64 = 10u64;
 const DEFAULT_TIMELOCK_PERIOD: u64 = 10000u64;
 const DEFAULT_EXPIRATION_PERIOD: u64 = 20000u64;
 const DEFAULT_PROPOSAL_DEPOSIT: u128 = 10000000000u128;
 
-fn mock_instantiate(deps: DepsMut) {
+fn mock_instantiate(mut deps: DepsMut) {
     let msg = InstantiateMsg {
+        id: 1,
         quorum: Decimal::percent(DEFAULT_QUORUM),
         threshold: Decimal::percent(DEFAULT_THRESHOLD),
+        veto_threshold: Some(Decimal::percent(DEFAULT_VETO_THRESHOLD)),
         voting_period: DEFAULT_VOTING_PERIOD,
         timelock_period: DEFAULT_TIMELOCK_PERIOD,
         expiration_period: DEFAULT_EXPIRATION_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
         snapshot_period: DEFAULT_FIX_PERIOD,
+        deposit_policy: None,
+        slash_recipient: None,
+        max_open_polls: None,
         endow_type: EndowmentType::Charity,
         endow_owner: TEST_CREATOR.to_string(),
         registrar_contract: TEST_CREATOR.to_string(),
-        token: DaoToken::NewBondingCurve(DaoBondingConfig {
+        token: DaoToken::BondingCurve {
             curve_type: CurveType::SquareRoot {
                 slope: Uint128::from(19307000u64),
                 power: Uint128::from(428571429u64),
@@ -51,16 +58,30 @@ fn mock_instantiate(deps: DepsMut) {
             },
             name: String::from("AP Endowment DAO Token"),
             symbol: String::from("APEDT"),
-            decimals: None,
-            reserve_decimals: None,
-            reserve_denom: None,
-            unbonding_period: None,
-        }),
+            decimals: 6,
+            reserve_denom: String::from("halo"),
+            reserve_decimals: 6,
+            unbonding_period: 21,
+        },
     };
 
     let info = mock_info(TEST_CREATOR, &[]);
-    let _res = instantiate(deps, mock_env(), info, msg)
+    let _res = instantiate(deps.branch(), mock_env(), info, msg)
         .expect("contract successfully executes instantiateMsg");
+    mock_dao_token_reply(deps);
+}
+
+// the DAO token contract instantiated by the DAO replies with its address
+fn mock_dao_token_reply(deps: DepsMut) {
+    let msg = Reply {
+        id: 1,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![Event::new("instantiate").add_attribute("_contract_address", VOTING_TOKEN)],
+            data: None,
+        }),
+    };
+    let _res =
+        reply(deps, mock_env(), msg).expect("contract successfully handles the DAO token reply");
 }
 
 fn mock_register_contracts(deps: DepsMut) {
@@ -82,17 +103,22 @@ fn mock_env_height(height: u64, time: u64) -> Env {
 
 fn instantiate_msg() -> InstantiateMsg {
     InstantiateMsg {
+        id: 1,
         quorum: Decimal::percent(DEFAULT_QUORUM),
         threshold: Decimal::percent(DEFAULT_THRESHOLD),
+        veto_threshold: Some(Decimal::percent(DEFAULT_VETO_THRESHOLD)),
         voting_period: DEFAULT_VOTING_PERIOD,
         timelock_period: DEFAULT_TIMELOCK_PERIOD,
         expiration_period: DEFAULT_EXPIRATION_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
         snapshot_period: DEFAULT_FIX_PERIOD,
+        deposit_policy: None,
+        slash_recipient: None,
+        max_open_polls: None,
         endow_type: EndowmentType::Charity,
         endow_owner: TEST_CREATOR.to_string(),
         registrar_contract: TEST_CREATOR.to_string(),
-        token: DaoToken::NewBondingCurve(DaoBondingConfig {
+        token: DaoToken::BondingCurve {
             curve_type: CurveType::SquareRoot {
                 slope: Uint128::from(19307000u64),
                 power: Uint128::from(428571429u64),
@@ -100,17 +126,17 @@ fn instantiate_msg() -> InstantiateMsg {
             },
             name: String::from("AP Endowment DAO Token"),
             symbol: String::from("APEDT"),
-            decimals: None,
-            reserve_decimals: None,
-            reserve_denom: None,
-            unbonding_period: None,
-        }),
+            decimals: 6,
+            reserve_denom: String::from("halo"),
+            reserve_decimals: 6,
+            unbonding_period: 21,
+        },
     }
 }
 
 #[test]
 fn proper_initialization() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies(&[]);
 
     let msg = instantiate_msg();
     let info = mock_info(TEST_CREATOR, &coins(2, VOTING_TOKEN));
@@ -127,28 +153,36 @@ fn proper_initialization() {
             owner: Addr::unchecked(TEST_CREATOR),
             quorum: Decimal::percent(DEFAULT_QUORUM),
             threshold: Decimal::percent(DEFAULT_THRESHOLD),
+            veto_threshold: Decimal::percent(DEFAULT_VETO_THRESHOLD),
             voting_period: DEFAULT_VOTING_PERIOD,
             timelock_period: DEFAULT_TIMELOCK_PERIOD,
             expiration_period: DEFAULT_EXPIRATION_PERIOD,
             proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
             snapshot_period: DEFAULT_FIX_PERIOD,
             registrar_contract: Addr::unchecked(TEST_CREATOR),
+            deposit_policy: DepositPolicy::default(),
+            slash_recipient: None,
+            max_open_polls: None,
         }
     );
 
+    mock_dao_token_reply(deps.as_mut());
+    let config: Config = config_read(deps.as_ref().storage).load().unwrap();
+    assert_eq!(config.dao_token, Addr::unchecked(VOTING_TOKEN));
+
     let msg = ExecuteMsg::RegisterContracts {
         ve_token: VE_TOKEN.to_string(),
         swap_factory: TERRASWAP_FACTORY.to_string(),
     };
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     let config: Config = config_read(deps.as_ref().storage).load().unwrap();
-    assert_eq!(config.dao_token, Addr::unchecked(VOTING_TOKEN),);
+    assert_eq!(config.ve_token, Addr::unchecked(VE_TOKEN));
+    assert_eq!(config.swap_factory, Addr::unchecked(TERRASWAP_FACTORY));
 
     let state: State = state_read(deps.as_ref().storage).load().unwrap();
     assert_eq!(
         state,
         State {
-            contract_addr: Addr::unchecked(MOCK_CONTRACT_ADDR),
             poll_count: 0,
             total_deposit: Uint128::zero(),
             total_share: Uint128::zero(),
@@ -156,6 +190,21 @@ fn proper_initialization() {
     );
 }
 
+#[test]
+fn veto_threshold_defaults() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        veto_threshold: None,
+        ..instantiate_msg()
+    };
+    let info = mock_info(TEST_CREATOR, &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let config: Config = config_read(deps.as_ref().storage).load().unwrap();
+    assert_eq!(config.veto_threshold, Decimal::permille(334));
+}
+
 #[test]
 fn poll_not_found() {
     let mut deps = mock_dependencies(&[]);
@@ -176,17 +225,22 @@ fn fails_init_invalid_quorum() {
     let mut deps = mock_dependencies(&[]);
     let info = mock_info("voter", &coins(11, VOTING_TOKEN));
     let msg = InstantiateMsg {
+        id: 1,
         quorum: Decimal::percent(101),
         threshold: Decimal::percent(DEFAULT_THRESHOLD),
+        veto_threshold: Some(Decimal::percent(DEFAULT_VETO_THRESHOLD)),
         voting_period: DEFAULT_VOTING_PERIOD,
         timelock_period: DEFAULT_TIMELOCK_PERIOD,
         expiration_period: DEFAULT_EXPIRATION_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
         snapshot_period: DEFAULT_FIX_PERIOD,
+        deposit_policy: None,
+        slash_recipient: None,
+        max_open_polls: None,
         endow_type: EndowmentType::Charity,
         endow_owner: TEST_CREATOR.to_string(),
         registrar_contract: TEST_CREATOR.to_string(),
-        token: DaoToken::NewBondingCurve(DaoBondingConfig {
+        token: DaoToken::BondingCurve {
             curve_type: CurveType::SquareRoot {
                 slope: Uint128::from(19307000u64),
                 power: Uint128::from(428571429u64),
@@ -194,11 +248,11 @@ fn fails_init_invalid_quorum() {
             },
             name: String::from("AP Endowment DAO Token"),
             symbol: String::from("APEDT"),
-            decimals: None,
-            reserve_decimals: None,
-            reserve_denom: None,
-            unbonding_period: None,
-        }),
+            decimals: 6,
+            reserve_denom: String::from("halo"),
+            reserve_decimals: 6,
+            unbonding_period: 21,
+        },
     };
 
     let res = instantiate(deps.as_mut(), mock_env(), info, msg);
@@ -217,17 +271,22 @@ fn fails_init_invalid_threshold() {
     let mut deps = mock_dependencies(&[]);
     let info = mock_info("voter", &coins(11, VOTING_TOKEN));
     let msg = InstantiateMsg {
+        id: 1,
         quorum: Decimal::percent(DEFAULT_QUORUM),
         threshold: Decimal::percent(101),
+        veto_threshold: Some(Decimal::percent(DEFAULT_VETO_THRESHOLD)),
         voting_period: DEFAULT_VOTING_PERIOD,
         timelock_period: DEFAULT_TIMELOCK_PERIOD,
         expiration_period: DEFAULT_EXPIRATION_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
         snapshot_period: DEFAULT_FIX_PERIOD,
+        deposit_policy: None,
+        slash_recipient: None,
+        max_open_polls: None,
         endow_type: EndowmentType::Charity,
         endow_owner: TEST_CREATOR.to_string(),
         registrar_contract: TEST_CREATOR.to_string(),
-        token: DaoToken::NewBondingCurve(DaoBondingConfig {
+        token: DaoToken::BondingCurve {
             curve_type: CurveType::SquareRoot {
                 slope: Uint128::from(19307000u64),
                 power: Uint128::from(428571429u64),
@@ -235,11 +294,11 @@ fn fails_init_invalid_threshold() {
             },
             name: String::from("AP Endowment DAO Token"),
             symbol: String::from("APEDT"),
-            decimals: None,
-            reserve_decimals: None,
-            reserve_denom: None,
-            unbonding_period: None,
-        }),
+            decimals: 6,
+            reserve_denom: String::from("halo"),
+            reserve_decimals: 6,
+            unbonding_period: 21,
+        },
     };
 
     let res = instantiate(deps.as_mut(), mock_env(), info, msg);
@@ -254,21 +313,26 @@ fn fails_init_invalid_threshold() {
 }
 
 #[test]
-fn fails_contract_already_registered() {
+fn fails_register_contracts_unauthorized() {
     let mut deps = mock_dependencies(&[]);
     let info = mock_info("voter", &coins(11, VOTING_TOKEN));
     let msg = InstantiateMsg {
+        id: 1,
         quorum: Decimal::percent(DEFAULT_QUORUM),
         threshold: Decimal::percent(DEFAULT_THRESHOLD),
+        veto_threshold: Some(Decimal::percent(DEFAULT_VETO_THRESHOLD)),
         voting_period: DEFAULT_VOTING_PERIOD,
         timelock_period: DEFAULT_TIMELOCK_PERIOD,
         expiration_period: DEFAULT_EXPIRATION_PERIOD,
         proposal_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
         snapshot_period: DEFAULT_FIX_PERIOD,
+        deposit_policy: None,
+        slash_recipient: None,
+        max_open_polls: None,
         endow_type: EndowmentType::Charity,
         endow_owner: TEST_CREATOR.to_string(),
         registrar_contract: TEST_CREATOR.to_string(),
-        token: DaoToken::NewBondingCurve(DaoBondingConfig {
+        token: DaoToken::BondingCurve {
             curve_type: CurveType::SquareRoot {
                 slope: Uint128::from(19307000u64),
                 power: Uint128::from(428571429u64),
@@ -276,11 +340,11 @@ fn fails_contract_already_registered() {
             },
             name: String::from("AP Endowment DAO Token"),
             symbol: String::from("APEDT"),
-            decimals: None,
-            reserve_decimals: None,
-            reserve_denom: None,
-            unbonding_period: None,
-        }),
+            decimals: 6,
+            reserve_denom: String::from("halo"),
+            reserve_decimals: 6,
+            unbonding_period: 21,
+        },
     };
 
     let _res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
@@ -289,8 +353,8 @@ fn fails_contract_already_registered() {
         ve_token: VE_TOKEN.to_string(),
         swap_factory: TERRASWAP_FACTORY.to_string(),
     };
-    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
-    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg);
     match res {
         Ok(_) => panic!("Must return error"),
         Err(ContractError::Unauthorized { .. }) => {}
@@ -544,8 +608,12 @@ fn query_polls() {
                 execute_data: Some(execute_msgs.clone()),
                 yes_votes: Uint128::zero(),
                 no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
+                veto_votes: Uint128::zero(),
                 staked_amount: Some(Uint128::zero()),
                 total_balance_at_end_poll: None,
+                options: vec![],
+                winning_option: None,
             },
             PollResponse {
                 id: 2u64,
@@ -560,8 +628,12 @@ fn query_polls() {
                 execute_data: None,
                 yes_votes: Uint128::zero(),
                 no_votes: Uint128::zero(),
+                abstain_votes: Uint128::zero(),
+                veto_votes: Uint128::zero(),
                 staked_amount: Some(Uint128::zero()),
                 total_balance_at_end_poll: None,
+                options: vec![],
+                winning_option: None,
             },
         ]
     );
@@ -593,8 +665,12 @@ fn query_polls() {
             execute_data: None,
             yes_votes: Uint128::zero(),
             no_votes: Uint128::zero(),
+            abstain_votes: Uint128::zero(),
+            veto_votes: Uint128::zero(),
             staked_amount: Some(Uint128::zero()),
             total_balance_at_end_poll: None,
+            options: vec![],
+            winning_option: None,
         },]
     );
 
@@ -625,8 +701,12 @@ fn query_polls() {
             execute_data: Some(execute_msgs),
             yes_votes: Uint128::zero(),
             no_votes: Uint128::zero(),
+            abstain_votes: Uint128::zero(),
+            veto_votes: Uint128::zero(),
             staked_amount: Some(Uint128::zero()),
             total_balance_at_end_poll: None,
+            options: vec![],
+            winning_option: None,
         }]
     );
 
@@ -657,8 +737,12 @@ fn query_polls() {
             execute_data: None,
             yes_votes: Uint128::zero(),
             no_votes: Uint128::zero(),
+            abstain_votes: Uint128::zero(),
+            veto_votes: Uint128::zero(),
             staked_amount: Some(Uint128::zero()),
             total_balance_at_end_poll: None,
+            options: vec![],
+            winning_option: None,
         },]
     );
 
@@ -882,18 +966,8 @@ fn happy_days_end_poll() {
             attr("passed", "true"),
         ]
     );
-    assert_eq!(
-        execute_res.messages,
-        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: VOTING_TOKEN.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: TEST_CREATOR.to_string(),
-                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
-            })
-            .unwrap(),
-            funds: vec![],
-        }))]
-    );
+    // The deposit of a poll with execute msgs is settled when it is executed or expires
+    assert_eq!(execute_res.messages, vec![]);
 
     // End poll will withdraw deposit balance
     deps.querier.with_token_balances(&[(
@@ -924,6 +998,15 @@ fn happy_days_end_poll() {
     assert_eq!(
         execute_res.messages,
         vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: VOTING_TOKEN.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: TEST_CREATOR.to_string(),
+                    amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
             SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: VOTING_TOKEN.to_string(),
                 msg: exec_msg_bz,
@@ -1139,18 +1222,8 @@ fn expire_poll() {
             attr("passed", "true"),
         ]
     );
-    assert_eq!(
-        execute_res.messages,
-        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: VOTING_TOKEN.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: TEST_CREATOR.to_string(),
-                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
-            })
-            .unwrap(),
-            funds: vec![],
-        }))]
-    );
+    // The deposit of a poll with execute msgs is settled when it is executed or expires
+    assert_eq!(execute_res.messages, vec![]);
 
     // Expiration period has not been passed
     let msg = ExecuteMsg::ExpirePoll { poll_id: 1 };
@@ -1167,7 +1240,19 @@ fn expire_poll() {
 
     creator_env.block.height += DEFAULT_EXPIRATION_PERIOD;
     let msg = ExecuteMsg::ExpirePoll { poll_id: 1 };
-    let _execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
+    let execute_res = execute(deps.as_mut(), creator_env, creator_info, msg).unwrap();
+    assert_eq!(
+        execute_res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_CREATOR.to_string(),
+                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
     let poll_res: PollResponse = from_binary(&res).unwrap();
@@ -1513,6 +1598,19 @@ fn end_poll_nay_rejected() {
             attr("passed", "false"),
         ]
     );
+    // A rejected poll refunds its deposit by default
+    assert_eq!(
+        execute_res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_CREATOR.to_string(),
+                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
 }
 
 #[test]
@@ -1711,7 +1809,6 @@ fn assert_create_poll_result(
     assert_eq!(
         state,
         State {
-            contract_addr: Addr::unchecked(MOCK_CONTRACT_ADDR),
             poll_count: 1,
             total_deposit: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
             total_share: Uint128::zero()
@@ -1730,7 +1827,6 @@ fn assert_stake_tokens_result(
     assert_eq!(
         state,
         State {
-            contract_addr: Addr::unchecked(MOCK_CONTRACT_ADDR),
             poll_count,
             total_deposit: Uint128::from(total_deposit),
             total_share: Uint128::zero()
@@ -1769,11 +1865,15 @@ fn update_config() {
         owner: Some("addr0001".to_string()),
         quorum: None,
         threshold: None,
+        veto_threshold: None,
         voting_period: None,
         timelock_period: None,
         expiration_period: None,
         proposal_deposit: None,
         snapshot_period: None,
+        deposit_policy: None,
+        slash_recipient: None,
+        max_open_polls: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -1795,11 +1895,15 @@ fn update_config() {
         owner: None,
         quorum: Some(Decimal::percent(20)),
         threshold: Some(Decimal::percent(75)),
+        veto_threshold: None,
         voting_period: Some(20000u64),
         timelock_period: Some(20000u64),
         expiration_period: Some(30000u64),
         proposal_deposit: Some(Uint128::from(123u128)),
         snapshot_period: Some(11),
+        deposit_policy: None,
+        slash_recipient: None,
+        max_open_polls: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -1823,11 +1927,15 @@ fn update_config() {
         owner: None,
         quorum: None,
         threshold: None,
+        veto_threshold: None,
         voting_period: None,
         timelock_period: None,
         expiration_period: None,
         proposal_deposit: None,
         snapshot_period: None,
+        deposit_policy: None,
+        slash_recipient: None,
+        max_open_polls: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -2056,18 +2164,8 @@ fn execute_poll_with_order() {
             attr("passed", "true"),
         ]
     );
-    assert_eq!(
-        execute_res.messages,
-        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: VOTING_TOKEN.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: TEST_CREATOR.to_string(),
-                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
-            })
-            .unwrap(),
-            funds: vec![],
-        }))]
-    );
+    // The deposit of a poll with execute msgs is settled when it is executed or expires
+    assert_eq!(execute_res.messages, vec![]);
 
     // End poll will withdraw deposit balance
     deps.querier.with_token_balances(&[(
@@ -2084,6 +2182,15 @@ fn execute_poll_with_order() {
     assert_eq!(
         execute_res.messages,
         vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: VOTING_TOKEN.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: TEST_CREATOR.to_string(),
+                    amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
             SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: VOTING_TOKEN.to_string(),
                 msg: exec_msg_bz,
@@ -2261,18 +2368,8 @@ fn happy_days_end_poll_with_controlled_quorum() {
             attr("passed", "true"),
         ]
     );
-    assert_eq!(
-        execute_res.messages,
-        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: VOTING_TOKEN.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: TEST_CREATOR.to_string(),
-                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
-            })
-            .unwrap(),
-            funds: vec![],
-        }))]
-    );
+    // The deposit of a poll with execute msgs is settled when it is executed or expires
+    assert_eq!(execute_res.messages, vec![]);
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
     let value: PollResponse = from_binary(&res).unwrap();
@@ -2295,3 +2392,469 @@ fn happy_days_end_poll_with_controlled_quorum() {
 
     // assert_eq!(actual_staked_weight.u128(), (10 * stake_amount))
 }
+
+#[test]
+fn end_poll_vetoed() {
+    const POLL_START_HEIGHT: u64 = 1000;
+    let stake_amount = 400u128;
+
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_contracts(deps.as_mut());
+
+    deps.querier.with_token_balances(&[
+        (
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            )],
+        ),
+        (
+            &VE_TOKEN.to_string(),
+            &[
+                (&TEST_VOTER.to_string(), &Uint128::from(stake_amount)),
+                (&TEST_VOTER_2.to_string(), &Uint128::from(stake_amount)),
+            ],
+        ),
+    ]);
+
+    let mut creator_env = mock_env_height(POLL_START_HEIGHT, 10000);
+    let creator_info = mock_info(VOTING_TOKEN, &[]);
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(deps.as_mut(), creator_env.clone(), creator_info, msg).unwrap();
+
+    for (voter, vote) in [
+        (TEST_VOTER, VoteOption::Veto),
+        (TEST_VOTER_2, VoteOption::Yes),
+    ] {
+        let msg = ExecuteMsg::CastVote { poll_id: 1, vote };
+        let env = mock_env_height(POLL_START_HEIGHT, 10000);
+        execute(deps.as_mut(), env, mock_info(voter, &[]), msg).unwrap();
+    }
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let response: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(response.yes_votes, Uint128::from(stake_amount));
+    assert_eq!(response.veto_votes, Uint128::from(stake_amount));
+
+    creator_env.block.height += DEFAULT_VOTING_PERIOD;
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    let execute_res = execute(
+        deps.as_mut(),
+        creator_env,
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", "Vetoed"),
+            attr("passed", "false"),
+        ]
+    );
+    assert_eq!(
+        execute_res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
+
+#[test]
+fn deposit_policy_and_open_poll_limit() {
+    const SLASH_RECIPIENT: &str = "fee_distributor";
+
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_contracts(deps.as_mut());
+
+    deps.querier.with_token_balances(&[
+        (
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            )],
+        ),
+        (
+            &VE_TOKEN.to_string(),
+            &[(&TEST_VOTER.to_string(), &Uint128::from(400u128))],
+        ),
+    ]);
+
+    let update_msg =
+        |deposit_policy: DepositPolicy, slash_recipient: Option<String>| ExecuteMsg::UpdateConfig {
+            owner: None,
+            quorum: None,
+            threshold: None,
+            veto_threshold: None,
+            voting_period: None,
+            timelock_period: None,
+            expiration_period: None,
+            proposal_deposit: None,
+            snapshot_period: None,
+            deposit_policy: Some(deposit_policy),
+            slash_recipient,
+            max_open_polls: Some(1),
+        };
+    let slash_policy = DepositPolicy {
+        quorum_not_reached: DepositOutcome::Slash,
+        ..DepositPolicy::default()
+    };
+
+    // slashing deposits requires a recipient
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        update_msg(slash_policy.clone(), None),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Slash recipient must be set to slash deposits"
+        ))
+    );
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        update_msg(slash_policy.clone(), Some(SLASH_RECIPIENT.to_string())),
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.deposit_policy, slash_policy);
+    assert_eq!(config.slash_recipient, Some(SLASH_RECIPIENT.to_string()));
+    assert_eq!(config.max_open_polls, Some(1));
+
+    let mut env = mock_env_height(1000, 10000);
+    let info = mock_info(VOTING_TOKEN, &[]);
+    let msg = create_poll_msg("test".to_string(), "test".to_string(), None, None);
+    execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+
+    // the creator already has a poll in progress
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::TooManyOpenPolls {});
+
+    // nobody votes, so the deposit is slashed to the recipient
+    env.block.height += DEFAULT_VOTING_PERIOD;
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(TEST_CREATOR, &[]),
+        ExecuteMsg::EndPoll { poll_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: SLASH_RECIPIENT.to_string(),
+                amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    let state: State = state_read(deps.as_ref().storage).load().unwrap();
+    assert_eq!(state.total_deposit, Uint128::zero());
+
+    // the ended poll no longer counts against the limit
+    execute(deps.as_mut(), env, info, msg).unwrap();
+}
+
+#[test]
+fn multiple_choice_poll_executes_winning_option() {
+    const POLL_START_HEIGHT: u64 = 1000;
+
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_contracts(deps.as_mut());
+
+    deps.querier.with_token_balances(&[
+        (
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            )],
+        ),
+        (
+            &VE_TOKEN.to_string(),
+            &[
+                (&TEST_VOTER.to_string(), &Uint128::from(400u128)),
+                (&TEST_VOTER_2.to_string(), &Uint128::from(300u128)),
+            ],
+        ),
+    ]);
+
+    let grant_msg = |amount: u128| {
+        Some(vec![PollExecuteMsg {
+            order: 1u64,
+            contract: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::from(amount),
+            })
+            .unwrap(),
+        }])
+    };
+    let options = vec![
+        PollOptionMsg {
+            label: "grant a".to_string(),
+            execute_msgs: grant_msg(10),
+        },
+        PollOptionMsg {
+            label: "grant b".to_string(),
+            execute_msgs: grant_msg(20),
+        },
+        PollOptionMsg {
+            label: "no grant".to_string(),
+            execute_msgs: None,
+        },
+    ];
+
+    // a single option is not a choice
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+        msg: to_binary(&Cw20HookMsg::CreateMultipleChoicePoll {
+            title: "test".to_string(),
+            description: "test".to_string(),
+            link: None,
+            options: options[..1].to_vec(),
+        })
+        .unwrap(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        mock_env_height(POLL_START_HEIGHT, 10000),
+        mock_info(VOTING_TOKEN, &[]),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("Too few options"))
+    );
+
+    let mut creator_env = mock_env_height(POLL_START_HEIGHT, 10000);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+        msg: to_binary(&Cw20HookMsg::CreateMultipleChoicePoll {
+            title: "test".to_string(),
+            description: "test".to_string(),
+            link: None,
+            options,
+        })
+        .unwrap(),
+    });
+    execute(
+        deps.as_mut(),
+        creator_env.clone(),
+        mock_info(VOTING_TOKEN, &[]),
+        msg,
+    )
+    .unwrap();
+
+    // only the options of the poll can be voted on
+    for vote in [VoteOption::Yes, VoteOption::Choice(3)] {
+        let msg = ExecuteMsg::CastVote { poll_id: 1, vote };
+        let err = execute(
+            deps.as_mut(),
+            creator_env.clone(),
+            mock_info(TEST_VOTER, &[]),
+            msg,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidVoteOption {});
+    }
+
+    for (voter, vote) in [
+        (TEST_VOTER, VoteOption::Choice(1)),
+        (TEST_VOTER_2, VoteOption::Choice(0)),
+    ] {
+        let msg = ExecuteMsg::CastVote { poll_id: 1, vote };
+        execute(
+            deps.as_mut(),
+            creator_env.clone(),
+            mock_info(voter, &[]),
+            msg,
+        )
+        .unwrap();
+    }
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let response: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(response.options.len(), 3);
+    assert_eq!(response.options[0].votes, Uint128::from(300u128));
+    assert_eq!(response.options[1].votes, Uint128::from(400u128));
+    assert_eq!(response.options[2].votes, Uint128::zero());
+    assert_eq!(response.winning_option, None);
+
+    creator_env.block.height += DEFAULT_VOTING_PERIOD;
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    let execute_res = execute(
+        deps.as_mut(),
+        creator_env.clone(),
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", ""),
+            attr("passed", "true"),
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let response: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(response.status, PollStatus::Passed);
+    assert_eq!(response.winning_option, Some(1));
+
+    // the deposit is refunded & only the messages of the winning option are executed
+    creator_env.block.height += DEFAULT_TIMELOCK_PERIOD;
+    let msg = ExecuteMsg::ExecutePoll { poll_id: 1 };
+    let execute_res = execute(
+        deps.as_mut(),
+        creator_env,
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: VOTING_TOKEN.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: TEST_CREATOR.to_string(),
+                    amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: VOTING_TOKEN.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::from(20u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+}
+
+#[test]
+fn multiple_choice_poll_tie_is_rejected() {
+    const POLL_START_HEIGHT: u64 = 1000;
+
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(deps.as_mut());
+    mock_register_contracts(deps.as_mut());
+
+    deps.querier.with_token_balances(&[
+        (
+            &VOTING_TOKEN.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+            )],
+        ),
+        (
+            &VE_TOKEN.to_string(),
+            &[
+                (&TEST_VOTER.to_string(), &Uint128::from(400u128)),
+                (&TEST_VOTER_2.to_string(), &Uint128::from(400u128)),
+            ],
+        ),
+    ]);
+
+    let options = vec![
+        PollOptionMsg {
+            label: "grant a".to_string(),
+            execute_msgs: None,
+        },
+        PollOptionMsg {
+            label: "grant b".to_string(),
+            execute_msgs: None,
+        },
+    ];
+    let mut creator_env = mock_env_height(POLL_START_HEIGHT, 10000);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::from(DEFAULT_PROPOSAL_DEPOSIT),
+        msg: to_binary(&Cw20HookMsg::CreateMultipleChoicePoll {
+            title: "test".to_string(),
+            description: "test".to_string(),
+            link: None,
+            options,
+        })
+        .unwrap(),
+    });
+    execute(
+        deps.as_mut(),
+        creator_env.clone(),
+        mock_info(VOTING_TOKEN, &[]),
+        msg,
+    )
+    .unwrap();
+
+    for (voter, vote) in [
+        (TEST_VOTER, VoteOption::Choice(0)),
+        (TEST_VOTER_2, VoteOption::Choice(1)),
+    ] {
+        let msg = ExecuteMsg::CastVote { poll_id: 1, vote };
+        execute(
+            deps.as_mut(),
+            creator_env.clone(),
+            mock_info(voter, &[]),
+            msg,
+        )
+        .unwrap();
+    }
+
+    // the options share the most votes, so none of them wins
+    creator_env.block.height += DEFAULT_VOTING_PERIOD;
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    let execute_res = execute(
+        deps.as_mut(),
+        creator_env,
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        execute_res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", "No winning option"),
+            attr("passed", "false"),
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id: 1 }).unwrap();
+    let response: PollResponse = from_binary(&res).unwrap();
+    assert_eq!(response.status, PollStatus::Rejected);
+    assert_eq!(response.winning_option, None);
+}
@@ -0,0 +1,124 @@
+//! Subset of the `astroport` (astroport-core) messages & types used by the vault.
+//! The upstream crate is built against an older `cosmwasm-std`, so the wire formats
+//! are mirrored here instead of pulling it in as a dependency.
+
+pub mod asset {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Addr, Uint128};
+    use std::fmt;
+
+    use super::factory::PairType;
+
+    #[cw_serde]
+    pub struct Asset {
+        pub info: AssetInfo,
+        pub amount: Uint128,
+    }
+
+    impl fmt::Display for Asset {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}{}", self.amount, self.info)
+        }
+    }
+
+    #[cw_serde]
+    pub enum AssetInfo {
+        Token { contract_addr: Addr },
+        NativeToken { denom: String },
+    }
+
+    impl fmt::Display for AssetInfo {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                AssetInfo::NativeToken { denom } => write!(f, "{}", denom),
+                AssetInfo::Token { contract_addr } => write!(f, "{}", contract_addr),
+            }
+        }
+    }
+
+    #[cw_serde]
+    pub struct PairInfo {
+        pub asset_infos: [AssetInfo; 2],
+        pub contract_addr: Addr,
+        pub liquidity_token: Addr,
+        pub pair_type: PairType,
+    }
+}
+
+pub mod factory {
+    use cosmwasm_schema::cw_serde;
+
+    #[cw_serde]
+    pub enum PairType {
+        Xyk {},
+        Stable {},
+        Custom(String),
+    }
+}
+
+pub mod pair {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::Decimal;
+
+    use super::asset::Asset;
+
+    #[cw_serde]
+    pub enum ExecuteMsg {
+        ProvideLiquidity {
+            assets: [Asset; 2],
+            slippage_tolerance: Option<Decimal>,
+            auto_stake: Option<bool>,
+            receiver: Option<String>,
+        },
+        Swap {
+            offer_asset: Asset,
+            belief_price: Option<Decimal>,
+            max_spread: Option<Decimal>,
+            to: Option<String>,
+        },
+    }
+
+    #[cw_serde]
+    pub enum Cw20HookMsg {
+        Swap {
+            belief_price: Option<Decimal>,
+            max_spread: Option<Decimal>,
+            to: Option<String>,
+        },
+        WithdrawLiquidity {},
+    }
+
+    #[cw_serde]
+    pub enum QueryMsg {
+        Pair {},
+    }
+}
+
+pub mod router {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Decimal, Uint128};
+
+    use super::asset::AssetInfo;
+
+    #[cw_serde]
+    pub enum SwapOperation {
+        NativeSwap {
+            offer_denom: String,
+            ask_denom: String,
+        },
+        AstroSwap {
+            offer_asset_info: AssetInfo,
+            ask_asset_info: AssetInfo,
+        },
+    }
+
+    #[cw_serde]
+    pub enum ExecuteMsg {
+        ExecuteSwapOperations {
+            operations: Vec<SwapOperation>,
+            minimum_receive: Option<Uint128>,
+            to: Option<String>,
+            max_spread: Option<Decimal>,
+        },
+    }
+}
@@ -6,7 +6,7 @@ use cw2::{get_contract_version, set_contract_version};
 use cw20::Cw20ReceiveMsg;
 use cw_asset::AssetInfoBase as CwAssetInfoBase;
 
-use astroport::{
+use crate::astroport::{
     asset::{AssetInfo, PairInfo},
     pair::QueryMsg as AstroPairQueryMsg,
 };
@@ -16,7 +16,9 @@ use angel_core::errors::vault::ContractError;
 use crate::executers;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiveMsg};
 use crate::queriers;
-use crate::state::{Config, MinterData, State, TokenInfo, APTAX, CONFIG, STATE, TOKEN_INFO};
+use crate::state::{
+    Config, FeeInfo, MinterData, State, TokenInfo, APTAX, CONFIG, FEES, STATE, TOKEN_INFO,
+};
 
 // version info for future migration info
 const CONTRACT_NAME: &str = "astroport_vault";
@@ -44,6 +46,12 @@ pub fn instantiate(
             msg.interest_distribution
         ))));
     }
+    if msg.performance_fee_rate > Decimal::one() {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Invalid performance_fee_rate: {}",
+            msg.performance_fee_rate
+        ))));
+    }
 
     // Store the configuration
     let sibling_vault = match msg.sibling_vault {
@@ -68,6 +76,7 @@ pub fn instantiate(
         ibc_controller: deps.api.addr_validate(&msg.ibc_controller)?,
         ap_tax_rate: msg.ap_tax_rate,
         interest_distribution: msg.interest_distribution,
+        performance_fee_rate: msg.performance_fee_rate,
 
         lp_token: pair_info.liquidity_token,
         lp_pair_token0: pair_info.asset_infos[0].clone(),
@@ -101,6 +110,15 @@ pub fn instantiate(
     STATE.save(deps.storage, &state)?;
 
     APTAX.save(deps.storage, &Uint128::zero())?;
+    FEES.save(
+        deps.storage,
+        &FeeInfo {
+            high_water_mark: Decimal::zero(),
+            ap_tax_accrued: Uint128::zero(),
+            performance_fee_accrued: Uint128::zero(),
+            claimed: Uint128::zero(),
+        },
+    )?;
 
     // Store vault token information
     let token_info = TokenInfo {
@@ -160,6 +178,8 @@ pub fn execute(
         }
         // Harvest from "lp_staking" contract `lp_reward`(LOOP) token --> more LPs
         ExecuteMsg::Harvest {} => executers::harvest(deps, env, info),
+        // -AP tax & performance fee vault tokens --> `native_token` --> registrar `treasury`
+        ExecuteMsg::ClaimTaxes {} => executers::claim_taxes(deps, env, info),
         // -Deposit Token/Yield Token (Vault) --> + tokens of lp pair --> `accounts_contract`
         ExecuteMsg::Redeem {
             endowment_id,
@@ -170,6 +190,14 @@ pub fn execute(
             endowment_id,
             amount,
         } => executers::reinvest_to_locked_execute(deps, env, info, endowment_id, amount),
+        // -Deposit Token/Yield Token(Liquid Vault) --> +Deposit Token(Vault Router)
+        ExecuteMsg::RedeemForReinvest {
+            endowment_id,
+            amount,
+        } => executers::redeem_for_reinvest(deps, env, info, endowment_id, amount),
+
+        // Unwind the whole LP position to `native_token` & switch to redeem-only mode
+        ExecuteMsg::EmergencyWithdraw {} => executers::emergency_withdraw(deps, env, info),
 
         /* --- INTERNAL ENTRIES --- */
         ExecuteMsg::RestakeClaimReward {
@@ -211,6 +239,9 @@ pub fn execute(
             lp_pair_token0_bal_before,
             lp_pair_token1_bal_before,
         ),
+        ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before,
+        } => executers::emergency_withdraw_finalize(deps, env, info, native_token_bal_before),
     }
 }
 
@@ -263,6 +294,8 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::TokenInfo {} => to_binary(&queriers::query_token_info(deps)),
         QueryMsg::TotalBalance {} => to_binary(&queriers::query_total_balance(deps)),
         QueryMsg::ApTaxBalance {} => to_binary(&queriers::query_ap_tax_balance(deps)),
+        QueryMsg::Fees {} => to_binary(&queriers::query_fees(deps)?),
+        QueryMsg::EmergencyExit {} => to_binary(&queriers::query_emergency_exit(deps)),
     }
 }
 
@@ -276,7 +309,7 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
         }));
     }
     // note: better to do proper semver compare, but string compare *usually* works
-    if ver.version >= CONTRACT_VERSION.to_string() {
+    if ver.version.as_str() >= CONTRACT_VERSION {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: "Cannot upgrade from a newer version".to_string(),
         }));
@@ -285,5 +318,25 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
     // set the new version
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    // initialize the fee accounting with the current share price & APTAX balance
+    if FEES.may_load(deps.storage)?.is_none() {
+        let state = STATE.load(deps.storage)?;
+        let high_water_mark = if state.total_shares.is_zero() {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(state.total_lp_amount, state.total_shares)
+        };
+        let ap_tax_accrued = APTAX.load(deps.storage)?;
+        FEES.save(
+            deps.storage,
+            &FeeInfo {
+                high_water_mark,
+                ap_tax_accrued,
+                performance_fee_accrued: Uint128::zero(),
+                claimed: Uint128::zero(),
+            },
+        )?;
+    }
+
     Ok(Response::default())
 }
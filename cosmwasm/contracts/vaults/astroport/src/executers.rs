@@ -1,13 +1,16 @@
-use angel_core::structs::AccountType;
-use angel_core::utils::{query_balance, query_token_balance};
+use angel_core::structs::{AccountType, EmergencyExit};
+use angel_core::utils::{
+    assert_not_emergency_exit, query_accounts_contract, query_balance, query_token_balance,
+    vault_payout_msg,
+};
 use cosmwasm_std::{
     attr, coins, to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+    MessageInfo, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
-use cw20::Cw20ReceiveMsg;
+use cw20::{Cw20ReceiveMsg, Denom};
 use cw_asset::AssetInfoBase as CwAssetInfoBase;
 
-use astroport::{
+use crate::astroport::{
     asset::{Asset, AssetInfo, PairInfo},
     pair::{
         Cw20HookMsg as AstroPairHookMsg, ExecuteMsg as AstroPairExecuteMsg,
@@ -17,13 +20,19 @@ use astroport::{
 };
 
 use angel_core::errors::vault::ContractError;
+use angel_core::msgs::registrar::{
+    ConfigResponse as RegistrarConfigResponse, QueryMsg as RegistrarQueryMsg,
+};
 
 use crate::msg::{
-    AstroportGeneratorExecuteMsg, ExecuteMsg, QueryMsg as VaultQueryMsg, ReceiveMsg,
-    UpdateConfigMsg,
+    AstroportGeneratorExecuteMsg, ExecuteMsg, QueryMsg as VaultQueryMsg, ReceiveIbcResponseMsg,
+    ReceiveMsg, UpdateConfigMsg,
 };
 use crate::responses::ConfigResponse;
-use crate::state::{Config, State, APTAX, BALANCES, CONFIG, STATE, TOKEN_INFO};
+use crate::state::{
+    Config, State, TaxBalance, APTAX, BALANCES, CONFIG, EMERGENCY_EXIT, FEES, STATE, TAXES,
+    TOKEN_INFO,
+};
 
 // Initial VT(vault token) mint amount
 const INIT_VT_MINT_AMOUNT: u128 = 1000000; // 1 VT
@@ -140,6 +149,18 @@ pub fn update_config(
         Some(addr) => deps.api.addr_validate(&addr)?,
         None => config.tax_collector,
     };
+    config.performance_fee_rate = match msg.performance_fee_rate {
+        Some(rate) => {
+            if rate > Decimal::one() {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "Invalid performance_fee_rate: {}",
+                    rate
+                ))));
+            }
+            rate
+        }
+        None => config.performance_fee_rate,
+    };
 
     config.native_token = match msg.native_token {
         None => config.native_token,
@@ -183,6 +204,7 @@ pub fn deposit(
     deposit_amount: Uint128,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
 
     // Check if the `caller` is "ibc_host" & "endowment_id" is valid
     if msg_sender != config.ibc_host {
@@ -379,7 +401,6 @@ pub fn redeem(
     burn_shares_amount: Uint128,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut state: State = STATE.load(deps.storage)?;
 
     let beneficiary: Addr;
     let id: Option<u32>;
@@ -400,13 +421,6 @@ pub fn redeem(
     }
 
     // First, burn the vault tokens
-    // The formula of calculating the amount of LP tokens to be burnt is as follows:
-    //   s = vault shares to burn
-    //   T = vault shares total (before burn)
-    //   a = LP tokens withdraw to Vault's balance <<< what we need to calculate given some # of Vault shares to be burned
-    //   B = Vault's total LP Token balance
-    //
-    //   a = (s * B) / T
     execute_burn(deps.branch(), env.clone(), info, id, burn_shares_amount).map_err(|e| {
         ContractError::Std(StdError::GenericErr {
             msg: format!(
@@ -418,7 +432,192 @@ pub fn redeem(
         })
     })?;
 
-    // Update the contract state
+    let (msgs, withdraw_asset) = prepare_withdraw_msgs(
+        deps.branch(),
+        env,
+        &config,
+        burn_shares_amount,
+        beneficiary,
+        id,
+    )?;
+
+    // The `tax_collector` redeems the APTAX balance, so book it like `claim_taxes`
+    if id.is_none() {
+        record_claimed_fees(deps.storage, burn_shares_amount, &withdraw_asset)?;
+    }
+
+    Ok(Response::default().add_messages(msgs).add_attributes(vec![
+        attr("action", "withdraw"),
+        attr("burn_shares", burn_shares_amount.to_string()),
+        attr("withdraw_asset", withdraw_asset.to_string()),
+    ]))
+}
+
+/// Contract entry: **RedeemForReinvest** (liquid vault logic)
+///   1. Burn the `vault_token`
+///   2. Unstake/unfarm the LP tokens & swap them back to the `native_token`
+///   3. Send the `native_token` to the `ibc_host`(Vault Router), which deposits it
+///      to the locked vault of another Strategy for the same endowment
+pub fn redeem_for_reinvest(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endowment_id: u32,
+    burn_shares_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Check that the vault acct_type is `liquid`
+    if config.acct_type != AccountType::Liquid {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "This is locked vault".to_string(),
+        }));
+    }
+
+    if info.sender != config.ibc_host {
+        return Err(ContractError::Unauthorized {});
+    }
+    let beneficiary = info.sender.clone();
+
+    // First, burn the vault tokens
+    execute_burn(
+        deps.branch(),
+        env.clone(),
+        info,
+        Some(endowment_id),
+        burn_shares_amount,
+    )
+    .map_err(|e| {
+        ContractError::Std(StdError::GenericErr {
+            msg: format!(
+                "Cannot burn the {} vault tokens from Endowment {} :: {}",
+                burn_shares_amount, endowment_id, e,
+            ),
+        })
+    })?;
+
+    // The proceeds go straight back to the router, not through the Accounts contract
+    let (msgs, withdraw_asset) =
+        prepare_withdraw_msgs(deps, env, &config, burn_shares_amount, beneficiary, None)?;
+
+    Ok(Response::default().add_messages(msgs).add_attributes(vec![
+        attr("action", "redeem_for_reinvest"),
+        attr("burn_shares", burn_shares_amount.to_string()),
+        attr("withdraw_asset", withdraw_asset.to_string()),
+    ]))
+}
+
+/// Contract entry: **ClaimTaxes**
+///   1. Burn all the vault tokens accrued as AP tax & performance fee (APTAX balance)
+///   2. Unstake/unfarm the LP tokens & swap them back to the `native_token`
+///   3. Send the `native_token` to the registrar `treasury`
+pub fn claim_taxes(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the `config.owner` or `config.keeper` can trigger the claim
+    if info.sender != config.owner && info.sender != config.keeper {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let burn_shares_amount = APTAX.load(deps.storage)?;
+    if burn_shares_amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let registrar_config: RegistrarConfigResponse = deps.querier.query_wasm_smart(
+        config.registrar_contract.to_string(),
+        &RegistrarQueryMsg::Config {},
+    )?;
+    let treasury = deps.api.addr_validate(&registrar_config.treasury)?;
+
+    execute_burn(deps.branch(), env.clone(), info, None, burn_shares_amount)?;
+
+    let (msgs, withdraw_asset) = prepare_withdraw_msgs(
+        deps.branch(),
+        env,
+        &config,
+        burn_shares_amount,
+        treasury.clone(),
+        None,
+    )?;
+
+    record_claimed_fees(deps.storage, burn_shares_amount, &withdraw_asset)?;
+
+    Ok(Response::default().add_messages(msgs).add_attributes(vec![
+        attr("action", "claim_taxes"),
+        attr("treasury", treasury.to_string()),
+        attr("burn_shares", burn_shares_amount.to_string()),
+        attr("withdraw_asset", withdraw_asset.to_string()),
+    ]))
+}
+
+/// Prepare the messages to convert the (already burned) vault tokens back to the `native_token`
+/// & send them to the `beneficiary`. Returns the messages & the LP tokens the vault tokens stood for,
+/// also in emergency exit mode, so that the claimed fees are recorded in the same unit as accrued.
+fn prepare_withdraw_msgs(
+    deps: DepsMut,
+    env: Env,
+    config: &Config,
+    burn_shares_amount: Uint128,
+    beneficiary: Addr,
+    id: Option<u32>,
+) -> Result<(Vec<CosmosMsg>, Asset), ContractError> {
+    let mut state: State = STATE.load(deps.storage)?;
+    let mut msgs = vec![];
+
+    // In emergency exit mode, the LP position is already unwound.
+    // Just send the pro-rata portion of `native_token` to the `beneficiary`.
+    if let Some(mut emergency_exit) = EMERGENCY_EXIT.may_load(deps.storage)? {
+        let native_amount = emergency_exit
+            .native_token_amount
+            .multiply_ratio(burn_shares_amount, state.total_shares);
+        let lp_amount = emergency_exit
+            .lp_token_amount
+            .multiply_ratio(burn_shares_amount, state.total_shares);
+        emergency_exit.native_token_amount -= native_amount;
+        emergency_exit.lp_token_amount -= lp_amount;
+        EMERGENCY_EXIT.save(deps.storage, &emergency_exit)?;
+
+        state.total_shares -= burn_shares_amount;
+        STATE.save(deps.storage, &state)?;
+
+        // Endowment redemptions are credited back through the Accounts contract,
+        // while the APTAX redemption goes straight to the `beneficiary`(tax_collector)
+        let beneficiary = match id {
+            Some(_) => query_accounts_contract(deps.as_ref(), config.registrar_contract.as_str())?,
+            None => beneficiary,
+        };
+        if !native_amount.is_zero() {
+            msgs.push(vault_payout_msg(
+                &asset_info_to_denom(&config.native_token),
+                &beneficiary,
+                id,
+                config.acct_type.clone(),
+                native_amount,
+            )?);
+        }
+        return Ok((
+            msgs,
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: config.lp_token.clone(),
+                },
+                amount: lp_amount,
+            },
+        ));
+    }
+
+    // The formula of calculating the amount of LP tokens to be withdrawn is as follows:
+    //   s = vault shares to burn
+    //   T = vault shares total (before burn)
+    //   a = LP tokens withdraw to Vault's balance <<< what we need to calculate given some # of Vault shares to be burned
+    //   B = Vault's total LP Token balance
+    //
+    //   a = (s * B) / T
     let lp_2_vt_rate = Decimal::from_ratio(state.total_lp_amount, state.total_shares);
     let lp_amount = burn_shares_amount * lp_2_vt_rate;
     state.total_lp_amount -= lp_amount;
@@ -427,8 +626,6 @@ pub fn redeem(
     STATE.save(deps.storage, &state)?;
 
     // Call the `astroport::farming::unstake_and_claim(unfarm)` entry
-    let mut msgs = vec![];
-
     msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: config.lp_staking_contract.to_string(),
         msg: to_binary(&AstroportGeneratorExecuteMsg::Withdraw {
@@ -471,11 +668,15 @@ pub fn redeem(
         funds: vec![],
     }));
 
-    Ok(Response::default().add_messages(msgs).add_attributes(vec![
-        attr("action", "withdraw"),
-        attr("burn_shares", burn_shares_amount.to_string()),
-        attr("lp_amount", lp_amount.to_string()),
-    ]))
+    Ok((
+        msgs,
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: config.lp_token.clone(),
+            },
+            amount: lp_amount,
+        },
+    ))
 }
 
 /// Contract entry: **Harvest**
@@ -484,6 +685,7 @@ pub fn redeem(
 ///   3. Re-stake the LP tokens to the `farming` contract for more yield
 pub fn harvest(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
 
     // Check if the caller is `keeper` address
     if info.sender != config.keeper {
@@ -536,6 +738,7 @@ pub fn reinvest_to_locked_execute(
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     let mut state: State = STATE.load(deps.storage)?;
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
 
     // Check that the vault acct_type is `liquid`
     if config.acct_type != AccountType::Liquid {
@@ -661,6 +864,7 @@ pub fn reinvest_to_locked_receive(
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     let mut state: State = STATE.load(deps.storage)?;
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
 
     // 0. Check that the message sender is the Sibling vault contract
     // ensure `received token` is `lp_token`
@@ -716,6 +920,7 @@ pub fn reinvest_to_locked_receive(
     //   B = Vault's total LP Token balance (before deposit)
     //
     //   s = (a * T) / B = a * (T / B)
+    let is_initial_mint = state.total_shares.is_zero();
     let vt_mint_amount = match state.total_shares.u128() {
         0 => {
             if lp_amount < config.minimum_initial_deposit {
@@ -733,6 +938,9 @@ pub fn reinvest_to_locked_receive(
     state.total_lp_amount += lp_amount;
     state.total_shares += vt_mint_amount;
     STATE.save(deps.storage, &state)?;
+    if is_initial_mint {
+        reset_high_water_mark(deps.storage, &state)?;
+    }
 
     // Mint the `vault_token`
     let minter_info = MessageInfo {
@@ -942,6 +1150,7 @@ pub fn stake_lp_token(
     match endowment_id {
         // Case of `deposit` from `endowment`
         Some(endowment_id) => {
+            let is_initial_mint = state.total_shares.is_zero();
             // Compute the `vault_token` amount
             // The formula of calculating the amount of vault tokens to be minted is as follows:
             //   s = vault shares to mint <<< what we need to calculate given some # of LP tokens created from despoit
@@ -966,6 +1175,9 @@ pub fn stake_lp_token(
             };
             state.total_lp_amount += lp_amount;
             state.total_shares += vt_mint_amount;
+            if is_initial_mint {
+                reset_high_water_mark(deps.storage, &state)?;
+            }
 
             // Mint the `vault_token`
             execute_mint(deps.branch(), env, info, Some(endowment_id), vt_mint_amount).map_err(
@@ -984,91 +1196,113 @@ pub fn stake_lp_token(
         }
         // Case of `harvest` from `keeper` wallet
         None => {
-            // Compute the `vault_token` amount
+            let mut fees = FEES.load(deps.storage)?;
+
+            // Compute the AP tax portion of the harvested LP tokens
+            let ap_tax_lp_amount = lp_amount * config.ap_tax_rate;
+            let mut lp_less_tax = lp_amount - ap_tax_lp_amount;
+
+            // Locked vault sends the `interest_distribution` portion to the sibling(liquid) vault
+            if config.acct_type == AccountType::Locked {
+                let send_liquid_lp_amount = lp_less_tax * config.interest_distribution;
+                lp_less_tax -= send_liquid_lp_amount;
+
+                harvest_to_liquid_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: config.lp_token.to_string(),
+                    msg: to_binary(&cw20::Cw20ExecuteMsg::Send {
+                        contract: config.sibling_vault.to_string(),
+                        amount: send_liquid_lp_amount,
+                        msg: to_binary(&ReceiveMsg::HarvestToLiquid {}).unwrap(),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }))
+            }
+
+            // Compute the performance fee on the gain above the high-water mark
+            //   B = Vault's total LP Token balance (after harvest, less AP tax)
+            //   T = vault shares total
+            //   H = high-water mark (LP tokens per vault share)
+            //
+            //   performance fee = (B - T * H) * performance_fee_rate
+            let hwm_lp_amount = state.total_shares * fees.high_water_mark;
+            let total_lp_less_tax = state.total_lp_amount + lp_less_tax;
+            let performance_fee_lp_amount = if total_lp_less_tax > hwm_lp_amount {
+                (total_lp_less_tax - hwm_lp_amount) * config.performance_fee_rate
+            } else {
+                Uint128::zero()
+            };
+
+            // Stake the harvested LP tokens (AP tax portion included)
+            lp_stake_amount = ap_tax_lp_amount + lp_less_tax;
+            state.total_lp_amount += lp_stake_amount;
+
+            // Mint the vault tokens for the fees (AP tax + performance fee) to the APTAX balance
             // The formula of calculating the amount of vault tokens to be minted is as follows:
-            //   s = vault shares to mint <<< what we need to calculate given some # of LP tokens created from despoit
+            //   s = vault shares to mint for the fees
             //   T = vault shares total (before mint)
-            //   a = LP tokens added to Vault's balance
-            //   B = Vault's total LP Token balance (before deposit)
+            //   F = LP tokens taken as fees
+            //   B = Vault's total LP Token balance (after harvest)
             //
-            //   s = (a * T) / B = a * (T / B)
-            let vt_mint_amount = match state.total_shares.u128() {
-                0 => {
-                    if lp_amount < config.minimum_initial_deposit {
-                        return Err(ContractError::Std(StdError::GenericErr {
-                            msg: format!(
-                                "Received {}, should be bigger than {}.",
-                                lp_amount, config.minimum_initial_deposit
-                            ),
-                        }));
-                    }
-                    Uint128::from(INIT_VT_MINT_AMOUNT)
-                }
-                _ => lp_amount * Decimal::from_ratio(state.total_shares, state.total_lp_amount),
+            //   s / (T + s) = F / B  =>  s = F * T / (B - F)
+            //
+            // Without any vault tokens outstanding there is no holders' yield to take the fees from,
+            // so nothing is minted & the harvest stays in the vault.
+            let fee_lp_amount = ap_tax_lp_amount + performance_fee_lp_amount;
+            let holders_lp_amount = state.total_lp_amount - fee_lp_amount;
+            let fee_mint_amount = if state.total_shares.is_zero() || holders_lp_amount.is_zero() {
+                Uint128::zero()
+            } else {
+                fee_lp_amount.multiply_ratio(state.total_shares, holders_lp_amount)
             };
-            state.total_lp_amount += lp_amount;
-            state.total_shares += vt_mint_amount;
 
-            // Compute the `ap_treasury tax portion` & store in APTAX
-            let tax_mint_amount = vt_mint_amount * config.ap_tax_rate;
-
-            APTAX.update(deps.storage, |balance: Uint128| -> StdResult<_> {
-                Ok(balance.checked_add(tax_mint_amount)?)
-            })?;
-
-            match config.acct_type {
-                AccountType::Liquid => {
-                    // Mint the `vault token`
-                    // update supply and enforce cap
-                    let mut token_info = TOKEN_INFO.load(deps.storage)?;
-                    token_info.total_supply += vt_mint_amount;
-                    if let Some(limit) = token_info.get_cap() {
-                        if token_info.total_supply > limit {
-                            return Err(ContractError::CannotExceedCap {});
-                        }
-                    }
-                    TOKEN_INFO.save(deps.storage, &token_info)?;
+            if !fee_lp_amount.is_zero() && !fee_mint_amount.is_zero() {
+                state.total_shares += fee_mint_amount;
 
-                    // Stake all the LP token
-                    lp_stake_amount = lp_amount;
+                // update supply and enforce cap
+                let mut token_info = TOKEN_INFO.load(deps.storage)?;
+                token_info.total_supply += fee_mint_amount;
+                if let Some(limit) = token_info.get_cap() {
+                    if token_info.total_supply > limit {
+                        return Err(ContractError::CannotExceedCap {});
+                    }
                 }
-                AccountType::Locked => {
-                    // Send the portion of LP tokens to the sibling(liquid) vault
-                    let lp_tax_amount = lp_amount * config.ap_tax_rate;
-                    let lp_less_tax = lp_amount - lp_tax_amount;
-
-                    let send_liquid_ratio = config.interest_distribution;
-                    let send_liquid_lp_amount = lp_less_tax * send_liquid_ratio;
-                    let vt_shares_to_burn = (vt_mint_amount - tax_mint_amount) * send_liquid_ratio;
-                    state.total_shares -= vt_shares_to_burn;
-                    state.total_lp_amount -= send_liquid_lp_amount;
-
-                    // Mint the `vault token` with left LP token
-                    // update supply and enforce cap
-                    let mut token_info = TOKEN_INFO.load(deps.storage)?;
-                    token_info.total_supply += vt_mint_amount - vt_shares_to_burn;
-                    if let Some(limit) = token_info.get_cap() {
-                        if token_info.total_supply > limit {
-                            return Err(ContractError::CannotExceedCap {});
-                        }
+                TOKEN_INFO.save(deps.storage, &token_info)?;
+
+                APTAX.update(deps.storage, |balance: Uint128| -> StdResult<_> {
+                    Ok(balance.checked_add(fee_mint_amount)?)
+                })?;
+
+                // Record the accrued amounts
+                let ap_tax_mint_amount =
+                    fee_mint_amount.multiply_ratio(ap_tax_lp_amount, fee_lp_amount);
+                fees.ap_tax_accrued += ap_tax_mint_amount;
+                fees.performance_fee_accrued += fee_mint_amount - ap_tax_mint_amount;
+                TAXES.update(
+                    deps.storage,
+                    AssetInfo::Token {
+                        contract_addr: config.lp_token.clone(),
                     }
-                    TOKEN_INFO.save(deps.storage, &token_info)?;
-
-                    // Stake only leftover LP tokens
-                    lp_stake_amount = lp_amount - send_liquid_lp_amount;
+                    .to_string(),
+                    |balance| -> StdResult<_> {
+                        let mut balance = balance.unwrap_or(TaxBalance {
+                            accrued: Uint128::zero(),
+                            claimed: Uint128::zero(),
+                        });
+                        balance.accrued += fee_lp_amount;
+                        Ok(balance)
+                    },
+                )?;
+            }
 
-                    harvest_to_liquid_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                        contract_addr: config.lp_token.to_string(),
-                        msg: to_binary(&cw20::Cw20ExecuteMsg::Send {
-                            contract: config.sibling_vault.to_string(),
-                            amount: send_liquid_lp_amount,
-                            msg: to_binary(&ReceiveMsg::HarvestToLiquid {}).unwrap(),
-                        })
-                        .unwrap(),
-                        funds: vec![],
-                    }))
+            // Raise the high-water mark to the share price after fees
+            if !state.total_shares.is_zero() {
+                let share_price = Decimal::from_ratio(state.total_lp_amount, state.total_shares);
+                if share_price > fees.high_water_mark {
+                    fees.high_water_mark = share_price;
                 }
             }
+            FEES.save(deps.storage, &fees)?;
         }
     }
     STATE.save(deps.storage, &state)?;
@@ -1100,6 +1334,7 @@ pub fn harvest_to_liquid(
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     let mut state: State = STATE.load(deps.storage)?;
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
     let lp_token_contract = config.lp_token.to_string();
 
     // 0. Check that the message sender is the Sibling vault contract
@@ -1209,21 +1444,26 @@ pub fn remove_liquidity(
         funds: vec![],
     }));
 
-    let native_token_bal = query_asset_balance(
-        deps.as_ref(),
-        env.contract.address.clone(),
-        config.native_token,
-    )?;
-    let send_asset_msgs = vec![CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: env.contract.address.to_string(),
-        msg: to_binary(&ExecuteMsg::SendAsset {
-            beneficiary,
-            id,
-            native_token_bal_before: native_token_bal,
-        })
-        .unwrap(),
-        funds: vec![],
-    })];
+    // NOTE: In case of `emergency_withdraw`, the vault itself is the `beneficiary`
+    //       & the `native_token`s just stay in the vault for later redemptions.
+    let mut send_asset_msgs = vec![];
+    if beneficiary != env.contract.address {
+        let native_token_bal = query_asset_balance(
+            deps.as_ref(),
+            env.contract.address.clone(),
+            config.native_token,
+        )?;
+        send_asset_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::SendAsset {
+                beneficiary,
+                id,
+                native_token_bal_before: native_token_bal,
+            })
+            .unwrap(),
+            funds: vec![],
+        }));
+    }
 
     Ok(Response::default()
         .add_messages(withdraw_liquidity_msgs)
@@ -1428,6 +1668,115 @@ pub fn swap_back(
         .add_attributes(vec![attr("action", "swap_pair_to_native")]))
 }
 
+/// Contract entry: **EmergencyWithdraw**
+///   1. Unstake all the LP tokens from the `astroport::generator` contract (rewards are left behind)
+///   2. Remove liquidity & swap the lp pair tokens back to the `native_token`
+///   3. Call the `(this contract::)EmergencyWithdrawFinalize` entry, which switches the vault to redeem-only mode
+pub fn emergency_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let state: State = STATE.load(deps.storage)?;
+
+    // Only the `config.owner` can trigger the emergency exit
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
+
+    let mut msgs = vec![];
+    if !state.total_lp_amount.is_zero() {
+        // Unstake all the LP tokens from the `astroport::generator` contract
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.lp_staking_contract.to_string(),
+            msg: to_binary(&AstroportGeneratorExecuteMsg::EmergencyWithdraw {
+                lp_token: config.lp_token.to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }));
+
+        // Remove liquidity & swap back to `native_token`, keeping them in the vault
+        let lp_token_bal = query_token_balance(
+            deps.as_ref(),
+            config.lp_token.to_string(),
+            env.contract.address.to_string(),
+        )?;
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::RemoveLiquidity {
+                lp_token_bal_before: lp_token_bal,
+                beneficiary: env.contract.address.clone(),
+                id: None,
+            })
+            .unwrap(),
+            funds: vec![],
+        }));
+    }
+
+    // Record the recovered `native_token` amount & enter the redeem-only mode
+    let native_token_bal = query_asset_balance(
+        deps.as_ref(),
+        env.contract.address.clone(),
+        config.native_token,
+    )?;
+    msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before: native_token_bal,
+        })
+        .unwrap(),
+        funds: vec![],
+    }));
+
+    Ok(Response::default().add_messages(msgs).add_attributes(vec![
+        attr("action", "emergency_withdraw"),
+        attr("lp_amount", state.total_lp_amount.to_string()),
+    ]))
+}
+
+/// Contract entry: **EmergencyWithdrawFinalize**
+///   1. Compute the amount of `native_token` recovered from `emergency_withdraw`
+///   2. Switch the vault to redeem-only mode
+pub fn emergency_withdraw_finalize(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    native_token_bal_before: Uint128,
+) -> Result<Response, ContractError> {
+    // Validations
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut state: State = STATE.load(deps.storage)?;
+
+    let native_token_bal =
+        query_asset_balance(deps.as_ref(), env.contract.address, config.native_token)?;
+    let native_token_amount = native_token_bal
+        .checked_sub(native_token_bal_before)
+        .map_err(|e| ContractError::Std(StdError::overflow(e)))?;
+
+    EMERGENCY_EXIT.save(
+        deps.storage,
+        &EmergencyExit {
+            native_token_amount,
+            lp_token_amount: state.total_lp_amount,
+        },
+    )?;
+
+    state.total_lp_amount = Uint128::zero();
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "emergency_withdraw_finalize"),
+        attr("native_amount", native_token_amount.to_string()),
+    ]))
+}
+
 fn prepare_swap_router_swap_msgs(
     swap_router: String,
     start_token: AssetInfo,
@@ -1668,3 +2017,46 @@ fn reverse_operation_order(op: &SwapOperation) -> SwapOperation {
         },
     }
 }
+
+/// Convert the astroport `AssetInfo` to the `Denom` used by the shared vault helpers
+fn asset_info_to_denom(asset_info: &AssetInfo) -> Denom {
+    match asset_info {
+        AssetInfo::NativeToken { denom } => Denom::Native(denom.to_string()),
+        AssetInfo::Token { contract_addr } => Denom::Cw20(contract_addr.clone()),
+    }
+}
+
+/// Record the APTAX vault tokens burned by `claim_taxes` or the `tax_collector` redemption,
+/// & the LP tokens they were redeemed for
+fn record_claimed_fees(
+    storage: &mut dyn Storage,
+    burn_shares_amount: Uint128,
+    withdraw_asset: &Asset,
+) -> StdResult<()> {
+    FEES.update(storage, |mut fees| -> StdResult<_> {
+        fees.claimed += burn_shares_amount;
+        Ok(fees)
+    })?;
+    TAXES.update(
+        storage,
+        withdraw_asset.info.to_string(),
+        |balance| -> StdResult<_> {
+            let mut balance = balance.unwrap_or(TaxBalance {
+                accrued: Uint128::zero(),
+                claimed: Uint128::zero(),
+            });
+            balance.claimed += withdraw_asset.amount;
+            Ok(balance)
+        },
+    )?;
+    Ok(())
+}
+
+/// Reset the high-water mark to the current share price (used when the first vault tokens are minted)
+fn reset_high_water_mark(storage: &mut dyn Storage, state: &State) -> StdResult<()> {
+    FEES.update(storage, |mut fees| -> StdResult<_> {
+        fees.high_water_mark = Decimal::from_ratio(state.total_lp_amount, state.total_shares);
+        Ok(fees)
+    })?;
+    Ok(())
+}
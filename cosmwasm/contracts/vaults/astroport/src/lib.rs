@@ -1,3 +1,4 @@
+pub mod astroport;
 pub mod contract;
 pub mod executers;
 pub mod msg;
@@ -1,7 +1,7 @@
+use crate::astroport::router::SwapOperation;
 use angel_core::structs::AccountType;
-use astroport::router::SwapOperation;
-use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 use cw_asset::AssetInfo as CwAssetInfo;
 
@@ -11,6 +11,7 @@ pub struct InstantiateMsg {
     pub ibc_controller: String,
     pub ap_tax_rate: Decimal,
     pub interest_distribution: Decimal,
+    pub performance_fee_rate: Decimal,
 
     pub acct_type: AccountType,
     pub sibling_vault: Option<String>,
@@ -59,6 +60,12 @@ pub enum ExecuteMsg {
         endowment_id: u32,
         amount: Uint128,
     },
+    /// redeem vault assets from self (if AccountType::Liquid) back to the caller(Vault Router),
+    /// which reinvests them in the AccountType::Locked vault of another Strategy
+    RedeemForReinvest {
+        endowment_id: u32,
+        amount: Uint128,
+    },
     Harvest {},
     RestakeClaimReward {
         reward_token_bal_before: Uint128,
@@ -86,11 +93,33 @@ pub enum ExecuteMsg {
         id: Option<u32>,
         native_token_bal_before: Uint128,
     },
+    /// send the accrued AP tax & performance fee to the registrar `treasury`
+    ClaimTaxes {},
+    /// unwind the whole vault position (unstake all LP, remove liquidity & swap back
+    /// to `native_token`) and switch the vault to redeem-only mode
+    EmergencyWithdraw {},
+    EmergencyWithdrawFinalize {
+        native_token_bal_before: Uint128,
+    },
     Receive(Cw20ReceiveMsg),
     // catch ICA msg responses from ICA Controller
     ReceiveIbcResponse(ReceiveIbcResponseMsg),
 }
 
+/// Callback sent by the ICA controller with the ack of an earlier packet
+#[cw_serde]
+pub struct ReceiveIbcResponseMsg {
+    /// The ID chosen by the caller in the original message
+    pub id: String,
+    pub msg: StdAck,
+}
+
+#[cw_serde]
+pub enum StdAck {
+    Result(Binary),
+    Error(String),
+}
+
 #[cw_serde]
 pub struct UpdateConfigMsg {
     pub ibc_host: Option<String>,
@@ -99,6 +128,7 @@ pub struct UpdateConfigMsg {
     pub sibling_vault: Option<String>,
     pub keeper: Option<String>,
     pub tax_collector: Option<String>,
+    pub performance_fee_rate: Option<Decimal>,
 
     pub native_token: Option<CwAssetInfo>,
     pub reward_to_native_route: Option<Vec<SwapOperation>>,
@@ -142,6 +172,12 @@ pub enum QueryMsg {
     /// Returns the APTAX balance of the contract
     /// Return type: BalanceResponse
     ApTaxBalance {},
+    /// Returns the accrued & claimed AP tax/performance fee totals
+    /// Return type: FeesResponse
+    Fees {},
+    /// Returns the emergency exit status of the contract
+    /// Return type: EmergencyExitResponse
+    EmergencyExit {},
 }
 
 #[cw_serde]
@@ -149,4 +185,5 @@ pub enum AstroportGeneratorExecuteMsg {
     Deposit {}, // Deposit(stake) LP token.(param: amount in `send` msg)
     Withdraw { lp_token: String, amount: Uint128 }, // Unfarm action. Unstake farmed LP token & rewards.(param: amount in `send` msg)
     ClaimRewards { lp_tokens: Vec<String> },        // Claim the reward. Enabled just after `stake`
+    EmergencyWithdraw { lp_token: String }, // Unstake all farmed LP tokens without caring about rewards
 }
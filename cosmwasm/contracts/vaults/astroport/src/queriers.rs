@@ -1,8 +1,12 @@
-use cosmwasm_std::{Deps, Uint128};
+use cosmwasm_std::{Deps, Order, StdResult, Uint128};
 use cw20::{BalanceResponse, TokenInfoResponse};
 
-use crate::responses::{ConfigResponse, StateResponse};
-use crate::state::{Config, APTAX, BALANCES, CONFIG, STATE, TOKEN_INFO};
+use crate::responses::{
+    AssetTaxResponse, ConfigResponse, EmergencyExitResponse, FeesResponse, StateResponse,
+};
+use crate::state::{
+    Config, APTAX, BALANCES, CONFIG, EMERGENCY_EXIT, FEES, STATE, TAXES, TOKEN_INFO,
+};
 
 pub fn query_balance(deps: Deps, id: u32) -> Uint128 {
     BALANCES.load(deps.storage, id).unwrap_or_default()
@@ -30,6 +34,7 @@ pub fn query_config(deps: Deps) -> ConfigResponse {
         registrar_contract: config.registrar_contract.to_string(),
         keeper: config.keeper.to_string(),
         tax_collector: config.tax_collector.to_string(),
+        performance_fee_rate: config.performance_fee_rate,
 
         native_token: config.native_token.to_string(),
         lp_token: config.lp_token.to_string(),
@@ -73,3 +78,43 @@ pub fn query_ap_tax_balance(deps: Deps) -> BalanceResponse {
     let ap_tax = APTAX.load(deps.storage).unwrap();
     BalanceResponse { balance: ap_tax }
 }
+
+pub fn query_emergency_exit(deps: Deps) -> EmergencyExitResponse {
+    match EMERGENCY_EXIT.may_load(deps.storage).unwrap() {
+        Some(emergency_exit) => EmergencyExitResponse {
+            active: true,
+            native_token_amount: emergency_exit.native_token_amount.to_string(),
+        },
+        None => EmergencyExitResponse {
+            active: false,
+            native_token_amount: Uint128::zero().to_string(),
+        },
+    }
+}
+
+pub fn query_fees(deps: Deps) -> StdResult<FeesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let fees = FEES.load(deps.storage)?;
+    let assets = TAXES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (asset, balance) = item?;
+            Ok(AssetTaxResponse {
+                asset,
+                accrued: balance.accrued,
+                claimed: balance.claimed,
+            })
+        })
+        .collect::<StdResult<Vec<AssetTaxResponse>>>()?;
+
+    Ok(FeesResponse {
+        ap_tax_rate: config.ap_tax_rate,
+        performance_fee_rate: config.performance_fee_rate,
+        high_water_mark: fees.high_water_mark,
+        ap_tax_accrued: fees.ap_tax_accrued,
+        performance_fee_accrued: fees.performance_fee_accrued,
+        claimed: fees.claimed,
+        unclaimed: APTAX.load(deps.storage)?,
+        assets,
+    })
+}
@@ -1,6 +1,7 @@
-use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Uint128};
 
-use astroport::router::SwapOperation;
+use crate::astroport::router::SwapOperation;
 
 use angel_core::structs::AccountType;
 
@@ -15,6 +16,7 @@ pub struct ConfigResponse {
     pub registrar_contract: String,
     pub keeper: String,
     pub tax_collector: String,
+    pub performance_fee_rate: Decimal,
 
     pub native_token: String,
     pub lp_token: String,
@@ -40,3 +42,28 @@ pub struct StateResponse {
     pub total_lp_amount: String,
     pub total_shares: String,
 }
+
+#[cw_serde]
+pub struct EmergencyExitResponse {
+    pub active: bool,
+    pub native_token_amount: String,
+}
+
+#[cw_serde]
+pub struct FeesResponse {
+    pub ap_tax_rate: Decimal,
+    pub performance_fee_rate: Decimal,
+    pub high_water_mark: Decimal,
+    pub ap_tax_accrued: Uint128,
+    pub performance_fee_accrued: Uint128,
+    pub claimed: Uint128,
+    pub unclaimed: Uint128,
+    pub assets: Vec<AssetTaxResponse>,
+}
+
+#[cw_serde]
+pub struct AssetTaxResponse {
+    pub asset: String,
+    pub accrued: Uint128,
+    pub claimed: Uint128,
+}
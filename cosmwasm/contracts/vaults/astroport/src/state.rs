@@ -1,10 +1,10 @@
-use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw_storage_plus::{Item, Map};
 
-use angel_core::structs::AccountType;
+use angel_core::structs::{AccountType, EmergencyExit};
 
-use astroport::{asset::AssetInfo, router::SwapOperation};
+use crate::astroport::{asset::AssetInfo, router::SwapOperation};
 
 #[cw_serde]
 pub struct Config {
@@ -20,6 +20,7 @@ pub struct Config {
     pub ibc_controller: Addr,
     pub ap_tax_rate: Decimal, // Same as `registrar::config.tax_rate`
     pub interest_distribution: Decimal, // Same as `registrar::config.rebalance.interest_distribution`
+    pub performance_fee_rate: Decimal, // Rate of the harvest gain above the high-water mark taken as performance fee
 
     // TOKENS
     pub native_token: AssetInfo, // the input token(and output back to Accounts) into Vault
@@ -49,6 +50,20 @@ pub struct State {
     pub total_shares: Uint128,    // total amount of minted vault tokens
 }
 
+#[cw_serde]
+pub struct FeeInfo {
+    pub high_water_mark: Decimal, // highest LP tokens per vault share on which performance fee was charged
+    pub ap_tax_accrued: Uint128,  // total vault tokens minted to APTAX as AP tax
+    pub performance_fee_accrued: Uint128, // total vault tokens minted to APTAX as performance fee
+    pub claimed: Uint128,         // total vault tokens burned from APTAX by `claim_taxes`
+}
+
+#[cw_serde]
+pub struct TaxBalance {
+    pub accrued: Uint128,
+    pub claimed: Uint128,
+}
+
 #[cw_serde]
 pub struct TokenInfo {
     pub name: String,
@@ -76,3 +91,6 @@ pub const STATE: Item<State> = Item::new("state");
 pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
 pub const BALANCES: Map<u32, Uint128> = Map::new("balance");
 pub const APTAX: Item<Uint128> = Item::new("ap_treasury_tax_balance");
+pub const FEES: Item<FeeInfo> = Item::new("fees");
+pub const TAXES: Map<String, TaxBalance> = Map::new("taxes"); // asset => AP tax & fee amounts
+pub const EMERGENCY_EXIT: Item<EmergencyExit> = Item::new("emergency_exit");
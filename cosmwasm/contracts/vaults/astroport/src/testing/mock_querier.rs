@@ -1,5 +1,5 @@
 // Contains mock functionality to test multi-contract scenarios
-use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     from_binary, from_slice, to_binary, Addr, Api, BalanceResponse, BankQuery, CanonicalAddr, Coin,
@@ -11,12 +11,15 @@ use cosmwasm_storage::to_length_prefixed;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
-use angel_core::msgs::{accounts::EndowmentDetailsResponse, registrar::ConfigResponse};
+use angel_core::msgs::{
+    accounts::EndowmentDetailsResponse,
+    registrar::{ConfigExtensionResponse, ConfigResponse},
+};
 use angel_core::structs::{
-    AcceptedTokens, AccountStrategies, Categories, OneOffVaults, RebalanceDetails, SplitDetails,
+    AcceptedTokens, Categories, Investments, RebalanceDetails, SplitDetails,
 };
 
-use astroport::{
+use crate::astroport::{
     asset::{AssetInfo, PairInfo},
     factory::PairType,
 };
@@ -26,6 +29,7 @@ pub enum QueryMsg {
     Endowment { id: u32 },
     Balance { address: String },
     Config {},
+    ConfigExtension {},
     Pair {},
     QueryFlpTokenFromPoolAddress { pool_address: String },
 }
@@ -37,11 +41,11 @@ pub fn mock_dependencies(
 ) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
     let contract_addr = MOCK_CONTRACT_ADDR;
     let mut custom_querier: WasmMockQuerier = WasmMockQuerier::new(
-        MockQuerier::new(&[(&contract_addr, contract_balance)]),
+        MockQuerier::new(&[(contract_addr, contract_balance)]),
         MockApi::default(),
     );
     let contract_balance: Vec<(&String, &Uint128)> = contract_balance
-        .into_iter()
+        .iter()
         .map(|x| (&(x.denom), &(x.amount)))
         .collect();
     custom_querier.token_querier =
@@ -129,7 +133,7 @@ impl WasmMockQuerier {
                 ))
             }
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
-                match from_binary(&msg).unwrap() {
+                match from_binary(msg).unwrap() {
                     // Simulating the `Registrar::QueryMsg::EndowmentList {...}`
                     QueryMsg::Endowment { id: _ } => SystemResult::Ok(ContractResult::Ok(
                         to_binary(&EndowmentDetailsResponse {
@@ -137,8 +141,7 @@ impl WasmMockQuerier {
                             status: angel_core::structs::EndowmentStatus::Approved,
                             endow_type: angel_core::structs::EndowmentType::Charity,
                             maturity_time: None,
-                            strategies: AccountStrategies::default(),
-                            oneoff_vaults: OneOffVaults::default(),
+                            invested_strategies: Investments::default(),
                             rebalance: RebalanceDetails::default(),
                             kyc_donors_only: false,
                             deposit_approved: true,
@@ -150,6 +153,8 @@ impl WasmMockQuerier {
                             tier: Some(3),
                             logo: Some("Some fancy logo".to_string()),
                             image: Some("Nice banner image".to_string()),
+                            referral_id: None,
+                            parent: None,
                         })
                         .unwrap(),
                     )),
@@ -172,6 +177,7 @@ impl WasmMockQuerier {
                                 registrar_contract: "registrar".to_string(),
                                 keeper: "keeper".to_string(),
                                 tax_collector: "tax-collector".to_string(),
+                                performance_fee_rate: Decimal::zero(),
                                 native_token: "ujuno".to_string(),
                                 lp_token: "lp-token".to_string(),
                                 lp_pair_token0: "token0".to_string(),
@@ -199,6 +205,7 @@ impl WasmMockQuerier {
                                 registrar_contract: "registrar".to_string(),
                                 keeper: "keeper".to_string(),
                                 tax_collector: "tax-collector".to_string(),
+                                performance_fee_rate: Decimal::zero(),
                                 native_token: "ujuno".to_string(),
                                 lp_token: "lp-token".to_string(),
                                 lp_pair_token0: "token0".to_string(),
@@ -220,32 +227,50 @@ impl WasmMockQuerier {
                             to_binary(&ConfigResponse {
                                 owner: "registrar-owner".to_string(),
                                 version: "1.0.0".to_string(),
-                                accounts_contract: Some("accounts-contract".to_string()),
                                 treasury: "treasury".to_string(),
                                 rebalance: RebalanceDetails::default(),
-                                index_fund: None,
                                 split_to_liquid: SplitDetails {
                                     max: Decimal::one(),
                                     min: Decimal::zero(),
                                     default: Decimal::default(),
                                 },
-                                halo_token: None,
-                                gov_contract: None,
-                                charity_shares_contract: None,
-                                cw3_code: Some(3_u64),
-                                cw4_code: Some(4_u64),
                                 accepted_tokens: AcceptedTokens {
                                     native: vec![],
                                     cw20: vec![],
                                 },
-                                applications_review: "applications-review".to_string(),
-                                swaps_router: None,
                                 axelar_gateway: "axelar-gateway".to_string(),
                                 axelar_ibc_channel: "channel-1".to_string(),
+                                axelar_chain_id: "juno".to_string(),
                             })
                             .unwrap(),
                         )),
                     },
+                    // Simulating the `registrar::QueryMsg::ConfigExtension {}`
+                    QueryMsg::ConfigExtension {} => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&ConfigExtensionResponse {
+                            cw3_code: Some(3_u64),
+                            cw4_code: Some(4_u64),
+                            subdao_gov_code: None,
+                            subdao_cw20_token_code: None,
+                            subdao_bonding_token_code: None,
+                            subdao_cw900_code: None,
+                            subdao_distributor_code: None,
+                            donation_match_code: None,
+                            halo_token: None,
+                            halo_token_lp_contract: None,
+                            gov_contract: None,
+                            accounts_contract: Some("accounts-contract".to_string()),
+                            index_fund: None,
+                            donation_match_charites_contract: None,
+                            collector_addr: "collector".to_string(),
+                            charity_shares_contract: None,
+                            swap_factory: None,
+                            applications_review: "applications-review".to_string(),
+                            swaps_router: None,
+                            accounts_settings_controller: None,
+                        })
+                        .unwrap(),
+                    )),
                     // Simulating the `astroport::pair::Pair {}` query
                     QueryMsg::Pair {} => SystemResult::Ok(ContractResult::Ok(
                         to_binary(&PairInfo {
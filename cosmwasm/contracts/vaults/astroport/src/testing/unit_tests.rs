@@ -1,12 +1,13 @@
-use astroport::router::SwapOperation;
+use crate::astroport::router::SwapOperation;
 use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    coins, from_binary, to_binary, Addr, Coin, Decimal, OverflowError, OwnedDeps, StdError, Uint128,
+    coins, from_binary, to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, OverflowError,
+    OwnedDeps, StdError, Uint128, WasmMsg,
 };
 
 use crate::executers::PENDING_OWNER_DEADLINE;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, UpdateConfigMsg};
-use crate::responses::{ConfigResponse, StateResponse};
+use crate::responses::{ConfigResponse, EmergencyExitResponse, FeesResponse, StateResponse};
 use angel_core::errors::vault::ContractError;
 use angel_core::structs::AccountType;
 use cw20::{BalanceResponse, TokenInfoResponse};
@@ -25,9 +26,10 @@ fn create_mock_vault(
         ibc_controller: "ibc-sender".to_string(),
         ap_tax_rate: Decimal::from_ratio(10_u128, 100_u128),
         interest_distribution: Decimal::from_ratio(25_u128, 100_u128),
+        performance_fee_rate: Decimal::from_ratio(20_u128, 100_u128),
 
         acct_type,
-        sibling_vault: sibling_vault,
+        sibling_vault,
         registrar_contract: "angelprotocolteamdano".to_string(),
         keeper: "keeper".to_string(),
         tax_collector: "tax-collector".to_string(),
@@ -64,6 +66,7 @@ fn proper_instantiation() {
         ibc_controller: "ibc-sender".to_string(),
         ap_tax_rate: Decimal::from_ratio(10_u128, 100_u128),
         interest_distribution: Decimal::from_ratio(25_u128, 100_u128),
+        performance_fee_rate: Decimal::from_ratio(20_u128, 100_u128),
 
         acct_type: AccountType::Locked,
         sibling_vault: Some("sibling-vault".to_string()),
@@ -222,6 +225,7 @@ fn test_update_config() {
         keeper: Some("new-keeper".to_string()),
         sibling_vault: None,
         tax_collector: Some("new-tax-collector".to_string()),
+        performance_fee_rate: None,
 
         native_token: Some(cw_asset::AssetInfoBase::Native("ujuno".to_string())),
         reward_to_native_route: None,
@@ -256,26 +260,26 @@ fn test_update_config() {
     // Succeed to update the "config"
     update_config_msg.native_token = Some(cw_asset::AssetInfoBase::Native("ujuno".to_string()));
     update_config_msg.native_to_lp0_route = Some(vec![SwapOperation::AstroSwap {
-        offer_asset_info: astroport::asset::AssetInfo::NativeToken {
+        offer_asset_info: crate::astroport::asset::AssetInfo::NativeToken {
             denom: "ujuno".to_string(),
         },
-        ask_asset_info: astroport::asset::AssetInfo::Token {
+        ask_asset_info: crate::astroport::asset::AssetInfo::Token {
             contract_addr: Addr::unchecked("token1"),
         },
     }]);
     update_config_msg.native_to_lp1_route = Some(vec![SwapOperation::AstroSwap {
-        offer_asset_info: astroport::asset::AssetInfo::NativeToken {
+        offer_asset_info: crate::astroport::asset::AssetInfo::NativeToken {
             denom: "ujuno".to_string(),
         },
-        ask_asset_info: astroport::asset::AssetInfo::Token {
+        ask_asset_info: crate::astroport::asset::AssetInfo::Token {
             contract_addr: Addr::unchecked("token2"),
         },
     }]);
     update_config_msg.reward_to_native_route = Some(vec![SwapOperation::AstroSwap {
-        offer_asset_info: astroport::asset::AssetInfo::Token {
+        offer_asset_info: crate::astroport::asset::AssetInfo::Token {
             contract_addr: Addr::unchecked("lp-reward"),
         },
-        ask_asset_info: astroport::asset::AssetInfo::NativeToken {
+        ask_asset_info: crate::astroport::asset::AssetInfo::NativeToken {
             denom: "ujuno".to_string(),
         },
     }]);
@@ -371,6 +375,7 @@ fn test_deposit_cw20_token() {
         ibc_controller: "ibc-sender".to_string(),
         ap_tax_rate: Decimal::from_ratio(10_u128, 100_u128),
         interest_distribution: Decimal::from_ratio(25_u128, 100_u128),
+        performance_fee_rate: Decimal::from_ratio(20_u128, 100_u128),
 
         acct_type: AccountType::Locked,
         sibling_vault: Some("sibling-vault".to_string()),
@@ -690,25 +695,6 @@ fn test_stake_lp_token_from_deposit() {
         })
     );
 
-    // Same as above when `stake` the LP tokens from `harvest`
-    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
-    let err = execute(
-        deps.as_mut(),
-        mock_env(),
-        info,
-        ExecuteMsg::Stake {
-            endowment_id: None,
-            lp_token_bal_before: Uint128::from(50_u128),
-        },
-    )
-    .unwrap_err();
-    assert_eq!(
-        err,
-        ContractError::Std(StdError::GenericErr {
-            msg: format!("Received {}, should be bigger than {}.", 100 - 50, 100),
-        })
-    );
-
     // Succeed to "stake" the LP tokens
     let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
 
@@ -847,6 +833,31 @@ fn test_stake_lp_token_from_deposit() {
     .unwrap();
 
     assert_eq!(res.messages.len(), 1);
+
+    // The LP tokens staked from `harvest` into a vault without any vault tokens
+    // stay in the vault, & no fee shares are minted
+    let mut deps = create_mock_vault(
+        AccountType::Locked,
+        Some("sibling-vault".to_string()),
+        vec![],
+    );
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Stake {
+            endowment_id: None,
+            lp_token_bal_before: Uint128::from(50_u128),
+        },
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.total_shares, "0");
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::ApTaxBalance {}).unwrap();
+    let ap_tax: BalanceResponse = from_binary(&res).unwrap();
+    assert_eq!(ap_tax.balance, Uint128::zero());
 }
 
 #[test]
@@ -1518,6 +1529,375 @@ fn test_swap_back() {
     assert_eq!(res.messages.len(), 1);
 }
 
+#[test]
+fn test_claim_taxes() {
+    let mut deps = create_mock_vault(
+        AccountType::Liquid,
+        Some("locked_sibling_vault".to_string()),
+        vec![],
+    );
+
+    // Only the `config.owner` or `config.keeper` can claim the taxes
+    let info = mock_info("anyone", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::ClaimTaxes {}).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fail to claim since nothing is accrued yet
+    let info = mock_info("keeper", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::ClaimTaxes {}).unwrap_err();
+    assert_eq!(err, ContractError::ZeroAmount {});
+
+    // Harvesting without any vault token holders does not accrue fees
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Stake {
+            endowment_id: None,
+            lp_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+    let fees: FeesResponse = from_binary(&res).unwrap();
+    assert_eq!(fees.unclaimed, Uint128::zero());
+    assert_eq!(fees.assets.len(), 0);
+
+    // Endowment deposit of 100 LP tokens (the previous harvest stays in the vault)
+    let mut deps = create_mock_vault(
+        AccountType::Liquid,
+        Some("locked_sibling_vault".to_string()),
+        vec![],
+    );
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Stake {
+            endowment_id: Some(1_u32),
+            lp_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap();
+
+    // Harvest 100 LP tokens, which accrues the AP tax & performance fee
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Stake {
+            endowment_id: None,
+            lp_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+    let fees: FeesResponse = from_binary(&res).unwrap();
+    assert_eq!(fees.claimed, Uint128::zero());
+    assert_eq!(
+        fees.unclaimed,
+        fees.ap_tax_accrued + fees.performance_fee_accrued
+    );
+    assert_eq!(fees.assets.len(), 1);
+    // AP tax: 100 * 10% = 10, performance fee: (100 - 10) * 20% = 18
+    assert_eq!(fees.assets[0].accrued, Uint128::from(28_u128));
+    // Fee shares are minted pro-rata to the 172 LP tokens left to the 1_000_000 holder shares
+    //   s = 28 * 1_000_000 / 172
+    assert_eq!(fees.unclaimed, Uint128::from(162790_u128));
+
+    // Succeed to claim the taxes: unstake + remove liquidity + re-stake reward
+    let info = mock_info("keeper", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::ClaimTaxes {}).unwrap();
+    assert_eq!(res.messages.len(), 3);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+    let fees: FeesResponse = from_binary(&res).unwrap();
+    assert_eq!(fees.unclaimed, Uint128::zero());
+    assert_eq!(
+        fees.claimed,
+        fees.ap_tax_accrued + fees.performance_fee_accrued
+    );
+    // The fee shares are redeemed for the accrued LP tokens (less the rounding)
+    assert_eq!(fees.assets[0].claimed, Uint128::from(27_u128));
+}
+
+#[test]
+fn test_tax_collector_redeem() {
+    let mut deps = create_mock_vault(
+        AccountType::Liquid,
+        Some("locked_sibling_vault".to_string()),
+        vec![],
+    );
+
+    // Endowment deposit of 100 LP tokens, then harvest 100 LP tokens to accrue the fees
+    for endowment_id in [Some(1_u32), None] {
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+        let _ = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Stake {
+                endowment_id,
+                lp_token_bal_before: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    }
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+    let fees: FeesResponse = from_binary(&res).unwrap();
+    let unclaimed = fees.unclaimed;
+
+    // The `tax_collector` redeems the APTAX balance, which is booked as claimed
+    let info = mock_info("tax-collector", &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Redeem {
+            endowment_id: 0,
+            amount: unclaimed,
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+    let fees: FeesResponse = from_binary(&res).unwrap();
+    assert_eq!(fees.unclaimed, Uint128::zero());
+    assert_eq!(fees.claimed, unclaimed);
+    assert_eq!(fees.assets.len(), 1);
+    assert_eq!(fees.assets[0].accrued, Uint128::from(28_u128));
+    assert_eq!(fees.assets[0].claimed, Uint128::from(27_u128));
+}
+
+#[test]
+fn test_claim_taxes_after_emergency_exit() {
+    let mut deps = create_mock_vault(
+        AccountType::Liquid,
+        Some("locked_sibling_vault".to_string()),
+        vec![],
+    );
+
+    // Endowment deposit of 100 LP tokens, then harvest 100 LP tokens to accrue the fees
+    for endowment_id in [Some(1_u32), None] {
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+        let _ = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Stake {
+                endowment_id,
+                lp_token_bal_before: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    }
+
+    // Unwind the whole position
+    let info = mock_info("creator", &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdraw {},
+    )
+    .unwrap();
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap();
+
+    // The fee shares are paid out in `native_token` straight to the `treasury`
+    let info = mock_info("keeper", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::ClaimTaxes {}).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: coins(13, "ujuno"),
+        })
+    );
+
+    // ...but the claim is still recorded in the LP tokens the fees accrued in
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+    let fees: FeesResponse = from_binary(&res).unwrap();
+    assert_eq!(fees.unclaimed, Uint128::zero());
+    assert_eq!(fees.assets.len(), 1);
+    assert_eq!(fees.assets[0].accrued, Uint128::from(28_u128));
+    assert_eq!(fees.assets[0].claimed, Uint128::from(27_u128));
+}
+
+#[test]
+fn test_emergency_withdraw() {
+    let mut deps = create_mock_vault(
+        AccountType::Locked,
+        Some("sibling-vault".to_string()),
+        vec![],
+    );
+
+    // Only the `config.owner` can trigger the emergency exit
+    let info = mock_info("non-owner", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdraw {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Vault without any LP position only outputs the "finalize" message
+    let info = mock_info("creator", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdraw {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    // Only the contract itself can call the "finalize" entry
+    let info = mock_info("non-contract", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap();
+
+    // Check the emergency exit status
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::EmergencyExit {}).unwrap();
+    let emergency_exit: EmergencyExitResponse = from_binary(&res).unwrap();
+    assert!(emergency_exit.active);
+    assert_eq!(emergency_exit.native_token_amount, "100".to_string());
+
+    // The vault is redeem-only from now on
+    let info = mock_info("ibc-relayer", &coins(100, "ujuno"));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Deposit { endowment_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EmergencyExitActive {});
+
+    let info = mock_info("keeper", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Harvest {}).unwrap_err();
+    assert_eq!(err, ContractError::EmergencyExitActive {});
+
+    let info = mock_info("creator", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdraw {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EmergencyExitActive {});
+}
+
+#[test]
+fn test_emergency_redeem() {
+    let mut deps = create_mock_vault(
+        AccountType::Locked,
+        Some("sibling-vault".to_string()),
+        vec![],
+    );
+
+    // Endowment 1 holds vault tokens for the staked LP position
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Stake {
+            endowment_id: Some(1_u32),
+            lp_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance { endowment_id: 1 },
+    )
+    .unwrap();
+    let balance: Uint128 = from_binary(&res).unwrap();
+
+    // Unwind the whole position
+    let info = mock_info("creator", &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdraw {},
+    )
+    .unwrap();
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap();
+
+    // The redeemed `native_token` is credited back to the Endowment through the Accounts contract
+    let info = mock_info("ibc-relayer", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Redeem {
+            endowment_id: 1,
+            amount: balance,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "accounts-contract".to_string(),
+            msg: to_binary(&angel_core::msgs::accounts::ExecuteMsg::VaultReceipt {
+                id: 1,
+                acct_type: AccountType::Locked,
+            })
+            .unwrap(),
+            funds: coins(100, "ujuno"),
+        })
+    );
+}
+
 #[test]
 fn test_migrate() {
     let mut deps = create_mock_vault(
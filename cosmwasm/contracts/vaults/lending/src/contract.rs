@@ -0,0 +1,163 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    Uint128,
+};
+use cw2::{get_contract_version, set_contract_version};
+
+use angel_core::errors::vault::ContractError;
+
+use crate::executers;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::queriers;
+use crate::state::{Config, MinterData, State, TokenInfo, APTAX, CONFIG, STATE, TOKEN_INFO};
+
+// version info for future migration info
+const CONTRACT_NAME: &str = "lending_vault";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.ap_tax_rate > Decimal::one() {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Invalid ap_tax_rate: {}",
+            msg.ap_tax_rate
+        ))));
+    }
+
+    // Store the configuration
+    let sibling_vault = match msg.sibling_vault {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => env.contract.address.clone(), // can set later with update_config
+    };
+    let config = Config {
+        owner: info.sender,
+        acct_type: msg.acct_type,
+        sibling_vault,
+        registrar_contract: deps.api.addr_validate(&msg.registrar_contract)?,
+        keeper: deps.api.addr_validate(&msg.keeper)?,
+        tax_collector: deps.api.addr_validate(&msg.tax_collector)?,
+
+        ibc_host: deps.api.addr_validate(&msg.ibc_host)?,
+        ibc_controller: deps.api.addr_validate(&msg.ibc_controller)?,
+        ap_tax_rate: msg.ap_tax_rate,
+
+        money_market: deps.api.addr_validate(&msg.money_market)?,
+        input_denom: msg.input_denom,
+
+        minimum_initial_deposit: msg.minimum_initial_deposit,
+        pending_owner: None,
+        pending_owner_deadline: None,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    // Initialize the contract state
+    STATE.save(
+        deps.storage,
+        &State {
+            total_shares: Uint128::zero(),
+            high_water_mark: Decimal::one(),
+        },
+    )?;
+
+    APTAX.save(deps.storage, &Uint128::zero())?;
+
+    // Store vault token information
+    let token_info = TokenInfo {
+        name: msg.name,
+        symbol: msg.symbol,
+        decimals: msg.decimals,
+        total_supply: Uint128::zero(),
+        // set self as minter, so we can properly execute mint and burn
+        mint: Some(MinterData {
+            minter: env.contract.address,
+            cap: None,
+        }),
+    };
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    Ok(Response::new().add_attribute("register_vault", token_info.symbol))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateOwner { new_owner } => {
+            executers::update_owner(deps, env, info, new_owner)
+        }
+        ExecuteMsg::UpdateConfig(msg) => executers::update_config(deps, env, info, msg),
+        // -Input token(eg. USDC) (Account) --> +Deposit Token/Yield Token (Vault)
+        ExecuteMsg::Deposit { endowment_id } => executers::deposit(deps, env, info, endowment_id),
+        // -Deposit Token/Yield Token (Vault) --> +Input token(principal + interest) --> Accounts
+        ExecuteMsg::Redeem {
+            endowment_id,
+            amount, // vault tokens to be burned
+        } => executers::redeem(deps, env, info, endowment_id, Some(amount)),
+        ExecuteMsg::RedeemAll { endowment_id } => {
+            executers::redeem(deps, env, info, endowment_id, None)
+        }
+        // -Deposit Token/Yield Token(Liquid Vault) --> +Deposit Token/Yield Token(Locked Vault)
+        ExecuteMsg::ReinvestToLocked {
+            endowment_id,
+            amount,
+        } => executers::reinvest_to_locked(deps, env, info, endowment_id, amount),
+        // -Deposit Token/Yield Token(Liquid Vault) --> +Deposit Token(Vault Router)
+        ExecuteMsg::RedeemForReinvest {
+            endowment_id,
+            amount,
+        } => executers::redeem_for_reinvest(deps, env, info, endowment_id, amount),
+        // Mint the AP tax on the interest accrued since last harvest
+        ExecuteMsg::Harvest { account_ids: _ } => executers::harvest(deps, env, info),
+        // Send the accrued AP tax to the registrar `treasury`
+        ExecuteMsg::ClaimTaxes {} => executers::claim_taxes(deps, env, info),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&queriers::query_config(deps)?),
+        QueryMsg::State {} => to_binary(&queriers::query_state(deps, env)?),
+        QueryMsg::Balance { endowment_id } => {
+            to_binary(&queriers::query_balance(deps, endowment_id))
+        }
+        QueryMsg::TokenInfo {} => to_binary(&queriers::query_token_info(deps)?),
+        QueryMsg::TotalBalance {} => to_binary(&queriers::query_total_balance(deps)?),
+        QueryMsg::ApTaxBalance {} => to_binary(&queriers::query_ap_tax_balance(deps)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let ver = get_contract_version(deps.storage)?;
+    // ensure we are migrating from an allowed contract
+    if ver.contract != CONTRACT_NAME {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Can only upgrade from same type".to_string(),
+        }));
+    }
+    // note: better to do proper semver compare, but string compare *usually* works
+    if ver.version.as_str() >= CONTRACT_VERSION {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Cannot upgrade from a newer version".to_string(),
+        }));
+    }
+
+    // set the new version
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default())
+}
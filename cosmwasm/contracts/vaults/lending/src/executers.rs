@@ -0,0 +1,582 @@
+use cosmwasm_std::{
+    attr, coins, to_binary, Addr, BankMsg, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::Denom;
+
+use angel_core::errors::vault::ContractError;
+use angel_core::msgs::registrar::{
+    ConfigResponse as RegistrarConfigResponse, QueryMsg as RegistrarQueryMsg,
+};
+use angel_core::structs::AccountType;
+use angel_core::utils::{query_accounts_contract, vault_payout_msg};
+
+use crate::msg::{ExecuteMsg, MoneyMarketExecuteMsg, UpdateConfigMsg};
+use crate::queriers::{query_balance, query_underlying_balance};
+use crate::state::{Config, APTAX, BALANCES, CONFIG, STATE, TOKEN_INFO};
+
+// Number of blocks until pending owner update is valid
+pub const PENDING_OWNER_DEADLINE: u64 = 42069;
+
+/// Contract entry: **UpdateOwner**
+pub fn update_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // 2-step process of updating `config.owner`
+    //
+    // 1. Current `config.owner` suggests `new_owner` address
+    //    - At this moment, the `pending_owner` is set with `new_owner` address.
+    //    - Also, the `pending_owner_deadline` is set as current block height + constant DEADLINE height
+    //
+    // 2. The `pending_owner`(new_owner) completes the process & becomes the `config.owner`,
+    //    OR the settings are unset for future process.
+    match (config.pending_owner, config.pending_owner_deadline) {
+        (None, None) => {
+            if info.sender != config.owner {
+                return Err(ContractError::Unauthorized {});
+            }
+            let new_owner = deps.api.addr_validate(&new_owner)?;
+            config.pending_owner = Some(new_owner);
+            config.pending_owner_deadline = Some(env.block.height + PENDING_OWNER_DEADLINE);
+        }
+        (Some(pending_owner), Some(deadline)) => {
+            if info.sender != pending_owner {
+                return Err(ContractError::Unauthorized {});
+            }
+            if env.block.height <= deadline {
+                config.owner = pending_owner;
+            }
+            config.pending_owner = None;
+            config.pending_owner_deadline = None;
+        }
+        _ => {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Invalid owner update settings",
+            )))
+        }
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// Contract entry: **UpdateConfig**
+///
+/// Update the **CONFIG** of the contract
+pub fn update_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: UpdateConfigMsg,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // only the SC admin can update these configs...for now
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.ibc_host = match msg.ibc_host {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => config.ibc_host,
+    };
+    config.ibc_controller = match msg.ibc_controller {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => config.ibc_controller,
+    };
+    config.sibling_vault = match msg.sibling_vault {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => config.sibling_vault,
+    };
+    config.keeper = match msg.keeper {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => config.keeper,
+    };
+    config.tax_collector = match msg.tax_collector {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => config.tax_collector,
+    };
+    config.ap_tax_rate = match msg.ap_tax_rate {
+        Some(rate) => {
+            if rate > Decimal::one() {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "Invalid ap_tax_rate: {}",
+                    rate
+                ))));
+            }
+            rate
+        }
+        None => config.ap_tax_rate,
+    };
+    config.minimum_initial_deposit = match msg.minimum_initial_deposit {
+        Some(v) => v,
+        None => config.minimum_initial_deposit,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// Contract entry: **Deposit**
+///   1. Mint the vault tokens for the deposited `input_denom`
+///   2. Supply the `input_denom` to the money market
+pub fn deposit(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endowment_id: u32,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // Deposits come from the `ibc_host` or, for a locked vault, from its liquid sibling (reinvest)
+    let from_sibling = config.acct_type == AccountType::Locked
+        && config.sibling_vault != env.contract.address
+        && info.sender == config.sibling_vault;
+    if info.sender != config.ibc_host && !from_sibling {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if info.funds.len() != 1 || info.funds[0].denom != config.input_denom {
+        return Err(ContractError::InvalidCoinsDeposited {});
+    }
+    let deposit_amount = info.funds[0].amount;
+    if deposit_amount.is_zero() {
+        return Err(ContractError::EmptyBalance {});
+    }
+
+    // Compute the `vault_token` amount
+    // The formula of calculating the amount of vault tokens to be minted is as follows:
+    //   s = vault shares to mint <<< what we need to calculate given some # of `input_denom` deposited
+    //   T = vault shares total (before mint)
+    //   a = `input_denom` added to Vault's money market position
+    //   B = Vault's money market position (principal + interest, before deposit)
+    //
+    //   s = (a * T) / B = a * (T / B)
+    let vt_mint_amount = if state.total_shares.is_zero() {
+        if deposit_amount < config.minimum_initial_deposit {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: format!(
+                    "Insufficient initial deposit amount: Needed {}, Received {}",
+                    config.minimum_initial_deposit, deposit_amount
+                ),
+            }));
+        }
+        // first depositor sets the share price at 1 `input_denom` per vault token
+        state.high_water_mark = Decimal::one();
+        deposit_amount
+    } else {
+        let total_underlying = query_underlying_balance(deps.as_ref(), &env, &config)?;
+        if total_underlying.is_zero() {
+            return Err(ContractError::EmptyBalance {});
+        }
+        deposit_amount.multiply_ratio(state.total_shares, total_underlying)
+    };
+
+    execute_mint(deps.branch(), Some(endowment_id), vt_mint_amount)?;
+    state.total_shares += vt_mint_amount;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.money_market.to_string(),
+            msg: to_binary(&MoneyMarketExecuteMsg::Deposit {})?,
+            funds: coins(deposit_amount.u128(), config.input_denom),
+        }))
+        .add_attributes(vec![
+            attr("action", "deposit"),
+            attr("endowment_id", endowment_id.to_string()),
+            attr("mint_shares", vt_mint_amount.to_string()),
+        ]))
+}
+
+/// Contract entry: **Redeem** & **RedeemAll**
+///   1. Burn the vault tokens (all of them when `burn_shares_amount` is `None`)
+///   2. Withdraw the `input_denom` equivalent(principal + interest) from the money market
+///   3. Send the `input_denom` to the `beneficiary`
+pub fn redeem(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endowment_id: u32,
+    burn_shares_amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let beneficiary: Addr;
+    let id: Option<u32>;
+    if info.sender == config.tax_collector {
+        beneficiary = config.tax_collector.clone();
+        id = None;
+    } else {
+        // Check if the `caller` is "ibc_host".
+        if info.sender != config.ibc_host {
+            return Err(ContractError::Unauthorized {});
+        }
+        // Endowment redemptions are credited back to the endowment through the Accounts contract
+        beneficiary = query_accounts_contract(deps.as_ref(), config.registrar_contract.as_str())?;
+        id = Some(endowment_id);
+    }
+
+    let burn_shares_amount = match burn_shares_amount {
+        Some(amount) => amount,
+        None => match id {
+            Some(id) => query_balance(deps.as_ref(), id),
+            None => APTAX.load(deps.storage)?,
+        },
+    };
+    let withdraw_amount = burn_shares(deps.branch(), &env, &config, id, burn_shares_amount)?;
+
+    Ok(Response::default()
+        .add_messages(prepare_withdraw_msgs(
+            &config,
+            withdraw_amount,
+            vault_payout_msg(
+                &Denom::Native(config.input_denom.to_string()),
+                &beneficiary,
+                id,
+                config.acct_type.clone(),
+                withdraw_amount,
+            )?,
+        )?)
+        .add_attributes(vec![
+            attr("action", "withdraw"),
+            attr("burn_shares", burn_shares_amount.to_string()),
+            attr("withdraw_amount", withdraw_amount.to_string()),
+        ]))
+}
+
+/// Contract entry: **ReinvestToLocked** (liquid vault logic)
+///   1. Burn the vault tokens
+///   2. Withdraw the `input_denom` equivalent from the money market
+///   3. Deposit the `input_denom` to the sibling vault(locked) for the same endowment
+pub fn reinvest_to_locked(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u32,
+    burn_shares_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Check that the vault acct_type is `liquid`
+    if config.acct_type != AccountType::Liquid {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "This is locked vault".to_string(),
+        }));
+    }
+
+    if info.sender != config.ibc_host {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if config.sibling_vault == env.contract.address {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Sibling vault not created".to_string(),
+        }));
+    }
+
+    let endowment_vt_balance = query_balance(deps.as_ref(), id);
+    if burn_shares_amount > endowment_vt_balance {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: format!(
+                "Insufficient balance: Needed {}, existing: {}",
+                burn_shares_amount, endowment_vt_balance
+            ),
+        }));
+    }
+    let withdraw_amount = burn_shares(deps.branch(), &env, &config, Some(id), burn_shares_amount)?;
+
+    Ok(Response::default()
+        .add_messages(prepare_withdraw_msgs(
+            &config,
+            withdraw_amount,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.sibling_vault.to_string(),
+                msg: to_binary(&ExecuteMsg::Deposit { endowment_id: id })?,
+                funds: coins(withdraw_amount.u128(), config.input_denom.to_string()),
+            }),
+        )?)
+        .add_attributes(vec![
+            attr("action", "reinvest_to_locked_vault"),
+            attr("burn_shares", burn_shares_amount.to_string()),
+            attr("reinvest_amount", withdraw_amount.to_string()),
+        ]))
+}
+
+/// Contract entry: **RedeemForReinvest** (liquid vault logic)
+///   1. Burn the vault tokens
+///   2. Withdraw the `input_denom` equivalent from the money market
+///   3. Send the `input_denom` to the `ibc_host`(Vault Router), which deposits it
+///      to the locked vault of another Strategy for the same endowment
+pub fn redeem_for_reinvest(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u32,
+    burn_shares_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Check that the vault acct_type is `liquid`
+    if config.acct_type != AccountType::Liquid {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "This is locked vault".to_string(),
+        }));
+    }
+
+    if info.sender != config.ibc_host {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let endowment_vt_balance = query_balance(deps.as_ref(), id);
+    if burn_shares_amount > endowment_vt_balance {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: format!(
+                "Insufficient balance: Needed {}, existing: {}",
+                burn_shares_amount, endowment_vt_balance
+            ),
+        }));
+    }
+    let withdraw_amount = burn_shares(deps.branch(), &env, &config, Some(id), burn_shares_amount)?;
+
+    Ok(Response::default()
+        .add_messages(prepare_withdraw_msgs(
+            &config,
+            withdraw_amount,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(withdraw_amount.u128(), config.input_denom.to_string()),
+            }),
+        )?)
+        .add_attributes(vec![
+            attr("action", "redeem_for_reinvest"),
+            attr("burn_shares", burn_shares_amount.to_string()),
+            attr("withdraw_amount", withdraw_amount.to_string()),
+        ]))
+}
+
+/// Contract entry: **Harvest**
+///
+/// The money market compounds the interest by itself, so harvesting only takes the AP tax
+/// on the interest accrued above the high-water mark by minting vault tokens to APTAX:
+///   gain = B - T * HWM
+///   F    = gain * ap_tax_rate
+///   s    = F * T / (B - F)
+pub fn harvest(mut deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // Only the `keeper` or `ibc_host` can harvest
+    if info.sender != config.keeper && info.sender != config.ibc_host {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let res = Response::default().add_attribute("action", "harvest");
+    if state.total_shares.is_zero() {
+        return Ok(res);
+    }
+
+    let total_underlying = query_underlying_balance(deps.as_ref(), &env, &config)?;
+    let gain = total_underlying.saturating_sub(state.total_shares * state.high_water_mark);
+    let ap_tax = gain * config.ap_tax_rate;
+    if ap_tax.is_zero() {
+        return Ok(res);
+    }
+
+    // the AP tax can only be minted as shares while some of the underlying is left to back them
+    let untaxed_underlying = total_underlying - ap_tax;
+    if untaxed_underlying.is_zero() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "AP tax cannot take the whole vault balance".to_string(),
+        }));
+    }
+    let ap_tax_shares = ap_tax.multiply_ratio(state.total_shares, untaxed_underlying);
+    if !ap_tax_shares.is_zero() {
+        execute_mint(deps.branch(), None, ap_tax_shares)?;
+        state.total_shares += ap_tax_shares;
+    }
+    state.high_water_mark = Decimal::from_ratio(total_underlying, state.total_shares);
+    STATE.save(deps.storage, &state)?;
+
+    Ok(res.add_attributes(vec![
+        attr("interest_gain", gain.to_string()),
+        attr("ap_tax_shares", ap_tax_shares.to_string()),
+    ]))
+}
+
+/// Contract entry: **ClaimTaxes**
+///   1. Burn all the vault tokens accrued as AP tax (APTAX balance)
+///   2. Withdraw the `input_denom` equivalent from the money market
+///   3. Send the `input_denom` to the registrar `treasury`
+pub fn claim_taxes(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the `config.owner` or `config.keeper` can trigger the claim
+    if info.sender != config.owner && info.sender != config.keeper {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let burn_shares_amount = APTAX.load(deps.storage)?;
+    if burn_shares_amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let registrar_config: RegistrarConfigResponse = deps.querier.query_wasm_smart(
+        config.registrar_contract.to_string(),
+        &RegistrarQueryMsg::Config {},
+    )?;
+    let treasury = deps.api.addr_validate(&registrar_config.treasury)?;
+
+    let withdraw_amount = burn_shares(deps.branch(), &env, &config, None, burn_shares_amount)?;
+
+    Ok(Response::default()
+        .add_messages(prepare_withdraw_msgs(
+            &config,
+            withdraw_amount,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: treasury.to_string(),
+                amount: coins(withdraw_amount.u128(), config.input_denom.to_string()),
+            }),
+        )?)
+        .add_attributes(vec![
+            attr("action", "claim_taxes"),
+            attr("treasury", treasury.to_string()),
+            attr("burn_shares", burn_shares_amount.to_string()),
+            attr("withdraw_amount", withdraw_amount.to_string()),
+        ]))
+}
+
+/// Burn the vault tokens of `endowment_id`(APTAX if `None`) & return the `input_denom` equivalent
+///   s = vault shares to burn
+///   T = vault shares total (before burn)
+///   a = `input_denom` to withdraw <<< what we need to calculate given some # of vault shares to be burned
+///   B = Vault's money market position (principal + interest)
+///
+///   a = (s * B) / T
+fn burn_shares(
+    mut deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    endowment_id: Option<u32>,
+    burn_shares_amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    let total_underlying = query_underlying_balance(deps.as_ref(), env, config)?;
+
+    execute_burn(deps.branch(), endowment_id, burn_shares_amount).map_err(|e| {
+        ContractError::Std(StdError::GenericErr {
+            msg: format!(
+                "Cannot burn the {} vault tokens from {} :: {}",
+                burn_shares_amount,
+                endowment_id.map_or_else(|| "APTAX".to_string(), |v| format!("Endowment {}", v)),
+                e,
+            ),
+        })
+    })?;
+
+    let withdraw_amount = burn_shares_amount.multiply_ratio(total_underlying, state.total_shares);
+    if withdraw_amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    state.total_shares -= burn_shares_amount;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(withdraw_amount)
+}
+
+/// Withdraw `amount` of `input_denom` from the money market, then forward it with `send_msg`
+fn prepare_withdraw_msgs(
+    config: &Config,
+    amount: Uint128,
+    send_msg: CosmosMsg,
+) -> StdResult<Vec<CosmosMsg>> {
+    Ok(vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.money_market.to_string(),
+            msg: to_binary(&MoneyMarketExecuteMsg::Withdraw {
+                denom: config.input_denom.to_string(),
+                amount: Some(amount),
+            })?,
+            funds: vec![],
+        }),
+        send_msg,
+    ])
+}
+
+/// Custom `mint` function for `vault token`
+fn execute_mint(
+    deps: DepsMut,
+    endowment_id: Option<u32>,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if amount == Uint128::zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    // update supply and enforce cap
+    let mut token_info = TOKEN_INFO.load(deps.storage)?;
+    token_info.total_supply += amount;
+    if let Some(limit) = token_info.get_cap() {
+        if token_info.total_supply > limit {
+            return Err(ContractError::CannotExceedCap {});
+        }
+    }
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    // add amount to recipient balance
+    match endowment_id {
+        Some(id) => BALANCES.update(
+            deps.storage,
+            id,
+            |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+        )?,
+        None => APTAX.update(deps.storage, |balance: Uint128| -> StdResult<_> {
+            Ok(balance.checked_add(amount)?)
+        })?,
+    };
+
+    Ok(())
+}
+
+/// Custom `burn` function for `vault token`
+fn execute_burn(
+    deps: DepsMut,
+    endowment_id: Option<u32>,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if amount == Uint128::zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    // lower balance
+    match endowment_id {
+        Some(id) => BALANCES.update(
+            deps.storage,
+            id,
+            |balance: Option<Uint128>| -> StdResult<_> {
+                Ok(balance.unwrap_or_default().checked_sub(amount)?)
+            },
+        )?,
+        None => APTAX.update(deps.storage, |balance: Uint128| -> StdResult<_> {
+            Ok(balance.checked_sub(amount)?)
+        })?,
+    };
+    // reduce total_supply
+    TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
+        info.total_supply = info.total_supply.checked_sub(amount)?;
+        Ok(info)
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+pub mod contract;
+pub mod executers;
+pub mod msg;
+pub mod queriers;
+pub mod responses;
+pub mod state;
+
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,132 @@
+use angel_core::structs::AccountType;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::{BalanceResponse, TokenInfoResponse};
+
+use crate::responses::{ConfigResponse, StateResponse};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub ibc_host: String,
+    pub ibc_controller: String,
+    pub ap_tax_rate: Decimal,
+
+    pub acct_type: AccountType,
+    pub sibling_vault: Option<String>,
+    pub registrar_contract: String,
+    pub keeper: String,
+    pub tax_collector: String,
+
+    pub money_market: String, // money market contract the `input_denom` is supplied to
+    pub input_denom: String,  // the input token(and output back to Accounts) into Vault
+
+    pub minimum_initial_deposit: Uint128,
+
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// We currently take no arguments for migrations
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateOwner {
+        new_owner: String,
+    },
+    UpdateConfig(UpdateConfigMsg),
+    Deposit {
+        endowment_id: u32,
+    },
+    Redeem {
+        endowment_id: u32,
+        amount: Uint128, // vault tokens to be burned
+    },
+    RedeemAll {
+        endowment_id: u32,
+    },
+    /// reinvest vault assets from self (if AccountType::Liquid)
+    /// over to it's AccountType::Locked (sibling) vault
+    ReinvestToLocked {
+        endowment_id: u32,
+        amount: Uint128,
+    },
+    /// redeem vault assets from self (if AccountType::Liquid) back to the caller(Vault Router),
+    /// which reinvests them in the AccountType::Locked vault of another Strategy
+    RedeemForReinvest {
+        endowment_id: u32,
+        amount: Uint128,
+    },
+    /// take the AP tax on the interest accrued since the last harvest.
+    /// Interest is compounded by the money market itself, so `account_ids` is unused.
+    Harvest {
+        account_ids: Vec<u32>,
+    },
+    /// burn the vault tokens accrued as AP tax (APTAX balance) & send
+    /// the redeemed `input_denom` to the registrar `treasury`
+    ClaimTaxes {},
+}
+
+#[cw_serde]
+pub struct UpdateConfigMsg {
+    pub ibc_host: Option<String>,
+    pub ibc_controller: Option<String>,
+
+    pub sibling_vault: Option<String>,
+    pub keeper: Option<String>,
+    pub tax_collector: Option<String>,
+    pub ap_tax_rate: Option<Decimal>,
+
+    pub minimum_initial_deposit: Option<Uint128>,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the configuration of the contract
+    #[returns(ConfigResponse)]
+    Config {},
+    /// Returns the state of the contract
+    #[returns(StateResponse)]
+    State {},
+    /// Returns the current balance of the given "Endowment ID", 0 if unset.
+    #[returns(Uint128)]
+    Balance { endowment_id: u32 },
+    /// Returns metadata on the contract - name, decimals, supply, etc.
+    #[returns(TokenInfoResponse)]
+    TokenInfo {},
+    /// Returns the total balance/total_share of the contract
+    #[returns(BalanceResponse)]
+    TotalBalance {},
+    /// Returns the APTAX balance of the contract
+    #[returns(BalanceResponse)]
+    ApTaxBalance {},
+}
+
+/// Supply side of a money market (Mars Red Bank style) lending protocol
+#[cw_serde]
+pub enum MoneyMarketExecuteMsg {
+    /// Supply the attached coins to the market
+    Deposit {},
+    /// Withdraw `amount`(or the whole position if `None`) of `denom` supplied by the sender
+    Withdraw {
+        denom: String,
+        amount: Option<Uint128>,
+    },
+}
+
+#[cw_serde]
+pub enum MoneyMarketQueryMsg {
+    /// Returns the supplied amount (principal + accrued interest) of `user`
+    /// Return type: UserDepositResponse
+    UserDeposit { user: String, denom: String },
+}
+
+#[cw_serde]
+pub struct UserDepositResponse {
+    pub denom: String,
+    pub amount_scaled: Uint128,
+    pub amount: Uint128,
+}
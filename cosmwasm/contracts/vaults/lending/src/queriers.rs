@@ -0,0 +1,80 @@
+use cosmwasm_std::{Deps, Env, StdResult, Uint128};
+use cw20::{BalanceResponse, TokenInfoResponse};
+
+use crate::msg::{MoneyMarketQueryMsg, UserDepositResponse};
+use crate::responses::{ConfigResponse, StateResponse};
+use crate::state::{Config, APTAX, BALANCES, CONFIG, STATE, TOKEN_INFO};
+
+pub fn query_balance(deps: Deps, id: u32) -> Uint128 {
+    BALANCES.load(deps.storage, id).unwrap_or_default()
+}
+
+pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
+    let info = TOKEN_INFO.load(deps.storage)?;
+    Ok(TokenInfoResponse {
+        name: info.name,
+        symbol: info.symbol,
+        decimals: info.decimals,
+        total_supply: info.total_supply,
+    })
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        ibc_host: config.ibc_host.to_string(),
+        ibc_controller: config.ibc_controller.to_string(),
+
+        owner: config.owner.to_string(),
+        acct_type: config.acct_type,
+        sibling_vault: config.sibling_vault.to_string(),
+        registrar_contract: config.registrar_contract.to_string(),
+        keeper: config.keeper.to_string(),
+        tax_collector: config.tax_collector.to_string(),
+        ap_tax_rate: config.ap_tax_rate,
+
+        money_market: config.money_market.to_string(),
+        input_denom: config.input_denom,
+
+        minimum_initial_deposit: config.minimum_initial_deposit.to_string(),
+        pending_owner: config
+            .pending_owner
+            .map(|addr| addr.to_string())
+            .unwrap_or_default(),
+        pending_owner_deadline: config.pending_owner_deadline.unwrap_or_default(),
+    })
+}
+
+pub fn query_state(deps: Deps, env: Env) -> StdResult<StateResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    Ok(StateResponse {
+        total_shares: state.total_shares,
+        total_underlying: query_underlying_balance(deps, &env, &config)?,
+        high_water_mark: state.high_water_mark,
+    })
+}
+
+pub fn query_total_balance(deps: Deps) -> StdResult<BalanceResponse> {
+    let info = TOKEN_INFO.load(deps.storage)?;
+    Ok(BalanceResponse {
+        balance: info.total_supply,
+    })
+}
+
+pub fn query_ap_tax_balance(deps: Deps) -> StdResult<BalanceResponse> {
+    let ap_tax = APTAX.load(deps.storage)?;
+    Ok(BalanceResponse { balance: ap_tax })
+}
+
+/// Query the amount of `input_denom`(principal + interest) the vault has supplied to the money market
+pub fn query_underlying_balance(deps: Deps, env: &Env, config: &Config) -> StdResult<Uint128> {
+    let res: UserDepositResponse = deps.querier.query_wasm_smart(
+        config.money_market.to_string(),
+        &MoneyMarketQueryMsg::UserDeposit {
+            user: env.contract.address.to_string(),
+            denom: config.input_denom.to_string(),
+        },
+    )?;
+    Ok(res.amount)
+}
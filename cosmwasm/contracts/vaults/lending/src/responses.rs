@@ -0,0 +1,32 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Uint128};
+
+use angel_core::structs::AccountType;
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub ibc_host: String,
+    pub ibc_controller: String,
+
+    pub owner: String,
+    pub acct_type: AccountType,
+    pub sibling_vault: String,
+    pub registrar_contract: String,
+    pub keeper: String,
+    pub tax_collector: String,
+    pub ap_tax_rate: Decimal,
+
+    pub money_market: String,
+    pub input_denom: String,
+
+    pub minimum_initial_deposit: String,
+    pub pending_owner: String,
+    pub pending_owner_deadline: u64,
+}
+
+#[cw_serde]
+pub struct StateResponse {
+    pub total_shares: Uint128,
+    pub total_underlying: Uint128, // `input_denom` supplied to the money market (principal + interest)
+    pub high_water_mark: Decimal,
+}
@@ -0,0 +1,60 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+
+use angel_core::structs::AccountType;
+
+#[cw_serde]
+pub struct Config {
+    pub owner: Addr,
+    pub acct_type: AccountType,
+    pub sibling_vault: Addr,
+    pub registrar_contract: Addr,
+    pub keeper: Addr,
+    pub tax_collector: Addr,
+
+    pub ibc_host: Addr,
+    pub ibc_controller: Addr,
+    pub ap_tax_rate: Decimal, // Rate of the accrued interest taken as AP tax on harvest
+
+    pub money_market: Addr, // money market contract the `input_denom` is supplied to
+    pub input_denom: String, // the input token(and output back to Accounts) into Vault
+
+    pub minimum_initial_deposit: Uint128, // Minimum deposit amount limit when `total_shares` = 0
+    pub pending_owner: Option<Addr>, // Pending owner address which is used in 2-step `update_owner` process
+    pub pending_owner_deadline: Option<u64>, // Block height until which the `pending_owner` is valid in `update_owner` process
+}
+
+#[cw_serde]
+pub struct State {
+    pub total_shares: Uint128,    // total amount of minted vault tokens
+    pub high_water_mark: Decimal, // `input_denom` per vault share on which AP tax was last taken
+}
+
+#[cw_serde]
+pub struct TokenInfo {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub total_supply: Uint128,
+    pub mint: Option<MinterData>,
+}
+
+#[cw_serde]
+pub struct MinterData {
+    pub minter: Addr,
+    /// cap is how many more tokens can be issued by the minter
+    pub cap: Option<Uint128>,
+}
+
+impl TokenInfo {
+    pub fn get_cap(&self) -> Option<Uint128> {
+        self.mint.as_ref().and_then(|v| v.cap)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const STATE: Item<State> = Item::new("state");
+pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
+pub const BALANCES: Map<u32, Uint128> = Map::new("balance");
+pub const APTAX: Item<Uint128> = Item::new("ap_treasury_tax_balance");
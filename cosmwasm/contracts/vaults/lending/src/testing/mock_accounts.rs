@@ -0,0 +1,55 @@
+//! Minimal Accounts contract used by the multi-test suite, recording the vault receipts.
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Uint128,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Map;
+
+use angel_core::structs::AccountType;
+
+const RECEIPTS: Map<u32, Uint128> = Map::new("receipts");
+
+#[cw_serde]
+pub enum MockExecuteMsg {
+    VaultReceipt { id: u32, acct_type: AccountType },
+}
+
+#[cw_serde]
+pub enum MockQueryMsg {
+    /// Returns the total `Uint128` received from vaults for the endowment
+    Receipts { id: u32 },
+}
+
+fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: MockExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        MockExecuteMsg::VaultReceipt { id, acct_type: _ } => {
+            let received: Uint128 = info.funds.iter().map(|c| c.amount).sum();
+            RECEIPTS.update(deps.storage, id, |v| -> StdResult<_> {
+                Ok(v.unwrap_or_default() + received)
+            })?;
+            Ok(Response::default())
+        }
+    }
+}
+
+fn query(deps: Deps, _env: Env, msg: MockQueryMsg) -> StdResult<Binary> {
+    match msg {
+        MockQueryMsg::Receipts { id } => {
+            to_binary(&RECEIPTS.may_load(deps.storage, id)?.unwrap_or_default())
+        }
+    }
+}
+
+pub fn contract_accounts() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
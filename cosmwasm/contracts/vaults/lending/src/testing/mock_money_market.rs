@@ -0,0 +1,116 @@
+//! Minimal money market used by the multi-test suite.
+//! Supplied amounts are stored scaled down by a per-denom liquidity index,
+//! which the tests raise with `SetLiquidityIndex` to accrue interest.
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coins, to_binary, Addr, BankMsg, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Map;
+
+use crate::msg::{MoneyMarketQueryMsg, UserDepositResponse};
+
+const LIQUIDITY_INDEX: Map<&str, Decimal> = Map::new("liquidity_index");
+const SCALED_DEPOSITS: Map<(&Addr, &str), Uint128> = Map::new("scaled_deposits");
+
+#[cw_serde]
+pub enum MockExecuteMsg {
+    Deposit {},
+    Withdraw {
+        denom: String,
+        amount: Option<Uint128>,
+    },
+    SetLiquidityIndex {
+        denom: String,
+        liquidity_index: Decimal,
+    },
+}
+
+fn liquidity_index(deps: Deps, denom: &str) -> Decimal {
+    LIQUIDITY_INDEX
+        .may_load(deps.storage, denom)
+        .unwrap()
+        .unwrap_or_else(Decimal::one)
+}
+
+fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: MockExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        MockExecuteMsg::Deposit {} => {
+            for coin in info.funds.iter() {
+                let index = liquidity_index(deps.as_ref(), &coin.denom);
+                let scaled = coin
+                    .amount
+                    .multiply_ratio(Decimal::one().atomics(), index.atomics());
+                SCALED_DEPOSITS.update(
+                    deps.storage,
+                    (&info.sender, &coin.denom),
+                    |v| -> StdResult<_> { Ok(v.unwrap_or_default() + scaled) },
+                )?;
+            }
+            Ok(Response::default())
+        }
+        MockExecuteMsg::Withdraw { denom, amount } => {
+            let index = liquidity_index(deps.as_ref(), &denom);
+            let scaled = SCALED_DEPOSITS
+                .may_load(deps.storage, (&info.sender, &denom))?
+                .unwrap_or_default();
+            let available = scaled * index;
+            let amount = amount.unwrap_or(available);
+            if amount > available {
+                return Err(StdError::generic_err("Insufficient deposit"));
+            }
+            // round the scaled amount up, so the market never pays out more than supplied
+            let mut scaled_burn = amount.multiply_ratio(Decimal::one().atomics(), index.atomics());
+            if scaled_burn * index < amount {
+                scaled_burn += Uint128::one();
+            }
+            SCALED_DEPOSITS.save(
+                deps.storage,
+                (&info.sender, &denom),
+                &scaled.saturating_sub(scaled_burn),
+            )?;
+            Ok(Response::default().add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(amount.u128(), denom),
+            }))
+        }
+        MockExecuteMsg::SetLiquidityIndex {
+            denom,
+            liquidity_index,
+        } => {
+            LIQUIDITY_INDEX.save(deps.storage, &denom, &liquidity_index)?;
+            Ok(Response::default())
+        }
+    }
+}
+
+fn query(deps: Deps, _env: Env, msg: MoneyMarketQueryMsg) -> StdResult<Binary> {
+    match msg {
+        MoneyMarketQueryMsg::UserDeposit { user, denom } => {
+            let user = deps.api.addr_validate(&user)?;
+            let amount_scaled = SCALED_DEPOSITS
+                .may_load(deps.storage, (&user, &denom))?
+                .unwrap_or_default();
+            let amount = amount_scaled * liquidity_index(deps, &denom);
+            to_binary(&UserDepositResponse {
+                denom,
+                amount_scaled,
+                amount,
+            })
+        }
+    }
+}
+
+pub fn contract_money_market() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
@@ -0,0 +1,4 @@
+pub mod mock_accounts;
+pub mod mock_money_market;
+pub mod mock_registrar;
+mod unit_tests;
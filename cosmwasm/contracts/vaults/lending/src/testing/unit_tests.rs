@@ -0,0 +1,666 @@
+use angel_core::errors::vault::ContractError;
+use angel_core::msgs::registrar::ConfigExtensionResponse;
+use angel_core::structs::AccountType;
+use cosmwasm_std::{coin, coins, Addr, Decimal, Empty, StdError, Uint128};
+use cw20::BalanceResponse;
+use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, UpdateConfigMsg};
+use crate::responses::{ConfigResponse, StateResponse};
+use crate::testing::mock_accounts::{contract_accounts, MockQueryMsg as AccountsQueryMsg};
+use crate::testing::mock_money_market::{contract_money_market, MockExecuteMsg};
+use crate::testing::mock_registrar::{contract_registrar, MockInstantiateMsg};
+
+const OWNER: &str = "creator";
+const KEEPER: &str = "keeper";
+const TAX_COLLECTOR: &str = "tax-collector";
+const IBC_HOST: &str = "ibc-host";
+const IBC_CONTROLLER: &str = "ibc-controller";
+const TREASURY: &str = "treasury";
+const INTEREST_PROVIDER: &str = "interest-provider";
+const DENOM: &str = "uusdc";
+
+fn contract_vault() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    ))
+}
+
+fn mock_app() -> App {
+    AppBuilder::new().build(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked(IBC_HOST),
+                vec![coin(1_000_000, "ujuno"), coin(1_000_000, DENOM)],
+            )
+            .unwrap();
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked(INTEREST_PROVIDER),
+                coins(1_000_000, DENOM),
+            )
+            .unwrap();
+    })
+}
+
+/// Instantiate the mock money market, funded to pay out interest
+fn instantiate_money_market(app: &mut App) -> Addr {
+    let code_id = app.store_code(contract_money_market());
+    let money_market = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "money-market",
+            None,
+        )
+        .unwrap();
+    app.send_tokens(
+        Addr::unchecked(INTEREST_PROVIDER),
+        money_market.clone(),
+        &coins(100_000, DENOM),
+    )
+    .unwrap();
+    money_market
+}
+
+fn instantiate_vault(
+    app: &mut App,
+    money_market: &Addr,
+    acct_type: AccountType,
+    sibling_vault: Option<String>,
+) -> Addr {
+    let accounts_code_id = app.store_code(contract_accounts());
+    let accounts = app
+        .instantiate_contract(
+            accounts_code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "accounts",
+            None,
+        )
+        .unwrap();
+    let registrar_code_id = app.store_code(contract_registrar());
+    let registrar = app
+        .instantiate_contract(
+            registrar_code_id,
+            Addr::unchecked(OWNER),
+            &MockInstantiateMsg {
+                treasury: TREASURY.to_string(),
+                accounts_contract: accounts.to_string(),
+            },
+            &[],
+            "registrar",
+            None,
+        )
+        .unwrap();
+
+    let code_id = app.store_code(contract_vault());
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(OWNER),
+        &InstantiateMsg {
+            ibc_host: IBC_HOST.to_string(),
+            ibc_controller: IBC_CONTROLLER.to_string(),
+            ap_tax_rate: Decimal::percent(10),
+            acct_type,
+            sibling_vault,
+            registrar_contract: registrar.to_string(),
+            keeper: KEEPER.to_string(),
+            tax_collector: TAX_COLLECTOR.to_string(),
+            money_market: money_market.to_string(),
+            input_denom: DENOM.to_string(),
+            minimum_initial_deposit: Uint128::from(100_u128),
+            name: "Lending vault token".to_string(),
+            symbol: "LVT".to_string(),
+            decimals: 6,
+        },
+        &[],
+        "lending-vault",
+        None,
+    )
+    .unwrap()
+}
+
+fn set_liquidity_index(app: &mut App, money_market: &Addr, liquidity_index: Decimal) {
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        money_market.clone(),
+        &MockExecuteMsg::SetLiquidityIndex {
+            denom: DENOM.to_string(),
+            liquidity_index,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn query_vt_balance(app: &App, vault: &Addr, endowment_id: u32) -> Uint128 {
+    app.wrap()
+        .query_wasm_smart(vault, &QueryMsg::Balance { endowment_id })
+        .unwrap()
+}
+
+/// Total amount the Accounts contract was paid for the endowment through vault receipts
+fn query_accounts_receipts(app: &App, vault: &Addr, endowment_id: u32) -> Uint128 {
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(vault, &QueryMsg::Config {})
+        .unwrap();
+    let registrar_config: ConfigExtensionResponse = app
+        .wrap()
+        .query_wasm_smart(
+            config.registrar_contract,
+            &angel_core::msgs::registrar::QueryMsg::ConfigExtension {},
+        )
+        .unwrap();
+    app.wrap()
+        .query_wasm_smart(
+            registrar_config.accounts_contract.unwrap(),
+            &AccountsQueryMsg::Receipts { id: endowment_id },
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_proper_initialization() {
+    let mut app = mock_app();
+    let money_market = instantiate_money_market(&mut app);
+    let vault = instantiate_vault(&mut app, &money_market, AccountType::Locked, None);
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.owner, OWNER.to_string());
+    assert_eq!(config.sibling_vault, vault.to_string());
+    assert_eq!(config.money_market, money_market.to_string());
+    assert_eq!(config.input_denom, DENOM.to_string());
+
+    let state: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(state.total_shares, Uint128::zero());
+    assert_eq!(state.total_underlying, Uint128::zero());
+    assert_eq!(state.high_water_mark, Decimal::one());
+}
+
+#[test]
+fn test_update_config() {
+    let mut app = mock_app();
+    let money_market = instantiate_money_market(&mut app);
+    let vault = instantiate_vault(&mut app, &money_market, AccountType::Locked, None);
+
+    let update_config_msg = UpdateConfigMsg {
+        ibc_host: None,
+        ibc_controller: None,
+        sibling_vault: None,
+        keeper: Some("new-keeper".to_string()),
+        tax_collector: None,
+        ap_tax_rate: Some(Decimal::percent(20)),
+        minimum_initial_deposit: None,
+    };
+
+    // Only the owner can update the config
+    let err = app
+        .execute_contract(
+            Addr::unchecked(KEEPER),
+            vault.clone(),
+            &ExecuteMsg::UpdateConfig(update_config_msg.clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // AP tax rate cannot exceed 100%
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            vault.clone(),
+            &ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+                ap_tax_rate: Some(Decimal::percent(101)),
+                ..update_config_msg.clone()
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Std(StdError::generic_err("Invalid ap_tax_rate: 1.01"))
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        vault.clone(),
+        &ExecuteMsg::UpdateConfig(update_config_msg),
+        &[],
+    )
+    .unwrap();
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.keeper, "new-keeper".to_string());
+    assert_eq!(config.ap_tax_rate, Decimal::percent(20));
+}
+
+#[test]
+fn test_deposit_and_redeem() {
+    let mut app = mock_app();
+    let money_market = instantiate_money_market(&mut app);
+    let vault = instantiate_vault(&mut app, &money_market, AccountType::Locked, None);
+
+    // Only the `ibc_host` can deposit
+    let err = app
+        .execute_contract(
+            Addr::unchecked(INTEREST_PROVIDER),
+            vault.clone(),
+            &ExecuteMsg::Deposit { endowment_id: 1 },
+            &coins(1000, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // Only the `input_denom` is accepted
+    let err = app
+        .execute_contract(
+            Addr::unchecked(IBC_HOST),
+            vault.clone(),
+            &ExecuteMsg::Deposit { endowment_id: 1 },
+            &coins(1000, "ujuno"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidCoinsDeposited {}
+    );
+
+    // First deposit should be above the `minimum_initial_deposit`
+    let err = app
+        .execute_contract(
+            Addr::unchecked(IBC_HOST),
+            vault.clone(),
+            &ExecuteMsg::Deposit { endowment_id: 1 },
+            &coins(50, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "Insufficient initial deposit amount: Needed 100, Received 50"
+        ))
+    );
+
+    // First deposit mints vault tokens 1:1
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::Deposit { endowment_id: 1 },
+        &coins(1000, DENOM),
+    )
+    .unwrap();
+    assert_eq!(query_vt_balance(&app, &vault, 1), Uint128::from(1000_u128));
+
+    // 10% interest accrues in the money market
+    set_liquidity_index(&mut app, &money_market, Decimal::percent(110));
+
+    // Later deposits mint at the current share price
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::Deposit { endowment_id: 2 },
+        &coins(1100, DENOM),
+    )
+    .unwrap();
+    assert_eq!(query_vt_balance(&app, &vault, 2), Uint128::from(1000_u128));
+
+    let state: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(state.total_shares, Uint128::from(2000_u128));
+    assert_eq!(state.total_underlying, Uint128::from(2200_u128));
+
+    // Only the `ibc_host` or `tax_collector` can redeem
+    let err = app
+        .execute_contract(
+            Addr::unchecked(KEEPER),
+            vault.clone(),
+            &ExecuteMsg::RedeemAll { endowment_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // Cannot redeem more vault tokens than the endowment holds
+    let err = app
+        .execute_contract(
+            Addr::unchecked(IBC_HOST),
+            vault.clone(),
+            &ExecuteMsg::Redeem {
+                endowment_id: 2,
+                amount: Uint128::from(2000_u128),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "Cannot burn the 2000 vault tokens from Endowment 2 :: Overflow: Cannot Sub with 1000 and 2000"
+        ))
+    );
+
+    // Redeem principal + interest back to the endowment through the Accounts contract
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::RedeemAll { endowment_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_vt_balance(&app, &vault, 1), Uint128::zero());
+    assert_eq!(
+        query_accounts_receipts(&app, &vault, 1),
+        Uint128::from(1100_u128)
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(IBC_CONTROLLER, DENOM)
+            .unwrap()
+            .amount,
+        Uint128::zero()
+    );
+
+    let state: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(state.total_shares, Uint128::from(1000_u128));
+    assert_eq!(state.total_underlying, Uint128::from(1100_u128));
+}
+
+#[test]
+fn test_harvest() {
+    let mut app = mock_app();
+    let money_market = instantiate_money_market(&mut app);
+    let vault = instantiate_vault(&mut app, &money_market, AccountType::Locked, None);
+
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::Deposit { endowment_id: 1 },
+        &coins(1000, DENOM),
+    )
+    .unwrap();
+
+    // Only the `keeper` or `ibc_host` can harvest
+    let err = app
+        .execute_contract(
+            Addr::unchecked(TAX_COLLECTOR),
+            vault.clone(),
+            &ExecuteMsg::Harvest {
+                account_ids: vec![],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // 20% interest accrues: AP tax is 10% of the 200 gain
+    //   s = 20 * 1000 / (1200 - 20) = 16
+    set_liquidity_index(&mut app, &money_market, Decimal::percent(120));
+    app.execute_contract(
+        Addr::unchecked(KEEPER),
+        vault.clone(),
+        &ExecuteMsg::Harvest {
+            account_ids: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let ap_tax: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::ApTaxBalance {})
+        .unwrap();
+    assert_eq!(ap_tax.balance, Uint128::from(16_u128));
+    let state: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(state.total_shares, Uint128::from(1016_u128));
+    assert_eq!(
+        state.high_water_mark,
+        Decimal::from_ratio(1200_u128, 1016_u128)
+    );
+
+    // No new interest, no new AP tax
+    app.execute_contract(
+        Addr::unchecked(KEEPER),
+        vault.clone(),
+        &ExecuteMsg::Harvest {
+            account_ids: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let ap_tax: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::ApTaxBalance {})
+        .unwrap();
+    assert_eq!(ap_tax.balance, Uint128::from(16_u128));
+
+    // The `tax_collector` redeems the AP tax vault tokens
+    //   a = 16 * 1200 / 1016 = 18
+    app.execute_contract(
+        Addr::unchecked(TAX_COLLECTOR),
+        vault.clone(),
+        &ExecuteMsg::RedeemAll { endowment_id: 0 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap()
+            .query_balance(TAX_COLLECTOR, DENOM)
+            .unwrap()
+            .amount,
+        Uint128::from(18_u128)
+    );
+    let ap_tax: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::ApTaxBalance {})
+        .unwrap();
+    assert_eq!(ap_tax.balance, Uint128::zero());
+}
+
+#[test]
+fn test_claim_taxes() {
+    let mut app = mock_app();
+    let money_market = instantiate_money_market(&mut app);
+    let vault = instantiate_vault(&mut app, &money_market, AccountType::Locked, None);
+
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::Deposit { endowment_id: 1 },
+        &coins(1000, DENOM),
+    )
+    .unwrap();
+
+    // Fail to claim since nothing is accrued yet
+    let err = app
+        .execute_contract(
+            Addr::unchecked(KEEPER),
+            vault.clone(),
+            &ExecuteMsg::ClaimTaxes {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ZeroAmount {}
+    );
+
+    // 20% interest accrues: 16 vault tokens are minted as AP tax
+    set_liquidity_index(&mut app, &money_market, Decimal::percent(120));
+    app.execute_contract(
+        Addr::unchecked(KEEPER),
+        vault.clone(),
+        &ExecuteMsg::Harvest {
+            account_ids: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Only the `config.owner` or `config.keeper` can claim the taxes
+    let err = app
+        .execute_contract(
+            Addr::unchecked(TAX_COLLECTOR),
+            vault.clone(),
+            &ExecuteMsg::ClaimTaxes {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // The AP tax vault tokens are redeemed to the registrar `treasury`
+    //   a = 16 * 1200 / 1016 = 18
+    app.execute_contract(
+        Addr::unchecked(KEEPER),
+        vault.clone(),
+        &ExecuteMsg::ClaimTaxes {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(TREASURY, DENOM).unwrap().amount,
+        Uint128::from(18_u128)
+    );
+    let ap_tax: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::ApTaxBalance {})
+        .unwrap();
+    assert_eq!(ap_tax.balance, Uint128::zero());
+}
+
+#[test]
+fn test_reinvest_to_locked() {
+    let mut app = mock_app();
+    let money_market = instantiate_money_market(&mut app);
+    let liquid_vault = instantiate_vault(&mut app, &money_market, AccountType::Liquid, None);
+    let locked_vault = instantiate_vault(
+        &mut app,
+        &money_market,
+        AccountType::Locked,
+        Some(liquid_vault.to_string()),
+    );
+
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        liquid_vault.clone(),
+        &ExecuteMsg::Deposit { endowment_id: 1 },
+        &coins(1000, DENOM),
+    )
+    .unwrap();
+
+    // Fail to reinvest since the sibling vault is not set
+    let err = app
+        .execute_contract(
+            Addr::unchecked(IBC_HOST),
+            liquid_vault.clone(),
+            &ExecuteMsg::ReinvestToLocked {
+                endowment_id: 1,
+                amount: Uint128::from(400_u128),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Std(StdError::generic_err("Sibling vault not created"))
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        liquid_vault.clone(),
+        &ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            ibc_host: None,
+            ibc_controller: None,
+            sibling_vault: Some(locked_vault.to_string()),
+            keeper: None,
+            tax_collector: None,
+            ap_tax_rate: None,
+            minimum_initial_deposit: None,
+        }),
+        &[],
+    )
+    .unwrap();
+
+    // Only liquid vaults can reinvest
+    let err = app
+        .execute_contract(
+            Addr::unchecked(IBC_HOST),
+            locked_vault.clone(),
+            &ExecuteMsg::ReinvestToLocked {
+                endowment_id: 1,
+                amount: Uint128::from(400_u128),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Std(StdError::generic_err("This is locked vault"))
+    );
+
+    // Succeed to reinvest a part of the liquid position
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        liquid_vault.clone(),
+        &ExecuteMsg::ReinvestToLocked {
+            endowment_id: 1,
+            amount: Uint128::from(400_u128),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_vt_balance(&app, &liquid_vault, 1),
+        Uint128::from(600_u128)
+    );
+    assert_eq!(
+        query_vt_balance(&app, &locked_vault, 1),
+        Uint128::from(400_u128)
+    );
+
+    let state: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&locked_vault, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(state.total_underlying, Uint128::from(400_u128));
+}
@@ -13,7 +13,9 @@ use terraswap::querier::query_pair_info_from_pair;
 
 use crate::executers;
 use crate::queriers;
-use crate::state::{Config, MinterData, State, TokenInfo, APTAX, CONFIG, STATE, TOKEN_INFO};
+use crate::state::{
+    Config, MinterData, State, TokenInfo, APTAX, BALANCES, CONFIG, STATE, TOKEN_INFO,
+};
 
 // version info for future migration info
 const CONTRACT_NAME: &str = "loopswap_vault";
@@ -137,18 +139,34 @@ pub fn execute(
             )
         }
         // Harvest from "lp_staking" contract `lp_reward`(LOOP) token --> more LPs
-        ExecuteMsg::Harvest {} => executers::harvest(deps, env, info),
+        ExecuteMsg::Harvest { account_ids: _ } => executers::harvest(deps, env, info),
         // -Deposit Token/Yield Token (Vault) --> + tokens of lp pair --> `accounts_contract`
         ExecuteMsg::Redeem {
             endowment_id,
             amount, // vault tokens to be burned
         } => executers::redeem(deps, env, info, endowment_id, amount),
+        ExecuteMsg::RedeemAll { endowment_id } => {
+            let amount = BALANCES
+                .may_load(deps.storage, endowment_id)?
+                .unwrap_or_default();
+            executers::redeem(deps, env, info, endowment_id, amount)
+        }
         // -Deposit Token/Yield Token(Liquid Vault) --> +Deposit Token/Yield Token(Locked Vault)
         ExecuteMsg::ReinvestToLocked {
             endowment_id,
             amount,
         } => executers::reinvest_to_locked_execute(deps, env, info, endowment_id, amount),
 
+        // Unwind the whole LP position to `native_token` & switch to redeem-only mode
+        ExecuteMsg::EmergencyWithdraw {} => executers::emergency_withdraw(deps, env, info),
+        // The loopswap vault sends its AP tax to the `tax_collector` through `Redeem`, &
+        // has no cross-strategy reinvestment
+        ExecuteMsg::RedeemForReinvest { .. } | ExecuteMsg::ClaimTaxes {} => {
+            Err(ContractError::Std(StdError::GenericErr {
+                msg: "Not supported by the loopswap vault".to_string(),
+            }))
+        }
+
         /* --- INTERNAL ENTRIES --- */
         ExecuteMsg::RestakeClaimReward {
             reward_token_bal_before,
@@ -189,6 +207,9 @@ pub fn execute(
             lp_pair_token0_bal_before,
             lp_pair_token1_bal_before,
         ),
+        ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before,
+        } => executers::emergency_withdraw_finalize(deps, env, info, native_token_bal_before),
     }
 }
 
@@ -241,6 +262,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::TokenInfo {} => to_binary(&queriers::query_token_info(deps)),
         QueryMsg::TotalBalance {} => to_binary(&queriers::query_total_balance(deps)),
         QueryMsg::ApTaxBalance {} => to_binary(&queriers::query_ap_tax_balance(deps)),
+        QueryMsg::EmergencyExit {} => to_binary(&queriers::query_emergency_exit(deps)),
     }
 }
 
@@ -254,7 +276,7 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
         }));
     }
     // note: better to do proper semver compare, but string compare *usually* works
-    if ver.version >= CONTRACT_VERSION.to_string() {
+    if ver.version.as_str() >= CONTRACT_VERSION {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: "Cannot upgrade from a newer version".to_string(),
         }));
@@ -1,9 +1,12 @@
-use angel_core::utils::{query_balance, query_token_balance};
+use angel_core::utils::{
+    assert_not_emergency_exit, query_accounts_contract, query_balance, query_token_balance,
+    vault_payout_msg,
+};
 use cosmwasm_std::{
-    attr, coins, to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+    attr, coins, to_binary, Addr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
 };
-use cw20::Cw20ReceiveMsg;
+use cw20::{Cw20ReceiveMsg, Denom};
 use cw_asset::AssetInfoBase as CwAssetInfoBase;
 use terraswap::asset::{Asset, AssetInfo};
 
@@ -15,10 +18,10 @@ use angel_core::msgs::vault::{
     UpdateConfigMsg,
 };
 use angel_core::msgs::{accounts::EndowmentDetailsResponse, registrar::ConfigResponse};
-use angel_core::structs::{AccountType, SwapOperation};
+use angel_core::structs::{AccountType, EmergencyExit, SwapOperation};
 use terraswap::querier::query_pair_info_from_pair;
 
-use crate::state::{Config, State, APTAX, BALANCES, CONFIG, STATE, TOKEN_INFO};
+use crate::state::{Config, State, APTAX, BALANCES, CONFIG, EMERGENCY_EXIT, STATE, TOKEN_INFO};
 
 // Initial VT(vault token) mint amount
 const INIT_VT_MINT_AMOUNT: u128 = 1000000; // 1 VT
@@ -176,6 +179,7 @@ pub fn deposit(
     deposit_amount: Uint128,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
 
     // Check if the `caller` is "accounts_contract" & "endowment_id" is valid
     validate_action_caller_n_endow_id(deps.as_ref(), &config, msg_sender.clone(), endowment_id)?;
@@ -386,14 +390,7 @@ pub fn redeem(
             info.sender.to_string(),
             endowment_id,
         )?;
-        let registar_config: ConfigResponse = deps.querier.query_wasm_smart(
-            config.registrar_contract.to_string(),
-            &RegistrarQueryMsg::Config {},
-        )?;
-        let accounts_contract = deps
-            .api
-            .addr_validate(&registar_config.accounts_contract.unwrap())?;
-        beneficiary = accounts_contract;
+        beneficiary = query_accounts_contract(deps.as_ref(), config.registrar_contract.as_str())?;
         id = Some(endowment_id);
     }
 
@@ -416,6 +413,38 @@ pub fn redeem(
         })
     })?;
 
+    // In emergency exit mode, the LP position is already unwound.
+    // Just send the pro-rata portion of `native_token` to the `beneficiary`.
+    if let Some(mut emergency_exit) = EMERGENCY_EXIT.may_load(deps.storage)? {
+        let native_amount = emergency_exit
+            .native_token_amount
+            .multiply_ratio(burn_shares_amount, state.total_shares);
+        emergency_exit.native_token_amount -= native_amount;
+        emergency_exit.lp_token_amount -= emergency_exit
+            .lp_token_amount
+            .multiply_ratio(burn_shares_amount, state.total_shares);
+        EMERGENCY_EXIT.save(deps.storage, &emergency_exit)?;
+
+        state.total_shares -= burn_shares_amount;
+        STATE.save(deps.storage, &state)?;
+
+        let mut msgs = vec![];
+        if !native_amount.is_zero() {
+            msgs.push(vault_payout_msg(
+                &asset_info_to_denom(&config.native_token),
+                &beneficiary,
+                id,
+                config.acct_type,
+                native_amount,
+            )?);
+        }
+        return Ok(Response::default().add_messages(msgs).add_attributes(vec![
+            attr("action", "emergency_redeem"),
+            attr("burn_shares", burn_shares_amount.to_string()),
+            attr("native_amount", native_amount.to_string()),
+        ]));
+    }
+
     // Update the contract state
     let lp_2_vt_rate = Decimal::from_ratio(state.total_lp_amount, state.total_shares);
     let lp_amount = burn_shares_amount * lp_2_vt_rate;
@@ -490,6 +519,7 @@ pub fn redeem(
 ///   3. Re-stake the LP tokens to the `farming` contract for more yield
 pub fn harvest(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
 
     // Check if the caller is `keeper` address
     if info.sender != config.keeper {
@@ -539,6 +569,7 @@ pub fn reinvest_to_locked_execute(
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     let mut state: State = STATE.load(deps.storage)?;
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
 
     // Check that the vault acct_type is `liquid`
     if config.acct_type != AccountType::Liquid {
@@ -670,6 +701,7 @@ pub fn reinvest_to_locked_receive(
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     let mut state: State = STATE.load(deps.storage)?;
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
 
     // 0. Check that the message sender is the Sibling vault contract
     // ensure `received token` is `lp_token`
@@ -1116,6 +1148,7 @@ pub fn harvest_to_liquid(
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     let mut state: State = STATE.load(deps.storage)?;
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
     let lp_token_contract = config.lp_token.to_string();
 
     // 0. Check that the message sender is the Sibling vault contract
@@ -1227,21 +1260,26 @@ pub fn remove_liquidity(
         funds: vec![],
     }));
 
-    let native_token_bal = query_asset_balance(
-        deps.as_ref(),
-        env.contract.address.clone(),
-        config.native_token,
-    )?;
-    let send_asset_msgs = vec![CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: env.contract.address.to_string(),
-        msg: to_binary(&ExecuteMsg::SendAsset {
-            beneficiary,
-            id,
-            native_token_bal_before: native_token_bal,
-        })
-        .unwrap(),
-        funds: vec![],
-    })];
+    // NOTE: In case of `emergency_withdraw`, the vault itself is the `beneficiary`
+    //       & the `native_token`s just stay in the vault for later redemptions.
+    let mut send_asset_msgs = vec![];
+    if beneficiary != env.contract.address {
+        let native_token_bal = query_asset_balance(
+            deps.as_ref(),
+            env.contract.address.clone(),
+            config.native_token,
+        )?;
+        send_asset_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::SendAsset {
+                beneficiary,
+                id,
+                native_token_bal_before: native_token_bal,
+            })
+            .unwrap(),
+            funds: vec![],
+        }));
+    }
 
     Ok(Response::default()
         .add_messages(withdraw_liquidity_msgs)
@@ -1276,54 +1314,16 @@ pub fn send_asset(
     )?;
     let send_amount = native_token_bal - native_token_bal_before;
 
-    let mut msgs = vec![];
-    match config.native_token {
-        AssetInfo::NativeToken { denom } => match id {
-            Some(id) => msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: beneficiary.to_string(),
-                msg: to_binary(&angel_core::msgs::accounts::ExecuteMsg::VaultReceipt {
-                    id,
-                    acct_type: config.acct_type,
-                })
-                .unwrap(),
-                funds: coins(send_amount.u128(), denom),
-            })),
-            None => msgs.push(CosmosMsg::Bank(BankMsg::Send {
-                to_address: beneficiary.to_string(),
-                amount: coins(send_amount.u128(), denom),
-            })),
-        },
-        AssetInfo::Token { contract_addr } => match id {
-            Some(id) => {
-                msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr,
-                    msg: to_binary(&cw20::Cw20ExecuteMsg::Send {
-                        contract: beneficiary.to_string(),
-                        amount: send_amount,
-                        msg: to_binary(&angel_core::msgs::accounts::ReceiveMsg::VaultReceipt {
-                            id,
-                            acct_type: config.acct_type,
-                        })
-                        .unwrap(),
-                    })
-                    .unwrap(),
-                    funds: vec![],
-                }));
-            }
-            None => msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr,
-                msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
-                    recipient: beneficiary.to_string(),
-                    amount: send_amount,
-                })
-                .unwrap(),
-                funds: vec![],
-            })),
-        },
-    };
+    let msg = vault_payout_msg(
+        &asset_info_to_denom(&config.native_token),
+        &beneficiary,
+        id,
+        config.acct_type,
+        send_amount,
+    )?;
 
     Ok(Response::default()
-        .add_messages(msgs)
+        .add_message(msg)
         .add_attributes(vec![attr("action", "send_asset")]))
 }
 
@@ -1412,6 +1412,123 @@ pub fn swap_back(
         .add_attributes(vec![attr("action", "swap_pair_to_native")]))
 }
 
+/// Contract entry: **EmergencyWithdraw**
+///   1. Unstake all the LP tokens from the `loopswap::farming` contract (rewards are left behind)
+///   2. Remove liquidity & swap the lp pair tokens back to the `native_token`
+///   3. Call the `(this contract::)EmergencyWithdrawFinalize` entry, which switches the vault to redeem-only mode
+pub fn emergency_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let state: State = STATE.load(deps.storage)?;
+
+    // Only the `config.owner` can trigger the emergency exit
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    assert_not_emergency_exit(deps.storage, &EMERGENCY_EXIT)?;
+
+    let mut msgs = vec![];
+    if !state.total_lp_amount.is_zero() {
+        // Unstake all the LP tokens from the `loopswap::farming` contract
+        let flp_token_contract: String = deps.querier.query_wasm_smart(
+            config.lp_staking_contract.to_string(),
+            &LoopFarmingQueryMsg::QueryFlpTokenFromPoolAddress {
+                pool_address: config.lp_token.to_string(),
+            },
+        )?;
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: flp_token_contract,
+            msg: to_binary(&cw20::Cw20ExecuteMsg::Send {
+                contract: config.lp_staking_contract.to_string(),
+                amount: state.total_lp_amount,
+                msg: to_binary(&LoopFarmingExecuteMsg::UnstakeAndClaim {}).unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }));
+
+        // Remove liquidity & swap back to `native_token`, keeping them in the vault
+        let lp_token_bal = query_token_balance(
+            deps.as_ref(),
+            config.lp_token.to_string(),
+            env.contract.address.to_string(),
+        )?;
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::RemoveLiquidity {
+                lp_token_bal_before: lp_token_bal,
+                beneficiary: env.contract.address.clone(),
+                id: None,
+            })
+            .unwrap(),
+            funds: vec![],
+        }));
+    }
+
+    // Record the recovered `native_token` amount & enter the redeem-only mode
+    let native_token_bal = query_asset_balance(
+        deps.as_ref(),
+        env.contract.address.clone(),
+        config.native_token,
+    )?;
+    msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before: native_token_bal,
+        })
+        .unwrap(),
+        funds: vec![],
+    }));
+
+    Ok(Response::default().add_messages(msgs).add_attributes(vec![
+        attr("action", "emergency_withdraw"),
+        attr("lp_amount", state.total_lp_amount.to_string()),
+    ]))
+}
+
+/// Contract entry: **EmergencyWithdrawFinalize**
+///   1. Compute the amount of `native_token` recovered from `emergency_withdraw`
+///   2. Switch the vault to redeem-only mode
+pub fn emergency_withdraw_finalize(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    native_token_bal_before: Uint128,
+) -> Result<Response, ContractError> {
+    // Validations
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut state: State = STATE.load(deps.storage)?;
+
+    let native_token_bal =
+        query_asset_balance(deps.as_ref(), env.contract.address, config.native_token)?;
+    let native_token_amount = native_token_bal
+        .checked_sub(native_token_bal_before)
+        .map_err(|e| ContractError::Std(StdError::overflow(e)))?;
+
+    EMERGENCY_EXIT.save(
+        deps.storage,
+        &EmergencyExit {
+            native_token_amount,
+            lp_token_amount: state.total_lp_amount,
+        },
+    )?;
+
+    state.total_lp_amount = Uint128::zero();
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "emergency_withdraw_finalize"),
+        attr("native_amount", native_token_amount.to_string()),
+    ]))
+}
+
 fn prepare_swap_router_swap_msgs(
     swap_router: String,
     start_token: AssetInfo,
@@ -1422,6 +1539,7 @@ fn prepare_swap_router_swap_msgs(
         AssetInfo::NativeToken { ref denom } => vec![CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: swap_router,
             msg: to_binary(&SwapRouterExecuteMsg::ExecuteSwapOperations {
+                strategy_key: None, // Placeholder value
                 operations,
                 minimum_receive: None,
                 endowment_id: 1,                // Placeholder value
@@ -1436,6 +1554,7 @@ fn prepare_swap_router_swap_msgs(
                 contract: swap_router,
                 amount: swap_amount,
                 msg: to_binary(&SwapRouterExecuteMsg::ExecuteSwapOperations {
+                    strategy_key: None, // Placeholder value
                     operations,
                     minimum_receive: None,
                     endowment_id: 1,                // Placeholder value
@@ -1555,22 +1674,15 @@ fn validate_action_caller_n_endow_id(
     endowment_id: u32,
 ) -> Result<(), ContractError> {
     // Check if sender address is the "accounts_contract"
-    let registar_config: ConfigResponse = deps.querier.query_wasm_smart(
-        config.registrar_contract.to_string(),
-        &RegistrarQueryMsg::Config {},
-    )?;
-    if let Some(ref accounts_contract) = registar_config.accounts_contract {
-        if caller != *accounts_contract {
-            return Err(ContractError::Unauthorized {});
-        }
-    } else {
+    let accounts_contract = query_accounts_contract(deps, config.registrar_contract.as_str())?;
+    if caller != accounts_contract {
         return Err(ContractError::Unauthorized {});
     }
 
     // Check that the "deposit-endowment-id" is an Accounts SC
     let _endowments_rsp: EndowmentDetailsResponse =
         deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: registar_config.accounts_contract.unwrap(),
+            contract_addr: accounts_contract.to_string(),
             msg: to_binary(&angel_core::msgs::accounts::QueryMsg::Endowment { id: endowment_id })
                 .unwrap(),
         }))?;
@@ -1666,3 +1778,11 @@ fn query_asset_balance(
         }
     }
 }
+
+/// Convert the terraswap `AssetInfo` to the `Denom` used by the shared vault helpers
+fn asset_info_to_denom(asset_info: &AssetInfo) -> Denom {
+    match asset_info {
+        AssetInfo::NativeToken { denom } => Denom::Native(denom.to_string()),
+        AssetInfo::Token { contract_addr } => Denom::Cw20(Addr::unchecked(contract_addr)),
+    }
+}
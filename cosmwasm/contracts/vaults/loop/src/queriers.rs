@@ -1,9 +1,9 @@
 use cosmwasm_std::{Deps, Uint128};
 use cw20::{BalanceResponse, TokenInfoResponse};
 
-use angel_core::msgs::vault::{ConfigResponse, StateResponse};
+use angel_core::msgs::vault::{ConfigResponse, EmergencyExitResponse, StateResponse};
 
-use crate::state::{Config, APTAX, BALANCES, CONFIG, STATE, TOKEN_INFO};
+use crate::state::{Config, APTAX, BALANCES, CONFIG, EMERGENCY_EXIT, STATE, TOKEN_INFO};
 
 pub fn query_balance(deps: Deps, id: u32) -> Uint128 {
     BALANCES.load(deps.storage, id).unwrap_or_default()
@@ -71,3 +71,16 @@ pub fn query_ap_tax_balance(deps: Deps) -> BalanceResponse {
     let ap_tax = APTAX.load(deps.storage).unwrap();
     BalanceResponse { balance: ap_tax }
 }
+
+pub fn query_emergency_exit(deps: Deps) -> EmergencyExitResponse {
+    match EMERGENCY_EXIT.may_load(deps.storage).unwrap() {
+        Some(emergency_exit) => EmergencyExitResponse {
+            active: true,
+            native_token_amount: emergency_exit.native_token_amount.to_string(),
+        },
+        None => EmergencyExitResponse {
+            active: false,
+            native_token_amount: Uint128::zero().to_string(),
+        },
+    }
+}
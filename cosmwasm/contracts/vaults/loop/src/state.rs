@@ -1,5 +1,5 @@
-use angel_core::structs::{AccountType, SwapOperation};
-use cosmwasm_schema::{cw_serde, QueryResponses};
+use angel_core::structs::{AccountType, EmergencyExit, SwapOperation};
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::{Item, Map};
 use terraswap::asset::AssetInfo;
@@ -69,3 +69,4 @@ pub const STATE: Item<State> = Item::new("state");
 pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
 pub const BALANCES: Map<u32, Uint128> = Map::new("balance");
 pub const APTAX: Item<Uint128> = Item::new("ap_treasury_tax_balance");
+pub const EMERGENCY_EXIT: Item<EmergencyExit> = Item::new("emergency_exit");
@@ -1,5 +1,5 @@
 // Contains mock functionality to test multi-contract scenarios
-use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     from_binary, from_slice, to_binary, Addr, Api, BalanceResponse, BankQuery, CanonicalAddr, Coin,
@@ -13,9 +13,10 @@ use terraswap::asset::{AssetInfo, PairInfo};
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
-use angel_core::msgs::{accounts::EndowmentDetailsResponse, registrar::ConfigResponse};
+use angel_core::msgs::accounts::EndowmentDetailsResponse;
+use angel_core::msgs::registrar::{ConfigExtensionResponse, ConfigResponse};
 use angel_core::structs::{
-    AcceptedTokens, AccountStrategies, Categories, OneOffVaults, RebalanceDetails, SplitDetails,
+    AcceptedTokens, Categories, Investments, RebalanceDetails, SplitDetails,
 };
 
 #[cw_serde]
@@ -23,6 +24,7 @@ pub enum QueryMsg {
     Endowment { id: u32 },
     Balance { address: String },
     Config {},
+    ConfigExtension {},
     Pair {},
     QueryFlpTokenFromPoolAddress { pool_address: String },
     Fee { name: String },
@@ -35,11 +37,11 @@ pub fn mock_dependencies(
 ) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
     let contract_addr = MOCK_CONTRACT_ADDR;
     let mut custom_querier: WasmMockQuerier = WasmMockQuerier::new(
-        MockQuerier::new(&[(&contract_addr, contract_balance)]),
+        MockQuerier::new(&[(contract_addr, contract_balance)]),
         MockApi::default(),
     );
     let contract_balance: Vec<(&String, &Uint128)> = contract_balance
-        .into_iter()
+        .iter()
         .map(|x| (&(x.denom), &(x.amount)))
         .collect();
     custom_querier.token_querier =
@@ -127,7 +129,7 @@ impl WasmMockQuerier {
                 ))
             }
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
-                match from_binary(&msg).unwrap() {
+                match from_binary(msg).unwrap() {
                     // Simulating the `Registrar::QueryMsg::EndowmentList {...}`
                     QueryMsg::Endowment { id: _ } => SystemResult::Ok(ContractResult::Ok(
                         to_binary(&EndowmentDetailsResponse {
@@ -135,8 +137,7 @@ impl WasmMockQuerier {
                             status: angel_core::structs::EndowmentStatus::Approved,
                             endow_type: angel_core::structs::EndowmentType::Charity,
                             maturity_time: None,
-                            strategies: AccountStrategies::default(),
-                            oneoff_vaults: OneOffVaults::default(),
+                            invested_strategies: Investments::default(),
                             rebalance: RebalanceDetails::default(),
                             kyc_donors_only: false,
                             deposit_approved: true,
@@ -148,6 +149,8 @@ impl WasmMockQuerier {
                             tier: Some(3),
                             logo: Some("Some fancy logo".to_string()),
                             image: Some("Nice banner image".to_string()),
+                            referral_id: None,
+                            parent: None,
                         })
                         .unwrap(),
                     )),
@@ -214,32 +217,50 @@ impl WasmMockQuerier {
                             to_binary(&ConfigResponse {
                                 owner: "registrar-owner".to_string(),
                                 version: "1.0.0".to_string(),
-                                accounts_contract: Some("accounts-contract".to_string()),
                                 treasury: "treasury".to_string(),
                                 rebalance: RebalanceDetails::default(),
-                                index_fund: None,
                                 split_to_liquid: SplitDetails {
                                     max: Decimal::one(),
                                     min: Decimal::zero(),
                                     default: Decimal::default(),
                                 },
-                                halo_token: None,
-                                gov_contract: None,
-                                charity_shares_contract: None,
-                                cw3_code: Some(3_u64),
-                                cw4_code: Some(4_u64),
                                 accepted_tokens: AcceptedTokens {
                                     native: vec![],
                                     cw20: vec![],
                                 },
-                                applications_review: "applications-review".to_string(),
-                                swaps_router: None,
                                 axelar_gateway: "axelar-gateway".to_string(),
                                 axelar_ibc_channel: "channel-1".to_string(),
+                                axelar_chain_id: "juno".to_string(),
                             })
                             .unwrap(),
                         )),
                     },
+                    // Simulating the `registrar::QueryMsg::ConfigExtension {}`
+                    QueryMsg::ConfigExtension {} => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&ConfigExtensionResponse {
+                            accounts_contract: Some("accounts-contract".to_string()),
+                            index_fund: None,
+                            halo_token: None,
+                            gov_contract: None,
+                            charity_shares_contract: None,
+                            cw3_code: Some(3_u64),
+                            cw4_code: Some(4_u64),
+                            applications_review: "applications-review".to_string(),
+                            swaps_router: None,
+                            donation_match_charites_contract: None,
+                            collector_addr: "collector-addr".to_string(),
+                            swap_factory: None,
+                            accounts_settings_controller: None,
+                            subdao_gov_code: None,
+                            subdao_cw20_token_code: None,
+                            subdao_bonding_token_code: None,
+                            subdao_cw900_code: None,
+                            subdao_distributor_code: None,
+                            donation_match_code: None,
+                            halo_token_lp_contract: None,
+                        })
+                        .unwrap(),
+                    )),
                     // Simulating the `loopswap::pair::Pair {}` query
                     QueryMsg::Pair {} => SystemResult::Ok(ContractResult::Ok(
                         to_binary(&PairInfo {
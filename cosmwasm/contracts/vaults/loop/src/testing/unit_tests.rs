@@ -1,10 +1,11 @@
 use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    coins, from_binary, to_binary, Addr, Coin, OverflowError, OwnedDeps, StdError, Uint128,
+    coins, from_binary, to_binary, Addr, Coin, CosmosMsg, OverflowError, OwnedDeps, StdError,
+    Uint128, WasmMsg,
 };
 
 use angel_core::errors::vault::ContractError;
-use angel_core::msgs::vault::{ConfigResponse, StateResponse};
+use angel_core::msgs::vault::{ConfigResponse, EmergencyExitResponse, StateResponse};
 use angel_core::msgs::vault::{ExecuteMsg, InstantiateMsg, QueryMsg, UpdateConfigMsg};
 use angel_core::structs::{AccountType, SwapOperation};
 use cw20::{BalanceResponse, TokenInfoResponse};
@@ -482,12 +483,28 @@ fn test_harvest() {
 
     // Only "config.keeper" address can call the "harvest" entry
     let info = mock_info("non-keeper", &[]);
-    let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Harvest {}).unwrap_err();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Harvest {
+            account_ids: vec![],
+        },
+    )
+    .unwrap_err();
     assert_eq!(err, ContractError::Unauthorized {});
 
     // "claim" entry outputs 2 messages
     let info = mock_info("keeper", &[]);
-    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Harvest {}).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Harvest {
+            account_ids: vec![],
+        },
+    )
+    .unwrap();
     assert_eq!(res.messages.len(), 2);
 }
 
@@ -1484,6 +1501,189 @@ fn test_swap_back() {
     assert_eq!(res.messages.len(), 1);
 }
 
+#[test]
+fn test_emergency_withdraw() {
+    let mut deps = create_mock_vault(
+        AccountType::Locked,
+        Some("liquid_sibling_vault".to_string()),
+        vec![],
+    );
+
+    // Only the `config.owner` can trigger the emergency exit
+    let info = mock_info("non-owner", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdraw {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Vault without any LP position only outputs the "finalize" message
+    let info = mock_info("creator", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdraw {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    // Only the contract itself can call the "finalize" entry
+    let info = mock_info("non-contract", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap();
+
+    // Check the emergency exit status
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::EmergencyExit {}).unwrap();
+    let emergency_exit: EmergencyExitResponse = from_binary(&res).unwrap();
+    assert!(emergency_exit.active);
+    assert_eq!(emergency_exit.native_token_amount, "100".to_string());
+
+    // The vault is redeem-only from now on
+    let info = mock_info("accounts-contract", &coins(100, "ujuno"));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Deposit { endowment_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EmergencyExitActive {});
+
+    let info = mock_info("keeper", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Harvest {
+            account_ids: vec![],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EmergencyExitActive {});
+
+    let info = mock_info("creator", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdraw {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EmergencyExitActive {});
+}
+
+#[test]
+fn test_emergency_redeem() {
+    let mut deps = create_mock_vault(
+        AccountType::Locked,
+        Some("liquid_sibling_vault".to_string()),
+        vec![],
+    );
+
+    // Endowment 1 holds vault tokens for the staked LP position
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Stake {
+            endowment_id: Some(1_u32),
+            lp_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance { endowment_id: 1 },
+    )
+    .unwrap();
+    let balance: Uint128 = from_binary(&res).unwrap();
+
+    // Unwind the whole position
+    let info = mock_info("creator", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdraw {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 3);
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let _ = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::EmergencyWithdrawFinalize {
+            native_token_bal_before: Uint128::zero(),
+        },
+    )
+    .unwrap();
+
+    // The LP position is gone
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.total_lp_amount, Uint128::zero().to_string());
+
+    // `RedeemAll` pays the whole pro-rata `native_token` back through the Accounts contract
+    let info = mock_info("accounts-contract", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::RedeemAll { endowment_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "accounts-contract".to_string(),
+            msg: to_binary(&angel_core::msgs::accounts::ExecuteMsg::VaultReceipt {
+                id: 1,
+                acct_type: AccountType::Locked,
+            })
+            .unwrap(),
+            funds: coins(100, "ujuno"),
+        })
+    );
+
+    // The vault tokens are burned
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance { endowment_id: 1 },
+    )
+    .unwrap();
+    let after: Uint128 = from_binary(&res).unwrap();
+    assert!(!balance.is_zero());
+    assert_eq!(after, Uint128::zero());
+}
+
 #[test]
 fn test_migrate() {
     let mut deps = create_mock_vault(
@@ -0,0 +1,682 @@
+use std::cmp::Reverse;
+
+use cosmwasm_std::{
+    attr, coin, coins, BankMsg, CosmosMsg, Decimal, Deps, DepsMut, DistributionMsg, Env,
+    MessageInfo, Order, Response, StakingMsg, StdError, StdResult, Uint128,
+};
+use cw20::Denom;
+
+use angel_core::errors::vault::ContractError;
+use angel_core::msgs::registrar::{
+    ConfigResponse as RegistrarConfigResponse, QueryMsg as RegistrarQueryMsg,
+};
+use angel_core::utils::{query_accounts_contract, vault_payout_msg};
+
+use crate::msg::UpdateConfigMsg;
+use crate::queriers::{query_balance, query_total_bonded, query_validators};
+use crate::state::{
+    Config, UnbondingClaim, APTAX, BALANCES, CONFIG, STATE, TAX_CLAIMS, TOKEN_INFO,
+    UNBONDING_CLAIMS,
+};
+
+// Number of blocks until pending owner update is valid
+pub const PENDING_OWNER_DEADLINE: u64 = 42069;
+
+/// Contract entry: **UpdateOwner**
+pub fn update_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // 2-step process of updating `config.owner`
+    //
+    // 1. Current `config.owner` suggests `new_owner` address
+    //    - At this moment, the `pending_owner` is set with `new_owner` address.
+    //    - Also, the `pending_owner_deadline` is set as current block height + constant DEADLINE height
+    //
+    // 2. The `pending_owner`(new_owner) completes the process & becomes the `config.owner`,
+    //    OR the settings are unset for future process.
+    match (config.pending_owner, config.pending_owner_deadline) {
+        (None, None) => {
+            if info.sender != config.owner {
+                return Err(ContractError::Unauthorized {});
+            }
+            let new_owner = deps.api.addr_validate(&new_owner)?;
+            config.pending_owner = Some(new_owner);
+            config.pending_owner_deadline = Some(env.block.height + PENDING_OWNER_DEADLINE);
+        }
+        (Some(pending_owner), Some(deadline)) => {
+            if info.sender != pending_owner {
+                return Err(ContractError::Unauthorized {});
+            }
+            if env.block.height <= deadline {
+                config.owner = pending_owner;
+            }
+            config.pending_owner = None;
+            config.pending_owner_deadline = None;
+        }
+        _ => {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Invalid owner update settings",
+            )))
+        }
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// Contract entry: **UpdateConfig**
+///
+/// Update the **CONFIG** of the contract
+pub fn update_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: UpdateConfigMsg,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // only the SC admin can update these configs...for now
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.ibc_host = match msg.ibc_host {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => config.ibc_host,
+    };
+    config.ibc_controller = match msg.ibc_controller {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => config.ibc_controller,
+    };
+    config.keeper = match msg.keeper {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => config.keeper,
+    };
+    config.tax_collector = match msg.tax_collector {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => config.tax_collector,
+    };
+    config.ap_tax_rate = match msg.ap_tax_rate {
+        Some(rate) => {
+            if rate > Decimal::one() {
+                return Err(ContractError::Std(StdError::generic_err(format!(
+                    "Invalid ap_tax_rate: {}",
+                    rate
+                ))));
+            }
+            rate
+        }
+        None => config.ap_tax_rate,
+    };
+    config.unbonding_period = match msg.unbonding_period {
+        Some(v) => v,
+        None => config.unbonding_period,
+    };
+    config.minimum_initial_deposit = match msg.minimum_initial_deposit {
+        Some(v) => v,
+        None => config.minimum_initial_deposit,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// Contract entry: **Deposit**
+///   1. Mint the vault tokens for the deposited `staking_denom`
+///   2. Delegate the `staking_denom` evenly across the Registrar's validator set
+pub fn deposit(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endowment_id: u32,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    if info.sender != config.ibc_host {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if info.funds.len() != 1 || info.funds[0].denom != config.staking_denom {
+        return Err(ContractError::InvalidCoinsDeposited {});
+    }
+    let deposit_amount = info.funds[0].amount;
+    if deposit_amount.is_zero() {
+        return Err(ContractError::EmptyBalance {});
+    }
+
+    // Compute the `vault_token` amount
+    // The formula of calculating the amount of vault tokens to be minted is as follows:
+    //   s = vault shares to mint <<< what we need to calculate given some # of `staking_denom` deposited
+    //   T = vault shares total (before mint)
+    //   a = `staking_denom` added to Vault's delegations
+    //   B = Vault's delegations (before deposit)
+    //
+    //   s = (a * T) / B = a * (T / B)
+    //
+    // NOTE: Staking rewards only count towards `B` once they are compounded by `Harvest`.
+    let vt_mint_amount = if state.total_shares.is_zero() {
+        if deposit_amount < config.minimum_initial_deposit {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: format!(
+                    "Insufficient initial deposit amount: Needed {}, Received {}",
+                    config.minimum_initial_deposit, deposit_amount
+                ),
+            }));
+        }
+        // first depositor sets the share price at 1 `staking_denom` per vault token
+        deposit_amount
+    } else {
+        let total_bonded = query_total_bonded(deps.as_ref(), &env)?;
+        if total_bonded.is_zero() {
+            return Err(ContractError::EmptyBalance {});
+        }
+        deposit_amount.multiply_ratio(state.total_shares, total_bonded)
+    };
+
+    let validators = query_validators(deps.as_ref(), &config)?;
+    let delegate_msgs = prepare_delegate_msgs(&config, &validators, deposit_amount)?;
+
+    execute_mint(deps.branch(), Some(endowment_id), vt_mint_amount)?;
+    state.total_shares += vt_mint_amount;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_messages(delegate_msgs)
+        .add_attributes(vec![
+            attr("action", "deposit"),
+            attr("endowment_id", endowment_id.to_string()),
+            attr("mint_shares", vt_mint_amount.to_string()),
+        ]))
+}
+
+/// Contract entry: **Redeem** & **RedeemAll**
+///   1. Burn the vault tokens (all of them when `burn_shares_amount` is `None`)
+///   2. Undelegate the `staking_denom` equivalent, starting from the largest delegations
+///   3. Queue an unbonding claim for the endowment, released after the `unbonding_period`
+pub fn redeem(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endowment_id: u32,
+    burn_shares_amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // Check if the `caller` is "ibc_host".
+    if info.sender != config.ibc_host {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let burn_shares_amount =
+        burn_shares_amount.unwrap_or_else(|| query_balance(deps.as_ref(), endowment_id));
+
+    // Compute the `staking_denom` amount to unbond
+    //   s = vault shares to burn
+    //   T = vault shares total (before burn)
+    //   a = `staking_denom` to unbond <<< what we need to calculate given some # of vault shares to be burned
+    //   B = Vault's delegations
+    //
+    //   a = (s * B) / T
+    let total_bonded = query_total_bonded(deps.as_ref(), &env)?;
+    execute_burn(deps.branch(), Some(endowment_id), burn_shares_amount).map_err(|e| {
+        ContractError::Std(StdError::GenericErr {
+            msg: format!(
+                "Cannot burn the {} vault tokens from Endowment {} :: {}",
+                burn_shares_amount, endowment_id, e,
+            ),
+        })
+    })?;
+    let unbond_amount = burn_shares_amount.multiply_ratio(total_bonded, state.total_shares);
+    if unbond_amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let undelegate_msgs = prepare_undelegate_msgs(deps.as_ref(), &env, &config, unbond_amount)?;
+
+    let claim = UnbondingClaim {
+        id: state.next_claim_id,
+        amount: unbond_amount,
+        release_at: env.block.time.plus_seconds(config.unbonding_period),
+    };
+    UNBONDING_CLAIMS.save(deps.storage, (endowment_id, claim.id), &claim)?;
+
+    state.total_shares -= burn_shares_amount;
+    state.total_unbonding += unbond_amount;
+    state.next_claim_id += 1;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::default()
+        .add_messages(undelegate_msgs)
+        .add_attributes(vec![
+            attr("action", "redeem"),
+            attr("burn_shares", burn_shares_amount.to_string()),
+            attr("unbond_amount", unbond_amount.to_string()),
+            attr("claim_id", claim.id.to_string()),
+            attr("release_at", claim.release_at.seconds().to_string()),
+        ]))
+}
+
+/// Contract entry: **Claim**
+///
+/// Send the `staking_denom` of all the matured unbonding claims of the endowment back to it
+/// through the Accounts contract. Claims still in their unbonding period are left untouched.
+pub fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    endowment_id: u32,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // Check if the `caller` is "ibc_host".
+    if info.sender != config.ibc_host {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let matured_claims = UNBONDING_CLAIMS
+        .prefix(endowment_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, claim)) => claim.release_at <= env.block.time,
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut claim_amount = Uint128::zero();
+    for (id, claim) in matured_claims.iter() {
+        UNBONDING_CLAIMS.remove(deps.storage, (endowment_id, *id));
+        claim_amount += claim.amount;
+    }
+
+    let mut res = Response::default().add_attributes(vec![
+        attr("action", "claim"),
+        attr("endowment_id", endowment_id.to_string()),
+        attr("claim_amount", claim_amount.to_string()),
+    ]);
+    // nothing matured yet, so that claiming on both sibling vaults at once never fails
+    if claim_amount.is_zero() {
+        return Ok(res);
+    }
+
+    state.total_unbonding -= claim_amount;
+    STATE.save(deps.storage, &state)?;
+
+    let accounts_contract =
+        query_accounts_contract(deps.as_ref(), config.registrar_contract.as_str())?;
+    res = res.add_message(vault_payout_msg(
+        &Denom::Native(config.staking_denom),
+        &accounts_contract,
+        Some(endowment_id),
+        config.acct_type,
+        claim_amount,
+    )?);
+    Ok(res)
+}
+
+/// Contract entry: **Harvest**
+///   1. Withdraw the accumulated rewards from every validator the vault delegates to
+///   2. Re-delegate the rewards across the Registrar's validator set
+///   3. Take the AP tax on the rewards by minting vault tokens to APTAX
+///   4. Move the stake left on validators removed from the Registrar's set over to the set
+///
+/// The AP tax vault tokens are minted as follows:
+///   F = rewards * ap_tax_rate
+///   T = vault shares total (before mint)
+///   B = Vault's delegations (after re-delegating the rewards)
+///   s = F * T / (B - F)
+///
+/// Rewards already withdrawn by the chain on (un)delegations sit idle in the vault's balance,
+/// so any balance not owed to the unbonding claims is compounded as well.
+pub fn harvest(mut deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // Only the `keeper` or `ibc_host` can harvest
+    if info.sender != config.keeper && info.sender != config.ibc_host {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validators = query_validators(deps.as_ref(), &config)?;
+    let delegations = deps
+        .querier
+        .query_all_delegations(env.contract.address.to_string())?;
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut rewards = Uint128::zero();
+    for delegation in delegations.iter() {
+        let accumulated = deps
+            .querier
+            .query_delegation(
+                env.contract.address.to_string(),
+                delegation.validator.clone(),
+            )?
+            .map(|full| {
+                full.accumulated_rewards
+                    .iter()
+                    .filter(|c| c.denom == config.staking_denom)
+                    .map(|c| c.amount)
+                    .sum::<Uint128>()
+            })
+            .unwrap_or_default();
+        if !accumulated.is_zero() {
+            rewards += accumulated;
+            msgs.push(CosmosMsg::Distribution(
+                DistributionMsg::WithdrawDelegatorReward {
+                    validator: delegation.validator.clone(),
+                },
+            ));
+        }
+    }
+    let idle_balance = deps
+        .querier
+        .query_balance(
+            env.contract.address.to_string(),
+            config.staking_denom.to_string(),
+        )?
+        .amount
+        .saturating_sub(state.total_unbonding);
+    let harvest_amount = rewards + idle_balance;
+
+    let ap_tax = harvest_amount * config.ap_tax_rate;
+    let mut ap_tax_shares = Uint128::zero();
+    // without any vault tokens, there is nobody to take the AP tax from
+    if !ap_tax.is_zero() && !state.total_shares.is_zero() {
+        let total_bonded: Uint128 = delegations.iter().map(|d| d.amount.amount).sum();
+        let untaxed_bonded = total_bonded + harvest_amount - ap_tax;
+        if untaxed_bonded.is_zero() {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: "AP tax cannot take the whole vault balance".to_string(),
+            }));
+        }
+        ap_tax_shares = ap_tax.multiply_ratio(state.total_shares, untaxed_bonded);
+        if !ap_tax_shares.is_zero() {
+            execute_mint(deps.branch(), None, ap_tax_shares)?;
+            state.total_shares += ap_tax_shares;
+            STATE.save(deps.storage, &state)?;
+        }
+    }
+    if !harvest_amount.is_zero() {
+        msgs.extend(prepare_delegate_msgs(&config, &validators, harvest_amount)?);
+    }
+
+    // stake left on validators removed from the Registrar's set keeps earning nothing for the
+    // vault once they go inactive, so it is moved over to the current set
+    let mut redelegate_amount = Uint128::zero();
+    for delegation in delegations
+        .iter()
+        .filter(|d| !validators.contains(&d.validator))
+    {
+        redelegate_amount += delegation.amount.amount;
+        msgs.extend(prepare_redelegate_msgs(
+            &config,
+            &validators,
+            &delegation.validator,
+            delegation.amount.amount,
+        )?);
+    }
+
+    Ok(Response::default().add_messages(msgs).add_attributes(vec![
+        attr("action", "harvest"),
+        attr("harvest_amount", harvest_amount.to_string()),
+        attr("ap_tax_shares", ap_tax_shares.to_string()),
+        attr("redelegate_amount", redelegate_amount.to_string()),
+    ]))
+}
+
+/// Contract entry: **ClaimTaxes**
+///   1. Send the `staking_denom` of the matured AP tax unbonding claims to the registrar `treasury`
+///   2. Burn all the vault tokens accrued as AP tax (APTAX balance)
+///   3. Undelegate the `staking_denom` equivalent & queue an AP tax unbonding claim,
+///      paid out by a later `ClaimTaxes` once the `unbonding_period` is over
+pub fn claim_taxes(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // Only the `config.owner` or `config.keeper` can trigger the claim
+    if info.sender != config.owner && info.sender != config.keeper {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+
+    let matured_claims = TAX_CLAIMS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, claim)) => claim.release_at <= env.block.time,
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut claim_amount = Uint128::zero();
+    for (id, claim) in matured_claims.iter() {
+        TAX_CLAIMS.remove(deps.storage, *id);
+        claim_amount += claim.amount;
+    }
+    if !claim_amount.is_zero() {
+        let registrar_config: RegistrarConfigResponse = deps.querier.query_wasm_smart(
+            config.registrar_contract.to_string(),
+            &RegistrarQueryMsg::Config {},
+        )?;
+        let treasury = deps.api.addr_validate(&registrar_config.treasury)?;
+        state.total_unbonding -= claim_amount;
+        msgs.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: treasury.to_string(),
+            amount: coins(claim_amount.u128(), config.staking_denom.to_string()),
+        }));
+    }
+
+    // Compute the `staking_denom` amount to unbond
+    //   a = (s * B) / T
+    let burn_shares_amount = APTAX.load(deps.storage)?;
+    let mut unbond_amount = Uint128::zero();
+    if !burn_shares_amount.is_zero() {
+        let total_bonded = query_total_bonded(deps.as_ref(), &env)?;
+        execute_burn(deps.branch(), None, burn_shares_amount)?;
+        unbond_amount = burn_shares_amount.multiply_ratio(total_bonded, state.total_shares);
+        state.total_shares -= burn_shares_amount;
+        if !unbond_amount.is_zero() {
+            msgs.extend(prepare_undelegate_msgs(
+                deps.as_ref(),
+                &env,
+                &config,
+                unbond_amount,
+            )?);
+            let claim = UnbondingClaim {
+                id: state.next_claim_id,
+                amount: unbond_amount,
+                release_at: env.block.time.plus_seconds(config.unbonding_period),
+            };
+            TAX_CLAIMS.save(deps.storage, claim.id, &claim)?;
+            state.total_unbonding += unbond_amount;
+            state.next_claim_id += 1;
+        }
+    }
+    if msgs.is_empty() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::default().add_messages(msgs).add_attributes(vec![
+        attr("action", "claim_taxes"),
+        attr("claim_amount", claim_amount.to_string()),
+        attr("burn_shares", burn_shares_amount.to_string()),
+        attr("unbond_amount", unbond_amount.to_string()),
+    ]))
+}
+
+/// Split `amount` evenly across the `validators`,
+/// the first validator takes the remainder of the split
+fn split_across_validators(
+    validators: &[String],
+    amount: Uint128,
+) -> Result<Vec<(&String, Uint128)>, ContractError> {
+    if validators.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "No validators are set in the Registrar",
+        )));
+    }
+    let share = amount.multiply_ratio(1u128, validators.len() as u128);
+    let remainder = amount - share * Uint128::from(validators.len() as u128);
+    Ok(validators
+        .iter()
+        .enumerate()
+        .map(|(i, validator)| {
+            let amount = if i == 0 { share + remainder } else { share };
+            (validator, amount)
+        })
+        .filter(|(_, amount)| !amount.is_zero())
+        .collect())
+}
+
+/// Delegate `amount` of `staking_denom` evenly across the `validators`
+fn prepare_delegate_msgs(
+    config: &Config,
+    validators: &[String],
+    amount: Uint128,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    Ok(split_across_validators(validators, amount)?
+        .into_iter()
+        .map(|(validator, amount)| {
+            CosmosMsg::Staking(StakingMsg::Delegate {
+                validator: validator.to_string(),
+                amount: coin(amount.u128(), config.staking_denom.to_string()),
+            })
+        })
+        .collect())
+}
+
+/// Move `amount` of `staking_denom` delegated to `src_validator` evenly over to the `validators`
+fn prepare_redelegate_msgs(
+    config: &Config,
+    validators: &[String],
+    src_validator: &str,
+    amount: Uint128,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    Ok(split_across_validators(validators, amount)?
+        .into_iter()
+        .map(|(validator, amount)| {
+            CosmosMsg::Staking(StakingMsg::Redelegate {
+                src_validator: src_validator.to_string(),
+                dst_validator: validator.to_string(),
+                amount: coin(amount.u128(), config.staking_denom.to_string()),
+            })
+        })
+        .collect())
+}
+
+/// Undelegate `amount` of `staking_denom`, taking from the largest delegations first
+fn prepare_undelegate_msgs(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    amount: Uint128,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut delegations = deps
+        .querier
+        .query_all_delegations(env.contract.address.to_string())?;
+    delegations.sort_by_key(|d| Reverse(d.amount.amount));
+
+    let mut msgs = vec![];
+    let mut remaining = amount;
+    for delegation in delegations {
+        if remaining.is_zero() {
+            break;
+        }
+        let undelegate_amount = remaining.min(delegation.amount.amount);
+        if undelegate_amount.is_zero() {
+            continue;
+        }
+        remaining -= undelegate_amount;
+        msgs.push(CosmosMsg::Staking(StakingMsg::Undelegate {
+            validator: delegation.validator,
+            amount: coin(undelegate_amount.u128(), config.staking_denom.to_string()),
+        }));
+    }
+    if !remaining.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Insufficient delegations: Needed {}, existing: {}",
+            amount,
+            amount - remaining
+        ))));
+    }
+    Ok(msgs)
+}
+
+/// Custom `mint` function for `vault token`
+fn execute_mint(
+    deps: DepsMut,
+    endowment_id: Option<u32>,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if amount == Uint128::zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    // update supply and enforce cap
+    let mut token_info = TOKEN_INFO.load(deps.storage)?;
+    token_info.total_supply += amount;
+    if let Some(limit) = token_info.get_cap() {
+        if token_info.total_supply > limit {
+            return Err(ContractError::CannotExceedCap {});
+        }
+    }
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    // add amount to recipient balance
+    match endowment_id {
+        Some(id) => BALANCES.update(
+            deps.storage,
+            id,
+            |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+        )?,
+        None => APTAX.update(deps.storage, |balance: Uint128| -> StdResult<_> {
+            Ok(balance.checked_add(amount)?)
+        })?,
+    };
+
+    Ok(())
+}
+
+/// Custom `burn` function for `vault token`
+fn execute_burn(
+    deps: DepsMut,
+    endowment_id: Option<u32>,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if amount == Uint128::zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    // lower balance
+    match endowment_id {
+        Some(id) => BALANCES.update(
+            deps.storage,
+            id,
+            |balance: Option<Uint128>| -> StdResult<_> {
+                Ok(balance.unwrap_or_default().checked_sub(amount)?)
+            },
+        )?,
+        None => APTAX.update(deps.storage, |balance: Uint128| -> StdResult<_> {
+            Ok(balance.checked_sub(amount)?)
+        })?,
+    };
+    // reduce total_supply
+    TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
+        info.total_supply = info.total_supply.checked_sub(amount)?;
+        Ok(info)
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use angel_core::structs::AccountType;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::{BalanceResponse, TokenInfoResponse};
+
+use crate::responses::{ConfigResponse, StateResponse, UnbondingClaimsResponse};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub ibc_host: String,
+    pub ibc_controller: String,
+    pub ap_tax_rate: Decimal,
+
+    pub acct_type: AccountType,
+    pub registrar_contract: String, // registrar holding the validator set to delegate to
+    pub keeper: String,
+    pub tax_collector: String,
+
+    pub unbonding_period: u64, // chain's unbonding time (in seconds)
+
+    pub minimum_initial_deposit: Uint128,
+
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// We currently take no arguments for migrations
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateOwner {
+        new_owner: String,
+    },
+    UpdateConfig(UpdateConfigMsg),
+    Deposit {
+        endowment_id: u32,
+    },
+    /// burn the vault tokens & start unbonding the staking denom equivalent.
+    /// The unbonded tokens are paid out with `Claim` once the unbonding period is over.
+    Redeem {
+        endowment_id: u32,
+        amount: Uint128, // vault tokens to be burned
+    },
+    RedeemAll {
+        endowment_id: u32,
+    },
+    /// send all the matured unbonding claims of the endowment back to it through Accounts
+    Claim {
+        endowment_id: u32,
+    },
+    /// withdraw & re-delegate the staking rewards, minting the AP tax as vault tokens to APTAX.
+    /// Stake left on validators removed from the Registrar set is moved to the current set.
+    /// Rewards are shared by all the vault tokens, so `account_ids` is unused.
+    Harvest {
+        account_ids: Vec<u32>,
+    },
+    /// send the matured AP tax unbonding claims to the registrar `treasury`, then
+    /// burn the vault tokens accrued as AP tax (APTAX balance) & start unbonding them
+    ClaimTaxes {},
+}
+
+#[cw_serde]
+pub struct UpdateConfigMsg {
+    pub ibc_host: Option<String>,
+    pub ibc_controller: Option<String>,
+
+    pub keeper: Option<String>,
+    pub tax_collector: Option<String>,
+    pub ap_tax_rate: Option<Decimal>,
+
+    pub unbonding_period: Option<u64>,
+    pub minimum_initial_deposit: Option<Uint128>,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the configuration of the contract
+    #[returns(ConfigResponse)]
+    Config {},
+    /// Returns the state of the contract
+    #[returns(StateResponse)]
+    State {},
+    /// Returns the current balance of the given "Endowment ID", 0 if unset.
+    #[returns(Uint128)]
+    Balance { endowment_id: u32 },
+    /// Returns the pending unbonding claims of the given "Endowment ID"
+    #[returns(UnbondingClaimsResponse)]
+    UnbondingClaims { endowment_id: u32 },
+    /// Returns metadata on the contract - name, decimals, supply, etc.
+    #[returns(TokenInfoResponse)]
+    TokenInfo {},
+    /// Returns the total balance/total_share of the contract
+    #[returns(BalanceResponse)]
+    TotalBalance {},
+    /// Returns the APTAX balance of the contract
+    #[returns(BalanceResponse)]
+    ApTaxBalance {},
+    /// Returns the pending unbonding claims of the AP tax
+    #[returns(UnbondingClaimsResponse)]
+    TaxClaims {},
+}
@@ -0,0 +1,105 @@
+use cosmwasm_std::{Deps, Env, Order, StdResult, Uint128};
+use cw20::{BalanceResponse, TokenInfoResponse};
+
+use angel_core::msgs::registrar::{QueryMsg as RegistrarQueryMsg, ValidatorsResponse};
+
+use crate::responses::{ConfigResponse, StateResponse, UnbondingClaimsResponse};
+use crate::state::{
+    Config, APTAX, BALANCES, CONFIG, STATE, TAX_CLAIMS, TOKEN_INFO, UNBONDING_CLAIMS,
+};
+
+pub fn query_balance(deps: Deps, id: u32) -> Uint128 {
+    BALANCES.load(deps.storage, id).unwrap_or_default()
+}
+
+pub fn query_unbonding_claims(deps: Deps, id: u32) -> StdResult<UnbondingClaimsResponse> {
+    let claims = UNBONDING_CLAIMS
+        .prefix(id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, claim)| claim))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(UnbondingClaimsResponse { claims })
+}
+
+pub fn query_tax_claims(deps: Deps) -> StdResult<UnbondingClaimsResponse> {
+    let claims = TAX_CLAIMS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, claim)| claim))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(UnbondingClaimsResponse { claims })
+}
+
+pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
+    let info = TOKEN_INFO.load(deps.storage)?;
+    Ok(TokenInfoResponse {
+        name: info.name,
+        symbol: info.symbol,
+        decimals: info.decimals,
+        total_supply: info.total_supply,
+    })
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        ibc_host: config.ibc_host.to_string(),
+        ibc_controller: config.ibc_controller.to_string(),
+
+        owner: config.owner.to_string(),
+        acct_type: config.acct_type,
+        registrar_contract: config.registrar_contract.to_string(),
+        keeper: config.keeper.to_string(),
+        tax_collector: config.tax_collector.to_string(),
+        ap_tax_rate: config.ap_tax_rate,
+
+        staking_denom: config.staking_denom,
+        unbonding_period: config.unbonding_period,
+
+        minimum_initial_deposit: config.minimum_initial_deposit.to_string(),
+        pending_owner: config
+            .pending_owner
+            .map(|addr| addr.to_string())
+            .unwrap_or_default(),
+        pending_owner_deadline: config.pending_owner_deadline.unwrap_or_default(),
+    })
+}
+
+pub fn query_state(deps: Deps, env: Env) -> StdResult<StateResponse> {
+    let state = STATE.load(deps.storage)?;
+    Ok(StateResponse {
+        total_shares: state.total_shares,
+        total_bonded: query_total_bonded(deps, &env)?,
+        total_unbonding: state.total_unbonding,
+    })
+}
+
+pub fn query_total_balance(deps: Deps) -> StdResult<BalanceResponse> {
+    let info = TOKEN_INFO.load(deps.storage)?;
+    Ok(BalanceResponse {
+        balance: info.total_supply,
+    })
+}
+
+pub fn query_ap_tax_balance(deps: Deps) -> StdResult<BalanceResponse> {
+    let ap_tax = APTAX.load(deps.storage)?;
+    Ok(BalanceResponse { balance: ap_tax })
+}
+
+/// Query the amount of `staking_denom` the vault has delegated across all validators
+pub fn query_total_bonded(deps: Deps, env: &Env) -> StdResult<Uint128> {
+    Ok(deps
+        .querier
+        .query_all_delegations(env.contract.address.to_string())?
+        .iter()
+        .map(|delegation| delegation.amount.amount)
+        .sum())
+}
+
+/// Query the validator set to delegate to from the Registrar
+pub fn query_validators(deps: Deps, config: &Config) -> StdResult<Vec<String>> {
+    let res: ValidatorsResponse = deps.querier.query_wasm_smart(
+        config.registrar_contract.to_string(),
+        &RegistrarQueryMsg::Validators {},
+    )?;
+    Ok(res.validators)
+}
@@ -0,0 +1,38 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Uint128};
+
+use angel_core::structs::AccountType;
+
+use crate::state::UnbondingClaim;
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub ibc_host: String,
+    pub ibc_controller: String,
+
+    pub owner: String,
+    pub acct_type: AccountType,
+    pub registrar_contract: String,
+    pub keeper: String,
+    pub tax_collector: String,
+    pub ap_tax_rate: Decimal,
+
+    pub staking_denom: String,
+    pub unbonding_period: u64,
+
+    pub minimum_initial_deposit: String,
+    pub pending_owner: String,
+    pub pending_owner_deadline: u64,
+}
+
+#[cw_serde]
+pub struct StateResponse {
+    pub total_shares: Uint128,
+    pub total_bonded: Uint128, // `staking_denom` currently delegated by the vault
+    pub total_unbonding: Uint128, // `staking_denom` owed to the pending unbonding claims
+}
+
+#[cw_serde]
+pub struct UnbondingClaimsResponse {
+    pub claims: Vec<UnbondingClaim>,
+}
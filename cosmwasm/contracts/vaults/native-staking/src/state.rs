@@ -0,0 +1,69 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+
+use angel_core::structs::AccountType;
+
+#[cw_serde]
+pub struct Config {
+    pub owner: Addr,
+    pub acct_type: AccountType,
+    pub registrar_contract: Addr, // registrar holding the validator set to delegate to
+    pub keeper: Addr,
+    pub tax_collector: Addr,
+
+    pub ibc_host: Addr,
+    pub ibc_controller: Addr,
+    pub ap_tax_rate: Decimal, // Rate of the staking rewards taken as AP tax on harvest
+
+    pub staking_denom: String, // the chain's bonded denom (and output back to Accounts)
+    pub unbonding_period: u64, // chain's unbonding time (in seconds)
+
+    pub minimum_initial_deposit: Uint128, // Minimum deposit amount limit when `total_shares` = 0
+    pub pending_owner: Option<Addr>, // Pending owner address which is used in 2-step `update_owner` process
+    pub pending_owner_deadline: Option<u64>, // Block height until which the `pending_owner` is valid in `update_owner` process
+}
+
+#[cw_serde]
+pub struct State {
+    pub total_shares: Uint128,    // total amount of minted vault tokens
+    pub total_unbonding: Uint128, // `staking_denom` owed to the pending unbonding claims
+    pub next_claim_id: u64,
+}
+
+#[cw_serde]
+pub struct UnbondingClaim {
+    pub id: u64,
+    pub amount: Uint128,
+    pub release_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct TokenInfo {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub total_supply: Uint128,
+    pub mint: Option<MinterData>,
+}
+
+#[cw_serde]
+pub struct MinterData {
+    pub minter: Addr,
+    /// cap is how many more tokens can be issued by the minter
+    pub cap: Option<Uint128>,
+}
+
+impl TokenInfo {
+    pub fn get_cap(&self) -> Option<Uint128> {
+        self.mint.as_ref().and_then(|v| v.cap)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const STATE: Item<State> = Item::new("state");
+pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
+pub const BALANCES: Map<u32, Uint128> = Map::new("balance");
+pub const UNBONDING_CLAIMS: Map<(u32, u64), UnbondingClaim> = Map::new("unbonding_claims");
+pub const APTAX: Item<Uint128> = Item::new("ap_treasury_tax_balance");
+pub const TAX_CLAIMS: Map<u64, UnbondingClaim> = Map::new("tax_claims");
@@ -0,0 +1,96 @@
+//! Minimal registrar used by the multi-test suite, only answering the config & validator set
+//! queries. The validator set can be updated like on the real Registrar.
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+
+use angel_core::msgs::registrar::{
+    ConfigExtensionResponse, ConfigResponse, ExecuteMsg, QueryMsg, ValidatorsResponse,
+};
+use angel_core::structs::{AcceptedTokens, RebalanceDetails, SplitDetails};
+
+const VALIDATORS: Item<Vec<String>> = Item::new("validators");
+const TREASURY: Item<String> = Item::new("treasury");
+const ACCOUNTS_CONTRACT: Item<String> = Item::new("accounts_contract");
+
+#[cw_serde]
+pub struct MockInstantiateMsg {
+    pub validators: Vec<String>,
+    pub treasury: String,
+    pub accounts_contract: String,
+}
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockInstantiateMsg,
+) -> StdResult<Response> {
+    VALIDATORS.save(deps.storage, &msg.validators)?;
+    TREASURY.save(deps.storage, &msg.treasury)?;
+    ACCOUNTS_CONTRACT.save(deps.storage, &msg.accounts_contract)?;
+    Ok(Response::default())
+}
+
+fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateValidators { add, remove } => {
+            VALIDATORS.update(deps.storage, |mut validators| -> StdResult<_> {
+                validators.retain(|v| !remove.contains(v));
+                validators.extend(add);
+                Ok(validators)
+            })?;
+            Ok(Response::default())
+        }
+        _ => Err(StdError::generic_err("Not supported")),
+    }
+}
+
+fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Validators {} => to_binary(&ValidatorsResponse {
+            validators: VALIDATORS.load(deps.storage)?,
+        }),
+        QueryMsg::Config {} => to_binary(&ConfigResponse {
+            owner: "registrar-owner".to_string(),
+            version: "1.0.0".to_string(),
+            treasury: TREASURY.load(deps.storage)?,
+            rebalance: RebalanceDetails::default(),
+            split_to_liquid: SplitDetails::default(),
+            accepted_tokens: AcceptedTokens::default(),
+            axelar_gateway: "axelar-gateway".to_string(),
+            axelar_ibc_channel: "channel-1".to_string(),
+            axelar_chain_id: "juno".to_string(),
+        }),
+        QueryMsg::ConfigExtension {} => to_binary(&ConfigExtensionResponse {
+            cw3_code: None,
+            cw4_code: None,
+            subdao_gov_code: None,
+            subdao_cw20_token_code: None,
+            subdao_bonding_token_code: None,
+            subdao_cw900_code: None,
+            subdao_distributor_code: None,
+            donation_match_code: None,
+            halo_token: None,
+            halo_token_lp_contract: None,
+            gov_contract: None,
+            accounts_contract: Some(ACCOUNTS_CONTRACT.load(deps.storage)?),
+            index_fund: None,
+            donation_match_charites_contract: None,
+            collector_addr: "collector".to_string(),
+            charity_shares_contract: None,
+            swap_factory: None,
+            applications_review: "applications-review".to_string(),
+            swaps_router: None,
+            accounts_settings_controller: None,
+        }),
+        _ => Err(StdError::generic_err("Not supported")),
+    }
+}
+
+pub fn contract_registrar() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
@@ -0,0 +1,3 @@
+pub mod mock_accounts;
+pub mod mock_registrar;
+mod unit_tests;
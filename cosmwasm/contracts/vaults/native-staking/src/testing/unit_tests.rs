@@ -0,0 +1,544 @@
+use angel_core::errors::vault::ContractError;
+use angel_core::msgs::registrar::ConfigExtensionResponse;
+use angel_core::structs::AccountType;
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{coins, Addr, Decimal, Empty, StdError, Uint128, Validator};
+use cw20::BalanceResponse;
+use cw_multi_test::{
+    App, AppBuilder, Contract, ContractWrapper, Executor, StakingInfo, StakingSudo, SudoMsg,
+};
+
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::responses::{ConfigResponse, StateResponse, UnbondingClaimsResponse};
+use crate::testing::mock_accounts::{contract_accounts, MockQueryMsg as AccountsQueryMsg};
+use crate::testing::mock_registrar::{contract_registrar, MockInstantiateMsg};
+
+const OWNER: &str = "creator";
+const KEEPER: &str = "keeper";
+const TAX_COLLECTOR: &str = "tax-collector";
+const IBC_HOST: &str = "ibc-host";
+const IBC_CONTROLLER: &str = "ibc-controller";
+const TREASURY: &str = "treasury";
+const VALIDATOR_1: &str = "validator1";
+const VALIDATOR_2: &str = "validator2";
+const VALIDATOR_3: &str = "validator3";
+const DENOM: &str = "ujuno";
+const UNBONDING_PERIOD: u64 = 60 * 60 * 24 * 21;
+
+fn contract_vault() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    ))
+}
+
+fn mock_app() -> App {
+    AppBuilder::new().build(|router, api, storage| {
+        let block = mock_env().block;
+        router
+            .staking
+            .setup(
+                storage,
+                StakingInfo {
+                    bonded_denom: DENOM.to_string(),
+                    unbonding_time: UNBONDING_PERIOD,
+                    apr: Decimal::percent(10),
+                },
+            )
+            .unwrap();
+        for validator in [VALIDATOR_1, VALIDATOR_2, VALIDATOR_3] {
+            router
+                .staking
+                .add_validator(
+                    api,
+                    storage,
+                    &block,
+                    Validator {
+                        address: validator.to_string(),
+                        commission: Decimal::zero(),
+                        max_commission: Decimal::percent(20),
+                        max_change_rate: Decimal::percent(1),
+                    },
+                )
+                .unwrap();
+        }
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked(IBC_HOST),
+                coins(10_000_000, DENOM),
+            )
+            .unwrap();
+    })
+}
+
+/// Instantiate the mock registrar & the vault delegating to its validator set
+fn instantiate_vault(app: &mut App) -> Addr {
+    let accounts_code_id = app.store_code(contract_accounts());
+    let accounts = app
+        .instantiate_contract(
+            accounts_code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "accounts",
+            None,
+        )
+        .unwrap();
+    let registrar_code_id = app.store_code(contract_registrar());
+    let registrar = app
+        .instantiate_contract(
+            registrar_code_id,
+            Addr::unchecked(OWNER),
+            &MockInstantiateMsg {
+                validators: vec![VALIDATOR_1.to_string(), VALIDATOR_2.to_string()],
+                treasury: TREASURY.to_string(),
+                accounts_contract: accounts.to_string(),
+            },
+            &[],
+            "registrar",
+            None,
+        )
+        .unwrap();
+
+    let code_id = app.store_code(contract_vault());
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(OWNER),
+        &InstantiateMsg {
+            ibc_host: IBC_HOST.to_string(),
+            ibc_controller: IBC_CONTROLLER.to_string(),
+            ap_tax_rate: Decimal::percent(10),
+            acct_type: AccountType::Locked,
+            registrar_contract: registrar.to_string(),
+            keeper: KEEPER.to_string(),
+            tax_collector: TAX_COLLECTOR.to_string(),
+            unbonding_period: UNBONDING_PERIOD,
+            minimum_initial_deposit: Uint128::from(100_u128),
+            name: "Staking vault token".to_string(),
+            symbol: "SVT".to_string(),
+            decimals: 6,
+        },
+        &[],
+        "native-staking-vault",
+        None,
+    )
+    .unwrap()
+}
+
+fn deposit(app: &mut App, vault: &Addr, endowment_id: u32, amount: u128) {
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::Deposit { endowment_id },
+        &coins(amount, DENOM),
+    )
+    .unwrap();
+}
+
+fn query_state(app: &App, vault: &Addr) -> StateResponse {
+    app.wrap()
+        .query_wasm_smart(vault, &QueryMsg::State {})
+        .unwrap()
+}
+
+fn query_delegated(app: &App, vault: &Addr, validator: &str) -> Uint128 {
+    app.wrap()
+        .query_delegation(vault, validator)
+        .unwrap()
+        .map(|d| d.amount.amount)
+        .unwrap_or_default()
+}
+
+fn query_registrar(app: &App, vault: &Addr) -> Addr {
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(vault, &QueryMsg::Config {})
+        .unwrap();
+    Addr::unchecked(config.registrar_contract)
+}
+
+/// Total amount the Accounts contract was paid for the endowment through vault receipts
+fn query_accounts_receipts(app: &App, vault: &Addr, endowment_id: u32) -> Uint128 {
+    let registrar_config: ConfigExtensionResponse = app
+        .wrap()
+        .query_wasm_smart(
+            query_registrar(app, vault),
+            &angel_core::msgs::registrar::QueryMsg::ConfigExtension {},
+        )
+        .unwrap();
+    app.wrap()
+        .query_wasm_smart(
+            registrar_config.accounts_contract.unwrap(),
+            &AccountsQueryMsg::Receipts { id: endowment_id },
+        )
+        .unwrap()
+}
+
+fn query_ap_tax(app: &App, vault: &Addr) -> Uint128 {
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(vault, &QueryMsg::ApTaxBalance {})
+        .unwrap();
+    res.balance
+}
+
+fn harvest(app: &mut App, vault: &Addr) {
+    app.execute_contract(
+        Addr::unchecked(KEEPER),
+        vault.clone(),
+        &ExecuteMsg::Harvest {
+            account_ids: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_proper_initialization() {
+    let mut app = mock_app();
+    let vault = instantiate_vault(&mut app);
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.owner, OWNER.to_string());
+    assert_eq!(config.staking_denom, DENOM.to_string());
+    assert_eq!(config.unbonding_period, UNBONDING_PERIOD);
+
+    let state = query_state(&app, &vault);
+    assert_eq!(state.total_shares, Uint128::zero());
+    assert_eq!(state.total_bonded, Uint128::zero());
+    assert_eq!(state.total_unbonding, Uint128::zero());
+}
+
+#[test]
+fn test_deposit() {
+    let mut app = mock_app();
+    let vault = instantiate_vault(&mut app);
+
+    // Only the `ibc_host` can deposit
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            vault.clone(),
+            &ExecuteMsg::Deposit { endowment_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // First deposit should meet the minimum
+    let err = app
+        .execute_contract(
+            Addr::unchecked(IBC_HOST),
+            vault.clone(),
+            &ExecuteMsg::Deposit { endowment_id: 1 },
+            &coins(99, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "Insufficient initial deposit amount: Needed 100, Received 99"
+        ))
+    );
+
+    // Deposits are split evenly across the validators, first one takes the remainder
+    deposit(&mut app, &vault, 1, 1001);
+    assert_eq!(
+        query_delegated(&app, &vault, VALIDATOR_1),
+        Uint128::from(501_u128)
+    );
+    assert_eq!(
+        query_delegated(&app, &vault, VALIDATOR_2),
+        Uint128::from(500_u128)
+    );
+
+    deposit(&mut app, &vault, 2, 2002);
+    let balance: Uint128 = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::Balance { endowment_id: 2 })
+        .unwrap();
+    assert_eq!(balance, Uint128::from(2002_u128));
+
+    let state = query_state(&app, &vault);
+    assert_eq!(state.total_shares, Uint128::from(3003_u128));
+    assert_eq!(state.total_bonded, Uint128::from(3003_u128));
+}
+
+#[test]
+fn test_redeem_and_claim() {
+    let mut app = mock_app();
+    let vault = instantiate_vault(&mut app);
+    deposit(&mut app, &vault, 1, 1000);
+
+    // Redeeming queues an unbonding claim instead of paying out
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::Redeem {
+            endowment_id: 1,
+            amount: Uint128::from(600_u128),
+        },
+        &[],
+    )
+    .unwrap();
+    let claims: UnbondingClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::UnbondingClaims { endowment_id: 1 })
+        .unwrap();
+    assert_eq!(claims.claims.len(), 1);
+    assert_eq!(claims.claims[0].amount, Uint128::from(600_u128));
+    let state = query_state(&app, &vault);
+    assert_eq!(state.total_shares, Uint128::from(400_u128));
+    assert_eq!(state.total_bonded, Uint128::from(400_u128));
+    assert_eq!(state.total_unbonding, Uint128::from(600_u128));
+
+    // Nothing to claim until the unbonding period is over
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::Claim { endowment_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_accounts_receipts(&app, &vault, 1), Uint128::zero());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(UNBONDING_PERIOD));
+    app.sudo(SudoMsg::Staking(StakingSudo::ProcessQueue {}))
+        .unwrap();
+
+    // Only the `ibc_host` can claim
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            vault.clone(),
+            &ExecuteMsg::Claim { endowment_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // Matured claims are credited back to the endowment through the Accounts contract
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::Claim { endowment_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_accounts_receipts(&app, &vault, 1),
+        Uint128::from(600_u128)
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(IBC_CONTROLLER, DENOM)
+            .unwrap()
+            .amount,
+        Uint128::zero()
+    );
+    let claims: UnbondingClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::UnbondingClaims { endowment_id: 1 })
+        .unwrap();
+    assert!(claims.claims.is_empty());
+    assert_eq!(query_state(&app, &vault).total_unbonding, Uint128::zero());
+
+    // Redeem the rest
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::RedeemAll { endowment_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let state = query_state(&app, &vault);
+    assert_eq!(state.total_shares, Uint128::zero());
+    assert_eq!(state.total_bonded, Uint128::zero());
+    assert_eq!(state.total_unbonding, Uint128::from(400_u128));
+}
+
+#[test]
+fn test_harvest() {
+    let mut app = mock_app();
+    let vault = instantiate_vault(&mut app);
+    deposit(&mut app, &vault, 1, 1_000_000);
+
+    // Only the `keeper` or `ibc_host` can harvest
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            vault.clone(),
+            &ExecuteMsg::Harvest {
+                account_ids: vec![],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // A year at 10% APR accrues 100_000 of rewards
+    app.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
+    harvest(&mut app, &vault);
+
+    // All the rewards are compounded & 10% of them are minted as vault tokens to APTAX
+    //   s = 10_000 * 1_000_000 / (1_100_000 - 10_000) = 9_174
+    assert_eq!(
+        app.wrap()
+            .query_balance(TAX_COLLECTOR, DENOM)
+            .unwrap()
+            .amount,
+        Uint128::zero()
+    );
+    assert_eq!(query_ap_tax(&app, &vault), Uint128::from(9_174_u128));
+    let state = query_state(&app, &vault);
+    assert_eq!(state.total_shares, Uint128::from(1_009_174_u128));
+    assert_eq!(state.total_bonded, Uint128::from(1_100_000_u128));
+
+    // The share price went up for redemptions
+    //   a = 100_000 * 1_100_000 / 1_009_174 = 109_000
+    app.execute_contract(
+        Addr::unchecked(IBC_HOST),
+        vault.clone(),
+        &ExecuteMsg::Redeem {
+            endowment_id: 1,
+            amount: Uint128::from(100_000_u128),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_state(&app, &vault).total_unbonding,
+        Uint128::from(109_000_u128)
+    );
+}
+
+#[test]
+fn test_harvest_redelegates_removed_validators() {
+    let mut app = mock_app();
+    let vault = instantiate_vault(&mut app);
+    deposit(&mut app, &vault, 1, 1000);
+
+    // validator2 leaves the Registrar's set & validator3 joins it
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        query_registrar(&app, &vault),
+        &angel_core::msgs::registrar::ExecuteMsg::UpdateValidators {
+            add: vec![VALIDATOR_3.to_string()],
+            remove: vec![VALIDATOR_2.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    harvest(&mut app, &vault);
+
+    // The 500 delegated to validator2 are split across the current set
+    assert_eq!(
+        query_delegated(&app, &vault, VALIDATOR_1),
+        Uint128::from(750_u128)
+    );
+    assert_eq!(query_delegated(&app, &vault, VALIDATOR_2), Uint128::zero());
+    assert_eq!(
+        query_delegated(&app, &vault, VALIDATOR_3),
+        Uint128::from(250_u128)
+    );
+    let state = query_state(&app, &vault);
+    assert_eq!(state.total_shares, Uint128::from(1000_u128));
+    assert_eq!(state.total_bonded, Uint128::from(1000_u128));
+}
+
+#[test]
+fn test_claim_taxes() {
+    let mut app = mock_app();
+    let vault = instantiate_vault(&mut app);
+    deposit(&mut app, &vault, 1, 1_000_000);
+
+    // Nothing to claim before any AP tax accrues
+    let err = app
+        .execute_contract(
+            Addr::unchecked(KEEPER),
+            vault.clone(),
+            &ExecuteMsg::ClaimTaxes {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ZeroAmount {}
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
+    harvest(&mut app, &vault);
+    assert_eq!(query_ap_tax(&app, &vault), Uint128::from(9_174_u128));
+
+    // Only the `owner` or `keeper` can claim the taxes
+    let err = app
+        .execute_contract(
+            Addr::unchecked(IBC_HOST),
+            vault.clone(),
+            &ExecuteMsg::ClaimTaxes {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // The APTAX vault tokens are burned & their stake starts unbonding
+    //   a = 9_174 * 1_100_000 / 1_009_174 = 9_999
+    app.execute_contract(
+        Addr::unchecked(KEEPER),
+        vault.clone(),
+        &ExecuteMsg::ClaimTaxes {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_ap_tax(&app, &vault), Uint128::zero());
+    let claims: UnbondingClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::TaxClaims {})
+        .unwrap();
+    assert_eq!(claims.claims.len(), 1);
+    assert_eq!(claims.claims[0].amount, Uint128::from(9_999_u128));
+    let state = query_state(&app, &vault);
+    assert_eq!(state.total_shares, Uint128::from(1_000_000_u128));
+    assert_eq!(state.total_unbonding, Uint128::from(9_999_u128));
+
+    // Once matured, the next claim pays the `treasury`
+    app.update_block(|block| block.time = block.time.plus_seconds(UNBONDING_PERIOD));
+    app.sudo(SudoMsg::Staking(StakingSudo::ProcessQueue {}))
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        vault.clone(),
+        &ExecuteMsg::ClaimTaxes {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(TREASURY, DENOM).unwrap().amount,
+        Uint128::from(9_999_u128)
+    );
+    let claims: UnbondingClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::TaxClaims {})
+        .unwrap();
+    assert!(claims.claims.is_empty());
+    assert_eq!(query_state(&app, &vault).total_unbonding, Uint128::zero());
+}
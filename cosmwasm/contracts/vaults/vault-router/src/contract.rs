@@ -1,5 +1,6 @@
 use crate::executers::{
-    execute_harvest, execute_invest, execute_redeem, execute_redeem_all, execute_update_config,
+    execute_claim, execute_harvest, execute_invest, execute_redeem, execute_redeem_all,
+    execute_reinvest_to_locked, execute_reinvest_to_locked_finalize, execute_update_config,
 };
 use crate::state::{Config, CONFIG};
 use angel_core::errors::core::ContractError;
@@ -56,7 +57,7 @@ pub fn receive_cw20(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -79,6 +80,23 @@ pub fn execute(
         ExecuteMsg::Redeem { action } => execute_redeem(deps, info.sender, action),
         ExecuteMsg::RedeemAll { action } => execute_redeem_all(deps, info.sender, action),
         ExecuteMsg::Harvest { action } => execute_harvest(deps, info.sender, action),
+        ExecuteMsg::Claim { action } => execute_claim(deps, info.sender, action),
+        ExecuteMsg::ReinvestToLocked {
+            action,
+            target_strategy,
+        } => execute_reinvest_to_locked(deps, env, info.sender, action, target_strategy),
+        ExecuteMsg::ReinvestToLockedFinalize {
+            action,
+            target_strategy,
+            token_bal_before,
+        } => execute_reinvest_to_locked_finalize(
+            deps,
+            env,
+            info.sender,
+            action,
+            target_strategy,
+            token_bal_before,
+        ),
     }
 }
 
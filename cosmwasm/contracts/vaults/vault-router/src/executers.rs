@@ -1,14 +1,15 @@
 use crate::state::CONFIG;
 use angel_core::errors::core::ContractError;
 use angel_core::msgs::registrar::{
+    ConfigExtensionResponse as RegistrarConfigExtensionResponse,
     ConfigResponse as RegistrarConfigResponse, QueryMsg as RegistrarQuerier, StrategyDetailResponse,
 };
 // use angel_core::msgs::registrar::QueryMsg as RegistrarQuerier;
 use angel_core::structs::{StrategyApprovalState, StrategyParams, VaultActionData};
 use angel_core::utils::validate_deposit_fund;
 use cosmwasm_std::{
-    to_binary, Addr, CosmosMsg, DepsMut, MessageInfo, QueryRequest, Response, StdError, SubMsg,
-    Uint128, WasmMsg, WasmQuery,
+    coins, to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, QueryRequest, Response, StdError,
+    SubMsg, Uint128, WasmMsg, WasmQuery,
 };
 use cw_asset::Asset;
 
@@ -294,6 +295,240 @@ pub fn execute_harvest(
     }
 }
 
+pub fn execute_claim(
+    deps: DepsMut,
+    _sender: Addr,
+    action: VaultActionData,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let registrar_config: RegistrarConfigResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.registrar_contract.to_string(),
+            msg: to_binary(&RegistrarQuerier::Config {})?,
+        }))?;
+    if action.destination_chain != registrar_config.axelar_chain_id {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: format!(
+                "Vault Router can only handle messages bound for {}",
+                action.destination_chain
+            ),
+        }));
+    }
+    if action.account_ids.len() != 1 {
+        return Err(ContractError::InvalidInputs {});
+    }
+
+    let strategy_res: StrategyDetailResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.registrar_contract.to_string(),
+            msg: to_binary(&RegistrarQuerier::Strategy {
+                strategy_key: action.strategy_id,
+            })?,
+        }))?;
+    let strategy: StrategyParams = strategy_res.strategy;
+
+    // vaults pay out only the matured claims, so it is safe to claim from both of them
+    let mut res = Response::default().add_attribute("action", "claim");
+    for vault in [strategy.locked_addr, strategy.liquid_addr]
+        .into_iter()
+        .flatten()
+    {
+        res = res.add_submessage(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: vault.to_string(),
+            msg: to_binary(&angel_core::msgs::vault::UnbondingExecuteMsg::Claim {
+                endowment_id: action.account_ids[0],
+            })?,
+            funds: vec![],
+        })));
+    }
+    Ok(res)
+}
+
+/// Move an Endowment's liquid strategy position over to a locked strategy.
+/// If both positions belong to the same strategy, the liquid vault hands its assets
+/// straight to its locked sibling. Otherwise, the liquid vault tokens are redeemed
+/// to the Router and the proceeds are deposited into the target locked vault.
+pub fn execute_reinvest_to_locked(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    action: VaultActionData,
+    target_strategy: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let registrar_config: RegistrarConfigResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.registrar_contract.to_string(),
+            msg: to_binary(&RegistrarQuerier::Config {})?,
+        }))?;
+    if action.destination_chain != registrar_config.axelar_chain_id {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: format!(
+                "Vault Router can only handle messages bound for {}",
+                action.destination_chain
+            ),
+        }));
+    }
+
+    // only the Accounts contract can move endowment positions around
+    let registrar_config_ext: RegistrarConfigExtensionResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.registrar_contract.to_string(),
+            msg: to_binary(&RegistrarQuerier::ConfigExtension {})?,
+        }))?;
+    if registrar_config_ext.accounts_contract != Some(sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // only liquid vault tokens can be reinvested
+    if action.liq_amt.is_zero() || !action.lock_amt.is_zero() || action.account_ids.len() != 1 {
+        return Err(ContractError::InvalidInputs {});
+    }
+
+    let source: StrategyParams = deps
+        .querier
+        .query::<StrategyDetailResponse>(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.registrar_contract.to_string(),
+            msg: to_binary(&RegistrarQuerier::Strategy {
+                strategy_key: action.strategy_id.clone(),
+            })?,
+        }))?
+        .strategy;
+    if source.approval_state != StrategyApprovalState::Approved
+        && source.approval_state != StrategyApprovalState::WithdrawOnly
+    {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Withdraws are not allowed from this Strategy".to_string(),
+        }));
+    }
+
+    let target: StrategyParams = deps
+        .querier
+        .query::<StrategyDetailResponse>(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.registrar_contract.to_string(),
+            msg: to_binary(&RegistrarQuerier::Strategy {
+                strategy_key: target_strategy.clone(),
+            })?,
+        }))?
+        .strategy;
+    if target.approval_state != StrategyApprovalState::Approved {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Strategy is not approved".to_string(),
+        }));
+    }
+    if target.input_denom != source.input_denom {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: "Strategies must share the same input token".to_string(),
+        }));
+    }
+
+    let (liquid_vault, locked_vault) = match (source.liquid_addr, target.locked_addr) {
+        (Some(liquid), Some(locked)) => (liquid, locked),
+        _ => return Err(ContractError::InvalidInputs {}),
+    };
+
+    // Check the vault token(VT) balance of the endowment in the source vault
+    let endowment_id = action.account_ids[0];
+    let available_liquid: Uint128 = deps.querier.query_wasm_smart(
+        liquid_vault.to_string(),
+        &angel_core::msgs::vault::QueryMsg::Balance { endowment_id },
+    )?;
+    if action.liq_amt > available_liquid {
+        return Err(ContractError::BalanceTooSmall {});
+    }
+
+    let res = Response::default().add_attribute("action", "reinvest_to_locked");
+
+    // same strategy: the liquid vault sends the assets directly to its locked sibling
+    if action.strategy_id == target_strategy {
+        return Ok(res.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: liquid_vault.to_string(),
+            msg: to_binary(&angel_core::msgs::vault::ExecuteMsg::ReinvestToLocked {
+                endowment_id,
+                amount: action.liq_amt,
+            })?,
+            funds: vec![],
+        })));
+    }
+
+    // different strategies: the liquid vault redeems to the Router, then the proceeds are deposited
+    let token_bal_before = deps
+        .querier
+        .query_balance(env.contract.address.to_string(), source.input_denom)?
+        .amount;
+    Ok(res
+        .add_attribute("locked_vault", locked_vault)
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: liquid_vault.to_string(),
+            msg: to_binary(&angel_core::msgs::vault::ExecuteMsg::RedeemForReinvest {
+                endowment_id,
+                amount: action.liq_amt,
+            })?,
+            funds: vec![],
+        }))
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(
+                &angel_core::msgs::vault_router::ExecuteMsg::ReinvestToLockedFinalize {
+                    action,
+                    target_strategy,
+                    token_bal_before,
+                },
+            )?,
+            funds: vec![],
+        })))
+}
+
+/// Deposit the tokens redeemed from the source liquid vault into the target locked vault
+pub fn execute_reinvest_to_locked_finalize(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    action: VaultActionData,
+    target_strategy: String,
+    token_bal_before: Uint128,
+) -> Result<Response, ContractError> {
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+
+    let strategy_res: StrategyDetailResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.registrar_contract.to_string(),
+            msg: to_binary(&RegistrarQuerier::Strategy {
+                strategy_key: target_strategy,
+            })?,
+        }))?;
+    let target: StrategyParams = strategy_res.strategy;
+    let locked_vault = match target.locked_addr {
+        Some(addr) => addr,
+        None => return Err(ContractError::InvalidInputs {}),
+    };
+
+    let token_bal = deps
+        .querier
+        .query_balance(env.contract.address.to_string(), target.input_denom.clone())?
+        .amount;
+    let redeemed_amount = token_bal.checked_sub(token_bal_before)?;
+    if redeemed_amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "reinvest_to_locked_finalize")
+        .add_attribute("reinvested_amount", redeemed_amount)
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: locked_vault.to_string(),
+            msg: to_binary(&angel_core::msgs::vault::ExecuteMsg::Deposit {
+                endowment_id: action.account_ids[0],
+            })?,
+            funds: coins(redeemed_amount.u128(), target.input_denom),
+        })))
+}
+
 pub fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
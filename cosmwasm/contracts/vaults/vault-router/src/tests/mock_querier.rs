@@ -1,13 +1,17 @@
 use angel_core::msgs::registrar::{
     ConfigExtensionResponse as RegistrarConfigExtensionResponse,
-    ConfigResponse as RegistrarConfigResponse,
+    ConfigResponse as RegistrarConfigResponse, StrategyDetailResponse,
+};
+use angel_core::structs::{
+    AcceptedTokens, RebalanceDetails, SplitDetails, StrategyApprovalState, StrategyLocale,
+    StrategyParams,
 };
-use angel_core::structs::{AcceptedTokens, RebalanceDetails, SplitDetails};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, from_slice, to_binary, Api, Coin, ContractResult, Decimal, Empty, OwnedDeps,
-    Querier, QuerierResult, QueryRequest, SystemError, SystemResult, WasmQuery,
+    from_binary, from_slice, to_binary, Addr, Api, Coin, ContractResult, Decimal, Empty,
+    OwnedDeps, Querier, QuerierResult, QueryRequest, SystemError, SystemResult, Uint128,
+    WasmQuery,
 };
 use std::marker::PhantomData;
 
@@ -16,6 +20,21 @@ pub enum QueryMsg {
     // Mock the `registrar::QueryMsg::Config {}` query
     Config {},
     ConfigExtension {},
+    // Mock the `registrar::QueryMsg::Strategy {}` query
+    Strategy { strategy_key: String },
+    // Mock the `vault::QueryMsg::Balance {}` query
+    Balance { endowment_id: u32 },
+}
+
+fn mock_strategy(id: &str) -> StrategyParams {
+    StrategyParams {
+        approval_state: StrategyApprovalState::Approved,
+        locale: StrategyLocale::Native,
+        chain: "juno".to_string(),
+        input_denom: "ujuno".to_string(),
+        locked_addr: Some(Addr::unchecked(format!("{}-locked-contract", id))),
+        liquid_addr: Some(Addr::unchecked(format!("{}-liquid-contract", id))),
+    }
 }
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
@@ -111,6 +130,15 @@ impl WasmMockQuerier {
                     })
                     .unwrap(),
                 )),
+                QueryMsg::Strategy { strategy_key } => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&StrategyDetailResponse {
+                        strategy: mock_strategy(&strategy_key),
+                    })
+                    .unwrap(),
+                )),
+                QueryMsg::Balance { endowment_id: _ } => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&Uint128::from(1000_u128)).unwrap(),
+                )),
             },
             QueryRequest::Wasm(WasmQuery::Raw {
                 contract_addr: _,
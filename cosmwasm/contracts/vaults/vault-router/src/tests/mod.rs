@@ -1,2 +1,3 @@
 pub mod mock_querier;
+mod multi_tests;
 mod unit_tests;
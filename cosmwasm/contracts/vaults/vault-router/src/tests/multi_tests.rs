@@ -0,0 +1,343 @@
+//! Multi-test suite running the Router against real lending vaults.
+use angel_core::msgs::registrar::{
+    ConfigExtensionResponse, ConfigResponse, QueryMsg as RegistrarQueryMsg, StrategyDetailResponse,
+};
+use angel_core::msgs::vault_router::{ExecuteMsg, InstantiateMsg};
+use angel_core::structs::{
+    AcceptedTokens, AccountType, RebalanceDetails, SplitDetails, StrategyApprovalState,
+    StrategyLocale, StrategyParams, VaultActionData,
+};
+use cosmwasm_std::{
+    coins, to_binary, Addr, BankMsg, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128,
+};
+use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use lending_vault::msg::{MoneyMarketExecuteMsg, MoneyMarketQueryMsg, UserDepositResponse};
+
+const OWNER: &str = "creator";
+const ACCOUNTS: &str = "accounts-contract";
+const DENOM: &str = "ujuno";
+
+const DEPOSITS: Map<(&Addr, &str), Uint128> = Map::new("deposits");
+const STRATEGIES: Map<&str, StrategyParams> = Map::new("strategies");
+
+/// Money market paying no interest
+fn contract_money_market() -> Box<dyn Contract<Empty>> {
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: MoneyMarketExecuteMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            MoneyMarketExecuteMsg::Deposit {} => {
+                for coin in info.funds.iter() {
+                    DEPOSITS.update(
+                        deps.storage,
+                        (&info.sender, &coin.denom),
+                        |v| -> StdResult<_> { Ok(v.unwrap_or_default() + coin.amount) },
+                    )?;
+                }
+                Ok(Response::default())
+            }
+            MoneyMarketExecuteMsg::Withdraw { denom, amount } => {
+                let available = DEPOSITS
+                    .may_load(deps.storage, (&info.sender, &denom))?
+                    .unwrap_or_default();
+                let amount = amount.unwrap_or(available);
+                let remaining = available
+                    .checked_sub(amount)
+                    .map_err(|_| StdError::generic_err("Insufficient deposit"))?;
+                DEPOSITS.save(deps.storage, (&info.sender, &denom), &remaining)?;
+                Ok(Response::default().add_message(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: coins(amount.u128(), denom),
+                }))
+            }
+        }
+    }
+    fn query(deps: Deps, _env: Env, msg: MoneyMarketQueryMsg) -> StdResult<Binary> {
+        match msg {
+            MoneyMarketQueryMsg::UserDeposit { user, denom } => {
+                let amount = DEPOSITS
+                    .may_load(deps.storage, (&Addr::unchecked(user), &denom))?
+                    .unwrap_or_default();
+                to_binary(&UserDepositResponse {
+                    denom,
+                    amount_scaled: amount,
+                    amount,
+                })
+            }
+        }
+    }
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+/// Registrar answering the config & strategy queries, with strategies set by the owner
+fn contract_registrar() -> Box<dyn Contract<Empty>> {
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: angel_core::msgs::registrar::ExecuteMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            angel_core::msgs::registrar::ExecuteMsg::StrategyAdd {
+                strategy_key,
+                strategy,
+            } => {
+                STRATEGIES.save(deps.storage, &strategy_key, &strategy)?;
+                Ok(Response::default())
+            }
+            _ => Err(StdError::generic_err("Not supported")),
+        }
+    }
+    fn query(deps: Deps, _env: Env, msg: RegistrarQueryMsg) -> StdResult<Binary> {
+        match msg {
+            RegistrarQueryMsg::Config {} => to_binary(&ConfigResponse {
+                owner: OWNER.to_string(),
+                version: "1.0.0".to_string(),
+                treasury: "treasury".to_string(),
+                rebalance: RebalanceDetails::default(),
+                split_to_liquid: SplitDetails::default(),
+                accepted_tokens: AcceptedTokens::default(),
+                axelar_gateway: "axelar-gateway".to_string(),
+                axelar_ibc_channel: "channel-1".to_string(),
+                axelar_chain_id: "juno".to_string(),
+            }),
+            RegistrarQueryMsg::ConfigExtension {} => to_binary(&ConfigExtensionResponse {
+                cw3_code: None,
+                cw4_code: None,
+                subdao_gov_code: None,
+                subdao_cw20_token_code: None,
+                subdao_bonding_token_code: None,
+                subdao_cw900_code: None,
+                subdao_distributor_code: None,
+                donation_match_code: None,
+                halo_token: None,
+                halo_token_lp_contract: None,
+                gov_contract: None,
+                accounts_contract: Some(ACCOUNTS.to_string()),
+                index_fund: None,
+                donation_match_charites_contract: None,
+                collector_addr: "collector".to_string(),
+                charity_shares_contract: None,
+                swap_factory: None,
+                applications_review: "applications-review".to_string(),
+                swaps_router: None,
+                accounts_settings_controller: None,
+            }),
+            RegistrarQueryMsg::Strategy { strategy_key } => to_binary(&StrategyDetailResponse {
+                strategy: STRATEGIES.load(deps.storage, &strategy_key)?,
+            }),
+            _ => Err(StdError::generic_err("Not supported")),
+        }
+    }
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn contract_router() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    ))
+}
+
+fn contract_lending_vault() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        lending_vault::contract::execute,
+        lending_vault::contract::instantiate,
+        lending_vault::contract::query,
+    ))
+}
+
+fn instantiate_vault(
+    app: &mut App,
+    code_id: u64,
+    router: &Addr,
+    registrar: &Addr,
+    money_market: &Addr,
+    acct_type: AccountType,
+) -> Addr {
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(OWNER),
+        &lending_vault::msg::InstantiateMsg {
+            ibc_host: router.to_string(),
+            ibc_controller: "ibc-controller".to_string(),
+            ap_tax_rate: Decimal::percent(10),
+            acct_type,
+            sibling_vault: None,
+            registrar_contract: registrar.to_string(),
+            keeper: "keeper".to_string(),
+            tax_collector: "tax-collector".to_string(),
+            money_market: money_market.to_string(),
+            input_denom: DENOM.to_string(),
+            minimum_initial_deposit: Uint128::from(100_u128),
+            name: "Lending vault token".to_string(),
+            symbol: "LVT".to_string(),
+            decimals: 6,
+        },
+        &[],
+        "lending-vault",
+        None,
+    )
+    .unwrap()
+}
+
+fn query_vt_balance(app: &App, vault: &Addr, endowment_id: u32) -> Uint128 {
+    app.wrap()
+        .query_wasm_smart(
+            vault,
+            &lending_vault::msg::QueryMsg::Balance { endowment_id },
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_reinvest_to_locked_across_strategies() {
+    let mut app = AppBuilder::new().build(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(ACCOUNTS), coins(1_000, DENOM))
+            .unwrap();
+    });
+
+    let registrar_code_id = app.store_code(contract_registrar());
+    let registrar = app
+        .instantiate_contract(
+            registrar_code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "registrar",
+            None,
+        )
+        .unwrap();
+    let money_market_code_id = app.store_code(contract_money_market());
+    let money_market = app
+        .instantiate_contract(
+            money_market_code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "money-market",
+            None,
+        )
+        .unwrap();
+    let router_code_id = app.store_code(contract_router());
+    let router = app
+        .instantiate_contract(
+            router_code_id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg {
+                registrar_contract: registrar.to_string(),
+            },
+            &[],
+            "vault-router",
+            None,
+        )
+        .unwrap();
+
+    // strategy-1 only has a liquid vault & strategy-2 only has a locked vault
+    let vault_code_id = app.store_code(contract_lending_vault());
+    let liquid_vault = instantiate_vault(
+        &mut app,
+        vault_code_id,
+        &router,
+        &registrar,
+        &money_market,
+        AccountType::Liquid,
+    );
+    let locked_vault = instantiate_vault(
+        &mut app,
+        vault_code_id,
+        &router,
+        &registrar,
+        &money_market,
+        AccountType::Locked,
+    );
+    for (strategy_key, liquid_addr, locked_addr) in [
+        ("strategy-1", Some(liquid_vault.clone()), None),
+        ("strategy-2", None, Some(locked_vault.clone())),
+    ] {
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            registrar.clone(),
+            &angel_core::msgs::registrar::ExecuteMsg::StrategyAdd {
+                strategy_key: strategy_key.to_string(),
+                strategy: StrategyParams {
+                    approval_state: StrategyApprovalState::Approved,
+                    locale: StrategyLocale::Native,
+                    chain: "juno".to_string(),
+                    input_denom: DENOM.to_string(),
+                    locked_addr,
+                    liquid_addr,
+                },
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // Endowment #1 holds 1000 vault tokens in the strategy-1 liquid vault
+    app.send_tokens(
+        Addr::unchecked(ACCOUNTS),
+        router.clone(),
+        &coins(1_000, DENOM),
+    )
+    .unwrap();
+    app.execute_contract(
+        router.clone(),
+        liquid_vault.clone(),
+        &lending_vault::msg::ExecuteMsg::Deposit { endowment_id: 1 },
+        &coins(1_000, DENOM),
+    )
+    .unwrap();
+    assert_eq!(
+        query_vt_balance(&app, &liquid_vault, 1),
+        Uint128::from(1_000_u128)
+    );
+
+    // Reinvest 400 of them into the strategy-2 locked vault
+    app.execute_contract(
+        Addr::unchecked(ACCOUNTS),
+        router.clone(),
+        &ExecuteMsg::ReinvestToLocked {
+            action: VaultActionData {
+                destination_chain: "juno".to_string(),
+                strategy_id: "strategy-1".to_string(),
+                selector: "reinvest_to_locked".to_string(),
+                account_ids: vec![1],
+                token: DENOM.to_string(),
+                lock_amt: Uint128::zero(),
+                liq_amt: Uint128::from(400_u128),
+            },
+            target_strategy: "strategy-2".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_vt_balance(&app, &liquid_vault, 1),
+        Uint128::from(600_u128)
+    );
+    assert_eq!(
+        query_vt_balance(&app, &locked_vault, 1),
+        Uint128::from(400_u128)
+    );
+    // The Router only passes the redeemed tokens through
+    assert_eq!(
+        app.wrap().query_balance(&router, DENOM).unwrap().amount,
+        Uint128::zero()
+    );
+}
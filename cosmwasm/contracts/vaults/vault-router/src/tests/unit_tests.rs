@@ -5,10 +5,11 @@ use crate::{
 };
 use angel_core::errors::core::ContractError;
 use angel_core::msgs::vault_router::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use angel_core::structs::VaultActionData;
 use cosmwasm_std::{
-    from_binary,
+    coins, from_binary,
     testing::{mock_env, mock_info},
-    Addr,
+    to_binary, Addr, CosmosMsg, Uint128, WasmMsg,
 };
 
 const REGISTRAR_CONTRACT: &str = "registrar-contract";
@@ -78,3 +79,223 @@ fn test_udpate_config() {
         Addr::unchecked("new-registrar-contract")
     );
 }
+
+#[test]
+fn test_reinvest_to_locked() {
+    let mut deps = mock_dependencies(&coins(500, "ujuno"));
+
+    // Instantiate the contract
+    let instantiate_msg = InstantiateMsg {
+        registrar_contract: REGISTRAR_CONTRACT.to_string(),
+    };
+    let info = mock_info(OWNER, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+    let action = VaultActionData {
+        destination_chain: "juno".to_string(),
+        strategy_id: "strategy-1".to_string(),
+        selector: "reinvest_to_locked".to_string(),
+        account_ids: vec![1],
+        token: "ujuno".to_string(),
+        lock_amt: Uint128::zero(),
+        liq_amt: Uint128::from(100_u128),
+    };
+
+    // Only the accounts contract can execute this entry
+    let info = mock_info("anyone", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ReinvestToLocked {
+            action: action.clone(),
+            target_strategy: "strategy-2".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Fail to reinvest more than the endowment holds in the liquid vault
+    let info = mock_info("accounts_contract_addr", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ReinvestToLocked {
+            action: VaultActionData {
+                liq_amt: Uint128::from(1001_u128),
+                ..action.clone()
+            },
+            target_strategy: "strategy-2".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::BalanceTooSmall {});
+
+    // Same strategy: liquid vault reinvests straight into its locked sibling
+    let info = mock_info("accounts_contract_addr", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ReinvestToLocked {
+            action: action.clone(),
+            target_strategy: "strategy-1".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "strategy-1-liquid-contract".to_string(),
+            msg: to_binary(&angel_core::msgs::vault::ExecuteMsg::ReinvestToLocked {
+                endowment_id: 1,
+                amount: Uint128::from(100_u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // Different strategies: redeem to the router, then deposit into the target locked vault
+    let info = mock_info("accounts_contract_addr", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ReinvestToLocked {
+            action: action.clone(),
+            target_strategy: "strategy-2".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "strategy-1-liquid-contract".to_string(),
+            msg: to_binary(&angel_core::msgs::vault::ExecuteMsg::RedeemForReinvest {
+                endowment_id: 1,
+                amount: Uint128::from(100_u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: mock_env().contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::ReinvestToLockedFinalize {
+                action: action.clone(),
+                target_strategy: "strategy-2".to_string(),
+                token_bal_before: Uint128::from(500_u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // Only the contract itself can finalize the reinvestment
+    let info = mock_info("anyone", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ReinvestToLockedFinalize {
+            action: action.clone(),
+            target_strategy: "strategy-2".to_string(),
+            token_bal_before: Uint128::from(200_u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info(mock_env().contract.address.as_str(), &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ReinvestToLockedFinalize {
+            action,
+            target_strategy: "strategy-2".to_string(),
+            token_bal_before: Uint128::from(200_u128),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "strategy-2-locked-contract".to_string(),
+            msg: to_binary(&angel_core::msgs::vault::ExecuteMsg::Deposit { endowment_id: 1 })
+                .unwrap(),
+            funds: coins(300, "ujuno"),
+        })
+    );
+}
+
+#[test]
+fn test_claim() {
+    let mut deps = mock_dependencies(&[]);
+
+    // Instantiate the contract
+    let instantiate_msg = InstantiateMsg {
+        registrar_contract: REGISTRAR_CONTRACT.to_string(),
+    };
+    let info = mock_info(OWNER, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+    let action = VaultActionData {
+        destination_chain: "juno".to_string(),
+        strategy_id: "strategy-1".to_string(),
+        selector: "claim".to_string(),
+        account_ids: vec![1],
+        token: "ujuno".to_string(),
+        lock_amt: Uint128::zero(),
+        liq_amt: Uint128::zero(),
+    };
+
+    // Fail to claim for multiple endowments at once
+    let info = mock_info("accounts_contract_addr", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::Claim {
+            action: VaultActionData {
+                account_ids: vec![1, 2],
+                ..action.clone()
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidInputs {});
+
+    // Claim from both the locked & liquid vaults of the strategy
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Claim { action },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    for (msg, vault) in res
+        .messages
+        .iter()
+        .zip(["strategy-1-locked-contract", "strategy-1-liquid-contract"])
+    {
+        assert_eq!(
+            msg.msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: vault.to_string(),
+                msg: to_binary(&angel_core::msgs::vault::UnbondingExecuteMsg::Claim {
+                    endowment_id: 1
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+}
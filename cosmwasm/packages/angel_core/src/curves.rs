@@ -1,23 +1,23 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal as StdDecimal, Uint128};
+use cosmwasm_std::{Decimal as StdDecimal, StdError, StdResult, Uint128};
 use integer_cbrt::IntegerCubeRoot;
 use integer_sqrt::IntegerSquareRoot;
-use rust_decimal::{prelude::ToPrimitive, Decimal};
+use rust_decimal::{prelude::ToPrimitive, Decimal, MathematicalOps, RoundingStrategy};
 use std::str::FromStr;
 
 pub trait Curve {
     /// Returns the spot price given the supply.
     /// `f(x)` from the README
-    fn spot_price(&self, supply: Uint128) -> StdDecimal;
+    fn spot_price(&self, supply: Uint128) -> StdResult<StdDecimal>;
 
     /// Returns the total price paid up to purchase supply tokens (integral)
     /// `F(x)` from the README
-    fn reserve(&self, supply: Uint128) -> Uint128;
+    fn reserve(&self, supply: Uint128) -> StdResult<Uint128>;
 
     /// Inverse of reserve. Returns how many tokens would be issued
     /// with a total paid amount of reserve.
     /// `F^-1(x)` from the README
-    fn supply(&self, reserve: Uint128) -> Uint128;
+    fn supply(&self, reserve: Uint128) -> StdResult<Uint128>;
 }
 
 /// decimal returns an object = num * 10 ^ -scale
@@ -28,9 +28,15 @@ pub fn decimal<T: Into<u128>>(num: T, scale: u32) -> Decimal {
 }
 
 /// StdDecimal stores as a u128 with 18 decimal points of precision
-fn decimal_to_std(x: Decimal) -> StdDecimal {
+fn decimal_to_std(x: Decimal) -> StdResult<StdDecimal> {
     // this seems straight-forward (if inefficient), converting via string representation
-    StdDecimal::from_str(&x.to_string()).unwrap()
+    // (dropping the digits StdDecimal can't hold)
+    let x = x.round_dp_with_strategy(18, RoundingStrategy::ToZero);
+    StdDecimal::from_str(&x.to_string())
+}
+
+fn curve_overflow() -> StdError {
+    StdError::generic_err("Bonding curve calculation overflow")
 }
 
 /// spot price is always a constant value
@@ -48,20 +54,20 @@ impl Constant {
 impl Curve for Constant {
     // we need to normalize value with the reserve decimal places
     // (eg 0.1 value would return 100_000 if reserve was uHALO)
-    fn spot_price(&self, _supply: Uint128) -> StdDecimal {
+    fn spot_price(&self, _supply: Uint128) -> StdResult<StdDecimal> {
         // f(x) = self.value
         decimal_to_std(self.value)
     }
 
     /// Returns total number of reserve tokens needed to purchase a given number of supply tokens.
     /// Note that both need to be normalized.
-    fn reserve(&self, supply: Uint128) -> Uint128 {
+    fn reserve(&self, supply: Uint128) -> StdResult<Uint128> {
         // f(x) = supply * self.value
         let reserve = self.normalize.from_supply(supply) * self.value;
         self.normalize.to_reserve(reserve)
     }
 
-    fn supply(&self, reserve: Uint128) -> Uint128 {
+    fn supply(&self, reserve: Uint128) -> StdResult<Uint128> {
         // f(x) = reserve / self.value
         let supply = self.normalize.from_reserve(reserve) / self.value;
         self.normalize.to_supply(supply)
@@ -81,13 +87,13 @@ impl Linear {
 }
 
 impl Curve for Linear {
-    fn spot_price(&self, supply: Uint128) -> StdDecimal {
+    fn spot_price(&self, supply: Uint128) -> StdResult<StdDecimal> {
         // f(x) = supply * self.value
         let out = self.normalize.from_supply(supply) * self.slope;
         decimal_to_std(out)
     }
 
-    fn reserve(&self, supply: Uint128) -> Uint128 {
+    fn reserve(&self, supply: Uint128) -> StdResult<Uint128> {
         // f(x) = self.slope * supply * supply / 2
         let normalized = self.normalize.from_supply(supply);
         let square = normalized * normalized;
@@ -96,7 +102,7 @@ impl Curve for Linear {
         self.normalize.to_reserve(reserve)
     }
 
-    fn supply(&self, reserve: Uint128) -> Uint128 {
+    fn supply(&self, reserve: Uint128) -> StdResult<Uint128> {
         // f(x) = (2 * reserve / self.slope) ^ 0.5
         // note: use addition here to optimize 2* operation
         let square = self.normalize.from_reserve(reserve + reserve) / self.slope;
@@ -106,6 +112,7 @@ impl Curve for Linear {
 }
 
 /// spot_price is slope * (supply)^0.5
+/// Note: `power` is not used by the math, use `Power` for other exponents
 pub struct SquareRoot {
     pub slope: Decimal,
     pub power: Decimal,
@@ -123,14 +130,14 @@ impl SquareRoot {
 }
 
 impl Curve for SquareRoot {
-    fn spot_price(&self, supply: Uint128) -> StdDecimal {
+    fn spot_price(&self, supply: Uint128) -> StdResult<StdDecimal> {
         // f(x) = self.slope * supply^(power)
         let square = self.normalize.from_supply(supply);
         let root = square_root(square);
         decimal_to_std(root * self.slope)
     }
 
-    fn reserve(&self, supply: Uint128) -> Uint128 {
+    fn reserve(&self, supply: Uint128) -> StdResult<Uint128> {
         // f(x) = self.slope * supply * supply^(power) / 1.5
         let normalized = self.normalize.from_supply(supply);
         let root = square_root(normalized);
@@ -138,7 +145,7 @@ impl Curve for SquareRoot {
         self.normalize.to_reserve(reserve)
     }
 
-    fn supply(&self, reserve: Uint128) -> Uint128 {
+    fn supply(&self, reserve: Uint128) -> StdResult<Uint128> {
         // f(x) = (1.5 * reserve / self.slope) ^ (2/3)
         let base = self.normalize.from_reserve(reserve) * Decimal::new(15, 1) / self.slope;
         let squared = base * base;
@@ -147,6 +154,304 @@ impl Curve for SquareRoot {
     }
 }
 
+/// spot_price is slope * (supply)^power
+pub struct Power {
+    pub slope: Decimal,
+    pub power: Decimal,
+    pub normalize: DecimalPlaces,
+}
+
+impl Power {
+    pub fn new(slope: Decimal, power: Decimal, normalize: DecimalPlaces) -> Self {
+        Self {
+            slope,
+            power,
+            normalize,
+        }
+    }
+}
+
+impl Curve for Power {
+    fn spot_price(&self, supply: Uint128) -> StdResult<StdDecimal> {
+        // f(x) = self.slope * supply^(power)
+        let normalized = self.normalize.from_supply(supply);
+        let price = normalized
+            .checked_powd(self.power)
+            .and_then(|pow| pow.checked_mul(self.slope))
+            .ok_or_else(curve_overflow)?;
+        decimal_to_std(price)
+    }
+
+    fn reserve(&self, supply: Uint128) -> StdResult<Uint128> {
+        // f(x) = self.slope * supply^(power + 1) / (power + 1)
+        let exponent = self.power + Decimal::ONE;
+        let normalized = self.normalize.from_supply(supply);
+        let reserve = normalized
+            .checked_powd(exponent)
+            .and_then(|pow| pow.checked_mul(self.slope))
+            .and_then(|area| area.checked_div(exponent))
+            .ok_or_else(curve_overflow)?;
+        self.normalize.to_reserve(reserve)
+    }
+
+    fn supply(&self, reserve: Uint128) -> StdResult<Uint128> {
+        // f(x) = ((power + 1) * reserve / self.slope) ^ (1 / (power + 1))
+        let exponent = self.power + Decimal::ONE;
+        let supply = self
+            .normalize
+            .from_reserve(reserve)
+            .checked_mul(exponent)
+            .and_then(|area| area.checked_div(self.slope))
+            .and_then(|base| base.checked_powd(Decimal::ONE / exponent))
+            .ok_or_else(curve_overflow)?;
+        self.normalize.to_supply(supply)
+    }
+}
+
+/// spot_price is initial_price * e^(growth * supply)
+pub struct Exponential {
+    pub initial_price: Decimal,
+    pub growth: Decimal,
+    pub normalize: DecimalPlaces,
+}
+
+impl Exponential {
+    pub fn new(initial_price: Decimal, growth: Decimal, normalize: DecimalPlaces) -> Self {
+        Self {
+            initial_price,
+            growth,
+            normalize,
+        }
+    }
+
+    /// e^(self.growth * supply)
+    fn growth_at(&self, supply: Uint128) -> StdResult<Decimal> {
+        self.normalize
+            .from_supply(supply)
+            .checked_mul(self.growth)
+            .and_then(|exponent| exponent.checked_exp())
+            .ok_or_else(curve_overflow)
+    }
+}
+
+impl Curve for Exponential {
+    fn spot_price(&self, supply: Uint128) -> StdResult<StdDecimal> {
+        // f(x) = self.initial_price * e^(self.growth * supply)
+        let price = self
+            .growth_at(supply)?
+            .checked_mul(self.initial_price)
+            .ok_or_else(curve_overflow)?;
+        decimal_to_std(price)
+    }
+
+    fn reserve(&self, supply: Uint128) -> StdResult<Uint128> {
+        // f(x) = self.initial_price * (e^(self.growth * supply) - 1) / self.growth
+        let reserve = (self.growth_at(supply)? - Decimal::ONE)
+            .checked_mul(self.initial_price)
+            .and_then(|area| area.checked_div(self.growth))
+            .ok_or_else(curve_overflow)?;
+        self.normalize.to_reserve(reserve)
+    }
+
+    fn supply(&self, reserve: Uint128) -> StdResult<Uint128> {
+        // f(x) = ln(1 + reserve * self.growth / self.initial_price) / self.growth
+        let supply = self
+            .normalize
+            .from_reserve(reserve)
+            .checked_mul(self.growth)
+            .and_then(|area| area.checked_div(self.initial_price))
+            .and_then(|ratio| ratio.checked_add(Decimal::ONE))
+            .and_then(|base| base.checked_ln())
+            .and_then(|ln| ln.checked_div(self.growth))
+            .ok_or_else(curve_overflow)?;
+        self.normalize.to_supply(supply)
+    }
+}
+
+/// spot_price is the logistic function max_price / (1 + e^(-steepness * (supply - midpoint))),
+/// with the supply capped at supply_cap
+pub struct Sigmoid {
+    pub max_price: Decimal,
+    pub steepness: Decimal,
+    pub midpoint: Decimal,
+    pub supply_cap: Decimal,
+    pub normalize: DecimalPlaces,
+}
+
+impl Sigmoid {
+    pub fn new(
+        max_price: Decimal,
+        steepness: Decimal,
+        midpoint: Decimal,
+        supply_cap: Decimal,
+        normalize: DecimalPlaces,
+    ) -> Self {
+        Self {
+            max_price,
+            steepness,
+            midpoint,
+            supply_cap,
+            normalize,
+        }
+    }
+
+    fn capped_supply(&self, supply: Uint128) -> Decimal {
+        self.normalize.from_supply(supply).min(self.supply_cap)
+    }
+}
+
+impl Curve for Sigmoid {
+    fn spot_price(&self, supply: Uint128) -> StdResult<StdDecimal> {
+        // f(x) = self.max_price / (1 + e^(-self.steepness * (supply - self.midpoint)))
+        // written as self.max_price * e^(-ln(1 + e^(...))) so large exponents don't overflow
+        let exponent = self.steepness * (self.midpoint - self.capped_supply(supply));
+        decimal_to_std(self.max_price * exp_negative(-softplus(exponent)?)?)
+    }
+
+    fn reserve(&self, supply: Uint128) -> StdResult<Uint128> {
+        // f(x) = self.max_price / self.steepness *
+        //     (ln(1 + e^(self.steepness * (supply - self.midpoint))) - ln(1 + e^(-self.steepness * self.midpoint)))
+        let at_supply = softplus(self.steepness * (self.capped_supply(supply) - self.midpoint))?;
+        let at_zero = softplus(-self.steepness * self.midpoint)?;
+        let reserve = self.max_price * (at_supply - at_zero) / self.steepness;
+        self.normalize.to_reserve(reserve)
+    }
+
+    fn supply(&self, reserve: Uint128) -> StdResult<Uint128> {
+        // f(x) = self.midpoint + ln(e^y - 1) / self.steepness,
+        //     with y = reserve * self.steepness / self.max_price + ln(1 + e^(-self.steepness * self.midpoint))
+        if reserve.is_zero() {
+            return Ok(Uint128::zero());
+        }
+        let at_zero = softplus(-self.steepness * self.midpoint)?;
+        let y = self
+            .normalize
+            .from_reserve(reserve)
+            .checked_mul(self.steepness)
+            .and_then(|area| area.checked_div(self.max_price))
+            .and_then(|area| area.checked_add(at_zero))
+            .ok_or_else(curve_overflow)?;
+        let supply = (self.midpoint + inverse_softplus(y)? / self.steepness).max(Decimal::ZERO);
+        // no more than the capped supply can be issued, so the reserve can't be worth more
+        let supply = self.normalize.to_supply(supply)?;
+        let supply_cap = self.normalize.to_supply(self.supply_cap)?;
+        if supply > supply_cap {
+            return Err(StdError::generic_err("Bonding curve supply cap exceeded"));
+        }
+        Ok(supply)
+    }
+}
+
+/// spot_price is interpolated linearly between (supply, price) breakpoints.
+/// The first breakpoint is at supply 0, and the price stays flat after the last one
+pub struct PiecewiseLinear {
+    pub points: Vec<(Decimal, Decimal)>,
+    pub normalize: DecimalPlaces,
+}
+
+impl PiecewiseLinear {
+    pub fn new(points: Vec<(Decimal, Decimal)>, normalize: DecimalPlaces) -> Self {
+        Self { points, normalize }
+    }
+
+    /// Segments between breakpoints, as (start supply, start price, width, slope).
+    /// The last one is open ended & flat.
+    fn segments(&self) -> impl Iterator<Item = (Decimal, Decimal, Option<Decimal>, Decimal)> + '_ {
+        self.points
+            .iter()
+            .enumerate()
+            .map(|(i, (supply, price))| match self.points.get(i + 1) {
+                Some((next_supply, next_price)) => {
+                    let width = next_supply - supply;
+                    (*supply, *price, Some(width), (next_price - price) / width)
+                }
+                None => (*supply, *price, None, Decimal::ZERO),
+            })
+    }
+}
+
+impl Curve for PiecewiseLinear {
+    fn spot_price(&self, supply: Uint128) -> StdResult<StdDecimal> {
+        // f(x) = start price + slope * (supply - start supply), in the segment holding supply
+        let normalized = self.normalize.from_supply(supply);
+        let (start, price, _, slope) = self
+            .segments()
+            .take_while(|(start, ..)| *start <= normalized)
+            .last()
+            .unwrap();
+        decimal_to_std(price + slope * (normalized - start))
+    }
+
+    fn reserve(&self, supply: Uint128) -> StdResult<Uint128> {
+        // f(x) = sum of the areas under each segment, up to supply
+        let normalized = self.normalize.from_supply(supply);
+        let mut reserve = Decimal::ZERO;
+        for (start, price, width, slope) in self.segments() {
+            if normalized <= start {
+                break;
+            }
+            let covered = match width {
+                Some(width) => width.min(normalized - start),
+                None => normalized - start,
+            };
+            reserve += price * covered + slope * covered * covered * Decimal::new(5, 1);
+        }
+        self.normalize.to_reserve(reserve)
+    }
+
+    fn supply(&self, reserve: Uint128) -> StdResult<Uint128> {
+        // f(x) = start supply + width covered by the rest of the reserve, in the segment it runs out
+        let mut remaining = self.normalize.from_reserve(reserve);
+        let mut supply = Decimal::ZERO;
+        for (start, price, width, slope) in self.segments() {
+            if let Some(width) = width {
+                let area = price * width + slope * width * width * Decimal::new(5, 1);
+                if remaining >= area {
+                    remaining -= area;
+                    supply = start + width;
+                    continue;
+                }
+            }
+            // solve price * d + slope * d^2 / 2 = remaining for d
+            let covered = if slope.is_zero() {
+                remaining / price
+            } else {
+                let discriminant = price * price + Decimal::TWO * slope * remaining;
+                (discriminant.sqrt().ok_or_else(curve_overflow)? - price) / slope
+            };
+            supply = start + covered;
+            break;
+        }
+        self.normalize.to_supply(supply)
+    }
+}
+
+/// ln(1 + e^x), written to not overflow for large x
+fn softplus(x: Decimal) -> StdResult<Decimal> {
+    let ln = (Decimal::ONE + exp_negative(-x.abs())?)
+        .checked_ln()
+        .ok_or_else(curve_overflow)?;
+    Ok(x.max(Decimal::ZERO) + ln)
+}
+
+/// ln(e^y - 1) for y > 0, the inverse of softplus
+fn inverse_softplus(y: Decimal) -> StdResult<Decimal> {
+    match (Decimal::ONE - exp_negative(-y)?).checked_ln() {
+        Some(ln) => Ok(y + ln),
+        // e^y - 1 is y for tiny y
+        None => y.checked_ln().ok_or_else(curve_overflow),
+    }
+}
+
+/// e^x for x <= 0. Below -64 the result is smaller than 10^-27, which rounds to 0 at the
+/// precision the curves work with, and is past what `checked_exp` can compute
+fn exp_negative(x: Decimal) -> StdResult<Decimal> {
+    if x < Decimal::from(-64) {
+        return Ok(Decimal::ZERO);
+    }
+    x.checked_exp().ok_or_else(curve_overflow)
+}
+
 // we multiply by 10^18, turn to int, take square root, then divide by 10^9 as we convert back to decimal
 fn square_root(square: Decimal) -> Decimal {
     // must be even
@@ -196,18 +501,16 @@ impl DecimalPlaces {
         }
     }
 
-    pub fn to_reserve(self, reserve: Decimal) -> Uint128 {
+    pub fn to_reserve(self, reserve: Decimal) -> StdResult<Uint128> {
         let factor = decimal(10u128.pow(self.reserve), 0);
-        let out = reserve * factor;
-        // TODO: execute overflow better? Result?
-        out.floor().to_u128().unwrap().into()
+        let out = reserve.checked_mul(factor).ok_or_else(curve_overflow)?;
+        Ok(out.floor().to_u128().ok_or_else(curve_overflow)?.into())
     }
 
-    pub fn to_supply(self, supply: Decimal) -> Uint128 {
+    pub fn to_supply(self, supply: Decimal) -> StdResult<Uint128> {
         let factor = decimal(10u128.pow(self.supply), 0);
-        let out = supply * factor;
-        // TODO: execute overflow better? Result?
-        out.floor().to_u128().unwrap().into()
+        let out = supply.checked_mul(factor).ok_or_else(curve_overflow)?;
+        Ok(out.floor().to_u128().ok_or_else(curve_overflow)?.into())
     }
 
     pub fn from_supply(&self, supply: Uint128) -> Decimal {
@@ -233,15 +536,15 @@ mod tests {
         // spot price is always 1.5 HALO
         assert_eq!(
             StdDecimal::percent(150),
-            curve.spot_price(Uint128::new(123))
+            curve.spot_price(Uint128::new(123)).unwrap()
         );
 
         // if we have 30 STEP, we should have 45 HALO
-        let reserve = curve.reserve(Uint128::new(30_000_000_000));
+        let reserve = curve.reserve(Uint128::new(30_000_000_000)).unwrap();
         assert_eq!(Uint128::new(45_000_000), reserve);
 
         // if we have 36 HALO, we should have 24 STEP
-        let supply = curve.supply(Uint128::new(36_000_000));
+        let supply = curve.supply(Uint128::new(36_000_000)).unwrap();
         assert_eq!(Uint128::new(24_000_000_000), supply);
     }
 
@@ -256,33 +559,33 @@ mod tests {
         // spot price is 0.1 with 1 UST supply
         assert_eq!(
             StdDecimal::permille(100),
-            curve.spot_price(Uint128::new(100))
+            curve.spot_price(Uint128::new(100)).unwrap()
         );
         // spot price is 1.7 with 17 UST supply
         assert_eq!(
             StdDecimal::permille(1700),
-            curve.spot_price(Uint128::new(1700))
+            curve.spot_price(Uint128::new(1700)).unwrap()
         );
         // spot price is 0.212 with 2.12 UST supply
         assert_eq!(
             StdDecimal::permille(212),
-            curve.spot_price(Uint128::new(212))
+            curve.spot_price(Uint128::new(212)).unwrap()
         );
 
         // if we have 10 UST, we should have 5 LUNA
-        let reserve = curve.reserve(Uint128::new(1000));
+        let reserve = curve.reserve(Uint128::new(1000)).unwrap();
         assert_eq!(Uint128::new(500_000_000), reserve);
         // if we have 20 UST, we should have 20 LUNA
-        let reserve = curve.reserve(Uint128::new(2000));
+        let reserve = curve.reserve(Uint128::new(2000)).unwrap();
         assert_eq!(Uint128::new(2_000_000_000), reserve);
 
         // if we have 1.25 LUNA, we should have 5 UST
-        let supply = curve.supply(Uint128::new(125_000_000));
+        let supply = curve.supply(Uint128::new(125_000_000)).unwrap();
         assert_eq!(Uint128::new(500), supply);
         // test square root rounding
         // TODO: test when supply has many more decimal places than reserve
         // if we have 1.11 LUNA, we should have 4.7116875957... UST
-        let supply = curve.supply(Uint128::new(111_000_000));
+        let supply = curve.supply(Uint128::new(111_000_000)).unwrap();
         assert_eq!(Uint128::new(471), supply);
     }
 
@@ -297,38 +600,257 @@ mod tests {
         // spot price is 0.35 with 1 CS supply
         assert_eq!(
             StdDecimal::percent(35),
-            curve.spot_price(Uint128::new(1_000_000))
+            curve.spot_price(Uint128::new(1_000_000)).unwrap()
         );
         // spot price is 3.5 with 100 CS supply
         assert_eq!(
             StdDecimal::percent(350),
-            curve.spot_price(Uint128::new(100_000_000))
+            curve.spot_price(Uint128::new(100_000_000)).unwrap()
         );
         // spot price should be 23.478713763747788 with 4500 CS supply (test rounding and reporting here)
         // rounds off around 8-9 sig figs (note diff for last points)
         assert_eq!(
             StdDecimal::from_ratio(2347871365u128, 100_000_000u128),
-            curve.spot_price(Uint128::new(4_500_000_000))
+            curve.spot_price(Uint128::new(4_500_000_000)).unwrap()
         );
 
         // if we have 1 CS, we should have 0.2333333333333 HALO
-        let reserve = curve.reserve(Uint128::new(1_000_000));
+        let reserve = curve.reserve(Uint128::new(1_000_000)).unwrap();
         assert_eq!(Uint128::new(23), reserve);
         // if we have 100 CS, we should have 233.333333333 HALO
-        let reserve = curve.reserve(Uint128::new(100_000_000));
+        let reserve = curve.reserve(Uint128::new(100_000_000)).unwrap();
         assert_eq!(Uint128::new(23_333), reserve);
         // test rounding
         // if we have 235 CS, we should have 840.5790828021146 HALO
-        let reserve = curve.reserve(Uint128::new(235_000_000));
+        let reserve = curve.reserve(Uint128::new(235_000_000)).unwrap();
         assert_eq!(Uint128::new(84_057), reserve); // round down
 
         // // if we have 0.23 HALO, we should have 0.990453 CS (round down)
-        let supply = curve.supply(Uint128::new(23));
+        let supply = curve.supply(Uint128::new(23)).unwrap();
         assert_eq!(Uint128::new(990_000), supply);
         // if we have 840.58 HALO, we should have 235.000170 CS (round down)
-        let supply = curve.supply(Uint128::new(84058));
+        let supply = curve.supply(Uint128::new(84058)).unwrap();
         assert_eq!(Uint128::new(235_000_000), supply);
     }
 
-    // Idea: generic test that curve.supply(curve.reserve(supply)) == supply (or within some small rounding margin)
+    #[test]
+    fn power_curve() {
+        // supply is uCS (6) reserve is uHALO (6)
+        let normalize = DecimalPlaces::new(6, 6);
+        // f(x) = x^2
+        let curve = Power::new(decimal(1u128, 0), decimal(2u128, 0), normalize);
+
+        // spot price is 9 with 3 CS supply
+        assert_eq!(
+            StdDecimal::percent(900),
+            curve.spot_price(Uint128::new(3_000_000)).unwrap()
+        );
+        // if we have 3 CS, we should have 9 HALO
+        let reserve = curve.reserve(Uint128::new(3_000_000)).unwrap();
+        assert_eq!(Uint128::new(9_000_000), reserve);
+        // if we have 72 HALO, we should have 6 CS (the cube root approximation rounds down)
+        let supply = curve.supply(Uint128::new(72_000_000)).unwrap();
+        assert_eq!(Uint128::new(5_999_999), supply);
+
+        // the power is honoured: with 0.5 it matches the square root curve (to the last digit)
+        let power = Power::new(decimal(35u128, 2), decimal(5u128, 1), normalize);
+        let sqrt = SquareRoot::new(decimal(35u128, 2), decimal(5u128, 1), normalize);
+        assert_eq!(
+            sqrt.spot_price(Uint128::new(100_000_000)).unwrap(),
+            power.spot_price(Uint128::new(100_000_000)).unwrap() + StdDecimal::raw(1)
+        );
+
+        assert_round_trip(&curve, 1_000_000_000_000);
+        assert_round_trip(&power, 1_000_000_000_000);
+    }
+
+    #[test]
+    fn exponential_curve() {
+        // supply is uCS (6) reserve is uHALO (6)
+        let normalize = DecimalPlaces::new(6, 6);
+        // price starts at 1 HALO, growing by e every 10 CS
+        let curve = Exponential::new(decimal(1u128, 0), decimal(1u128, 1), normalize);
+
+        // spot price is 1 with no supply
+        assert_eq!(
+            StdDecimal::one(),
+            curve.spot_price(Uint128::zero()).unwrap()
+        );
+        // spot price is e with 10 CS supply
+        assert_eq!(
+            StdDecimal::from_str("2.718281828459045235").unwrap(),
+            curve.spot_price(Uint128::new(10_000_000)).unwrap()
+        );
+        // if we have 10 CS, we should have 17.18281828 HALO (round down)
+        let reserve = curve.reserve(Uint128::new(10_000_000)).unwrap();
+        assert_eq!(Uint128::new(17_182_818), reserve);
+        // if we have 17.182819 HALO, we should have 10 CS
+        let supply = curve.supply(Uint128::new(17_182_819)).unwrap();
+        assert_eq!(Uint128::new(10_000_000), supply);
+
+        assert_round_trip(&curve, 500_000_000);
+
+        // e^(0.1 * 10_000) is past what a decimal holds
+        let err = curve.reserve(Uint128::new(10_000_000_000)).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Bonding curve calculation overflow")
+        );
+    }
+
+    #[test]
+    fn sigmoid_curve() {
+        // supply is uCS (6) reserve is uHALO (6)
+        let normalize = DecimalPlaces::new(6, 6);
+        // price goes up to 2 HALO, passing 1 HALO at 10 CS. No more than 20 CS can be issued
+        let curve = Sigmoid::new(
+            decimal(2u128, 0),
+            decimal(1u128, 0),
+            decimal(10u128, 0),
+            decimal(20u128, 0),
+            normalize,
+        );
+
+        // spot price is 1 at the midpoint (rounding down the last digit)
+        assert_eq!(
+            StdDecimal::from_str("0.999999999999999999").unwrap(),
+            curve.spot_price(Uint128::new(10_000_000)).unwrap()
+        );
+        // spot price is close to 0 with no supply, and close to 2 at the cap
+        assert!(curve.spot_price(Uint128::zero()).unwrap() < StdDecimal::permille(1));
+        assert!(curve.spot_price(Uint128::new(20_000_000)).unwrap() > StdDecimal::permille(1999));
+        // the supply (and so the price & reserve) stop at the cap
+        assert_eq!(
+            curve.spot_price(Uint128::new(20_000_000)).unwrap(),
+            curve.spot_price(Uint128::new(30_000_000)).unwrap()
+        );
+        assert_eq!(
+            curve.reserve(Uint128::new(20_000_000)).unwrap(),
+            curve.reserve(Uint128::new(30_000_000)).unwrap()
+        );
+        // & buys worth more than the capped supply are rejected
+        let err = curve.supply(Uint128::new(1_000_000_000)).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Bonding curve supply cap exceeded")
+        );
+        let reserve_at_cap = curve.reserve(Uint128::new(20_000_000)).unwrap();
+        assert_eq!(
+            Uint128::new(20_000_000),
+            curve.supply(reserve_at_cap).unwrap()
+        );
+        curve
+            .supply(reserve_at_cap + Uint128::new(1_000))
+            .unwrap_err();
+
+        // the curve is symmetric around the midpoint, so the reserves below & above it
+        // (up to twice the midpoint) add up to 10 CS at the max price: 20 HALO
+        let lower = curve.reserve(Uint128::new(10_000_000)).unwrap();
+        let upper = curve.reserve(Uint128::new(20_000_000)).unwrap() - lower;
+        assert!((lower + upper).u128().abs_diff(20_000_000) <= 1);
+
+        assert_round_trip(&curve, 19_990_000);
+    }
+
+    #[test]
+    fn piecewise_linear_curve() {
+        // supply is uCS (6) reserve is uHALO (6)
+        let normalize = DecimalPlaces::new(6, 6);
+        // price goes from 1 HALO to 3 HALO over the first 10 CS, then stays flat
+        let curve = PiecewiseLinear::new(
+            vec![
+                (decimal(0u128, 0), decimal(1u128, 0)),
+                (decimal(10u128, 0), decimal(3u128, 0)),
+                (decimal(20u128, 0), decimal(3u128, 0)),
+            ],
+            normalize,
+        );
+
+        // spot price is interpolated between the breakpoints
+        assert_eq!(
+            StdDecimal::one(),
+            curve.spot_price(Uint128::zero()).unwrap()
+        );
+        assert_eq!(
+            StdDecimal::percent(200),
+            curve.spot_price(Uint128::new(5_000_000)).unwrap()
+        );
+        assert_eq!(
+            StdDecimal::percent(300),
+            curve.spot_price(Uint128::new(50_000_000)).unwrap()
+        );
+
+        // if we have 10 / 20 / 25 CS, we should have 20 / 50 / 65 HALO
+        assert_eq!(
+            Uint128::new(20_000_000),
+            curve.reserve(Uint128::new(10_000_000)).unwrap()
+        );
+        assert_eq!(
+            Uint128::new(50_000_000),
+            curve.reserve(Uint128::new(20_000_000)).unwrap()
+        );
+        assert_eq!(
+            Uint128::new(65_000_000),
+            curve.reserve(Uint128::new(25_000_000)).unwrap()
+        );
+        // and the other way around
+        assert_eq!(
+            Uint128::new(10_000_000),
+            curve.supply(Uint128::new(20_000_000)).unwrap()
+        );
+        assert_eq!(
+            Uint128::new(25_000_000),
+            curve.supply(Uint128::new(65_000_000)).unwrap()
+        );
+        // if we have 5 HALO, we should have (3^0.5 - 1) / 0.2 = 3.660254 CS (round down)
+        let supply = curve.supply(Uint128::new(5_000_000)).unwrap();
+        assert_eq!(Uint128::new(3_660_254), supply);
+
+        assert_round_trip(&curve, 1_000_000_000_000);
+    }
+
+    #[test]
+    fn round_trips() {
+        let normalize = DecimalPlaces::new(6, 6);
+        assert_round_trip(
+            &Constant::new(decimal(15u128, 1), normalize),
+            1_000_000_000_000,
+        );
+        assert_round_trip(
+            &Linear::new(decimal(1u128, 1), normalize),
+            1_000_000_000_000,
+        );
+    }
+
+    /// Property: supply(reserve(x)) gives back x for any x up to max_supply, give or take
+    /// the rounding down of the reserve to a whole unit (& of the approximations)
+    fn assert_round_trip(curve: &dyn Curve, max_supply: u128) {
+        // powers of 10 & a pseudo-random spread of supplies
+        let mut supplies: Vec<u128> = (0..=38)
+            .map(|exp| 10u128.pow(exp))
+            .take_while(|supply| *supply <= max_supply)
+            .collect();
+        let mut seed = 0x853c_49e6_748f_ea9b_u64;
+        for _ in 0..200 {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            supplies.push(seed as u128 % max_supply + 1);
+        }
+
+        for supply in supplies {
+            let reserve = curve.reserve(Uint128::new(supply)).unwrap();
+            let round_trip = curve.supply(reserve).unwrap().u128();
+            // supply issued for one more unit of reserve
+            let step = curve.supply(reserve + Uint128::one()).unwrap().u128() - round_trip;
+            let tolerance = step + 1 + supply / 100_000_000;
+            assert!(
+                round_trip.abs_diff(supply) <= tolerance,
+                "supply {} gave reserve {} and back supply {}",
+                supply,
+                reserve,
+                round_trip
+            );
+        }
+    }
 }
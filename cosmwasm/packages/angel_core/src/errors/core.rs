@@ -38,6 +38,9 @@ pub enum ContractError {
     #[error("Allowance is expired")]
     Expired {},
 
+    #[error("Queued change cannot be applied before {eta}")]
+    TimelockNotExpired { eta: u64 },
+
     #[error("Updates are not allowed after endowment has been closed")]
     UpdatesAfterClosed {},
 
@@ -71,9 +74,21 @@ pub enum ContractError {
     #[error("Account does not exist")]
     AccountDoesNotExist {},
 
+    #[error("Endowment {id} is not a child Endowment")]
+    NotChildEndowment { id: u32 },
+
+    #[error("Child Endowments cannot have children of their own")]
+    NestedChildEndowment {},
+
+    #[error("Endowment {id} has open child Endowments to cascade the closure to or re-parent")]
+    OpenChildEndowments { id: u32 },
+
     #[error("Contract is not properly configured")]
     ContractNotConfigured {},
 
+    #[error("Endowment does not have a subdao")]
+    EndowmentDaoNotSet {},
+
     #[error("Index Fund already exists with given ID")]
     IndexFundAlreadyExists {},
 
@@ -101,6 +116,12 @@ pub enum ContractError {
     #[error("Strategy already exists at given key")]
     StrategyAlreadyExists {},
 
+    #[error("Strategy cannot move from {from} to {to}")]
+    StrategyTransitionNotAllowed { from: String, to: String },
+
+    #[error("Strategy still has {positions} endowment positions open")]
+    StrategyHasOpenPositions { positions: u64 },
+
     #[error("Index Fund has no members in it")]
     IndexFundEmpty {},
 
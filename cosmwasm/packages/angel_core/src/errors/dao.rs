@@ -60,6 +60,12 @@ pub enum ContractError {
     #[error("User has already voted")]
     AlreadyVoted {},
 
+    #[error("Address has too many polls in progress")]
+    TooManyOpenPolls {},
+
+    #[error("Vote option is not available on this poll")]
+    InvalidVoteOption {},
+
     #[error("Cannot make a text proposal to expired state")]
     NoExecuteData {},
 
@@ -31,6 +31,9 @@ pub enum ContractError {
 
     #[error("No Balance found")]
     EmptyBalance {},
+
+    #[error("Vault is in emergency exit mode. Only redemptions are allowed")]
+    EmergencyExitActive {},
 }
 
 impl From<cw20ContractError> for ContractError {
@@ -1,8 +1,9 @@
 #[allow(unused_imports)]
 use crate::structs::{
-    AccountType, Allowances, BalanceInfo, Beneficiary, Categories, DaoSetup, DonationsReceived,
-    EndowmentController, EndowmentFee, EndowmentStatus, EndowmentType, Investments,
-    RebalanceDetails, SplitDetails, StrategyInvestment, SwapOperation,
+    AccountType, Allowances, BalanceInfo, Beneficiary, Categories, ChildEndowmentsOnClose,
+    DaoSetup, DonationsReceived, EndowmentController, EndowmentFee, EndowmentStatus, EndowmentType,
+    GenericBalance, Investments, RebalanceDetails, ReferralConfig, SplitDetails,
+    StrategyInvestment, SwapOperation,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Decimal, Uint128};
@@ -62,6 +63,18 @@ pub enum ExecuteMsg {
         id: u32,
         strategies: Vec<StrategyInvestment>,
     },
+    // Claim matured redemptions from Strategies that pay out after an unbonding period (ie. native staking)
+    StrategiesClaim {
+        id: u32,
+        strategies: Vec<String>,
+    },
+    // Move vault tokens held in a Liquid Strategy over to a Locked Strategy (via the Vault Router)
+    ReinvestToLocked {
+        id: u32,
+        amount: Uint128, // vault tokens of the source strategy
+        source_strategy: String,
+        target_strategy: String,
+    },
     // create a new endowment
     CreateEndowment(CreateEndowmentMsg),
     // Winding up / closing of an endowment. Returns all funds to a specified Beneficiary address if provided.
@@ -73,11 +86,13 @@ pub enum ExecuteMsg {
     DistributeToBeneficiary {
         id: u32,
     },
-    // Allows the SC owner (only!) to change ownership & upper limit of general categories ID allowed
+    // Allows the SC owner (only!) to change ownership, upper limit of general categories ID allowed
+    // & the referral program parameters
     UpdateConfig {
         new_owner: Option<String>,
         new_registrar: Option<String>,
         max_general_category_id: Option<u8>,
+        referral: Option<ReferralConfig>,
     },
     // Update an Endowment owner, beneficiary, and other core items
     UpdateEndowmentDetails(UpdateEndowmentDetailsMsg),
@@ -97,6 +112,18 @@ pub enum ExecuteMsg {
         endowment_id: u32,
         asset: Asset,
     },
+    // Parent Endowment moves TOH funds into the same account type of one of its children
+    AllocateToChild {
+        child_id: u32,
+        acct_type: AccountType,
+        assets: Vec<AssetUnchecked>,
+    },
+    // Parent Endowment pulls TOH funds back from the same account type of one of its children
+    SweepFromChild {
+        child_id: u32,
+        acct_type: AccountType,
+        assets: Vec<AssetUnchecked>,
+    },
 }
 
 #[cw_serde]
@@ -122,17 +149,30 @@ pub struct CreateEndowmentMsg {
     pub dao: Option<DaoSetup>,      // SubDAO setup options
     pub proposal_link: Option<u64>, // link back to the proposal that created an Endowment (set @ init)
     pub endowment_controller: Option<EndowmentController>,
-    pub parent: Option<u32>,
+    pub parent: Option<u32>, // only the parent Endowment's owner can create a child under it
     pub split_to_liquid: Option<SplitDetails>,
     pub ignore_user_splits: bool,
-    pub referral_id: Option<u32>,
+    pub referral_id: Option<u32>, // Endowment that referred this one, credited with fee rewards for a time
 }
 
 #[cw_serde]
 pub struct UpdateEndowmentStatusMsg {
     pub endowment_id: u32,
     pub status: u8,
-    pub beneficiary: Option<Beneficiary>,
+    pub beneficiary: Option<Beneficiary>, // defaults to the parent Endowment (if any) when closing
+    pub children: Option<ChildEndowmentsOnClose>, // required when closing an Endowment with open children
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct EndowmentsQueryMsg {
+    pub start_after: Option<u32>,
+    pub limit: Option<u32>, // number of Endowments scanned, so a page can hold fewer matches
+    pub status: Option<EndowmentStatus>,
+    pub endow_type: Option<EndowmentType>,
+    pub tier: Option<u8>,
+    pub sdg: Option<u8>,
+    pub owner: Option<String>,
 }
 
 #[cw_serde]
@@ -188,9 +228,53 @@ pub enum QueryMsg {
     // Gets the Endowment by "proposal_link"
     #[returns(EndowmentDetailsResponse)]
     EndowmentByProposalLink { proposal_link: u64 },
+    // Get a page of Endowments, optionally filtered by any of the given fields
+    #[returns(EndowmentListResponse)]
+    Endowments(EndowmentsQueryMsg),
     // Get the Allowances for Endowment
     #[returns(Allowances)]
     Allowances { id: u32, spender: String },
+    // Get the number of endowment positions (locked & liquid) held in a Strategy
+    #[returns(StrategyPositionsResponse)]
+    StrategyPositions { strategy_key: String },
+    // Get the consolidated balances of an Endowment and all of its children
+    #[returns(FamilyBalanceResponse)]
+    FamilyBalance { id: u32 },
+    // Get a page of the Endowments referred by an Endowment & the rewards credited for them
+    #[returns(ReferralsResponse)]
+    Referrals {
+        referrer_id: u32,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct ReferralResponse {
+    pub endowment_id: u32,
+    pub expires_at: u64,
+    pub rewards: GenericBalance,
+}
+
+#[cw_serde]
+pub struct ReferralsResponse {
+    pub referrer_id: u32,
+    pub referrals: Vec<ReferralResponse>,
+    pub total_rewards: GenericBalance, // across all the referrals, not just this page
+}
+
+#[cw_serde]
+pub struct FamilyBalanceResponse {
+    pub id: u32,
+    pub children: Vec<u32>,
+    pub locked: GenericBalance,
+    pub liquid: GenericBalance,
+}
+
+#[cw_serde]
+pub struct StrategyPositionsResponse {
+    pub strategy_key: String,
+    pub positions: u64,
 }
 
 #[cw_serde]
@@ -208,6 +292,7 @@ pub struct ConfigResponse {
     pub registrar_contract: String,
     pub next_account_id: u32,
     pub max_general_category_id: u8,
+    pub referral: Option<ReferralConfig>,
 }
 
 #[cw_serde]
@@ -229,6 +314,28 @@ pub struct EndowmentDetailsResponse {
     pub pending_redemptions: u8,
     pub proposal_link: Option<u64>,
     pub referral_id: Option<u32>,
+    pub parent: Option<u32>,
+}
+
+#[cw_serde]
+pub struct EndowmentEntry {
+    pub id: u32,
+    pub owner: Addr,
+    pub name: String,
+    pub categories: Categories,
+    pub tier: Option<u8>,
+    pub endow_type: EndowmentType,
+    pub logo: Option<String>,
+    pub image: Option<String>,
+    pub status: EndowmentStatus,
+    pub proposal_link: Option<u64>,
+    pub parent: Option<u32>,
+}
+
+#[cw_serde]
+pub struct EndowmentListResponse {
+    pub endowments: Vec<EndowmentEntry>,
+    pub next_start_after: Option<u32>, // set while there may be more Endowments to scan
 }
 
 #[cw_serde]
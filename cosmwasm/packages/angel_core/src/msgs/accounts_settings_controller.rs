@@ -1,5 +1,6 @@
 use crate::structs::{
-    DaoSetup, DonationMatch, EndowmentController, EndowmentFee, SettingsPermissions, SplitDetails,
+    DaoSetup, DelegateAction, DonationMatch, EndowmentController, EndowmentFee, EndowmentSetting,
+    SettingsPermissions, SplitDetails,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
@@ -30,12 +31,12 @@ pub enum ExecuteMsg {
         endowment_id: u32,
         setup: DonationMatch,
     },
-    UpdateDelegate {
-        endowment_id: u32,
-        setting: String,
-        action: String,
-        delegate_address: String,
-        delegate_expiry: Option<u64>,
+    // Set/revoke one delegate across several settings of an Endowment at once
+    UpdateDelegate(UpdateDelegateMsg),
+    // Accounts contract keeps the recorded parent in sync when children are re-parented
+    UpdateEndowmentParent {
+        id: u32,
+        parent: Option<u32>,
     },
 }
 
@@ -84,6 +85,16 @@ pub struct UpdateMaturityAllowlist {
     pub remove: Vec<String>,
 }
 
+#[cw_serde]
+pub struct UpdateDelegateMsg {
+    pub endowment_id: u32,
+    pub settings: Vec<EndowmentSetting>,
+    pub action: DelegateAction,
+    pub delegate_address: String,
+    pub delegate_expiry: Option<u64>,
+    pub delegate_max_uses: Option<u64>, // changes the delegate may make per setting
+}
+
 #[cw_serde]
 pub struct UpdateEndowmentControllerMsg {
     pub id: u32,
@@ -116,6 +127,23 @@ pub enum QueryMsg {
     EndowmentController { id: u32 },
     #[returns(EndowmentPermissionsResponse)]
     EndowmentPermissions { id: u32, updater: Addr },
+    // Get the permissions (incl. any delegate) of every setting of an Endowment
+    #[returns(ListPermissionsResponse)]
+    ListPermissions { id: u32 },
+    // Get a page of the settings an address is a delegate of, across Endowments
+    #[returns(DelegationsResponse)]
+    Delegations {
+        delegate: String,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    // Get a page of the recorded settings & permission changes of an Endowment
+    #[returns(EndowmentSettingsHistoryResponse)]
+    EndowmentSettingsHistory {
+        id: u32,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -160,3 +188,48 @@ pub struct EndowmentPermissionsResponse {
     pub split_to_liquid: bool,
     pub ignore_user_splits: bool,
 }
+
+/// A single recorded mutation of an Endowment's settings or permissions.
+/// `old_value` & `new_value` hold the JSON encoding of the field.
+#[cw_serde]
+pub struct SettingsChange {
+    pub seq: u64,
+    pub id: u32,
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub sender: Addr,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct EndowmentSettingsHistoryResponse {
+    pub changes: Vec<SettingsChange>,
+}
+
+#[cw_serde]
+pub struct SettingPermissionsResponse {
+    pub setting: EndowmentSetting,
+    pub permissions: SettingsPermissions,
+    pub delegate: Option<Addr>,
+    pub delegate_expiry: Option<u64>,
+    pub delegate_uses_remaining: Option<u64>,
+}
+
+#[cw_serde]
+pub struct ListPermissionsResponse {
+    pub permissions: Vec<SettingPermissionsResponse>,
+}
+
+#[cw_serde]
+pub struct DelegationResponse {
+    pub endowment_id: u32,
+    pub setting: EndowmentSetting,
+    pub expires: Option<u64>,
+    pub uses_remaining: Option<u64>,
+}
+
+#[cw_serde]
+pub struct DelegationsResponse {
+    pub delegations: Vec<DelegationResponse>,
+}
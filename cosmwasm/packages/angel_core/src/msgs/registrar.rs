@@ -1,9 +1,9 @@
 use crate::structs::{
     AcceptedTokens, NetworkInfo, RebalanceDetails, SplitDetails, StrategyApprovalState,
-    StrategyParams,
+    StrategyLocale, StrategyParams,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Decimal;
+use cosmwasm_std::{to_binary, CosmosMsg, Decimal, StdResult, WasmMsg};
 
 #[cw_serde]
 pub struct MigrateMsg {
@@ -42,10 +42,23 @@ pub enum ExecuteMsg {
     // Allows the contract Configs (core OR extension) to be updated (only by the owner for now)
     UpdateConfig(UpdateConfigMsg),
     UpdateConfigExtension(UpdateConfigExtensionMsg),
-    // Allows the SC owner to change ownership
+    // Allows the SC owner to propose a new owner (behind the timelock), who calls this again to accept the ownership
     UpdateOwner {
         new_owner: String,
     },
+    // Sets how long (in seconds) owner config changes are queued before they can be applied.
+    // A period of zero applies changes immediately.
+    UpdateTimelock {
+        period: u64,
+    },
+    // Applies a queued config change once its ETA has passed (anyone can call)
+    ExecuteQueuedChange {
+        id: u64,
+    },
+    // Drops a queued config change (only by the owner)
+    CancelQueuedChange {
+        id: u64,
+    },
     // Updates the NETWORK_CONNECTIONS
     UpdateNetworkConnections {
         chain_id: String,
@@ -55,6 +68,37 @@ pub enum ExecuteMsg {
     UpdateFees {
         fees: Vec<(String, Decimal)>,
     },
+    // Add/remove validators from the set that native staking vaults delegate to
+    UpdateValidators {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // Add/remove contracts that get a `RegistrarUpdated` hook when config extension addresses change
+    UpdateSubscribers {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+}
+
+/// Hook message the Registrar pushes to its subscribers
+#[cw_serde]
+pub enum RegistrarHookMsg {
+    /// Sent when `UpdateConfigExtension` changes addresses. `changed` lists the
+    /// names of the config extension fields that were updated. A subscriber that errors
+    /// on the hook doesn't revert the update.
+    RegistrarUpdated { changed: Vec<String> },
+}
+
+impl RegistrarHookMsg {
+    /// creates a cosmos_msg sending this hook to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: to_binary(&self)?,
+            funds: vec![],
+        }
+        .into())
+    }
 }
 
 #[cw_serde]
@@ -101,6 +145,15 @@ pub enum QueryMsg {
     // Get details on single strategy
     #[returns(StrategyDetailResponse)]
     Strategy { strategy_key: String },
+    // Get a page of `limit` strategies, keeping those matching the approval state, locale and chain
+    #[returns(StrategyListResponse)]
+    Strategies {
+        start_after: Option<String>,
+        limit: Option<u64>,
+        approval_state: Option<StrategyApprovalState>,
+        locale: Option<StrategyLocale>,
+        chain: Option<String>,
+    },
     // Get Core Config details for the contract
     #[returns(ConfigResponse)]
     Config {},
@@ -110,8 +163,45 @@ pub enum QueryMsg {
     // Get a network connection info
     #[returns(NetworkConnectionResponse)]
     NetworkConnection { chain_id: String },
+    // Get a page of network connections
+    #[returns(NetworkConnectionListResponse)]
+    NetworkConnections {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
     #[returns(FeesResponse)]
     Fee { name: String },
+    // Get every fee set in the registrar
+    #[returns(FeeListResponse)]
+    Fees {},
+    // Get the validator set used by native staking vaults
+    #[returns(ValidatorsResponse)]
+    Validators {},
+    // Get the config change timelock period
+    #[returns(TimelockResponse)]
+    Timelock {},
+    // Get a page of the queued config changes
+    #[returns(QueuedChangesResponse)]
+    QueuedChanges {
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+    // Get the owner proposed by the current owner, if any
+    #[returns(PendingOwnerResponse)]
+    PendingOwner {},
+    // Get the contracts subscribed to the `RegistrarUpdated` hook
+    #[returns(SubscribersResponse)]
+    Subscribers {},
+}
+
+/// An owner config change that can be queued behind the timelock
+#[cw_serde]
+pub enum ConfigChange {
+    UpdateOwner { new_owner: String },
+    UpdateConfig(UpdateConfigMsg),
+    UpdateConfigExtension(UpdateConfigExtensionMsg),
+    UpdateFees { fees: Vec<(String, Decimal)> },
+    UpdateTimelock { period: u64 },
 }
 
 #[cw_serde]
@@ -119,6 +209,19 @@ pub struct StrategyDetailResponse {
     pub strategy: StrategyParams,
 }
 
+#[cw_serde]
+pub struct StrategyListItem {
+    pub strategy_key: String,
+    pub strategy: StrategyParams,
+}
+
+#[cw_serde]
+pub struct StrategyListResponse {
+    pub strategies: Vec<StrategyListItem>,
+    /// Key to start the next page after, when the page was full before filtering
+    pub next_start_after: Option<String>,
+}
+
 #[cw_serde]
 pub struct ConfigResponse {
     pub owner: String,
@@ -156,6 +259,11 @@ pub struct ConfigExtensionResponse {
     pub accounts_settings_controller: Option<String>,
 }
 
+#[cw_serde]
+pub struct FeeListResponse {
+    pub fees: Vec<(String, Decimal)>,
+}
+
 #[cw_serde]
 pub struct FeesResponse {
     pub tax_rate: Decimal,
@@ -167,8 +275,46 @@ pub struct AccTokensListResponse {
     pub tokens: Vec<String>,
 }
 
+#[cw_serde]
+pub struct ValidatorsResponse {
+    pub validators: Vec<String>,
+}
+
 #[cw_serde]
 pub struct NetworkConnectionResponse {
     pub chain: String,
     pub network_connection: NetworkInfo,
 }
+
+#[cw_serde]
+pub struct NetworkConnectionListResponse {
+    pub network_connections: Vec<NetworkConnectionResponse>,
+}
+
+#[cw_serde]
+pub struct TimelockResponse {
+    pub period: u64,
+}
+
+#[cw_serde]
+pub struct QueuedChangeResponse {
+    pub id: u64,
+    pub change: ConfigChange,
+    pub eta: u64,
+}
+
+#[cw_serde]
+pub struct QueuedChangesResponse {
+    pub changes: Vec<QueuedChangeResponse>,
+}
+
+#[cw_serde]
+pub struct PendingOwnerResponse {
+    pub pending_owner: Option<String>,
+    pub deadline: Option<u64>,
+}
+
+#[cw_serde]
+pub struct SubscribersResponse {
+    pub subscribers: Vec<String>,
+}
@@ -1,9 +1,9 @@
 #[allow(unused_imports)]
 use crate::msgs::registrar::{
-    ConfigResponse, FeesResponse, NetworkConnectionResponse, StrategyDetailResponse,
-    UpdateConfigMsg,
+    ConfigResponse, FeeListResponse, FeesResponse, NetworkConnectionListResponse,
+    NetworkConnectionResponse, StrategyDetailResponse, StrategyListResponse, UpdateConfigMsg,
 };
-use crate::structs::{NetworkInfo, StrategyApprovalState, StrategyParams};
+use crate::structs::{NetworkInfo, StrategyApprovalState, StrategyLocale, StrategyParams};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Decimal;
 
@@ -46,12 +46,30 @@ pub enum QueryMsg {
     // Get details on single strategy
     #[returns(StrategyDetailResponse)]
     Strategy { strategy_key: String },
+    // Get a page of `limit` strategies, keeping those matching the approval state, locale and chain
+    #[returns(StrategyListResponse)]
+    Strategies {
+        start_after: Option<String>,
+        limit: Option<u64>,
+        approval_state: Option<StrategyApprovalState>,
+        locale: Option<StrategyLocale>,
+        chain: Option<String>,
+    },
     // Get Core Config details for the contract
     #[returns(ConfigResponse)]
     Config {},
     // Get a network connection info
     #[returns(NetworkConnectionResponse)]
     NetworkConnection { chain_id: String },
+    // Get a page of network connections
+    #[returns(NetworkConnectionListResponse)]
+    NetworkConnections {
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
     #[returns(FeesResponse)]
     Fee { name: String },
+    // Get every fee set in the registrar
+    #[returns(FeeListResponse)]
+    Fees {},
 }
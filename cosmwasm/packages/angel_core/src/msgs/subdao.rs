@@ -10,6 +10,8 @@ pub struct InstantiateMsg {
     pub id: u32,
     pub quorum: Decimal,
     pub threshold: Decimal,
+    /// Share of the tallied weight that has to veto a poll to reject it. Defaults to 33.4%
+    pub veto_threshold: Option<Decimal>,
     pub voting_period: u64,
     pub timelock_period: u64,
     pub expiration_period: u64,
@@ -19,6 +21,12 @@ pub struct InstantiateMsg {
     pub endow_type: EndowmentType,
     pub endow_owner: String,
     pub registrar_contract: String,
+    /// What happens to the proposal deposit for each poll result. Defaults to `DepositPolicy::default()`
+    pub deposit_policy: Option<DepositPolicy>,
+    /// Receives the slashed deposits, eg. the fee-distributor
+    pub slash_recipient: Option<String>,
+    /// Most polls a single address can have in progress at once. Unlimited if unset
+    pub max_open_polls: Option<u64>,
 }
 
 #[cw_serde]
@@ -37,11 +45,15 @@ pub enum ExecuteMsg {
         owner: Option<String>,
         quorum: Option<Decimal>,
         threshold: Option<Decimal>,
+        veto_threshold: Option<Decimal>,
         voting_period: Option<u64>,
         timelock_period: Option<u64>,
         expiration_period: Option<u64>,
         proposal_deposit: Option<Uint128>,
         snapshot_period: Option<u64>,
+        deposit_policy: Option<DepositPolicy>,
+        slash_recipient: Option<String>,
+        max_open_polls: Option<u64>,
     },
     CastVote {
         poll_id: u64,
@@ -67,6 +79,14 @@ pub enum Cw20HookMsg {
         link: Option<String>,
         execute_msgs: Option<Vec<PollExecuteMsg>>,
     },
+    /// CreateMultipleChoicePoll need to receive deposit from a proposer as well
+    /// The option with the most votes wins & only its messages are executed
+    CreateMultipleChoicePoll {
+        title: String,
+        description: String,
+        link: Option<String>,
+        options: Vec<PollOptionMsg>,
+    },
 }
 
 #[cw_serde]
@@ -76,6 +96,12 @@ pub struct PollExecuteMsg {
     pub msg: Binary,
 }
 
+#[cw_serde]
+pub struct PollOptionMsg {
+    pub label: String,
+    pub execute_msgs: Option<Vec<PollExecuteMsg>>,
+}
+
 /// We currently take no arguments for migrations
 #[cw_serde]
 pub struct MigrateMsg {
@@ -114,11 +140,15 @@ pub struct ConfigResponse {
     pub swap_factory: String,
     pub quorum: Decimal,
     pub threshold: Decimal,
+    pub veto_threshold: Decimal,
     pub voting_period: u64,
     pub timelock_period: u64,
     pub expiration_period: u64,
     pub proposal_deposit: Uint128,
     pub snapshot_period: u64,
+    pub deposit_policy: DepositPolicy,
+    pub slash_recipient: Option<String>,
+    pub max_open_polls: Option<u64>,
 }
 
 #[cw_serde]
@@ -140,10 +170,22 @@ pub struct PollResponse {
     pub link: Option<String>,
     pub deposit_amount: Uint128,
     pub execute_data: Option<Vec<PollExecuteMsg>>,
-    pub yes_votes: Uint128, // balance
-    pub no_votes: Uint128,  // balance
+    pub yes_votes: Uint128,     // balance
+    pub no_votes: Uint128,      // balance
+    pub abstain_votes: Uint128, // balance
+    pub veto_votes: Uint128,    // balance
     pub staked_amount: Option<Uint128>,
     pub total_balance_at_end_poll: Option<Uint128>,
+    /// Options of a multiple-choice poll, empty for a yes/no poll
+    pub options: Vec<PollOptionResponse>,
+    pub winning_option: Option<u32>,
+}
+
+#[cw_serde]
+pub struct PollOptionResponse {
+    pub label: String,
+    pub votes: Uint128, // balance
+    pub execute_data: Option<Vec<PollExecuteMsg>>,
 }
 
 #[cw_serde]
@@ -174,6 +216,54 @@ pub struct VoterInfo {
     pub balance: Uint128,
 }
 
+#[cw_serde]
+pub enum DepositOutcome {
+    /// The deposit goes back to the poll creator
+    Refund,
+    /// The deposit stays with the DAO
+    Keep,
+    /// The deposit goes to the slash recipient
+    Slash,
+    /// The deposit is burned
+    Burn,
+}
+
+#[cw_serde]
+pub struct DepositPolicy {
+    pub passed: DepositOutcome,
+    /// Polls that reached quorum but not the threshold (or a winning option)
+    pub rejected: DepositOutcome,
+    pub quorum_not_reached: DepositOutcome,
+    /// Passed polls whose messages were not executed in time
+    pub expired: DepositOutcome,
+    pub vetoed: DepositOutcome,
+}
+
+impl Default for DepositPolicy {
+    fn default() -> Self {
+        DepositPolicy {
+            passed: DepositOutcome::Refund,
+            rejected: DepositOutcome::Refund,
+            quorum_not_reached: DepositOutcome::Keep,
+            expired: DepositOutcome::Refund,
+            vetoed: DepositOutcome::Burn,
+        }
+    }
+}
+
+impl DepositPolicy {
+    pub fn slashes(&self) -> bool {
+        [
+            &self.passed,
+            &self.rejected,
+            &self.quorum_not_reached,
+            &self.expired,
+            &self.vetoed,
+        ]
+        .contains(&&DepositOutcome::Slash)
+    }
+}
+
 #[cw_serde]
 pub enum PollStatus {
     InProgress,
@@ -193,14 +283,22 @@ impl fmt::Display for PollStatus {
 pub enum VoteOption {
     Yes,
     No,
+    /// Counts toward the quorum, but not toward the threshold
+    Abstain,
+    /// Votes no & rejects the poll, burning its deposit, once the veto threshold is passed
+    Veto,
+    /// Picks the option with the given index on a multiple-choice poll
+    Choice(u32),
 }
 
 impl fmt::Display for VoteOption {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if *self == VoteOption::Yes {
-            write!(f, "yes")
-        } else {
-            write!(f, "no")
+        match self {
+            VoteOption::Yes => write!(f, "yes"),
+            VoteOption::No => write!(f, "no"),
+            VoteOption::Abstain => write!(f, "abstain"),
+            VoteOption::Veto => write!(f, "veto"),
+            VoteOption::Choice(index) => write!(f, "choice_{}", index),
         }
     }
 }
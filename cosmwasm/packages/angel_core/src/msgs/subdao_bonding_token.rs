@@ -1,6 +1,9 @@
-use crate::curves::{decimal, Constant, Curve, DecimalPlaces, Linear, SquareRoot};
+use crate::curves::{
+    decimal, Constant, Curve, DecimalPlaces, Exponential, Linear, PiecewiseLinear, Power, Sigmoid,
+    SquareRoot,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, Decimal, Uint128};
+use cosmwasm_std::{Binary, Decimal, StdError, StdResult, Uint128};
 use cw20::Cw20ReceiveMsg;
 #[allow(unused_imports)]
 use cw_controllers::ClaimsResponse;
@@ -28,6 +31,41 @@ pub struct InstantiateMsg {
     pub curve_type: CurveType,
     // days of unbonding
     pub unbonding_period: u64,
+    /// split of the tokens minted for a donor match between the donor, the endowment
+    /// & the burn. Defaults to 40% / 40% / 20%
+    pub donor_match_split: Option<DonorMatchSplit>,
+    /// fees charged on buys & sells, paid to the endowment's liquid account.
+    /// No fees are charged if unset
+    pub fees: Option<TradingFees>,
+}
+
+#[cw_serde]
+pub struct DonorMatchSplit {
+    pub donor: Decimal,
+    pub endowment: Decimal,
+    pub burn: Decimal,
+}
+
+impl Default for DonorMatchSplit {
+    fn default() -> Self {
+        DonorMatchSplit {
+            donor: Decimal::percent(40),
+            endowment: Decimal::percent(40),
+            burn: Decimal::percent(20),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct TradingFees {
+    /// share of the reserve tokens paid in on a buy, kept as fee
+    pub buy_fee: Decimal,
+    /// share of the reserve tokens released on a sell, kept as fee
+    pub sell_fee: Decimal,
+    /// endowment whose liquid account receives the fees
+    pub endowment_id: u32,
+    /// accounts contract holding the endowment
+    pub accounts_contract: String,
 }
 
 pub type CurveFn = Box<dyn Fn(DecimalPlaces) -> Box<dyn Curve>>;
@@ -38,15 +76,82 @@ pub enum CurveType {
     Constant { value: Uint128, scale: u32 },
     /// Linear returns `slope * 10^-scale * supply` as spot price
     Linear { slope: Uint128, scale: u32 },
-    /// SquareRoot returns `slope * 10^-scale * supply^(power)` as spot price
+    /// SquareRoot returns `slope * 10^-scale * supply^0.5` as spot price (`power` is not used)
     SquareRoot {
         slope: Uint128,
         power: Uint128,
         scale: u32,
     },
+    /// Power returns `slope * 10^-scale * supply^(power * 10^-scale)` as spot price
+    Power {
+        slope: Uint128,
+        power: Uint128,
+        scale: u32,
+    },
+    /// Exponential returns `initial_price * 10^-scale * e^(growth * 10^-scale * supply)` as spot price
+    Exponential {
+        initial_price: Uint128,
+        growth: Uint128,
+        scale: u32,
+    },
+    /// Sigmoid returns `max_price / (1 + e^(-steepness * (supply - midpoint)))` as spot price,
+    /// with every parameter scaled by `10^-scale`. The supply can't go past `supply_cap`
+    Sigmoid {
+        max_price: Uint128,
+        steepness: Uint128,
+        midpoint: Uint128,
+        supply_cap: Uint128,
+        scale: u32,
+    },
+    /// PiecewiseLinear interpolates the spot price between the breakpoints, scaled by `10^-scale`.
+    /// The first breakpoint has to be at supply 0, and the price stays flat after the last one
+    PiecewiseLinear {
+        points: Vec<CurveBreakpoint>,
+        scale: u32,
+    },
+}
+
+#[cw_serde]
+pub struct CurveBreakpoint {
+    pub supply: Uint128,
+    pub price: Uint128,
 }
 
 impl CurveType {
+    /// Checks the parameters describe a curve that can be bought into & sold from
+    pub fn validate(&self) -> StdResult<()> {
+        let valid = match self {
+            CurveType::Constant { value, .. } => !value.is_zero(),
+            CurveType::Linear { slope, .. }
+            | CurveType::SquareRoot { slope, .. }
+            | CurveType::Power { slope, .. } => !slope.is_zero(),
+            CurveType::Exponential {
+                initial_price,
+                growth,
+                ..
+            } => !initial_price.is_zero() && !growth.is_zero(),
+            CurveType::Sigmoid {
+                max_price,
+                steepness,
+                supply_cap,
+                ..
+            } => !max_price.is_zero() && !steepness.is_zero() && !supply_cap.is_zero(),
+            // supplies start at 0 & increase, and the price is only 0 at the start
+            CurveType::PiecewiseLinear { points, .. } => {
+                points.first().is_some_and(|first| first.supply.is_zero())
+                    && points
+                        .windows(2)
+                        .all(|pair| pair[0].supply < pair[1].supply)
+                    && points.iter().skip(1).all(|point| !point.price.is_zero())
+                    && points.last().is_some_and(|last| !last.price.is_zero())
+            }
+        };
+        if !valid {
+            return Err(StdError::generic_err("Invalid curve parameters"));
+        }
+        Ok(())
+    }
+
     pub fn to_curve_fn(&self) -> CurveFn {
         match self.clone() {
             CurveType::Constant { value, scale } => {
@@ -75,6 +180,62 @@ impl CurveType {
                 };
                 Box::new(calc)
             }
+            CurveType::Power {
+                slope,
+                power,
+                scale,
+            } => {
+                let calc = move |places| -> Box<dyn Curve> {
+                    Box::new(Power::new(
+                        decimal(slope, scale),
+                        decimal(power, scale),
+                        places,
+                    ))
+                };
+                Box::new(calc)
+            }
+            CurveType::Exponential {
+                initial_price,
+                growth,
+                scale,
+            } => {
+                let calc = move |places| -> Box<dyn Curve> {
+                    Box::new(Exponential::new(
+                        decimal(initial_price, scale),
+                        decimal(growth, scale),
+                        places,
+                    ))
+                };
+                Box::new(calc)
+            }
+            CurveType::Sigmoid {
+                max_price,
+                steepness,
+                midpoint,
+                supply_cap,
+                scale,
+            } => {
+                let calc = move |places| -> Box<dyn Curve> {
+                    Box::new(Sigmoid::new(
+                        decimal(max_price, scale),
+                        decimal(steepness, scale),
+                        decimal(midpoint, scale),
+                        decimal(supply_cap, scale),
+                        places,
+                    ))
+                };
+                Box::new(calc)
+            }
+            CurveType::PiecewiseLinear { points, scale } => {
+                let points: Vec<_> = points
+                    .iter()
+                    .map(|point| (decimal(point.supply, scale), decimal(point.price, scale)))
+                    .collect();
+                let calc = move |places| -> Box<dyn Curve> {
+                    Box::new(PiecewiseLinear::new(points.clone(), places))
+                };
+                Box::new(calc)
+            }
         }
     }
 }
@@ -83,14 +244,18 @@ impl CurveType {
 pub enum Cw20HookMsg {
     /// Buy will attempt to purchase as many supply tokens as possible.
     /// You must send only CW20 reserve tokens (HALO)
-    Buy {},
+    Buy {
+        /// fails the buy if less supply tokens than this would be minted
+        min_tokens_out: Option<Uint128>,
+    },
     /// DonorMatch will attempt to receive the CW20 reserve tokens (HALO).
-    /// It will also attempt to send the dao tokens (CS) to "donor" & "endowment" contract.
+    /// It will also attempt to send the dao tokens (CS) to "donor" & the endowment's subdao.
     /// You must send only CW20 reserve tokens (HALO)
     DonorMatch {
         amount: Uint128,
         donor: String,
-        accounts_contract: String,
+        /// subdao of the endowment, receiving the endowment's share
+        endowment_dao: String,
         endowment_id: u32,
     },
 }
@@ -103,7 +268,11 @@ pub enum ExecuteMsg {
     /// Implements CW20. Transfer is a base message to move tokens to another account without triggering actions
     Transfer { recipient: String, amount: Uint128 },
     /// Implements CW20. Burn is a base message to destroy tokens forever
-    Burn { amount: Uint128 },
+    /// Fails if less than `min_reserve_out` reserve tokens would be claimable, after fees
+    Burn {
+        amount: Uint128,
+        min_reserve_out: Option<Uint128>,
+    },
     /// Implements CW20.  Send is a base message to transfer tokens to a contract and trigger an action
     /// on the receiving contract.
     Send {
@@ -143,9 +312,15 @@ pub enum ExecuteMsg {
         msg: Binary,
     },
     /// Implements CW20 "approval" extension. Destroys tokens forever
-    BurnFrom { owner: String, amount: Uint128 },
+    BurnFrom {
+        owner: String,
+        amount: Uint128,
+        min_reserve_out: Option<Uint128>,
+    },
     /// Claim all tokens available for the message sender
     ClaimTokens {},
+    /// Replaces the trading fees. Only the contract that instantiated the token (subdao) can do this
+    UpdateTradingFees { fees: Option<TradingFees> },
     // Implements CW20. Receive is a base message to receive tokens to a this contract and trigger an action
     /// on the receiving contract.
     Receive(Cw20ReceiveMsg),
@@ -170,6 +345,12 @@ pub enum QueryMsg {
     /// Returns claims for an address
     #[returns(ClaimsResponse)]
     Claims { address: String },
+    /// Returns the supply tokens minted & the fee charged for buying with `reserve_amount`
+    #[returns(SimulateBuyResponse)]
+    SimulateBuy { reserve_amount: Uint128 },
+    /// Returns the reserve tokens claimable & the fee charged for selling `token_amount`
+    #[returns(SimulateSellResponse)]
+    SimulateSell { token_amount: Uint128 },
 }
 
 #[cw_serde]
@@ -181,3 +362,19 @@ pub struct CurveInfoResponse {
     pub spot_price: Decimal,
     pub reserve_denom: String,
 }
+
+#[cw_serde]
+pub struct SimulateBuyResponse {
+    // how many supply tokens would be minted
+    pub tokens_out: Uint128,
+    // how many reserve tokens would be sent to the endowment as fee
+    pub fee_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct SimulateSellResponse {
+    // how many reserve tokens could be claimed
+    pub reserve_out: Uint128,
+    // how many reserve tokens would be sent to the endowment as fee
+    pub fee_amount: Uint128,
+}
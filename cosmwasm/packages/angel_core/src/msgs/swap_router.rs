@@ -7,7 +7,6 @@ use cw_asset::AssetInfo;
 #[cw_serde]
 pub struct InstantiateMsg {
     pub registrar_contract: Addr,
-    pub accounts_contract: Addr,
     pub pairs: Vec<Pair>,
 }
 
@@ -82,7 +81,7 @@ pub enum QueryMsg {
 #[cw_serde]
 pub struct ConfigResponse {
     pub registrar_contract: Addr,
-    pub accounts_contract: Addr,
+    pub accounts_contract: Option<Addr>, // resolved from the Registrar's config extension
 }
 
 // We define a custom struct for each query response
@@ -61,9 +61,18 @@ pub enum ExecuteMsg {
         endowment_id: u32,
         amount: Uint128,
     },
+    /// redeem vault assets from self (if AccountType::Liquid) back to the caller(Vault Router),
+    /// which reinvests them in the AccountType::Locked vault of another Strategy
+    RedeemForReinvest {
+        endowment_id: u32,
+        amount: Uint128,
+    },
     Harvest {
         account_ids: Vec<u32>,
     },
+    /// burn the vault tokens accrued as AP tax (APTAX balance) & send
+    /// the redeemed `native_token` to the registrar `treasury`
+    ClaimTaxes {},
     RestakeClaimReward {
         reward_token_bal_before: Uint128,
     },
@@ -90,9 +99,23 @@ pub enum ExecuteMsg {
         id: Option<u32>,
         native_token_bal_before: Uint128,
     },
+    /// unwind the whole vault position (unstake all LP, remove liquidity & swap back
+    /// to `native_token`) and switch the vault to redeem-only mode
+    EmergencyWithdraw {},
+    EmergencyWithdrawFinalize {
+        native_token_bal_before: Uint128,
+    },
     Receive(Cw20ReceiveMsg),
 }
 
+/// Extra entries of the vaults whose redemptions are paid out only after
+/// an unbonding period (ie. native staking vaults)
+#[cw_serde]
+pub enum UnbondingExecuteMsg {
+    /// send the matured unbonding claims of the endowment to the `ibc_controller`
+    Claim { endowment_id: u32 },
+}
+
 #[cw_serde]
 pub struct UpdateConfigMsg {
     pub sibling_vault: Option<String>,
@@ -155,6 +178,9 @@ pub enum QueryMsg {
     /// Returns the APTAX balance of the contract
     #[returns(BalanceResponse)]
     ApTaxBalance {},
+    /// Returns the emergency exit status of the contract
+    #[returns(EmergencyExitResponse)]
+    EmergencyExit {},
 }
 
 #[cw_serde]
@@ -215,3 +241,9 @@ pub struct StateResponse {
     pub total_lp_amount: String,
     pub total_shares: String,
 }
+
+#[cw_serde]
+pub struct EmergencyExitResponse {
+    pub active: bool,
+    pub native_token_amount: String,
+}
@@ -1,5 +1,6 @@
 use crate::structs::VaultActionData;
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
 use cw20::Cw20ReceiveMsg;
 
 #[cw_serde]
@@ -34,6 +35,23 @@ pub enum ExecuteMsg {
     Harvest {
         action: VaultActionData,
     },
+    /// Claim the matured unbonding redemptions of `action.account_ids[0]`
+    /// from both vaults of an unbonding (ie. native staking) Strategy
+    Claim {
+        action: VaultActionData,
+    },
+    /// Move an Endowment's liquid position in `action.strategy_id` (`action.liq_amt` vault tokens)
+    /// into the locked vault of `target_strategy`. Only callable by the Accounts contract.
+    ReinvestToLocked {
+        action: VaultActionData,
+        target_strategy: String,
+    },
+    /// Internal: deposit the tokens redeemed from the liquid vault into the target locked vault
+    ReinvestToLockedFinalize {
+        action: VaultActionData,
+        target_strategy: String,
+        token_bal_before: Uint128,
+    },
 }
 
 #[cw_serde]
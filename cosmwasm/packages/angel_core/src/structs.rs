@@ -1,4 +1,5 @@
 use crate::errors::core::ContractError;
+use crate::msgs::subdao::DepositPolicy;
 use crate::msgs::subdao_bonding_token::CurveType;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Coin, Decimal, StdError, SubMsg, Timestamp, Uint128};
@@ -100,14 +101,30 @@ pub struct RegistrarConfigExtension {
 #[cw_serde]
 pub struct Delegate {
     address: Addr,
-    expires: Option<u64>, // datetime int of delegation expiry
+    expires: Option<u64>,        // datetime int of delegation expiry
+    uses_remaining: Option<u64>, // number of changes the delegate may still make (None: unlimited)
 }
 
 impl Delegate {
+    pub fn address(&self) -> &Addr {
+        &self.address
+    }
+
+    pub fn expires(&self) -> Option<u64> {
+        self.expires
+    }
+
+    pub fn uses_remaining(&self) -> Option<u64> {
+        self.uses_remaining
+    }
+
+    /// A delegate can act until its expiry and while it has uses left
     pub fn can_take_action(&self, sender: &Addr, env_time: Timestamp) -> bool {
-        sender == &self.address
-            && (self.expires.is_none()
-                || env_time >= Timestamp::from_seconds(self.expires.unwrap()))
+        let not_expired = match self.expires {
+            Some(expires) => env_time < Timestamp::from_seconds(expires),
+            None => true,
+        };
+        sender == &self.address && not_expired && self.uses_remaining != Some(0)
     }
 }
 
@@ -127,6 +144,10 @@ pub struct SettingsPermissions {
 }
 
 impl SettingsPermissions {
+    pub fn delegate(&self) -> Option<&Delegate> {
+        self.delegate.as_ref()
+    }
+
     pub fn default(endow_type: &EndowmentType) -> Self {
         SettingsPermissions {
             owner_controlled: true,
@@ -146,6 +167,7 @@ impl SettingsPermissions {
         gov: Option<&Addr>,
         delegate_addr: Addr,
         delegate_expiry: Option<u64>,
+        delegate_max_uses: Option<u64>,
     ) {
         if sender == owner && self.owner_controlled
             || gov.is_some() && self.gov_controlled && sender == gov.unwrap()
@@ -153,10 +175,28 @@ impl SettingsPermissions {
             self.delegate = Some(Delegate {
                 address: delegate_addr,
                 expires: delegate_expiry,
+                uses_remaining: delegate_max_uses,
             })
         }
     }
 
+    /// Counts a change made by the `sender` against the delegate's usage limit.
+    /// Changes made by the owner or gov contract are not counted.
+    pub fn use_delegate(&mut self, sender: &Addr, owner: &Addr, gov: Option<&Addr>) {
+        if sender == owner && self.owner_controlled
+            || gov.is_some() && self.gov_controlled && sender == gov.unwrap()
+        {
+            return;
+        }
+        if let Some(delegate) = self.delegate.as_mut() {
+            if delegate.address == *sender {
+                if let Some(uses) = delegate.uses_remaining.as_mut() {
+                    *uses = uses.saturating_sub(1);
+                }
+            }
+        }
+    }
+
     pub fn revoke_delegate(
         &mut self,
         sender: &Addr,
@@ -328,179 +368,153 @@ impl EndowmentController {
         }
     }
 
-    pub fn get_permissions(&self, name: String) -> Result<SettingsPermissions, ContractError> {
-        match name.as_str() {
-            "endowment_controller" => Ok(self.endowment_controller.clone()),
-            "beneficiaries_allowlist" => Ok(self.beneficiaries_allowlist.clone()),
-            "contributors_allowlist" => Ok(self.contributors_allowlist.clone()),
-            "maturity_allowlist" => Ok(self.maturity_allowlist.clone()),
-            "split_to_liquid" => Ok(self.split_to_liquid.clone()),
-            "ignore_user_splits" => Ok(self.ignore_user_splits.clone()),
-            "earnings_fee" => Ok(self.earnings_fee.clone()),
-            "withdraw_fee" => Ok(self.withdraw_fee.clone()),
-            "deposit_fee" => Ok(self.deposit_fee.clone()),
-            "aum_fee" => Ok(self.aum_fee.clone()),
-            "kyc_donors_only" => Ok(self.kyc_donors_only.clone()),
-            "name" => Ok(self.name.clone()),
-            "image" => Ok(self.image.clone()),
-            "logo" => Ok(self.logo.clone()),
-            "categories" => Ok(self.categories.clone()),
-            "donation_match_active" => Ok(self.donation_match_active.clone()),
-            _ => Err(ContractError::InvalidInputs {}),
+    pub fn get_permissions(&self, setting: EndowmentSetting) -> SettingsPermissions {
+        match setting {
+            EndowmentSetting::EndowmentController => self.endowment_controller.clone(),
+            EndowmentSetting::BeneficiariesAllowlist => self.beneficiaries_allowlist.clone(),
+            EndowmentSetting::ContributorsAllowlist => self.contributors_allowlist.clone(),
+            EndowmentSetting::MaturityAllowlist => self.maturity_allowlist.clone(),
+            EndowmentSetting::SplitToLiquid => self.split_to_liquid.clone(),
+            EndowmentSetting::IgnoreUserSplits => self.ignore_user_splits.clone(),
+            EndowmentSetting::EarningsFee => self.earnings_fee.clone(),
+            EndowmentSetting::WithdrawFee => self.withdraw_fee.clone(),
+            EndowmentSetting::DepositFee => self.deposit_fee.clone(),
+            EndowmentSetting::AumFee => self.aum_fee.clone(),
+            EndowmentSetting::KycDonorsOnly => self.kyc_donors_only.clone(),
+            EndowmentSetting::Name => self.name.clone(),
+            EndowmentSetting::Image => self.image.clone(),
+            EndowmentSetting::Logo => self.logo.clone(),
+            EndowmentSetting::Categories => self.categories.clone(),
+            EndowmentSetting::DonationMatchActive => self.donation_match_active.clone(),
+        }
+    }
+
+    fn permissions_mut(&mut self, setting: EndowmentSetting) -> &mut SettingsPermissions {
+        match setting {
+            EndowmentSetting::EndowmentController => &mut self.endowment_controller,
+            EndowmentSetting::BeneficiariesAllowlist => &mut self.beneficiaries_allowlist,
+            EndowmentSetting::ContributorsAllowlist => &mut self.contributors_allowlist,
+            EndowmentSetting::MaturityAllowlist => &mut self.maturity_allowlist,
+            EndowmentSetting::SplitToLiquid => &mut self.split_to_liquid,
+            EndowmentSetting::IgnoreUserSplits => &mut self.ignore_user_splits,
+            EndowmentSetting::EarningsFee => &mut self.earnings_fee,
+            EndowmentSetting::WithdrawFee => &mut self.withdraw_fee,
+            EndowmentSetting::DepositFee => &mut self.deposit_fee,
+            EndowmentSetting::AumFee => &mut self.aum_fee,
+            EndowmentSetting::KycDonorsOnly => &mut self.kyc_donors_only,
+            EndowmentSetting::Name => &mut self.name,
+            EndowmentSetting::Image => &mut self.image,
+            EndowmentSetting::Logo => &mut self.logo,
+            EndowmentSetting::Categories => &mut self.categories,
+            EndowmentSetting::DonationMatchActive => &mut self.donation_match_active,
         }
     }
 
     pub fn set_permissions(
         &mut self,
-        name: String,
+        setting: EndowmentSetting,
         permissions: SettingsPermissions,
     ) -> Result<(), ContractError> {
-        match name.as_str() {
-            "beneficiaries_allowlist" => {
-                if self.beneficiaries_allowlist.modifiable {
-                    self.beneficiaries_allowlist = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "contributors_allowlist" => {
-                if self.contributors_allowlist.modifiable {
-                    self.contributors_allowlist = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "maturity_allowlist" => {
-                if self.maturity_allowlist.modifiable {
-                    self.maturity_allowlist = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "split_to_liquid" => {
-                if self.split_to_liquid.modifiable {
-                    self.split_to_liquid = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "ignore_user_splits" => {
-                if self.ignore_user_splits.modifiable {
-                    self.ignore_user_splits = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "earnings_fee" => {
-                if self.earnings_fee.modifiable {
-                    self.earnings_fee = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "withdraw_fee" => {
-                if self.withdraw_fee.modifiable {
-                    self.withdraw_fee = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "deposit_fee" => {
-                if self.deposit_fee.modifiable {
-                    self.deposit_fee = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "aum_fee" => {
-                if self.aum_fee.modifiable {
-                    self.aum_fee = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "kyc_donors_only" => {
-                if self.kyc_donors_only.modifiable {
-                    self.kyc_donors_only = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "name" => {
-                if self.name.modifiable {
-                    self.name = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "image" => {
-                if self.image.modifiable {
-                    self.image = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "logo" => {
-                if self.logo.modifiable {
-                    self.logo = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            "categories" => {
-                if self.categories.modifiable {
-                    self.categories = permissions;
-                    Ok(())
-                } else {
-                    Err(ContractError::Std(StdError::generic_err(
-                        "You are attempting to modify a field which has been locked forever",
-                    )))
-                }
-            }
-            _ => Err(ContractError::InvalidInputs {}),
+        let current = self.permissions_mut(setting);
+        if !current.modifiable {
+            return Err(ContractError::Std(StdError::generic_err(
+                "You are attempting to modify a field which has been locked forever",
+            )));
         }
+        *current = permissions;
+        Ok(())
+    }
+
+    /// Counts a change the `sender` made to the setting against its delegate's usage limit.
+    /// This is bookkeeping on the delegate, so it also applies to settings locked forever.
+    pub fn use_delegate(
+        &mut self,
+        setting: EndowmentSetting,
+        sender: &Addr,
+        owner: &Addr,
+        gov: Option<&Addr>,
+    ) {
+        self.permissions_mut(setting)
+            .use_delegate(sender, owner, gov);
     }
 }
 
+/// Names of the settings held in an `EndowmentController`
+#[cw_serde]
+#[derive(Copy)]
+pub enum EndowmentSetting {
+    EndowmentController,
+    BeneficiariesAllowlist,
+    ContributorsAllowlist,
+    MaturityAllowlist,
+    EarningsFee,
+    WithdrawFee,
+    DepositFee,
+    AumFee,
+    KycDonorsOnly,
+    Name,
+    Image,
+    Logo,
+    Categories,
+    IgnoreUserSplits,
+    SplitToLiquid,
+    DonationMatchActive,
+}
+
+impl EndowmentSetting {
+    pub const ALL: [EndowmentSetting; 16] = [
+        EndowmentSetting::EndowmentController,
+        EndowmentSetting::BeneficiariesAllowlist,
+        EndowmentSetting::ContributorsAllowlist,
+        EndowmentSetting::MaturityAllowlist,
+        EndowmentSetting::EarningsFee,
+        EndowmentSetting::WithdrawFee,
+        EndowmentSetting::DepositFee,
+        EndowmentSetting::AumFee,
+        EndowmentSetting::KycDonorsOnly,
+        EndowmentSetting::Name,
+        EndowmentSetting::Image,
+        EndowmentSetting::Logo,
+        EndowmentSetting::Categories,
+        EndowmentSetting::IgnoreUserSplits,
+        EndowmentSetting::SplitToLiquid,
+        EndowmentSetting::DonationMatchActive,
+    ];
+}
+
+impl fmt::Display for EndowmentSetting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                EndowmentSetting::EndowmentController => "endowment_controller",
+                EndowmentSetting::BeneficiariesAllowlist => "beneficiaries_allowlist",
+                EndowmentSetting::ContributorsAllowlist => "contributors_allowlist",
+                EndowmentSetting::MaturityAllowlist => "maturity_allowlist",
+                EndowmentSetting::EarningsFee => "earnings_fee",
+                EndowmentSetting::WithdrawFee => "withdraw_fee",
+                EndowmentSetting::DepositFee => "deposit_fee",
+                EndowmentSetting::AumFee => "aum_fee",
+                EndowmentSetting::KycDonorsOnly => "kyc_donors_only",
+                EndowmentSetting::Name => "name",
+                EndowmentSetting::Image => "image",
+                EndowmentSetting::Logo => "logo",
+                EndowmentSetting::Categories => "categories",
+                EndowmentSetting::IgnoreUserSplits => "ignore_user_splits",
+                EndowmentSetting::SplitToLiquid => "split_to_liquid",
+                EndowmentSetting::DonationMatchActive => "donation_match_active",
+            }
+        )
+    }
+}
+
+#[cw_serde]
+pub enum DelegateAction {
+    Set,
+    Revoke,
+}
+
 #[cw_serde]
 pub struct Pair {
     pub assets: [AssetInfo; 2],
@@ -566,6 +580,59 @@ pub enum StrategyApprovalState {
     Deprecated,
 }
 
+impl StrategyApprovalState {
+    /// A Strategy is approved once, can be frozen (Deprecated) or wound down
+    /// (WithdrawOnly) from there, and a frozen Strategy is only reopened for
+    /// withdrawals before it is removed.
+    pub fn can_transition_to(&self, next: &StrategyApprovalState) -> bool {
+        matches!(
+            (self, next),
+            (
+                StrategyApprovalState::NotApproved,
+                StrategyApprovalState::Approved
+            ) | (
+                StrategyApprovalState::Approved,
+                StrategyApprovalState::WithdrawOnly
+            ) | (
+                StrategyApprovalState::Approved,
+                StrategyApprovalState::Deprecated
+            ) | (
+                StrategyApprovalState::WithdrawOnly,
+                StrategyApprovalState::Approved
+            ) | (
+                StrategyApprovalState::WithdrawOnly,
+                StrategyApprovalState::Deprecated
+            ) | (
+                StrategyApprovalState::Deprecated,
+                StrategyApprovalState::WithdrawOnly
+            )
+        )
+    }
+
+    /// Only Strategies that never took deposits, or are being wound down, can be removed
+    pub fn is_removable(&self) -> bool {
+        matches!(
+            self,
+            StrategyApprovalState::NotApproved | StrategyApprovalState::WithdrawOnly
+        )
+    }
+}
+
+impl fmt::Display for StrategyApprovalState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                StrategyApprovalState::NotApproved => "not_approved",
+                StrategyApprovalState::Approved => "approved",
+                StrategyApprovalState::WithdrawOnly => "withdraw_only",
+                StrategyApprovalState::Deprecated => "deprecated",
+            }
+        )
+    }
+}
+
 // The "locale" of a given Strategy will drive:
 // 1. Encoding of the payload (IBC vs EVM)
 // 2. Should the Router pass the deposit msg off to a Gateway (IBC/EVM) or a Vault(s) directly (Native)
@@ -655,6 +722,12 @@ pub struct RedeemResults {
     pub total: Uint128,
 }
 
+#[cw_serde]
+pub struct EmergencyExit {
+    pub native_token_amount: Uint128, // amount of `native_token` left to be redeemed pro-rata by vault token holders
+    pub lp_token_amount: Uint128, // LP tokens unwound by the exit, which the outstanding vault tokens still represent
+}
+
 #[cw_serde]
 pub struct SplitDetails {
     pub max: Decimal,
@@ -745,6 +818,24 @@ pub enum Beneficiary {
     Wallet { address: String },
 }
 
+/// Referral program run by the Accounts contract: referrer Endowments are credited
+/// a share of the fees collected from the Endowments they referred
+#[cw_serde]
+pub struct ReferralConfig {
+    pub protocol_fee_share: Decimal, // share of withdraw fees otherwise sent to the AP Treasury
+    pub deposit_fee_share: Decimal, // share of deposit fees otherwise sent to the Endowment's payout address
+    pub period: u64, // seconds after a referred Endowment's creation that rewards are paid out for
+}
+
+/// What happens to the child Endowments of a parent that is being closed
+#[cw_serde]
+pub enum ChildEndowmentsOnClose {
+    /// Close every child as well, sending their funds to the parent's beneficiary
+    Cascade,
+    /// Move every child under another open, top-level Endowment of the same type
+    Reparent { new_parent: u32 },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum EndowmentType {
@@ -1040,12 +1131,17 @@ impl GenericBalance {
 pub struct DaoSetup {
     pub quorum: Decimal,
     pub threshold: Decimal,
+    /// Share of the tallied weight that has to veto a poll to reject it. Defaults to 33.4%
+    pub veto_threshold: Option<Decimal>,
     pub voting_period: u64,
     pub timelock_period: u64,
     pub expiration_period: u64,
     pub proposal_deposit: Uint128,
     pub snapshot_period: u64,
     pub token: DaoToken,
+    pub deposit_policy: Option<DepositPolicy>,
+    pub slash_recipient: Option<String>,
+    pub max_open_polls: Option<u64>,
 }
 
 #[cw_serde]
@@ -1,16 +1,20 @@
 use crate::errors::core::{ContractError, PaymentError};
+use crate::errors::vault::ContractError as VaultError;
+use crate::msgs::accounts::{ExecuteMsg as AccountsExecuteMsg, ReceiveMsg as AccountsReceiveMsg};
 use crate::msgs::registrar::{
+    ConfigExtensionResponse as RegistrarConfigExtensionResponse,
     ConfigResponse as RegistrarConfigResponse, QueryMsg as RegistrarQuerier,
 };
 use crate::msgs::vault::QueryMsg as VaultQuerier;
-use crate::structs::{GenericBalance, SplitDetails};
+use crate::structs::{AccountType, EmergencyExit, GenericBalance, SplitDetails};
 use cosmwasm_std::{
-    to_binary, to_vec, Addr, BankMsg, Coin, ContractResult, Decimal, Deps, DepsMut, Empty, Event,
-    MessageInfo, QueryRequest, StdError, StdResult, SubMsg, SystemError, SystemResult, Uint128,
-    WasmMsg, WasmQuery,
+    coins, to_binary, to_vec, Addr, BankMsg, Coin, ContractResult, CosmosMsg, Decimal, Deps,
+    DepsMut, Empty, Event, MessageInfo, QueryRequest, StdError, StdResult, Storage, SubMsg,
+    SystemError, SystemResult, Uint128, WasmMsg, WasmQuery,
 };
 use cw20::{Balance, Cw20CoinVerified, Cw20ExecuteMsg, Denom};
 use cw_asset::{Asset, AssetInfoBase};
+use cw_storage_plus::Item;
 
 /// Determine if a reply event contains a specific key-value pair
 pub fn event_contains_attr(event: &Event, key: &str, value: &str) -> bool {
@@ -137,6 +141,73 @@ pub fn send_tokens(to: &Addr, balance: &GenericBalance) -> StdResult<Vec<SubMsg>
     Ok(msgs)
 }
 
+/// Prepare the msg paying `amount` of a vault's `native_token` out to the `beneficiary`.
+/// Endowment payouts (`id` is set) go through the Accounts contract's `VaultReceipt` entry,
+/// so that the funds are credited to the Endowment's `acct_type` balance.
+pub fn vault_payout_msg(
+    native_token: &Denom,
+    beneficiary: &Addr,
+    id: Option<u32>,
+    acct_type: AccountType,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    let msg = match (native_token, id) {
+        (Denom::Native(denom), Some(id)) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: beneficiary.to_string(),
+            msg: to_binary(&AccountsExecuteMsg::VaultReceipt { id, acct_type })?,
+            funds: coins(amount.u128(), denom),
+        }),
+        (Denom::Native(denom), None) => CosmosMsg::Bank(BankMsg::Send {
+            to_address: beneficiary.to_string(),
+            amount: coins(amount.u128(), denom),
+        }),
+        (Denom::Cw20(contract_addr), Some(id)) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: beneficiary.to_string(),
+                amount,
+                msg: to_binary(&AccountsReceiveMsg::VaultReceipt { id, acct_type })?,
+            })?,
+            funds: vec![],
+        }),
+        (Denom::Cw20(contract_addr), None) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: beneficiary.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }),
+    };
+    Ok(msg)
+}
+
+/// Look up the Accounts contract from the Registrar, so that vaults paying out to
+/// Endowments never hold a stale copy of its address
+pub fn query_accounts_contract(deps: Deps, registrar_contract: &str) -> StdResult<Addr> {
+    let registrar_config: RegistrarConfigExtensionResponse = deps.querier.query_wasm_smart(
+        registrar_contract.to_string(),
+        &RegistrarQuerier::ConfigExtension {},
+    )?;
+    match registrar_config.accounts_contract {
+        Some(addr) => deps.api.addr_validate(&addr),
+        None => Err(StdError::generic_err(
+            "Accounts contract not set in the Registrar",
+        )),
+    }
+}
+
+/// Fail if a vault is in emergency exit (redeem-only) mode
+pub fn assert_not_emergency_exit(
+    storage: &dyn Storage,
+    emergency_exit: &Item<EmergencyExit>,
+) -> Result<(), VaultError> {
+    if emergency_exit.may_load(storage)?.is_some() {
+        return Err(VaultError::EmergencyExitActive {});
+    }
+    Ok(())
+}
+
 pub fn vault_endowment_balance(deps: Deps, vault_address: String, endowment_id: u32) -> Uint128 {
     // get an account's balance held with a vault
     deps.querier
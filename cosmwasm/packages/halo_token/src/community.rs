@@ -1,3 +1,5 @@
+use crate::common::OrderBy;
+use crate::vesting::VestingInfo;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Uint128;
 
@@ -18,6 +20,19 @@ pub enum ExecuteMsg {
         recipient: String,
         amount: Uint128,
     },
+    /// Streams `amount` of HALO to `recipient` over the `(start, end, amount)` schedules
+    CreateGrant {
+        recipient: String,
+        schedules: Vec<(u64, u64, Uint128)>,
+    },
+    /// Recipient claims the part of a grant released so far
+    ClaimGrant {
+        grant_id: u64,
+    },
+    /// Pays out what has been released and returns the unvested remainder to the community pool
+    CancelGrant {
+        grant_id: u64,
+    },
 }
 
 /// We currently take no arguments for migrations
@@ -29,6 +44,14 @@ pub struct MigrateMsg {}
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
+    #[returns(GrantResponse)]
+    Grant { grant_id: u64 },
+    #[returns(GrantsResponse)]
+    Grants {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order_by: Option<OrderBy>,
+    },
 }
 
 // We define a custom struct for each query response
@@ -38,3 +61,15 @@ pub struct ConfigResponse {
     pub halo_token: String,
     pub spend_limit: Uint128,
 }
+
+#[cw_serde]
+pub struct GrantResponse {
+    pub grant_id: u64,
+    pub recipient: String,
+    pub info: VestingInfo,
+}
+
+#[cw_serde]
+pub struct GrantsResponse {
+    pub grants: Vec<GrantResponse>,
+}
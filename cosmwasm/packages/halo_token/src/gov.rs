@@ -2,7 +2,7 @@ use crate::common::OrderBy;
 #[allow(unused_imports)]
 use crate::staking::StakerInfoResponse;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, Decimal, Uint128};
+use cosmwasm_std::{Binary, Coin, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 #[allow(unused_imports)]
 use cw_controllers::{Claim, ClaimsResponse};
@@ -17,6 +17,7 @@ pub struct MigrateMsg {}
 pub struct InstantiateMsg {
     pub quorum: u64,
     pub threshold: u64,
+    pub veto_threshold: u64, // percentage of the tallied weight vetoing a poll to reject it
     pub voting_period: u64,
     pub timelock_period: u64,
     pub proposal_deposit: Uint128,
@@ -40,6 +41,7 @@ pub enum ExecuteMsg {
         owner: Option<String>,
         quorum: Option<u64>,
         threshold: Option<u64>,
+        veto_threshold: Option<u64>,
         voting_period: Option<u64>,
         timelock_period: Option<u64>,
         proposal_deposit: Option<Uint128>,
@@ -62,6 +64,14 @@ pub enum ExecuteMsg {
     ExecutePoll {
         poll_id: u64,
     },
+    /// Delegate the sender's staked voting power to another staker.
+    /// Replaces any delegation the sender has already made.
+    Delegate {
+        to: String,
+    },
+    /// Remove the sender's delegation. Votes the delegate has already cast
+    /// stay locked until their polls end.
+    Undelegate {},
 }
 
 #[cw_serde]
@@ -82,11 +92,9 @@ pub enum Cw20HookMsg {
 #[cw_serde]
 pub struct PollExecuteMsg {
     pub order: u64,
+    pub contract: String, // contract the `msg` is executed against
     pub msg: Binary,
-    pub funding_goal: Option<Uint128>,
-    pub fund_rotation: Option<u64>,
-    pub split_to_liquid: Option<Decimal>,
-    pub treasury_tax_rate: Option<Decimal>,
+    pub funds: Vec<Coin>, // native funds sent by the gov contract along with the `msg`
 }
 
 #[cw_serde]
@@ -117,6 +125,17 @@ pub enum QueryMsg {
         limit: Option<u32>,
         order_by: Option<OrderBy>,
     },
+    /// Delegation shows the address the given staker delegates their voting power to
+    #[returns(DelegationResponse)]
+    Delegation { address: String },
+    /// Delegators lists the stakers that delegate their voting power to the given address
+    #[returns(DelegatorsResponse)]
+    Delegators {
+        address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        order_by: Option<OrderBy>,
+    },
 }
 
 #[cw_serde]
@@ -125,6 +144,7 @@ pub struct ConfigResponse {
     pub halo_token: String,
     pub quorum: Decimal,
     pub threshold: Decimal,
+    pub veto_threshold: Decimal,
     pub voting_period: u64,
     pub timelock_period: u64,
     pub proposal_deposit: Uint128,
@@ -151,8 +171,10 @@ pub struct PollResponse {
     pub proposal_type: Option<String>,
     pub deposit_amount: Uint128,
     pub execute_data: Option<Vec<PollExecuteMsg>>,
-    pub yes_votes: Uint128, // balance
-    pub no_votes: Uint128,  // balance
+    pub yes_votes: Uint128,     // balance
+    pub no_votes: Uint128,      // balance
+    pub abstain_votes: Uint128, // balance
+    pub veto_votes: Uint128,    // balance
     pub staked_amount: Option<Uint128>,
     pub total_balance_at_end_poll: Option<Uint128>,
 }
@@ -187,6 +209,22 @@ pub struct VotersResponse {
     pub voters: Vec<VotersResponseItem>,
 }
 
+#[cw_serde]
+pub struct DelegationResponse {
+    pub delegate: Option<String>,
+}
+
+#[cw_serde]
+pub struct DelegatorsResponseItem {
+    pub delegator: String,
+    pub balance: Uint128, // staked balance of the delegator
+}
+
+#[cw_serde]
+pub struct DelegatorsResponse {
+    pub delegators: Vec<DelegatorsResponseItem>,
+}
+
 #[cw_serde]
 pub struct VoterInfo {
     pub vote: VoteOption,
@@ -213,14 +251,19 @@ impl fmt::Display for PollStatus {
 pub enum VoteOption {
     Yes,
     No,
+    /// Counts toward the quorum, but not toward the threshold
+    Abstain,
+    /// Votes no & rejects the poll, burning its deposit, once the veto threshold is passed
+    Veto,
 }
 
 impl fmt::Display for VoteOption {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if *self == VoteOption::Yes {
-            write!(f, "yes")
-        } else {
-            write!(f, "no")
+        match self {
+            VoteOption::Yes => write!(f, "yes"),
+            VoteOption::No => write!(f, "no"),
+            VoteOption::Abstain => write!(f, "abstain"),
+            VoteOption::Veto => write!(f, "veto"),
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::common::OrderBy;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, StdError, StdResult, Uint128};
 
 #[cw_serde]
 pub struct MigrateMsg {}
@@ -43,6 +43,57 @@ pub struct VestingInfo {
     pub last_claim_time: u64,
 }
 
+impl VestingInfo {
+    /// Amount released by the schedules between the last claim and `current_time`
+    pub fn compute_claim_amount(&self, current_time: u64) -> Uint128 {
+        let mut claimable_amount: Uint128 = Uint128::zero();
+        for s in self.schedules.iter() {
+            if s.0 > current_time || s.1 < self.last_claim_time {
+                continue;
+            }
+
+            // min(s.1, current_time) - max(s.0, last_claim_time)
+            let passed_time =
+                std::cmp::min(s.1, current_time) - std::cmp::max(s.0, self.last_claim_time);
+
+            // prevent zero time_period case
+            let time_period = s.1 - s.0;
+            let release_amount_per_time: Decimal = Decimal::from_ratio(s.2, time_period);
+
+            claimable_amount += Uint128::from(passed_time as u128) * release_amount_per_time;
+        }
+
+        claimable_amount
+    }
+
+    /// Amount the schedules have yet to release after `current_time`
+    pub fn compute_unvested_amount(&self, current_time: u64) -> Uint128 {
+        let mut unvested_amount: Uint128 = Uint128::zero();
+        for s in self.schedules.iter() {
+            if s.1 <= current_time {
+                continue;
+            }
+
+            let remaining_time = s.1 - std::cmp::max(s.0, current_time);
+            unvested_amount += s.2.multiply_ratio(remaining_time, s.1 - s.0);
+        }
+
+        unvested_amount
+    }
+}
+
+pub fn assert_vesting_schedules(vesting_schedules: &[(u64, u64, Uint128)]) -> StdResult<()> {
+    for vesting_schedule in vesting_schedules.iter() {
+        if vesting_schedule.0 >= vesting_schedule.1 {
+            return Err(StdError::generic_err(
+                "end_time must greater than start_time",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {